@@ -125,6 +125,16 @@ impl ProvingOptions {
     pub const fn execution_options(&self) -> &ExecutionOptions {
         &self.exec_options
     }
+
+    /// Returns the factor by which the execution trace is extended in STARK proof generation.
+    pub fn blowup_factor(&self) -> usize {
+        self.proof_options.blowup_factor()
+    }
+
+    /// Returns the field extension used in STARK proof generation.
+    pub fn field_extension(&self) -> FieldExtension {
+        self.proof_options.field_extension()
+    }
 }
 
 impl Default for ProvingOptions {
@@ -2,7 +2,7 @@ use super::{
     AssemblyError, CallSet, CodeBlock, CodeBlockTable, Kernel, LibraryPath, NamedProcedure,
     Procedure, ProcedureCache, ProcedureId, ProcedureName, RpoDigest,
 };
-use crate::ast::{ModuleAst, ProgramAst};
+use crate::ast::{ModuleAst, ProgramAst, SourceLocation};
 use alloc::collections::BTreeMap;
 use alloc::string::ToString;
 use alloc::vec::Vec;
@@ -21,6 +21,7 @@ pub struct AssemblyContext {
     is_kernel: bool,
     kernel: Option<Kernel>,
     allow_phantom_calls: bool,
+    error_location: Option<SourceLocation>,
 }
 
 impl AssemblyContext {
@@ -36,6 +37,7 @@ impl AssemblyContext {
             is_kernel: is_kernel_module,
             kernel: None,
             allow_phantom_calls: false,
+            error_location: None,
         }
     }
 
@@ -52,6 +54,7 @@ impl AssemblyContext {
             is_kernel: false,
             kernel: None,
             allow_phantom_calls: false,
+            error_location: None,
         }
     }
 
@@ -81,6 +84,23 @@ impl AssemblyContext {
         self.current_proc_context().expect("no procedures").num_locals
     }
 
+    /// Returns the number of cycles saved so far by the peephole optimizer in the procedure
+    /// currently being compiled.
+    pub fn num_saved_cycles(&self) -> usize {
+        self.current_proc_context().expect("no procedures").num_saved_cycles
+    }
+
+    /// Returns the location in the program source of the code which failed to compile, if the
+    /// compilation of the program failed and the program was parsed with source locations.
+    ///
+    /// The location points to the innermost node of the program whose compilation failed, or to
+    /// the `begin` token for errors concerning the program as a whole (e.g., overlapping memory
+    /// regions). Errors raised while compiling imported modules are attributed to the instruction
+    /// of the program which invoked the module.
+    pub fn error_location(&self) -> Option<&SourceLocation> {
+        self.error_location.as_ref()
+    }
+
     /// Returns the name of the procedure by its ID from the procedure map.
     pub fn get_imported_procedure_name(&self, id: &ProcedureId) -> Option<ProcedureName> {
         if let Some(module) = self.module_stack.last() {
@@ -90,6 +110,12 @@ impl AssemblyContext {
         }
     }
 
+    /// Returns the name of the local procedure with the specified index, if it has been compiled.
+    pub fn get_local_procedure_name(&self, idx: u16) -> Option<ProcedureName> {
+        let module_context = self.module_stack.last()?;
+        module_context.compiled_procs.get(idx as usize).map(|proc| proc.name().clone())
+    }
+
     /// Returns the [Procedure] by its index from the vector of local procedures.
     pub fn get_local_procedure(&self, idx: u16) -> Result<&Procedure, AssemblyError> {
         let module_context = self.module_stack.last().expect("no modules");
@@ -190,6 +216,20 @@ impl AssemblyContext {
         self.module_stack.last_mut().expect("no modules").complete_proc(code);
     }
 
+    /// Records the location of the code which failed to compile, unless the location of a more
+    /// specific node has been recorded already.
+    pub(crate) fn record_error_location(&mut self, location: SourceLocation) {
+        self.error_location.get_or_insert(location);
+    }
+
+    /// Adds the specified number of cycles to the number of cycles saved by the peephole optimizer
+    /// in the procedure currently being compiled.
+    pub fn record_saved_cycles(&mut self, num_cycles: usize) {
+        let module_context = self.module_stack.last_mut().expect("no modules");
+        let proc_context = module_context.proc_stack.last_mut().expect("no procedures");
+        proc_context.num_saved_cycles += num_cycles;
+    }
+
     // CALL PROCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -335,6 +375,14 @@ impl AssemblyContext {
             .map(|p| p.name().as_ref())
             .expect("library compilation mode is currently not supported!")
     }
+
+    /// Returns the path of the module currently being compiled.
+    ///
+    /// # Panics
+    /// Panics if the module stack is empty.
+    pub(crate) fn current_module_path(&self) -> &LibraryPath {
+        &self.module_stack.last().expect("no modules").path
+    }
 }
 
 // MODULE CONTEXT
@@ -539,6 +587,7 @@ struct ProcedureContext {
     is_export: bool,
     num_locals: u16,
     callset: CallSet,
+    num_saved_cycles: usize,
 }
 
 impl ProcedureContext {
@@ -548,6 +597,7 @@ impl ProcedureContext {
             is_export,
             num_locals,
             callset: CallSet::default(),
+            num_saved_cycles: 0,
         }
     }
 
@@ -568,6 +618,7 @@ impl ProcedureContext {
             is_export,
             num_locals,
             callset,
+            ..
         } = self;
 
         NamedProcedure::new(name, is_export, num_locals as u32, code_root, callset)
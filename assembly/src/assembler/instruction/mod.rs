@@ -1,6 +1,6 @@
 use super::{
     Assembler, AssemblyContext, AssemblyError, CodeBlock, Felt, Instruction, Operation,
-    ProcedureId, RpoDigest, SpanBuilder, ONE, ZERO,
+    ProcedureId, RpoDigest, SourceLocation, SpanBuilder, ONE, ZERO,
 };
 use crate::utils::bound_into_included_u64;
 use core::ops::RangeBounds;
@@ -24,6 +24,7 @@ impl Assembler {
     pub(super) fn compile_instruction(
         &self,
         instruction: &Instruction,
+        location: Option<&SourceLocation>,
         span: &mut SpanBuilder,
         ctx: &mut AssemblyContext,
     ) -> Result<Option<CodeBlock>, AssemblyError> {
@@ -33,7 +34,7 @@ impl Assembler {
         // this will allow us to map the instruction to the sequence of operations which were
         // executed as a part of this instruction.
         if self.in_debug_mode() {
-            span.track_instruction(instruction, ctx);
+            span.track_instruction(instruction, location, ctx);
         }
 
         let result = match instruction {
@@ -303,7 +304,7 @@ impl Assembler {
             Instruction::Breakpoint => {
                 if self.in_debug_mode() {
                     span.add_op(Noop)?;
-                    span.track_instruction(instruction, ctx);
+                    span.track_instruction(instruction, location, ctx);
                 }
                 Ok(None)
             }
@@ -328,9 +329,10 @@ impl Assembler {
             }
         };
 
-        // compute and update the cycle count of the instruction which just finished executing
+        // compute and update the cycle count of the instruction which just finished executing;
+        // only instructions invoking procedures produce code blocks of their own
         if self.in_debug_mode() {
-            span.set_instruction_cycle_count();
+            span.set_instruction_cycle_count(matches!(result, Ok(Some(_))));
         }
 
         result
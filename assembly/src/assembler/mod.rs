@@ -1,11 +1,19 @@
 use super::{
-    ast::{instrument, Instruction, ModuleAst, Node, ProcedureAst, ProgramAst},
+    ast::{
+        event, instrument, CodeBody, ConstantProvider, Instruction, Level, MemoryRegion, ModuleAst,
+        ModuleImports, NamedError, Node, ProcedureAst, ProgramAst, SourceLocation,
+        CONSTANT_LABEL_PARSER,
+    },
     crypto::hash::RpoDigest,
-    AssemblyError, CallSet, CodeBlock, CodeBlockTable, Felt, Kernel, Library, LibraryError,
-    LibraryPath, Module, NamedProcedure, Operation, Procedure, ProcedureId, ProcedureName, Program,
-    ONE, ZERO,
+    library::{format_error, format_region},
+    stack_effect::StackEffectAnalyzer,
+    AssemblyError, CallSet, CodeBlock, CodeBlockTable, ErrorTable, Felt, Kernel, Library,
+    LibraryError, LibraryPath, MemoryLayout, Module, NamedProcedure, Operation, Procedure,
+    ProcedureId, ProcedureName, Program, StackEffect, StackEffectError, StackEffectPolicy,
+    StarkField, ONE, ZERO,
 };
-use alloc::collections::BTreeMap;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use core::{borrow::Borrow, cell::RefCell};
 use vm_core::{Decorator, DecoratorList};
@@ -13,17 +21,26 @@ use vm_core::{Decorator, DecoratorList};
 mod instruction;
 
 mod module_provider;
-use module_provider::ModuleProvider;
+pub use module_provider::ModuleProvider;
 
 mod span_builder;
 use span_builder::SpanBuilder;
 
+mod peephole;
+
 mod context;
 pub use context::AssemblyContext;
 
 mod procedure_cache;
 use procedure_cache::ProcedureCache;
 
+#[cfg(feature = "std")]
+mod module_cache;
+#[cfg(feature = "std")]
+use module_cache::CacheLookup;
+#[cfg(feature = "std")]
+pub use module_cache::{ModuleCache, ModuleFingerprint};
+
 #[cfg(test)]
 mod tests;
 
@@ -35,12 +52,43 @@ mod tests;
 /// - If `with_kernel()` or `with_kernel_module()` methods are not used, the assembler will be
 ///   instantiated with a default empty kernel. Programs compiled using such assembler
 ///   cannot make calls to kernel procedures via `syscall` instruction.
+/// - If `with_module_cache()` method is used, compiled library modules are persisted on disk and
+///   reused by subsequent assembler instances as long as neither the module nor any of the modules
+///   it (transitively) imports have changed.
+/// - If `with_stack_effect_policy()` method is used, problems found while inferring stack effects
+///   of procedures (e.g., a procedure not matching its declared `@stack` signature) are reported
+///   as warnings or errors. By default, such problems are ignored.
+/// - If `with_optimizations()` method is used, operations inside SPAN blocks are rewritten by a
+///   peephole optimizer into equivalent but shorter sequences of operations (e.g., `SWAP SWAP` is
+///   removed, and `PUSH(2) PUSH(3) ADD` is replaced with `PUSH(5)`). Optimizations are not applied
+///   in debug mode so that cycle counts of individual instructions remain accurate.
+/// - If `with_define()` method is used, the specified define is available to the program and the
+///   kernel compiled by the assembler as a constant, and can be used to select the code compiled
+///   by `cfg` blocks.
+///
+/// When a program is compiled, memory regions declared in the program, in the kernel, and in all
+/// modules (transitively) imported by them are checked for overlaps, and named errors declared in
+/// these modules are checked for conflicting error codes. Thus, libraries should be added to the
+/// assembler before the kernel if the kernel imports modules from these libraries.
 #[derive(Default)]
 pub struct Assembler {
     kernel: Kernel,
+    kernel_layout: MemoryLayout,
+    kernel_errors: ErrorTable,
     module_provider: ModuleProvider,
     proc_cache: RefCell<ProcedureCache>,
     in_debug_mode: bool,
+    stack_effect_policy: StackEffectPolicy,
+    stack_effects: RefCell<BTreeMap<ProcedureId, StackEffect>>,
+    optimize: bool,
+    saved_cycles: RefCell<BTreeMap<String, usize>>,
+    defines: BTreeMap<String, u64>,
+    #[cfg(feature = "std")]
+    module_cache: Option<ModuleCache>,
+    #[cfg(feature = "std")]
+    module_fingerprints: RefCell<BTreeMap<LibraryPath, ModuleFingerprint>>,
+    #[cfg(feature = "std")]
+    cached_modules: RefCell<BTreeSet<LibraryPath>>,
 }
 
 impl Assembler {
@@ -53,6 +101,55 @@ impl Assembler {
         self
     }
 
+    /// Sets the policy for reporting problems found while inferring stack effects of procedures.
+    pub fn with_stack_effect_policy(mut self, policy: StackEffectPolicy) -> Self {
+        self.stack_effect_policy = policy;
+        self
+    }
+
+    /// Enables or disables the peephole optimization of SPAN blocks.
+    ///
+    /// The optimizer is not applied in debug mode.
+    pub fn with_optimizations(mut self, optimize: bool) -> Self {
+        self.optimize = optimize;
+        self
+    }
+
+    /// Sets a define with the specified name and value.
+    ///
+    /// Defines are available to the program and the kernel as constants. A constant declared in
+    /// the source with the same name as a define serves as the default value of the define, and
+    /// is overridden by the value of the define. Since libraries added to the assembler are
+    /// already parsed, defines are passed to library modules when the library is built, e.g., via
+    /// [MaslLibrary::read_from_dir_with_constants()](crate::MaslLibrary) with an assembler
+    /// carrying the defines.
+    ///
+    /// # Errors
+    /// Returns an error if the name is not a valid constant name, or if the value is not a valid
+    /// field element.
+    pub fn with_define(mut self, name: &str, value: u64) -> Result<Self, AssemblyError> {
+        check_define(name, value)?;
+        self.defines.insert(name.to_string(), value);
+        Ok(self)
+    }
+
+    /// Enables the persistent module cache stored in the specified directory.
+    ///
+    /// Library modules compiled by this assembler are stored in the cache, and modules which have
+    /// been compiled previously (by this or any other assembler using the same directory) are
+    /// loaded from the cache instead of being recompiled.
+    ///
+    /// # Errors
+    /// Returns an error if the cache directory does not exist and cannot be created.
+    #[cfg(feature = "std")]
+    pub fn with_module_cache<P>(mut self, dir: P) -> Result<Self, AssemblyError>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        self.module_cache = Some(ModuleCache::new(dir)?);
+        Ok(self)
+    }
+
     /// Adds the library to provide modules for the compilation.
     pub fn with_library<L>(mut self, library: &L) -> Result<Self, AssemblyError>
     where
@@ -79,7 +176,7 @@ impl Assembler {
     /// # Panics
     /// Panics if the assembler has already been used to compile programs.
     pub fn with_kernel(self, kernel_source: &str) -> Result<Self, AssemblyError> {
-        let kernel_ast = ModuleAst::parse(kernel_source)?;
+        let kernel_ast = ModuleAst::parse_with_constants(kernel_source, &self)?;
         self.with_kernel_module(kernel_ast)
     }
 
@@ -97,6 +194,24 @@ impl Assembler {
         // exported form the kernel module
         self.kernel = context.into_kernel();
 
+        // record the memory regions used by the kernel so that programs can be checked against
+        // them
+        self.kernel_layout = self.build_memory_layout(
+            &kernel.path,
+            kernel.ast.memory_regions(),
+            kernel.ast.import_info(),
+        );
+        check_memory_layout(&self.kernel_layout)?;
+
+        // record the named errors declared by the kernel so that programs can be checked against
+        // them
+        self.kernel_errors = self.build_error_table(
+            &kernel.path,
+            kernel.ast.named_errors(),
+            kernel.ast.import_info(),
+        );
+        check_error_table(&self.kernel_errors)?;
+
         Ok(self)
     }
 
@@ -115,6 +230,76 @@ impl Assembler {
         &self.kernel
     }
 
+    /// Returns the memory layout of the provided program.
+    ///
+    /// The layout contains memory regions declared in the program, in the kernel of this
+    /// assembler, and in all modules (transitively) imported by the program.
+    pub fn memory_layout(&self, program: &ProgramAst) -> MemoryLayout {
+        let mut layout = self.build_memory_layout(
+            &LibraryPath::exec_path(),
+            program.memory_regions(),
+            program.import_info(),
+        );
+        for (path, region) in self.kernel_layout.regions() {
+            layout.add_regions(path, core::slice::from_ref(region));
+        }
+        layout
+    }
+
+    /// Returns the table of named errors of the provided program.
+    ///
+    /// The table contains errors declared in the program, in the kernel of this assembler, and in
+    /// all modules (transitively) imported by the program.
+    pub fn error_table(&self, program: &ProgramAst) -> ErrorTable {
+        let mut table = self.build_error_table(
+            &LibraryPath::exec_path(),
+            program.named_errors(),
+            program.import_info(),
+        );
+        table.extend(&self.kernel_errors);
+        table
+    }
+
+    /// Returns the stack effect of the exported procedure with the specified ID.
+    ///
+    /// Returns `None` if the module of the procedure has not been compiled by this assembler, or
+    /// if the stack effect of the procedure could not be inferred (e.g., because it makes dynamic
+    /// calls) and was not declared in its doc comment.
+    pub fn stack_effect(&self, proc_id: &ProcedureId) -> Option<StackEffect> {
+        self.stack_effects.borrow().get(proc_id).copied()
+    }
+
+    /// Returns true if SPAN blocks compiled by this assembler are rewritten by the peephole
+    /// optimizer.
+    pub fn optimizations_enabled(&self) -> bool {
+        self.optimize && !self.in_debug_mode
+    }
+
+    /// Returns fully-qualified names of all procedures compiled by this assembler for which the
+    /// peephole optimizer reduced the number of executed cycles, together with the number of
+    /// cycles saved, sorted by procedure name.
+    ///
+    /// The number of saved cycles is counted for a single execution of a procedure's body, with
+    /// bodies of `repeat` blocks counted as many times as they are repeated and bodies of `while`
+    /// loops counted once. Cycles saved in procedures invoked via `exec` are attributed to the
+    /// invoked procedures only. The body of a program is reported as `#exec::#main`.
+    ///
+    /// Procedures of modules loaded from the persistent module cache are not included.
+    pub fn cycle_savings(&self) -> Vec<(String, usize)> {
+        self.saved_cycles
+            .borrow()
+            .iter()
+            .map(|(name, &cycles)| (name.clone(), cycles))
+            .collect()
+    }
+
+    /// Returns paths of all library modules which this assembler loaded from the persistent module
+    /// cache instead of compiling them, sorted by path.
+    #[cfg(feature = "std")]
+    pub fn cached_modules(&self) -> Vec<LibraryPath> {
+        self.cached_modules.borrow().iter().cloned().collect()
+    }
+
     // PROGRAM COMPILER
     // --------------------------------------------------------------------------------------------
 
@@ -127,9 +312,10 @@ impl Assembler {
     where
         S: AsRef<str>,
     {
-        // parse the program into an AST
+        // parse the program into an AST; constants exported from library modules are resolved
+        // via the module provider, and defines are resolved via the assembler
         let source = source.as_ref();
-        let program = ProgramAst::parse(source)?;
+        let program = ProgramAst::parse_with_constants(source, self)?;
 
         // compile the program and return
         self.compile_ast(&program)
@@ -181,7 +367,36 @@ impl Assembler {
         }
 
         // compile the program body
-        let program_root = self.compile_body(program.body().nodes().iter(), context, None)?;
+        let program_root = self.compile_body(program.body(), context, None)?;
+        self.record_saved_cycles(&ProcedureName::main(), context);
+
+        // make sure memory regions used by the program do not overlap, and that error codes
+        // identify errors raised by the program unambiguously; such errors are attributed to the
+        // beginning of the program
+        check_memory_layout(&self.memory_layout(program))
+            .and_then(|_| check_error_table(&self.error_table(program)))
+            .map_err(|err| {
+                // the first location of a program points to its `begin` token
+                if let Some(start) = program.source_locations().next() {
+                    if program.body().has_locations() {
+                        context.record_error_location(*start);
+                    }
+                }
+                err
+            })?;
+
+        // check stack effects of the program procedures and of the program body
+        let errors = {
+            let exec_path = LibraryPath::exec_path();
+            let stack_effects = self.stack_effects.borrow();
+            let mut analyzer = StackEffectAnalyzer::new(&exec_path, &stack_effects);
+            for proc_ast in program.procedures() {
+                analyzer.analyze_procedure(proc_ast);
+            }
+            analyzer.analyze_program_body(program.body());
+            analyzer.into_parts().1
+        };
+        self.report_stack_effect_errors(errors)?;
 
         Ok(program_root)
     }
@@ -205,10 +420,45 @@ impl Assembler {
         path: Option<&LibraryPath>,
         context: &mut AssemblyContext,
     ) -> Result<Vec<RpoDigest>, AssemblyError> {
-        // a variable to track MAST roots of all procedures exported from this module
-        let mut proc_roots = Vec::new();
         context.begin_module(path.unwrap_or(&LibraryPath::anon_path()), module)?;
 
+        // if the module has been compiled previously, load it from the persistent module cache
+        #[cfg(feature = "std")]
+        if let Some(path) = path {
+            match self.load_cached_module(module, path, context)? {
+                CacheLookup::Hit(compiled) => {
+                    context.complete_module()?;
+                    self.check_module_stack_effects(module, Some(path))?;
+                    return self.add_compiled_module(compiled);
+                }
+                CacheLookup::Miss(fingerprint) => {
+                    let compiled = self.compile_module_procs(module, Some(path), context)?;
+                    self.check_module_stack_effects(module, Some(path))?;
+                    self.store_cached_module(path, fingerprint, &compiled)?;
+                    return self.add_compiled_module(compiled);
+                }
+                CacheLookup::Disabled => (),
+            }
+        }
+
+        let compiled = self.compile_module_procs(module, path, context)?;
+        self.check_module_stack_effects(module, path)?;
+        self.add_compiled_module(compiled)
+    }
+
+    /// Compiles all procedures of the module at the top of the module stack of the provided
+    /// context, and completes the compilation of this module.
+    ///
+    /// Returns the procedures which are to be added to the procedure cache; the procedure cache
+    /// itself is not modified.
+    fn compile_module_procs(
+        &self,
+        module: &ModuleAst,
+        path: Option<&LibraryPath>,
+        context: &mut AssemblyContext,
+    ) -> Result<CompiledModule, AssemblyError> {
+        let mut compiled = CompiledModule::default();
+
         // process all re-exported procedures
         for reexporteed_proc in module.reexported_procs().iter() {
             // make sure the re-exported procedure is loaded into the procedure cache
@@ -217,26 +467,23 @@ impl Assembler {
                 AssemblyError::ReExportedProcModuleNotFound(reexporteed_proc.clone())
             })?;
 
-            // if the library path is provided, build procedure ID for the alias and add it to the
-            // procedure cache
-            let proc_mast_root = if let Some(path) = path {
-                let proc_name = reexporteed_proc.name();
-                let alias_proc_id = ProcedureId::from_name(proc_name, path);
-                self.proc_cache
-                    .try_borrow_mut()
-                    .map_err(|_| AssemblyError::InvalidCacheLock)?
-                    .insert_proc_alias(alias_proc_id, ref_proc_id)?
-            } else {
-                self.proc_cache
-                    .try_borrow_mut()
-                    .map_err(|_| AssemblyError::InvalidCacheLock)?
-                    .get_proc_root_by_id(&ref_proc_id)
-                    .expect("procedure ID not in cache")
-            };
+            // if the library path is provided, build procedure ID for the alias which will be
+            // added to the procedure cache
+            if let Some(path) = path {
+                let alias_proc_id = ProcedureId::from_name(reexporteed_proc.name(), path);
+                compiled.aliases.push((alias_proc_id, ref_proc_id));
+            }
 
             // add the MAST root of the re-exported procedure to the set of procedures exported
             // from this module
-            proc_roots.push(proc_mast_root);
+            let proc_mast_root = self
+                .proc_cache
+                .try_borrow()
+                .map_err(|_| AssemblyError::InvalidCacheLock)?
+                .get_by_id(&ref_proc_id)
+                .expect("procedure ID not in cache")
+                .mast_root();
+            compiled.proc_roots.push(proc_mast_root);
         }
 
         // compile all local (internal end exported) procedures in the module; once the compilation
@@ -247,29 +494,113 @@ impl Assembler {
         }
         let (module_procs, module_callset) = context.complete_module()?;
 
-        // add the compiled procedures to the assembler's cache. the procedures are added to the
-        // cache only if:
+        // the procedures are added to the cache only if:
         // - a procedure is exported from the module, or
         // - a procedure is present in the combined callset - i.e., it is an internal procedure
         //   which has been invoked via a local call instruction.
         for (proc_index, proc) in module_procs.into_iter().enumerate() {
             if proc.is_export() {
-                proc_roots.push(proc.mast_root());
+                compiled.proc_roots.push(proc.mast_root());
             }
 
             if proc.is_export() || module_callset.contains(&proc.mast_root()) {
                 // build the procedure ID if this module has the library path
                 let proc_id = build_procedure_id(path, &proc, proc_index);
+                compiled.procs.push((proc_id, proc));
+            }
+        }
+
+        Ok(compiled)
+    }
+
+    /// Adds the procedures of a compiled module to the assembler's procedure cache and returns
+    /// MAST roots of all procedures exported from the module.
+    ///
+    /// # Errors
+    /// - If a lock to the [ProcedureCache] can not be attained.
+    /// - If any of the procedures or procedure aliases conflicts with the procedures already in
+    ///   the cache.
+    fn add_compiled_module(
+        &self,
+        compiled: CompiledModule,
+    ) -> Result<Vec<RpoDigest>, AssemblyError> {
+        // this is safe because we fail if the cache is borrowed.
+        let mut proc_cache =
+            self.proc_cache.try_borrow_mut().map_err(|_| AssemblyError::InvalidCacheLock)?;
+        for (alias_proc_id, ref_proc_id) in compiled.aliases {
+            proc_cache.insert_proc_alias(alias_proc_id, ref_proc_id)?;
+        }
+        for (proc_id, proc) in compiled.procs {
+            proc_cache.insert(proc, proc_id)?;
+        }
+
+        Ok(compiled.proc_roots)
+    }
 
-                // this is safe because we fail if the cache is borrowed.
-                self.proc_cache
-                    .try_borrow_mut()
-                    .map_err(|_| AssemblyError::InvalidCacheLock)?
-                    .insert(proc, proc_id)?;
+    // STACK EFFECTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Infers stack effects of all procedures in the specified module, and records the effects of
+    /// the procedures exported from the module (if the module has a path).
+    ///
+    /// All procedures imported by the module are expected to be compiled already, so that their
+    /// stack effects are known.
+    ///
+    /// # Errors
+    /// Returns an error if problems were found in the module and the stack effect policy is
+    /// [StackEffectPolicy::Deny].
+    fn check_module_stack_effects(
+        &self,
+        module: &ModuleAst,
+        path: Option<&LibraryPath>,
+    ) -> Result<(), AssemblyError> {
+        let anon_path = LibraryPath::anon_path();
+        let module_path = path.unwrap_or(&anon_path);
+        let (proc_effects, errors) = {
+            let stack_effects = self.stack_effects.borrow();
+            let mut analyzer = StackEffectAnalyzer::new(module_path, &stack_effects);
+            for proc_ast in module.procs().iter() {
+                analyzer.analyze_procedure(proc_ast);
+            }
+            analyzer.into_parts()
+        };
+
+        if let Some(path) = path {
+            let mut stack_effects = self.stack_effects.borrow_mut();
+            for (proc_ast, effect) in module.procs().iter().zip(proc_effects) {
+                if let (true, Some(effect)) = (proc_ast.is_export, effect) {
+                    stack_effects.insert(ProcedureId::from_name(&proc_ast.name, path), effect);
+                }
+            }
+            for reexported_proc in module.reexported_procs().iter() {
+                if let Some(&effect) = stack_effects.get(&reexported_proc.proc_id()) {
+                    stack_effects.insert(reexported_proc.get_alias_id(path), effect);
+                }
             }
         }
 
-        Ok(proc_roots)
+        self.report_stack_effect_errors(errors)
+    }
+
+    /// Reports the provided stack effect problems according to the stack effect policy of this
+    /// assembler.
+    fn report_stack_effect_errors(
+        &self,
+        errors: Vec<StackEffectError>,
+    ) -> Result<(), AssemblyError> {
+        match self.stack_effect_policy {
+            StackEffectPolicy::Ignore => Ok(()),
+            StackEffectPolicy::Warn => {
+                for err in errors {
+                    event!(Level::WARN, "{}", err);
+                }
+                Ok(())
+            }
+            StackEffectPolicy::Deny => match errors.into_iter().next() {
+                Some(err) => Err(err.into()),
+                None => Ok(()),
+            },
+        }
     }
 
     // PROCEDURE COMPILER
@@ -292,11 +623,12 @@ impl Assembler {
                 prologue: vec![Operation::Push(num_locals), Operation::FmpUpdate],
                 epilogue: vec![Operation::Push(-num_locals), Operation::FmpUpdate],
             };
-            self.compile_body(proc.body.nodes().iter(), context, Some(wrapper))?
+            self.compile_body(&proc.body, context, Some(wrapper))?
         } else {
-            self.compile_body(proc.body.nodes().iter(), context, None)?
+            self.compile_body(&proc.body, context, None)?
         };
 
+        self.record_saved_cycles(&proc.name, context);
         context.complete_proc(code);
 
         Ok(())
@@ -305,77 +637,119 @@ impl Assembler {
     // CODE BODY COMPILER
     // --------------------------------------------------------------------------------------------
 
-    /// TODO: add comments
-    fn compile_body<A, N>(
+    /// Compiles the nodes of the specified code body into a single code block.
+    ///
+    /// If the body has source locations, each tracked instruction is bound to its location in the
+    /// source code of the module being compiled (only applicable in debug mode).
+    fn compile_body(
         &self,
-        body: A,
+        body: &CodeBody,
         context: &mut AssemblyContext,
         wrapper: Option<BodyWrapper>,
-    ) -> Result<CodeBlock, AssemblyError>
-    where
-        A: Iterator<Item = N>,
-        N: Borrow<Node>,
-    {
+    ) -> Result<CodeBlock, AssemblyError> {
         let mut blocks: Vec<CodeBlock> = Vec::new();
-        let mut span = SpanBuilder::new(wrapper);
-
-        for node in body {
-            match node.borrow() {
-                Node::Instruction(inner) => {
-                    if let Some(block) = self.compile_instruction(inner, &mut span, context)? {
-                        span.extract_span_into(&mut blocks);
-                        blocks.push(block);
-                    }
-                }
+        let mut span = SpanBuilder::new(wrapper, self.optimizations_enabled());
 
-                Node::IfElse {
-                    true_case,
-                    false_case,
-                } => {
-                    span.extract_span_into(&mut blocks);
+        // locations of nodes which failed to compile are recorded only for the program itself, as
+        // they would not point into the program source for nodes of imported modules
+        let in_program = context.current_module_path().is_exec_path();
 
-                    let true_case = self.compile_body(true_case.nodes().iter(), context, None)?;
-
-                    // else is an exception because it is optional; hence, will have to be replaced
-                    // by noop span
-                    let false_case = if !false_case.nodes().is_empty() {
-                        self.compile_body(false_case.nodes().iter(), context, None)?
-                    } else {
-                        CodeBlock::new_span(vec![Operation::Noop])
-                    };
+        for (node_idx, node) in body.nodes().iter().enumerate() {
+            let location = body.source_locations().get(node_idx);
+            if let Err(err) = self.compile_node(node, location, &mut span, &mut blocks, context) {
+                if let Some(location) = location.filter(|_| in_program) {
+                    context.record_error_location(*location);
+                }
+                return Err(err);
+            }
+        }
 
-                    let block = CodeBlock::new_split(true_case, false_case);
+        let saved_cycles = span.extract_final_span_into(&mut blocks);
+        context.record_saved_cycles(saved_cycles);
+        Ok(if blocks.is_empty() {
+            CodeBlock::new_span(vec![Operation::Noop])
+        } else {
+            combine_blocks(blocks)
+        })
+    }
 
+    /// Compiles the specified node, appending the resulting code blocks to `blocks`; instructions
+    /// which do not produce code blocks of their own are added to the provided span.
+    fn compile_node(
+        &self,
+        node: &Node,
+        location: Option<&SourceLocation>,
+        span: &mut SpanBuilder,
+        blocks: &mut Vec<CodeBlock>,
+        context: &mut AssemblyContext,
+    ) -> Result<(), AssemblyError> {
+        match node {
+            Node::Instruction(inner) => {
+                if let Some(block) = self.compile_instruction(inner, location, span, context)? {
+                    span.extract_span_into(blocks);
                     blocks.push(block);
                 }
+            }
 
-                Node::Repeat { times, body } => {
-                    span.extract_span_into(&mut blocks);
+            Node::IfElse {
+                true_case,
+                false_case,
+            } => {
+                span.extract_span_into(blocks);
 
-                    let block = self.compile_body(body.nodes().iter(), context, None)?;
+                let true_case = self.compile_body(true_case, context, None)?;
 
-                    for _ in 0..*times {
-                        blocks.push(block.clone());
-                    }
-                }
+                // else is an exception because it is optional; hence, will have to be replaced
+                // by noop span
+                let false_case = if !false_case.nodes().is_empty() {
+                    self.compile_body(false_case, context, None)?
+                } else {
+                    CodeBlock::new_span(vec![Operation::Noop])
+                };
 
-                Node::While { body } => {
-                    span.extract_span_into(&mut blocks);
+                let block = CodeBlock::new_split(true_case, false_case);
 
-                    let block = self.compile_body(body.nodes().iter(), context, None)?;
-                    let block = CodeBlock::new_loop(block);
+                blocks.push(block);
+            }
 
-                    blocks.push(block);
+            Node::Repeat { times, body } => {
+                span.extract_span_into(blocks);
+
+                let saved_cycles = context.num_saved_cycles();
+                let block = self.compile_body(body, context, None)?;
+
+                // cycles saved in the body are saved in each repetition of the body
+                let body_saved_cycles = context.num_saved_cycles() - saved_cycles;
+                let num_repetitions = (*times as usize).saturating_sub(1);
+                context.record_saved_cycles(body_saved_cycles * num_repetitions);
+
+                for _ in 0..*times {
+                    blocks.push(block.clone());
                 }
             }
+
+            Node::While { body } => {
+                span.extract_span_into(blocks);
+
+                let block = self.compile_body(body, context, None)?;
+                let block = CodeBlock::new_loop(block);
+
+                blocks.push(block);
+            }
         }
 
-        span.extract_final_span_into(&mut blocks);
-        Ok(if blocks.is_empty() {
-            CodeBlock::new_span(vec![Operation::Noop])
-        } else {
-            combine_blocks(blocks)
-        })
+        Ok(())
+    }
+
+    /// Records the number of cycles saved by the peephole optimizer in the procedure currently
+    /// being compiled in the provided context.
+    fn record_saved_cycles(&self, proc_name: &ProcedureName, context: &AssemblyContext) {
+        let num_cycles = context.num_saved_cycles();
+        if num_cycles > 0 {
+            let name = format!("{}::{}", context.current_module_path(), proc_name);
+            event!(Level::DEBUG, "peephole optimizer saved {} cycles in {}", num_cycles, name);
+            self.saved_cycles.borrow_mut().insert(name, num_cycles);
+        }
     }
 
     // PROCEDURE CACHE
@@ -413,6 +787,63 @@ impl Assembler {
         Ok(())
     }
 
+    // MEMORY LAYOUT
+    // --------------------------------------------------------------------------------------------
+
+    /// Builds the memory layout of the provided regions declared in the module with the specified
+    /// path, and of the regions declared in all modules (transitively) imported by this module.
+    ///
+    /// Imported modules which are not available from the module provider are skipped.
+    fn build_memory_layout(
+        &self,
+        path: &LibraryPath,
+        regions: &[MemoryRegion],
+        import_info: &ModuleImports,
+    ) -> MemoryLayout {
+        let mut layout = MemoryLayout::default();
+        layout.add_regions(path, regions);
+        for module in self.imported_modules(import_info) {
+            layout.add_regions(&module.path, module.ast.memory_regions());
+        }
+        layout
+    }
+
+    /// Builds the table of the provided named errors declared in the module with the specified
+    /// path, and of the errors declared in all modules (transitively) imported by this module.
+    ///
+    /// Imported modules which are not available from the module provider are skipped.
+    fn build_error_table(
+        &self,
+        path: &LibraryPath,
+        errors: &[NamedError],
+        import_info: &ModuleImports,
+    ) -> ErrorTable {
+        let mut table = ErrorTable::default();
+        table.add_errors(path, errors);
+        for module in self.imported_modules(import_info) {
+            table.add_errors(&module.path, module.ast.named_errors());
+        }
+        table
+    }
+
+    /// Returns all modules (transitively) imported by a module with the provided imports which
+    /// are available from the module provider.
+    fn imported_modules(&self, import_info: &ModuleImports) -> Vec<&Module> {
+        let mut modules = Vec::new();
+        let mut visited = BTreeSet::new();
+        let mut worklist = import_info.import_paths();
+        while let Some(path) = worklist.pop() {
+            if !visited.insert(path) {
+                continue;
+            }
+            if let Some(module) = self.module_provider.get_module_by_path(path) {
+                worklist.extend(module.ast.import_info().import_paths());
+                modules.push(module);
+            }
+        }
+        modules
+    }
+
     // CODE BLOCK BUILDER
     // --------------------------------------------------------------------------------------------
     /// Returns the [CodeBlockTable] associated with the [AssemblyContext].
@@ -427,6 +858,30 @@ impl Assembler {
     }
 }
 
+impl ConstantProvider for Assembler {
+    fn get_constant(&self, module_path: &LibraryPath, name: &str) -> Option<u64> {
+        self.module_provider.get_constant(module_path, name)
+    }
+
+    fn get_define(&self, name: &str) -> Option<u64> {
+        self.defines.get(name).copied()
+    }
+}
+
+// COMPILED MODULE
+// ================================================================================================
+
+/// Contains the results of compiling a module which are to be added to the procedure cache.
+#[derive(Debug, Default)]
+struct CompiledModule {
+    /// MAST roots of all procedures exported from the module, including re-exported procedures.
+    proc_roots: Vec<RpoDigest>,
+    /// Pairs of procedure IDs of re-exported procedures and IDs of the procedures they refer to.
+    aliases: Vec<(ProcedureId, ProcedureId)>,
+    /// Compiled procedures together with their procedure IDs.
+    procs: Vec<(Option<ProcedureId>, NamedProcedure)>,
+}
+
 // BODY WRAPPER
 // ================================================================================================
 
@@ -440,6 +895,45 @@ struct BodyWrapper {
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Returns an error if the provided name is not a valid constant name, or if the provided value is
+/// not a valid field element.
+pub(crate) fn check_define(name: &str, value: u64) -> Result<(), AssemblyError> {
+    CONSTANT_LABEL_PARSER
+        .parse_label(name)
+        .map_err(|err| AssemblyError::invalid_define(name, &err.to_string()))?;
+    if value >= Felt::MODULUS {
+        let reason = format!("value must be smaller than {}", Felt::MODULUS);
+        return Err(AssemblyError::invalid_define(name, &reason));
+    }
+    Ok(())
+}
+
+/// Returns an error if any two regions of the provided memory layout overlap.
+fn check_memory_layout(layout: &MemoryLayout) -> Result<(), AssemblyError> {
+    match layout.find_overlap() {
+        Some([(first_path, first), (second_path, second)]) => {
+            Err(AssemblyError::overlapping_memory_regions(
+                &format_region(first_path, first),
+                &format_region(second_path, second),
+            ))
+        }
+        None => Ok(()),
+    }
+}
+
+/// Returns an error if any two distinct errors in the provided table have the same code.
+fn check_error_table(table: &ErrorTable) -> Result<(), AssemblyError> {
+    match table.find_conflict() {
+        Some([(first_path, first), (second_path, second)]) => {
+            Err(AssemblyError::conflicting_error_codes(
+                &format_error(first_path, first),
+                &format_error(second_path, second),
+            ))
+        }
+        None => Ok(()),
+    }
+}
+
 fn combine_blocks(mut blocks: Vec<CodeBlock>) -> CodeBlock {
     debug_assert!(!blocks.is_empty(), "cannot combine empty block list");
     // merge consecutive Span blocks.
@@ -0,0 +1,307 @@
+use super::{
+    Assembler, AssemblyContext, AssemblyError, CallSet, CompiledModule, LibraryPath, ModuleAst,
+    NamedProcedure, ProcedureId, RpoDigest,
+};
+use crate::{
+    ast::AstSerdeOptions, ByteReader, ByteWriter, Deserializable, DeserializationError,
+    ProcedureName, Serializable, SliceReader,
+};
+use alloc::{collections::BTreeSet, string::String, vec::Vec};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+use vm_core::crypto::hash::Blake3_256;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Version of the cache entry format; bumping it invalidates all existing cache entries.
+const CACHE_FORMAT_VERSION: u8 = 1;
+
+// MODULE FINGERPRINT
+// ================================================================================================
+
+/// A hash which uniquely identifies the result of compiling a module.
+///
+/// The fingerprint commits to the source of the module (including source locations in debug
+/// mode), the compilation options, the kernel, and to both MAST roots and fingerprints of all
+/// modules from which the module imports procedures. Thus, a change to any module which is
+/// transitively imported by a module changes the fingerprint of the module as well.
+pub type ModuleFingerprint = [u8; 32];
+
+// MODULE CACHE
+// ================================================================================================
+
+/// A persistent cache of compiled modules stored in a directory on disk.
+///
+/// Every entry holds all procedures which compiling a single library module adds to the procedure
+/// cache of an [Assembler], and is stored in a separate file named after the fingerprint of the
+/// module. Entries which cannot be read are treated as missing and are overwritten once the module
+/// is recompiled.
+#[derive(Debug, Clone)]
+pub struct ModuleCache {
+    dir: PathBuf,
+}
+
+impl ModuleCache {
+    /// File extension for the cache entries.
+    pub const FILE_EXTENSION: &'static str = "masc";
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new [ModuleCache] backed by the specified directory; the directory is created if
+    /// it does not exist yet.
+    pub fn new<P>(dir: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the directory in which the cache entries are stored.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    // PUBLIC MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Removes all entries from this cache.
+    pub fn clear(&self) -> io::Result<()> {
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == Self::FILE_EXTENSION) {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the compiled module stored under the specified fingerprint, if any.
+    fn get(&self, fingerprint: &ModuleFingerprint) -> Option<CompiledModule> {
+        let bytes = fs::read(self.entry_path(fingerprint)).ok()?;
+        let mut reader = SliceReader::new(&bytes);
+        if reader.read_u8().ok()? != CACHE_FORMAT_VERSION {
+            return None;
+        }
+        CompiledModule::read_from(&mut reader).ok()
+    }
+
+    /// Stores the compiled module under the specified fingerprint.
+    ///
+    /// The entry is first written into a temporary file which is then renamed, so that a partially
+    /// written entry is never observed by concurrent readers.
+    fn insert(&self, fingerprint: &ModuleFingerprint, module: &CompiledModule) -> io::Result<()> {
+        let mut bytes = vec![CACHE_FORMAT_VERSION];
+        module.write_into(&mut bytes);
+
+        let path = self.entry_path(fingerprint);
+        let tmp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+        fs::write(&tmp_path, bytes)?;
+        fs::rename(tmp_path, path)
+    }
+
+    fn entry_path(&self, fingerprint: &ModuleFingerprint) -> PathBuf {
+        let name: String = fingerprint.iter().map(|byte| format!("{byte:02x}")).collect();
+        self.dir.join(name).with_extension(Self::FILE_EXTENSION)
+    }
+}
+
+// ASSEMBLER INTEGRATION
+// ================================================================================================
+
+impl Assembler {
+    /// Attempts to load the module at the specified path from the persistent module cache.
+    ///
+    /// If the module is not in the cache, its fingerprint is returned so that the module can be
+    /// stored in the cache once it is compiled.
+    ///
+    /// Computing the fingerprint requires all procedures imported by the module to be compiled;
+    /// thus, this method compiles (or loads from the cache) all modules on which this module
+    /// depends. The module is expected to be at the top of the module stack of the `context`.
+    pub(super) fn load_cached_module(
+        &self,
+        module: &ModuleAst,
+        path: &LibraryPath,
+        context: &mut AssemblyContext,
+    ) -> Result<CacheLookup, AssemblyError> {
+        let cache = match &self.module_cache {
+            Some(cache) if !context.is_kernel() => cache,
+            _ => return Ok(CacheLookup::Disabled),
+        };
+
+        let fingerprint = self.build_module_fingerprint(module, path, context)?;
+        match cache.get(&fingerprint) {
+            Some(compiled) => {
+                self.module_fingerprints.borrow_mut().insert(path.clone(), fingerprint);
+                self.cached_modules.borrow_mut().insert(path.clone());
+                Ok(CacheLookup::Hit(compiled))
+            }
+            None => Ok(CacheLookup::Miss(fingerprint)),
+        }
+    }
+
+    /// Stores the compiled module at the specified path in the persistent module cache.
+    pub(super) fn store_cached_module(
+        &self,
+        path: &LibraryPath,
+        fingerprint: ModuleFingerprint,
+        compiled: &CompiledModule,
+    ) -> Result<(), AssemblyError> {
+        if let Some(cache) = &self.module_cache {
+            cache.insert(&fingerprint, compiled)?;
+            self.module_fingerprints.borrow_mut().insert(path.clone(), fingerprint);
+        }
+        Ok(())
+    }
+
+    /// Computes the fingerprint of the specified module, making sure that all procedures imported
+    /// by the module are in the procedure cache.
+    fn build_module_fingerprint(
+        &self,
+        module: &ModuleAst,
+        path: &LibraryPath,
+        context: &mut AssemblyContext,
+    ) -> Result<ModuleFingerprint, AssemblyError> {
+        let mut target = Vec::new();
+        path.write_into(&mut target);
+        target.write_bool(self.in_debug_mode);
+        target.write_bool(self.optimizations_enabled());
+        self.kernel.write_into(&mut target);
+        module.write_into(&mut target, AstSerdeOptions::new(true));
+        if self.in_debug_mode {
+            module.write_source_locations(&mut target);
+        }
+
+        // collect IDs of all procedures this module depends on and make sure they are compiled
+        let mut dependencies = BTreeSet::new();
+        for proc_id in module.import_info().get_imported_procedures().into_keys() {
+            self.ensure_procedure_is_in_cache(&proc_id, context)?;
+            dependencies.insert(proc_id);
+        }
+        for reexported_proc in module.reexported_procs() {
+            let proc_id = reexported_proc.proc_id();
+            self.ensure_procedure_is_in_cache(&proc_id, context).map_err(|_| {
+                AssemblyError::ReExportedProcModuleNotFound(reexported_proc.clone())
+            })?;
+            dependencies.insert(proc_id);
+        }
+
+        // commit to the MAST root of each dependency and to the fingerprint of its module
+        let proc_cache = self.proc_cache.borrow();
+        let module_fingerprints = self.module_fingerprints.borrow();
+        for proc_id in dependencies {
+            let mast_root =
+                proc_cache.get_by_id(&proc_id).expect("procedure not in cache").mast_root();
+            proc_id.write_into(&mut target);
+            mast_root.write_into(&mut target);
+
+            let dep_fingerprint = self
+                .module_provider
+                .get_module(&proc_id)
+                .and_then(|dep_module| module_fingerprints.get(&dep_module.path));
+            match dep_fingerprint {
+                Some(dep_fingerprint) => {
+                    target.write_bool(true);
+                    target.write_bytes(dep_fingerprint);
+                }
+                None => target.write_bool(false),
+            }
+        }
+
+        Ok(Blake3_256::hash(&target).into())
+    }
+}
+
+/// The result of looking up a module in the persistent module cache.
+pub(super) enum CacheLookup {
+    /// The assembler does not use a module cache for this module.
+    Disabled,
+    /// The module was not found in the cache and must be compiled.
+    Miss(ModuleFingerprint),
+    /// The module was found in the cache.
+    Hit(CompiledModule),
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for CompiledModule {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.proc_roots.write_into(target);
+
+        target.write_usize(self.aliases.len());
+        for (alias_id, ref_id) in self.aliases.iter() {
+            alias_id.write_into(target);
+            ref_id.write_into(target);
+        }
+
+        target.write_usize(self.procs.len());
+        for (proc_id, proc) in self.procs.iter() {
+            match proc_id {
+                Some(proc_id) => {
+                    target.write_bool(true);
+                    proc_id.write_into(target);
+                }
+                None => target.write_bool(false),
+            }
+            proc.name().write_into(target);
+            target.write_bool(proc.is_export());
+            target.write_u32(proc.num_locals());
+            proc.code().write_into(target);
+            target.write_usize(proc.callset().len());
+            for mast_root in proc.callset().iter() {
+                mast_root.write_into(target);
+            }
+        }
+    }
+}
+
+impl Deserializable for CompiledModule {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let proc_roots: Vec<RpoDigest> = source.read()?;
+
+        let num_aliases = source.read_usize()?;
+        let mut aliases = Vec::with_capacity(num_aliases);
+        for _ in 0..num_aliases {
+            let alias_id = ProcedureId::read_from(source)?;
+            let ref_id = ProcedureId::read_from(source)?;
+            aliases.push((alias_id, ref_id));
+        }
+
+        let num_procs = source.read_usize()?;
+        let mut procs = Vec::with_capacity(num_procs);
+        for _ in 0..num_procs {
+            let proc_id = if source.read_bool()? {
+                Some(ProcedureId::read_from(source)?)
+            } else {
+                None
+            };
+            let name = ProcedureName::read_from(source)?;
+            let is_export = source.read_bool()?;
+            let num_locals = source.read_u32()?;
+            let code = source.read()?;
+            let mut callset = CallSet::default();
+            for _ in 0..source.read_usize()? {
+                callset.insert(RpoDigest::read_from(source)?);
+            }
+            procs.push((proc_id, NamedProcedure::new(name, is_export, num_locals, code, callset)));
+        }
+
+        Ok(Self {
+            proc_roots,
+            aliases,
+            procs,
+        })
+    }
+}
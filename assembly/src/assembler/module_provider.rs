@@ -1,4 +1,4 @@
-use super::{Library, LibraryError, Module, ProcedureId};
+use super::{ConstantProvider, Library, LibraryError, LibraryPath, Module, ProcedureId};
 use alloc::collections::BTreeMap;
 use alloc::vec::Vec;
 
@@ -6,7 +6,8 @@ use alloc::vec::Vec;
 // ================================================================================================
 
 /// A module provider contains all modules from libraries available to a given assembler. It is
-/// used during compilation to resolve references to imported procedures.
+/// used during compilation to resolve references to imported procedures, and while parsing to
+/// resolve references to constants exported from imported modules.
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct ModuleProvider {
     modules: Vec<Module>,
@@ -18,6 +19,16 @@ impl ModuleProvider {
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns an iterator over all modules in this module provider.
+    pub fn modules(&self) -> impl Iterator<Item = &Module> {
+        self.modules.iter()
+    }
+
+    /// Fetch a module with the provided path.
+    pub fn get_module_by_path(&self, path: &LibraryPath) -> Option<&Module> {
+        self.modules.iter().find(|module| &module.path == path)
+    }
+
     /// Fetch a module that contains the provided procedure id.
     pub fn get_module(&self, id: &ProcedureId) -> Option<&Module> {
         // this will panic only if there is a bug in `Self::add_module`.
@@ -43,8 +54,9 @@ impl ModuleProvider {
             self.procedures.insert(proc_id, module_idx);
         }
         for proc in module.ast.procs().iter() {
-            let proc_path = module.path.append(&proc.name)?;
-            let proc_id = ProcedureId::from(&proc_path);
+            // names of procedures are validated when they are parsed, and names of template
+            // instances (e.g., `foo<8>`) are not valid path components
+            let proc_id = ProcedureId::from_name(&proc.name, &module.path);
             self.procedures.insert(proc_id, module_idx);
         }
         self.modules.push(module);
@@ -67,3 +79,10 @@ impl ModuleProvider {
         })
     }
 }
+
+impl ConstantProvider for ModuleProvider {
+    fn get_constant(&self, module_path: &LibraryPath, name: &str) -> Option<u64> {
+        let module = self.get_module_by_path(module_path)?;
+        module.ast.exported_constants().get(name).copied()
+    }
+}
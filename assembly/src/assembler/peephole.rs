@@ -0,0 +1,129 @@
+use super::{DecoratorList, Felt, Operation, ONE, ZERO};
+use alloc::vec::Vec;
+
+// PEEPHOLE OPTIMIZER
+// ================================================================================================
+
+/// Rewrites the operations of a SPAN block into an equivalent but shorter sequence of operations,
+/// and returns the number of operations removed from the block.
+///
+/// Since every operation in a SPAN block takes exactly one cycle to execute, the returned value
+/// is also the number of cycles saved by executing the optimized block.
+///
+/// Operations are never moved across decorators: each sequence of operations between two
+/// consecutive decorator positions is optimized independently, and the positions of all
+/// decorators are updated to point to the same operations in the optimized sequence. If all
+/// operations following the last decorator are removed, a single `NOOP` is appended so that the
+/// decorator still has an operation to be attached to. This does not apply to decorators which
+/// were placed after all operations of the block in the first place, unless no operations remain
+/// in the block.
+pub fn optimize_span(ops: &mut Vec<Operation>, decorators: &mut DecoratorList) -> usize {
+    let num_attached = decorators.iter().filter(|(position, _)| *position < ops.len()).count();
+    let mut optimized = Vec::with_capacity(ops.len());
+    let mut segment_start = 0;
+    let mut decorator_idx = 0;
+    while decorator_idx < decorators.len() {
+        let segment_end = decorators[decorator_idx].0;
+        optimize_segment(&ops[segment_start..segment_end], &mut optimized);
+        segment_start = segment_end;
+
+        // all decorators attached to the same operation are moved together
+        while decorator_idx < decorators.len() && decorators[decorator_idx].0 == segment_end {
+            decorators[decorator_idx].0 = optimized.len();
+            decorator_idx += 1;
+        }
+    }
+    optimize_segment(&ops[segment_start..], &mut optimized);
+
+    let is_detached = decorators[..num_attached]
+        .iter()
+        .any(|(position, _)| *position == optimized.len());
+    if is_detached || (optimized.is_empty() && !decorators.is_empty()) {
+        optimized.push(Operation::Noop);
+    }
+
+    let num_removed = ops.len() - optimized.len();
+    *ops = optimized;
+    num_removed
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Appends the optimized version of the provided operations to the target.
+///
+/// Operations are appended one by one, and after each operation the tail of the target is
+/// simplified for as long as any of the rewrite rules applies to it. This way, the result of a
+/// rewrite can enable further rewrites (e.g., `PUSH(1) PUSH(2) ADD DROP` is removed completely).
+fn optimize_segment(ops: &[Operation], target: &mut Vec<Operation>) {
+    let segment_start = target.len();
+    for &op in ops {
+        target.push(op);
+        while let Some((num_removed, replacement)) = rewrite_tail(&target[segment_start..]) {
+            target.truncate(target.len() - num_removed);
+            target.extend(replacement);
+        }
+    }
+}
+
+/// Returns the number of operations to be removed from the end of the provided sequence together
+/// with an optional operation which replaces them, or None if no rewrite rule applies.
+fn rewrite_tail(ops: &[Operation]) -> Option<(usize, Option<Operation>)> {
+    use Operation::*;
+
+    // constant folding
+    if let [.., a, b, op @ (Add | Mul)] = *ops {
+        if let (Some(a), Some(b)) = (constant_value(a), constant_value(b)) {
+            let value = if op == Add { a + b } else { a * b };
+            return Some((3, Some(push_constant(value))));
+        }
+    }
+    if let [.., a, op @ (Incr | Neg)] = *ops {
+        if let Some(a) = constant_value(a) {
+            let value = if op == Incr { a + ONE } else { -a };
+            return Some((2, Some(push_constant(value))));
+        }
+    }
+
+    match *ops {
+        // padding is not needed in a sequence of other operations
+        [.., Noop] => Some((1, None)),
+
+        // operations which cancel each other out
+        [.., Swap, Swap]
+        | [.., SwapW, SwapW]
+        | [.., SwapW2, SwapW2]
+        | [.., SwapW3, SwapW3]
+        | [.., SwapDW, SwapDW] => Some((2, None)),
+        [.., Pad | Push(_), Drop] => Some((2, None)),
+        [.., Dup0 | Dup1 | Dup2 | Dup3 | Dup4 | Dup5 | Dup6 | Dup7, Drop]
+        | [.., Dup9 | Dup11 | Dup13 | Dup15, Drop] => Some((2, None)),
+
+        // arithmetic with identity elements and increments
+        [.., Pad, Add] => Some((2, None)),
+        [.., Push(a), Add] if a == ZERO => Some((2, None)),
+        [.., Push(a), Mul] if a == ONE => Some((2, None)),
+        [.., Push(a), Add] if a == ONE => Some((2, Some(Incr))),
+
+        _ => None,
+    }
+}
+
+/// Returns the value pushed onto the stack by the specified operation, or None if the operation
+/// does not push a constant onto the stack.
+fn constant_value(op: Operation) -> Option<Felt> {
+    match op {
+        Operation::Pad => Some(ZERO),
+        Operation::Push(value) => Some(value),
+        _ => None,
+    }
+}
+
+/// Returns the operation which pushes the specified constant onto the stack.
+fn push_constant(value: Felt) -> Operation {
+    if value == ZERO {
+        Operation::Pad
+    } else {
+        Operation::Push(value)
+    }
+}
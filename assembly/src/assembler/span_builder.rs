@@ -1,10 +1,10 @@
 use super::{
-    AssemblyContext, AssemblyError, BodyWrapper, Borrow, CodeBlock, Decorator, DecoratorList,
-    Instruction, Operation,
+    peephole, AssemblyContext, AssemblyError, BodyWrapper, Borrow, CodeBlock, Decorator,
+    DecoratorList, Instruction, Operation, SourceLocation,
 };
-use alloc::string::ToString;
+use alloc::string::{String, ToString};
 use alloc::vec::Vec;
-use vm_core::{AdviceInjector, AssemblyOp};
+use vm_core::{AdviceInjector, AssemblyOp, Location};
 
 // SPAN BUILDER
 // ================================================================================================
@@ -16,12 +16,18 @@ use vm_core::{AdviceInjector, AssemblyOp};
 ///
 /// The same span builder can be used to construct many blocks. It is expected that when the last
 /// SPAN block in a procedure's body is constructed `extract_final_span_into()` will be used.
+///
+/// If optimizations are enabled, operations of each SPAN block are rewritten by the peephole
+/// optimizer before the block is constructed, and the builder keeps track of the number of
+/// operations removed by the optimizer.
 #[derive(Default)]
 pub struct SpanBuilder {
     ops: Vec<Operation>,
     decorators: DecoratorList,
     epilogue: Vec<Operation>,
     last_asmop_pos: usize,
+    optimize: bool,
+    num_removed_ops: usize,
 }
 
 impl SpanBuilder {
@@ -32,15 +38,23 @@ impl SpanBuilder {
     /// If the wrapper is provided, the prologue of the wrapper is immediately appended to the
     /// vector of span operations. The epilogue of the wrapper is appended to the list of
     /// operations upon consumption of the builder via `extract_final_span_into()` method.
-    pub(super) fn new(wrapper: Option<BodyWrapper>) -> Self {
+    ///
+    /// If `optimize` is true, operations of all SPAN blocks extracted from the builder are
+    /// rewritten by the peephole optimizer.
+    pub(super) fn new(wrapper: Option<BodyWrapper>, optimize: bool) -> Self {
         match wrapper {
             Some(wrapper) => Self {
                 ops: wrapper.prologue,
                 decorators: Vec::new(),
                 epilogue: wrapper.epilogue,
                 last_asmop_pos: 0,
+                optimize,
+                num_removed_ops: 0,
+            },
+            None => Self {
+                optimize,
+                ..Self::default()
             },
-            None => Self::default(),
         }
     }
 
@@ -100,12 +114,24 @@ impl SpanBuilder {
     ///
     /// This indicates that the provided instruction should be tracked and the cycle count for
     /// this instruction will be computed when the call to set_instruction_cycle_count() is made.
-    pub fn track_instruction(&mut self, instruction: &Instruction, ctx: &AssemblyContext) {
+    ///
+    /// If the location of the instruction is provided, it is recorded in the AsmOp decorator
+    /// together with the path of the module currently being compiled.
+    pub fn track_instruction(
+        &mut self,
+        instruction: &Instruction,
+        location: Option<&SourceLocation>,
+        ctx: &AssemblyContext,
+    ) {
         let context_name = ctx.current_context_name().to_string();
         let num_cycles = 0;
-        let op = instruction.to_string();
+        let op = format_instruction(instruction, ctx);
         let should_break = instruction.should_break();
-        let op = AssemblyOp::new(context_name, num_cycles, op, should_break);
+        let mut op = AssemblyOp::new(context_name, num_cycles, op, should_break);
+        if let Some(location) = location {
+            let path = ctx.current_module_path().path().to_string();
+            op = op.with_location(Location::new(path, location.line(), location.column()));
+        }
         self.push_decorator(Decorator::AsmOp(op));
         self.last_asmop_pos = self.decorators.len() - 1;
     }
@@ -115,8 +141,16 @@ impl SpanBuilder {
     ///
     /// If the cycle count is 0, the original decorator is removed from the list. This can happen
     /// for instructions which do not contribute any operations to the span block - e.g., exec,
-    /// call, and syscall.
-    pub fn set_instruction_cycle_count(&mut self) {
+    /// call, and syscall. However, the decorators of instructions which invoke procedures are kept
+    /// with the cycle count of 0 so that the call sites of procedures can be located at runtime.
+    /// Such a decorator is placed after all operations of the span block, and thus is executed
+    /// right before the invoked procedure; if the span block has no operations, a NOOP is added
+    /// for the decorator to be attached to.
+    pub fn set_instruction_cycle_count(&mut self, is_invocation: bool) {
+        if is_invocation && self.ops.is_empty() {
+            self.ops.push(Operation::Noop);
+        }
+
         // get the last asmop decorator and the cycle at which it was added
         let (op_start, assembly_op) =
             self.decorators.get_mut(self.last_asmop_pos).expect("no asmop decorator");
@@ -125,8 +159,9 @@ impl SpanBuilder {
         // compute the cycle count for the instruction
         let cycle_count = self.ops.len() - *op_start;
 
-        // if the cycle count is 0, remove the decorator; otherwise update its cycle count
-        if cycle_count == 0 {
+        // if the cycle count is 0, remove the decorator unless the instruction invokes a
+        // procedure; otherwise update its cycle count
+        if cycle_count == 0 && !is_invocation {
             self.decorators.remove(self.last_asmop_pos);
         } else if let Decorator::AsmOp(assembly_op) = assembly_op {
             assembly_op.set_num_cycles(cycle_count as u8)
@@ -142,6 +177,10 @@ impl SpanBuilder {
     /// This consumes all operations and decorators in the builder, but does not touch the
     /// operations in the epilogue of the builder.
     pub fn extract_span_into(&mut self, target: &mut Vec<CodeBlock>) {
+        if self.optimize && !self.ops.is_empty() {
+            self.num_removed_ops += peephole::optimize_span(&mut self.ops, &mut self.decorators);
+        }
+
         if !self.ops.is_empty() {
             let ops = self.ops.drain(..).collect();
             let decorators = self.decorators.drain(..).collect();
@@ -160,9 +199,31 @@ impl SpanBuilder {
     /// The main differences from the `extract_span_int()` method above are:
     /// - Operations contained in the epilogue of the span builder are appended to the list of
     ///   ops which go into the new SPAN block.
-    /// - The span builder is consumed in the process.
-    pub fn extract_final_span_into(mut self, target: &mut Vec<CodeBlock>) {
+    /// - The span builder is consumed in the process, and the total number of operations removed
+    ///   by the peephole optimizer from all SPAN blocks extracted from the builder is returned.
+    pub fn extract_final_span_into(mut self, target: &mut Vec<CodeBlock>) -> usize {
         self.ops.append(&mut self.epilogue);
         self.extract_span_into(target);
+        self.num_removed_ops
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the string representation of the specified instruction, in which procedures invoked by
+/// the instruction are referred to by their names (if known) rather than by their indexes or IDs.
+fn format_instruction(instruction: &Instruction, ctx: &AssemblyContext) -> String {
+    let (invocation, name) = match instruction {
+        Instruction::ExecLocal(idx) => ("exec", ctx.get_local_procedure_name(*idx)),
+        Instruction::CallLocal(idx) => ("call", ctx.get_local_procedure_name(*idx)),
+        Instruction::ExecImported(id) => ("exec", ctx.get_imported_procedure_name(id)),
+        Instruction::CallImported(id) => ("call", ctx.get_imported_procedure_name(id)),
+        Instruction::SysCall(id) => ("syscall", ctx.get_imported_procedure_name(id)),
+        _ => return instruction.to_string(),
+    };
+    match name {
+        Some(name) => format!("{invocation}.{name}"),
+        None => instruction.to_string(),
     }
 }
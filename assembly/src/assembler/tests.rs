@@ -1,8 +1,16 @@
-use super::{combine_blocks, Assembler, CodeBlock, Library, Module, Operation};
-use crate::{ast::ModuleAst, LibraryNamespace, LibraryPath, Version};
-use alloc::string::ToString;
+use super::{
+    combine_blocks, peephole::optimize_span, Assembler, AssemblyContext, CodeBlock, Decorator,
+    Felt, Library, Module, Operation,
+};
+use crate::{
+    ast::{ModuleAst, ProgramAst, SourceLocation},
+    LibraryNamespace, LibraryPath, Version,
+};
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use core::slice::Iter;
+use vm_core::AdviceInjector;
 
 // TESTS
 // ================================================================================================
@@ -141,3 +149,572 @@ fn nested_blocks() {
 
     assert_eq!(combined.hash(), program.hash());
 }
+
+#[cfg(feature = "std")]
+#[test]
+fn module_cache_reuse_and_invalidation() {
+    use crate::{MaslLibrary, Serializable};
+    use std::{env, fs};
+
+    const FOO: &str = r#"
+        use.test::bar
+
+        export.foo
+            exec.bar::bar
+            push.1 add
+        end
+
+        export.foo_call
+            call.bar::bar
+        end"#;
+    const PROGRAM: &str = r#"
+        use.test::foo
+
+        begin
+            exec.foo::foo
+            call.foo::foo_call
+        end"#;
+
+    let dir = env::temp_dir().join(format!("miden-module-cache-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+
+    let build_library = |bar_source: &str| {
+        let namespace = LibraryNamespace::new("test").unwrap();
+        let modules = [("test::foo", FOO), ("test::bar", bar_source)]
+            .into_iter()
+            .map(|(path, source)| {
+                let path = LibraryPath::new(path).unwrap();
+                Module::new(path, ModuleAst::parse(source).unwrap())
+            })
+            .collect();
+        MaslLibrary::new(namespace, Version::MIN, false, modules, Vec::new()).unwrap()
+    };
+    // returns the compiled program together with the paths of modules loaded from the cache
+    let compile = |library: &MaslLibrary, use_cache: bool| {
+        let mut assembler = Assembler::default();
+        if use_cache {
+            assembler = assembler.with_module_cache(&dir).unwrap();
+        }
+        let assembler = assembler.with_library(library).unwrap();
+        let program = assembler.compile(PROGRAM).unwrap().to_bytes();
+        let cached_modules: Vec<String> =
+            assembler.cached_modules().iter().map(|path| path.to_string()).collect();
+        (program, cached_modules)
+    };
+    let num_entries = || fs::read_dir(&dir).unwrap().count();
+    let all_modules = vec!["test::bar".to_string(), "test::foo".to_string()];
+
+    // compiling with an empty cache stores both modules in the cache
+    let library = build_library("export.bar push.2 mul end");
+    let (expected, _) = compile(&library, false);
+    assert_eq!(compile(&library, true), (expected.clone(), Vec::new()));
+    assert_eq!(num_entries(), 2);
+
+    // a new assembler loads both modules from the cache instead of compiling them
+    assert_eq!(compile(&library, true), (expected, all_modules.clone()));
+    assert_eq!(num_entries(), 2);
+
+    // changing the imported module invalidates both this module and the module importing it
+    let library = build_library("export.bar push.3 mul end");
+    let (expected, _) = compile(&library, false);
+    assert_eq!(compile(&library, true), (expected.clone(), Vec::new()));
+    assert_eq!(num_entries(), 4);
+    assert_eq!(compile(&library, true), (expected, all_modules));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn exported_constants() {
+    use crate::MaslLibrary;
+    use std::{env, fs};
+
+    // modules are listed in the reverse order of their imports to make sure the library parses
+    // them in the order of their imports
+    const BUFFER: &str = r#"
+        use.test::layout
+
+        const.END_PTR=layout::BASE_PTR+layout::SIZE
+
+        export.store_end
+            mem_store.END_PTR
+        end"#;
+    const LAYOUT: &str = r#"
+        export.const.BASE_PTR=1000
+        export.const.SIZE=4
+
+        export.base_ptr
+            push.BASE_PTR
+        end"#;
+    const PROGRAM: &str = r#"
+        use.test::buffer
+        use.test::layout
+
+        begin
+            push.layout::SIZE
+            exec.buffer::store_end
+        end"#;
+
+    let dir = env::temp_dir().join(format!("miden-exported-constants-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("buffer.masm"), BUFFER).unwrap();
+    fs::write(dir.join("layout.masm"), LAYOUT).unwrap();
+
+    let namespace = LibraryNamespace::new("test").unwrap();
+    let library = MaslLibrary::read_from_dir(&dir, namespace, false, Version::MIN).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    let program = Assembler::default().with_library(&library).unwrap().compile(PROGRAM).unwrap();
+    let expected = Assembler::default()
+        .compile("proc.store_end mem_store.1004 end begin push.4 exec.store_end end")
+        .unwrap();
+    assert_eq!(program.hash(), expected.hash());
+
+    // imported constants cannot be resolved if the library is not available
+    assert!(Assembler::default().compile(PROGRAM).is_err());
+
+    // a library can reference constants exported from its dependencies
+    const CONFIG: &str = r#"
+        use.test::layout
+
+        export.const.LIMIT=layout::BASE_PTR+layout::SIZE*2"#;
+
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("config.masm"), CONFIG).unwrap();
+    let namespace = LibraryNamespace::new("app").unwrap();
+    let dependencies = Assembler::default().with_library(&library).unwrap();
+    let app = MaslLibrary::read_from_dir_with_constants(
+        &dir,
+        namespace.clone(),
+        false,
+        Version::MIN,
+        &dependencies,
+    );
+    let no_dependencies = MaslLibrary::read_from_dir(&dir, namespace, false, Version::MIN);
+    fs::remove_dir_all(&dir).unwrap();
+
+    let config = app.unwrap().modules().next().unwrap().ast.clone();
+    assert_eq!(config.exported_constants().get("LIMIT"), Some(&1008));
+    assert!(no_dependencies.is_err());
+}
+
+#[test]
+fn memory_regions() {
+    use crate::{ast::MemoryRegion, MaslLibrary};
+
+    const NOTES: &str = r#"
+        export.region.NOTES.64=1000
+
+        export.load_note
+            mem_loadw.NOTES
+        end"#;
+    const ACCOUNT: &str = r#"
+        use.test::notes
+
+        export.region.ACCOUNT.16=notes::NOTES+64
+
+        export.load_account
+            mem_loadw.ACCOUNT
+        end"#;
+
+    let namespace = LibraryNamespace::new("test").unwrap();
+    let notes_path = LibraryPath::new("test::notes").unwrap();
+    let account_path = LibraryPath::new("test::account").unwrap();
+    let notes = ModuleAst::parse(NOTES).unwrap();
+    let modules = BTreeMap::from([(notes_path.clone(), notes.clone())]);
+    let account = ModuleAst::parse_with_constants(ACCOUNT, &modules).unwrap();
+    let modules = vec![Module::new(notes_path, notes), Module::new(account_path, account)];
+    let library = MaslLibrary::new(namespace, Version::MIN, false, modules, Vec::new()).unwrap();
+
+    // the layout of the library contains regions of all its modules
+    let layout = library.memory_layout();
+    let regions: Vec<_> = layout.regions().map(|(path, region)| (path.path(), region)).collect();
+    assert_eq!(
+        regions,
+        vec![
+            ("test::notes", &MemoryRegion::new("NOTES".to_string(), 1000, 64, true)),
+            ("test::account", &MemoryRegion::new("ACCOUNT".to_string(), 1064, 16, true)),
+        ]
+    );
+    assert_eq!(layout.find_region(1070).unwrap().1.name(), "ACCOUNT");
+    assert!(layout.find_overlap().is_none());
+
+    // regions of the program are checked against the regions of all imported modules
+    let assembler = Assembler::default().with_library(&library).unwrap();
+    let source = r#"
+        use.test::account
+
+        region.BUFFER.8=2000
+
+        begin
+            exec.account::load_account
+            mem_storew.BUFFER
+        end"#;
+    let program = ProgramAst::parse(source).unwrap();
+    assert_eq!(assembler.memory_layout(&program).len(), 3);
+    assert!(assembler.compile_ast(&program).is_ok());
+
+    let source = r#"
+        use.test::account
+
+        region.BUFFER.8=1060
+
+        begin
+            exec.account::load_account
+            mem_storew.BUFFER
+        end"#;
+    let err = assembler.compile(source).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "memory region test::notes::NOTES [1000..1064) overlaps with memory region \
+        #exec::BUFFER [1060..1068)"
+    );
+
+    // regions of modules which are not imported by the program are not checked
+    let source = "region.BUFFER.8=1060 begin mem_storew.BUFFER end";
+    assert!(assembler.compile(source).is_ok());
+}
+
+#[test]
+fn named_errors() {
+    use crate::MaslLibrary;
+
+    const WALLET: &str = r#"
+        #! Wallet is locked.
+        export.error.LOCKED=1
+
+        export.check
+            push.1
+            assert.err=LOCKED
+        end"#;
+    const NOTES: &str = r#"
+        #! Note is not valid.
+        export.error.INVALID_NOTE=1
+
+        export.check
+            push.1
+            assert.err=INVALID_NOTE
+        end"#;
+
+    // distinct errors declared in unrelated libraries may have the same code
+    let library = |namespace: &str, source: &str| {
+        let path = LibraryPath::new(format!("{namespace}::errors")).unwrap();
+        let module = Module::new(path, ModuleAst::parse(source).unwrap());
+        let namespace = LibraryNamespace::new(namespace).unwrap();
+        MaslLibrary::new(namespace, Version::MIN, false, vec![module], Vec::new()).unwrap()
+    };
+    let assembler = Assembler::default()
+        .with_library(&library("wallet", WALLET))
+        .unwrap()
+        .with_library(&library("notes", NOTES))
+        .unwrap();
+
+    // the error table of a program contains errors of the program and of all imported modules
+    let source = r#"
+        use.wallet::errors
+
+        #! Amount is too large.
+        error.TOO_LARGE=2
+
+        begin
+            exec.errors::check
+        end"#;
+    let program = ProgramAst::parse(source).unwrap();
+    let table = assembler.error_table(&program);
+    let names: Vec<&str> = table.errors().map(|(_, error)| error.name()).collect();
+    assert_eq!(names, vec!["LOCKED", "TOO_LARGE"]);
+    assert!(assembler.compile_ast(&program).is_ok());
+
+    // but a program cannot raise distinct errors with the same code
+    let source = r#"
+        use.wallet::errors->wallet
+        use.notes::errors->notes
+
+        begin
+            exec.wallet::check
+            exec.notes::check
+        end"#;
+    let err = assembler.compile(source).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "error wallet::errors::LOCKED (1) has the same code as error notes::errors::INVALID_NOTE (1)"
+    );
+
+    // the conflict is attributed to the beginning of the program
+    let program = ProgramAst::parse(source).unwrap();
+    let mut context = AssemblyContext::for_program(Some(&program));
+    assert!(assembler.compile_in_context(&program, &mut context).is_err());
+    assert_eq!(context.error_location(), Some(&SourceLocation::new(5, 9)));
+
+    let source = r#"
+        use.wallet::errors
+
+        #! Amount is too large.
+        error.TOO_LARGE=1
+
+        begin
+            exec.errors::check
+        end"#;
+    let err = assembler.compile(source).unwrap_err();
+    assert!(err.to_string().contains("has the same code as"), "{err}");
+}
+
+#[test]
+fn error_location() {
+    let assembler = Assembler::default();
+    let compile = |source: &str| {
+        let program = ProgramAst::parse(source).unwrap();
+        let mut context = AssemblyContext::for_program(Some(&program));
+        let result = assembler.compile_in_context(&program, &mut context);
+        (result.is_err(), context.error_location().copied())
+    };
+
+    // errors are attributed to the innermost node of the program which failed to compile
+    let source = "\
+proc.foo
+    push.1
+    if.true
+        syscall.bar
+    end
+end
+
+begin
+    exec.foo
+end";
+    assert_eq!(compile(source), (true, Some(SourceLocation::new(4, 9))));
+
+    // no location is recorded for programs which compile successfully
+    assert_eq!(compile("begin push.1 end"), (false, None));
+}
+
+#[test]
+fn procedure_templates() {
+    use crate::Serializable;
+
+    let source = "
+        const.SHIFT=12
+
+        proc.xor_rotr<N>
+            u32xor
+            u32rotr.N
+        end
+
+        begin
+            exec.xor_rotr<16>
+            exec.xor_rotr<SHIFT>
+            call.xor_rotr<16>
+        end";
+    let expected = "
+        proc.xor_rotr_16
+            u32xor
+            u32rotr.16
+        end
+
+        proc.xor_rotr_12
+            u32xor
+            u32rotr.12
+        end
+
+        begin
+            exec.xor_rotr_16
+            exec.xor_rotr_12
+            call.xor_rotr_16
+        end";
+
+    let assembler = Assembler::default();
+    let program = assembler.compile(source).unwrap();
+    assert_eq!(program.hash(), assembler.compile(expected).unwrap().hash());
+
+    // instances with identical bodies have the same MAST root, and thus are stored only once
+    let source = "
+        proc.push_one<N>
+            push.1
+        end
+
+        begin
+            call.push_one<1>
+            call.push_one<2>
+        end";
+    let expected = "
+        proc.push_one
+            push.1
+        end
+
+        begin
+            call.push_one
+            call.push_one
+        end";
+    let program = assembler.compile(source).unwrap();
+    let expected = assembler.compile(expected).unwrap();
+    assert_eq!(program.hash(), expected.hash());
+    assert_eq!(program.cb_table().to_bytes(), expected.cb_table().to_bytes());
+}
+
+#[test]
+fn defines() {
+    let source = "
+        const.CHUNK_SIZE=4
+        const.DEBUG=0
+        const.TARGET=1
+
+        begin
+            push.CHUNK_SIZE
+            cfg.DEBUG
+                debug.stack
+            end
+            cfg.TARGET=2
+                push.2
+            else
+                push.1
+            end
+        end";
+
+    // declared constants serve as default values of defines
+    let assembler = Assembler::default();
+    let expected = assembler.compile("begin push.4 push.1 end").unwrap();
+    assert_eq!(assembler.compile(source).unwrap().hash(), expected.hash());
+
+    let assembler = Assembler::default()
+        .with_define("CHUNK_SIZE", 8)
+        .unwrap()
+        .with_define("TARGET", 2)
+        .unwrap()
+        .with_define("DEBUG", 0)
+        .unwrap();
+    let expected = assembler.compile("begin push.8 push.2 end").unwrap();
+    assert_eq!(assembler.compile(source).unwrap().hash(), expected.hash());
+
+    // defines are available to the kernel as well
+    let kernel = "
+        const.FAST=0
+
+        export.foo
+            cfg.FAST
+                push.1
+            else
+                push.1 push.0 add
+            end
+        end";
+    let fast = Assembler::default()
+        .with_define("FAST", 1)
+        .unwrap()
+        .with_kernel(kernel)
+        .unwrap();
+    let slow = Assembler::default().with_kernel(kernel).unwrap();
+    assert_ne!(fast.kernel().proc_hashes(), slow.kernel().proc_hashes());
+
+    // defines are available to library modules parsed with an assembler carrying the defines
+    use crate::MaslLibrary;
+    use std::{env, fs};
+
+    let dir = env::temp_dir().join(format!("miden-defines-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("foo.masm"), kernel).unwrap();
+    let namespace = LibraryNamespace::new("test").unwrap();
+    let defines = Assembler::default().with_define("FAST", 1).unwrap();
+    let library =
+        MaslLibrary::read_from_dir_with_constants(&dir, namespace, false, Version::MIN, &defines);
+    fs::remove_dir_all(&dir).unwrap();
+
+    let assembler = Assembler::default().with_library(&library.unwrap()).unwrap();
+    let program = assembler.compile("use.test::foo begin exec.foo::foo end").unwrap();
+    let expected = assembler.compile("begin push.1 end").unwrap();
+    assert_eq!(program.hash(), expected.hash());
+
+    // invalid defines are rejected
+    assert!(Assembler::default().with_define("debug", 1).is_err());
+    assert!(Assembler::default().with_define("DEBUG", u64::MAX).is_err());
+
+    // regions cannot have the same names as defines
+    let assembler = Assembler::default().with_define("BUFFER", 1).unwrap();
+    let err = assembler.compile("region.BUFFER.4=100 begin push.BUFFER end").unwrap_err();
+    assert!(err.to_string().contains("duplicate constant name"), "{err}");
+
+    // cfg blocks cannot reference names which are neither declared nor defined
+    let err = Assembler::default().compile("begin cfg.DEBUG push.1 end end").unwrap_err();
+    assert!(err.to_string().contains("unknown cfg name: DEBUG"), "{err}");
+    let assembler = Assembler::default().with_define("DEBUG", 1).unwrap();
+    let expected = assembler.compile("begin push.1 end").unwrap();
+    let program = assembler.compile("begin cfg.DEBUG push.1 end end").unwrap();
+    assert_eq!(program.hash(), expected.hash());
+}
+
+#[test]
+fn peephole_optimizations() {
+    use Operation::*;
+
+    let optimize = |ops: &[Operation]| {
+        let mut ops = ops.to_vec();
+        let num_removed = optimize_span(&mut ops, &mut Vec::new());
+        (ops, num_removed)
+    };
+
+    assert_eq!(optimize(&[Swap, Swap, Add]), (vec![Add], 2));
+    assert_eq!(optimize(&[SwapW, Swap, Swap, SwapW, Mul]), (vec![Mul], 4));
+    assert_eq!(optimize(&[Pad, Pad, Drop, Drop, Dup3, Drop]), (Vec::new(), 6));
+    assert_eq!(optimize(&[Noop, Add, Noop]), (vec![Add], 2));
+    assert_eq!(optimize(&[Push(Felt::new(1)), Add, Push(Felt::new(0)), Add]), (vec![Incr], 3));
+    assert_eq!(optimize(&[Push(Felt::new(1)), Mul, Swap]), (vec![Swap], 2));
+
+    // constants are folded repeatedly
+    let ops = [Push(Felt::new(2)), Push(Felt::new(3)), Add, Push(Felt::new(4)), Mul, Incr, Neg];
+    assert_eq!(optimize(&ops), (vec![Push(-Felt::new(21))], 6));
+    let ops = [Push(Felt::new(2)), Push(Felt::new(3)), Add, Drop, Add];
+    assert_eq!(optimize(&ops), (vec![Add], 4));
+    assert_eq!(optimize(&[Pad, Incr, Add]), (vec![Incr], 2));
+    assert_eq!(optimize(&[Pad, Push(Felt::new(5)), Mul, Swap]), (vec![Pad, Swap], 2));
+
+    // operations are not rewritten across decorators
+    let injector = || Decorator::Advice(AdviceInjector::MemToMap);
+    let mut ops = vec![Swap, Swap, Swap, Swap, Noop, Noop, Add];
+    let mut decorators = vec![(3, injector()), (4, injector())];
+    assert_eq!(optimize_span(&mut ops, &mut decorators), 4);
+    assert_eq!(ops, vec![Swap, Swap, Add]);
+    assert_eq!(decorators, vec![(1, injector()), (2, injector())]);
+
+    // decorators at the end of the optimized sequence are attached to a NOOP
+    let mut ops = vec![Add, Swap, Swap];
+    let mut decorators = vec![(1, injector())];
+    assert_eq!(optimize_span(&mut ops, &mut decorators), 1);
+    assert_eq!(ops, vec![Add, Noop]);
+    assert_eq!(decorators, vec![(1, injector())]);
+}
+
+#[test]
+fn peephole_cycle_savings() {
+    const PROGRAM: &str = r#"
+        proc.foo.1
+            swap swap loc_store.0
+        end
+
+        proc.bar
+            push.1 push.2 add
+        end
+
+        begin
+            exec.foo
+            repeat.3
+                add.0 exec.bar
+            end
+            swap dup drop swap
+        end"#;
+
+    let assembler = Assembler::default().with_optimizations(true);
+    let program = assembler.compile(PROGRAM).unwrap();
+    assert_ne!(program.hash(), Assembler::default().compile(PROGRAM).unwrap().hash());
+
+    let expected = vec![
+        (String::from("#exec::#main"), 7),
+        (String::from("#exec::bar"), 3),
+        (String::from("#exec::foo"), 2),
+    ];
+    assert_eq!(assembler.cycle_savings(), expected);
+
+    // optimizations are not applied in debug mode
+    let assembler = Assembler::default().with_optimizations(true).with_debug_mode(true);
+    assert!(!assembler.optimizations_enabled());
+    assembler.compile(PROGRAM).unwrap();
+    assert_eq!(assembler.cycle_savings(), Vec::new());
+}
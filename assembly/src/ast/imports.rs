@@ -1,9 +1,9 @@
 use super::{
-    ByteReader, ByteWriter, Deserializable, DeserializationError, InvokedProcsMap, LibraryPath,
-    ParsingError, ProcedureId, ProcedureName, Serializable, Token, TokenStream, MAX_IMPORTS,
-    MAX_INVOKED_IMPORTED_PROCS,
+    ByteReader, ByteWriter, ConstantProvider, Deserializable, DeserializationError,
+    InvokedProcsMap, LibraryPath, ParsingError, ProcedureId, ProcedureName, Serializable, Token,
+    TokenStream, MAX_IMPORTS, MAX_INVOKED_IMPORTED_PROCS,
 };
-use alloc::collections::BTreeMap;
+use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 
@@ -22,6 +22,8 @@ pub struct ModuleImports {
     imports: ImportedModulesMap,
     /// Imported procedures that are called from somewhere in the AST.
     invoked_procs: InvokedProcsMap,
+    /// Imported modules whose constants are referenced from somewhere in the AST.
+    const_modules: BTreeSet<LibraryPath>,
 }
 
 impl ModuleImports {
@@ -41,6 +43,7 @@ impl ModuleImports {
         Self {
             imports,
             invoked_procs,
+            const_modules: BTreeSet::new(),
         }
     }
 
@@ -74,6 +77,7 @@ impl ModuleImports {
         Ok(Self {
             imports,
             invoked_procs: BTreeMap::new(),
+            const_modules: BTreeSet::new(),
         })
     }
 
@@ -120,6 +124,11 @@ impl ModuleImports {
         self.imports.values().collect()
     }
 
+    /// Return the paths of all imported modules whose constants are referenced in the AST.
+    pub fn const_module_paths(&self) -> Vec<&LibraryPath> {
+        self.const_modules.iter().collect()
+    }
+
     /// Returns a map containing IDs and names of imported procedures.
     pub fn get_imported_procedures(&self) -> BTreeMap<ProcedureId, ProcedureName> {
         self.invoked_procs.iter().map(|(id, (name, _))| (*id, name.clone())).collect()
@@ -162,10 +171,29 @@ impl ModuleImports {
         Ok(proc_id)
     }
 
-    /// Clears all stored information about imported modules and invoked procedures
+    /// Returns the value of the constant with the specified name exported from the imported module
+    /// with the specified name, and records that the constants of this module are referenced.
+    ///
+    /// Returns None if the module has not been imported via the `use` statement, or if the
+    /// provider does not know the value of the constant.
+    pub fn get_constant(
+        &mut self,
+        module_name: &str,
+        const_name: &str,
+        provider: &dyn ConstantProvider,
+    ) -> Option<u64> {
+        let module_path = self.imports.get(module_name)?;
+        let value = provider.get_constant(module_path, const_name)?;
+        self.const_modules.insert(module_path.clone());
+        Some(value)
+    }
+
+    /// Clears all stored information about imported modules, invoked procedures, and referenced
+    /// constants
     pub fn clear(&mut self) {
         self.imports.clear();
         self.invoked_procs.clear();
+        self.const_modules.clear();
     }
 }
 
@@ -181,6 +209,8 @@ impl Serializable for ModuleImports {
             proc_name.write_into(target);
             lib_path.write_into(target);
         }
+        target.write_u16(self.const_modules.len() as u16);
+        self.const_modules.iter().for_each(|path| path.write_into(target));
     }
 }
 
@@ -201,6 +231,15 @@ impl Deserializable for ModuleImports {
             let lib_path = LibraryPath::read_from(source)?;
             used_imported_procs.insert(proc_id, (proc_name, lib_path));
         }
-        Ok(Self::new(imports, used_imported_procs))
+
+        let mut const_modules = BTreeSet::new();
+        let num_const_modules = source.read_u16()?;
+        for _ in 0..num_const_modules {
+            const_modules.insert(LibraryPath::read_from(source)?);
+        }
+        Ok(Self {
+            const_modules,
+            ..Self::new(imports, used_imported_procs)
+        })
     }
 }
@@ -0,0 +1,134 @@
+use super::{
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, Token,
+    MAX_LABEL_LEN,
+};
+use alloc::string::{String, ToString};
+use core::{fmt, str::from_utf8};
+
+// MEMORY REGION
+// ================================================================================================
+
+/// A named range of global memory addresses declared at the top of a module or a program.
+///
+/// A memory region is declared via `region.<NAME>.<NUM_WORDS>=<ADDRESS>` (or via
+/// `export.region.<NAME>.<NUM_WORDS>=<ADDRESS>` to make the region available to other modules),
+/// and occupies `NUM_WORDS` consecutive memory addresses starting with `ADDRESS`. Since every
+/// memory address holds a single word, the size of a region is expressed in words.
+///
+/// The name of a region is also a constant which resolves to the first address of the region, and
+/// thus can be used as an immediate value of memory instructions (e.g., `mem_storew.NAME`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryRegion {
+    name: String,
+    address: u32,
+    num_words: u32,
+    is_export: bool,
+}
+
+impl MemoryRegion {
+    /// Total number of addressable words in memory.
+    pub const ADDRESS_SPACE_SIZE: u64 = 1 << 32;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new memory region with the specified name which occupies `num_words` addresses
+    /// starting with `address`.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - The name is longer than [MAX_LABEL_LEN] bytes.
+    /// - The region is empty or extends beyond the last memory address.
+    pub fn new(name: String, address: u32, num_words: u32, is_export: bool) -> Self {
+        assert!(name.len() <= MAX_LABEL_LEN, "memory region name too long");
+        assert!(num_words > 0, "memory region must contain at least one word");
+        assert!(
+            address as u64 + num_words as u64 <= Self::ADDRESS_SPACE_SIZE,
+            "memory region extends beyond the last memory address"
+        );
+        Self {
+            name,
+            address,
+            num_words,
+            is_export,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the name of this region.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the first address of this region.
+    pub fn address(&self) -> u32 {
+        self.address
+    }
+
+    /// Returns the number of words (i.e., memory addresses) in this region.
+    pub fn num_words(&self) -> u32 {
+        self.num_words
+    }
+
+    /// Returns the address immediately following the last address of this region.
+    pub fn end(&self) -> u64 {
+        self.address as u64 + self.num_words as u64
+    }
+
+    /// Returns true if this region can be referenced from other modules.
+    pub fn is_export(&self) -> bool {
+        self.is_export
+    }
+
+    /// Returns true if the specified address belongs to this region.
+    pub fn contains(&self, address: u32) -> bool {
+        address >= self.address && (address as u64) < self.end()
+    }
+
+    /// Returns true if this region and the other region have at least one address in common.
+    pub fn overlaps(&self, other: &MemoryRegion) -> bool {
+        (self.address as u64) < other.end() && (other.address as u64) < self.end()
+    }
+}
+
+impl fmt::Display for MemoryRegion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_export {
+            write!(f, "{}.", Token::EXPORT)?;
+        }
+        write!(f, "{}.{}.{}={}", Token::REGION, self.name, self.num_words, self.address)
+    }
+}
+
+impl Serializable for MemoryRegion {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.name.len() as u8);
+        target.write_bytes(self.name.as_bytes());
+        target.write_u32(self.address);
+        target.write_u32(self.num_words);
+        target.write_bool(self.is_export);
+    }
+}
+
+impl Deserializable for MemoryRegion {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let name_len = source.read_u8()? as usize;
+        let name = source.read_vec(name_len)?;
+        let name =
+            from_utf8(&name).map_err(|e| DeserializationError::InvalidValue(e.to_string()))?;
+        let address = source.read_u32()?;
+        let num_words = source.read_u32()?;
+        let is_export = source.read_bool()?;
+
+        if name.len() > MAX_LABEL_LEN
+            || num_words == 0
+            || address as u64 + num_words as u64 > Self::ADDRESS_SPACE_SIZE
+        {
+            let reason = format!("memory region '{name}' has invalid bounds");
+            return Err(DeserializationError::InvalidValue(reason));
+        }
+        Ok(Self::new(name.to_string(), address, num_words, is_export))
+    }
+}
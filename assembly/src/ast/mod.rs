@@ -32,6 +32,12 @@ pub use imports::ModuleImports;
 mod invocation_target;
 pub use invocation_target::InvocationTarget;
 
+mod memory;
+pub use memory::MemoryRegion;
+
+mod named_error;
+pub use named_error::NamedError;
+
 mod parsers;
 
 mod module;
@@ -44,11 +50,13 @@ mod program;
 pub use program::ProgramAst;
 
 pub(crate) use parsers::{
-    parse_param_with_constant_lookup, NAMESPACE_LABEL_PARSER, PROCEDURE_LABEL_PARSER,
+    parse_param_with_constant_lookup, ConstantResolver, CONSTANT_LABEL_PARSER,
+    NAMESPACE_LABEL_PARSER, PROCEDURE_LABEL_PARSER,
 };
 
 mod serde;
 pub use serde::AstSerdeOptions;
+pub(crate) use serde::{read_format_version, AST_FORMAT_VERSION};
 
 #[cfg(test)]
 pub mod tests;
@@ -62,6 +70,19 @@ const MAX_LOCAL_PROCS: usize = u16::MAX as usize;
 /// Maximum number of re-exported procedures in a module.
 const MAX_REEXPORTED_PROCS: usize = u16::MAX as usize;
 
+/// Maximum number of constants exported from a module.
+const MAX_EXPORTED_CONSTANTS: usize = u16::MAX as usize;
+
+/// Maximum number of memory regions declared in a module or a program.
+const MAX_MEMORY_REGIONS: usize = u16::MAX as usize;
+
+/// Maximum number of named errors declared in a module or a program.
+const MAX_NAMED_ERRORS: usize = u16::MAX as usize;
+
+/// Maximum number of procedure template instances which can be nested in each other (i.e., an
+/// instance which invokes another template, which in turn invokes another template, etc.).
+const MAX_TEMPLATE_DEPTH: usize = 64;
+
 /// Maximum number of bytes for a single documentation comment.
 const MAX_DOCS_LEN: usize = u16::MAX as usize;
 
@@ -84,6 +105,33 @@ type LocalConstMap = BTreeMap<String, u64>;
 type ReExportedProcMap = BTreeMap<ProcedureName, ProcReExport>;
 type InvokedProcsMap = BTreeMap<ProcedureId, (ProcedureName, LibraryPath)>;
 
+// CONSTANT PROVIDER
+// ================================================================================================
+
+/// A source of constants exported from modules and of build-time defines.
+///
+/// A constant provider is used while parsing a module or a program to resolve references to
+/// constants exported from the imported modules (e.g., `push.layout::BASE_PTR` where `layout` is
+/// an imported module which declares `export.const.BASE_PTR=...`), and to resolve defines which
+/// can be referenced as regular constants and which enable `cfg` blocks.
+pub trait ConstantProvider {
+    /// Returns the value of the constant with the specified name exported from the module with
+    /// the specified path, or None if the module or the constant is not available.
+    fn get_constant(&self, module_path: &LibraryPath, name: &str) -> Option<u64>;
+
+    /// Returns the value of the define with the specified name, or None if the define is not set.
+    fn get_define(&self, _name: &str) -> Option<u64> {
+        None
+    }
+}
+
+impl ConstantProvider for BTreeMap<LibraryPath, ModuleAst> {
+    fn get_constant(&self, module_path: &LibraryPath, name: &str) -> Option<u64> {
+        self.get(module_path)
+            .and_then(|module| module.exported_constants().get(name).copied())
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -96,13 +144,17 @@ fn sort_procs_into_vec(proc_map: LocalProcMap) -> Vec<ProcedureAst> {
 }
 
 /// Logging a warning message for every imported but unused module.
+///
+/// A module is considered to be used if any of its procedures are invoked, or if any of its
+/// constants are referenced.
 fn check_unused_imports(import_info: &ModuleImports) {
     let import_lib_paths = import_info.import_paths();
-    let invoked_procs_paths: Vec<&LibraryPath> =
+    let mut used_paths: Vec<&LibraryPath> =
         import_info.invoked_procs().iter().map(|(_id, (_name, path))| path).collect();
+    used_paths.extend(import_info.const_module_paths());
 
     for lib in import_lib_paths {
-        if !invoked_procs_paths.contains(&lib) {
+        if !used_paths.contains(&lib) {
             event!(Level::WARN, "unused import: \"{}\"", lib);
         }
     }
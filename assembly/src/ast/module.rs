@@ -2,16 +2,18 @@ use super::check_unused_imports;
 use super::{
     format::*,
     imports::ModuleImports,
-    parsers::{parse_constants, ParserContext},
+    parsers::{parse_constants, ParserContext, TemplateRegistry},
     serde::AstSerdeOptions,
-    sort_procs_into_vec, LocalProcMap, ProcReExport, ProcedureAst, ReExportedProcMap, MAX_DOCS_LEN,
-    MAX_LOCAL_PROCS, MAX_REEXPORTED_PROCS,
+    sort_procs_into_vec, ConstantProvider, LibraryPath, LocalProcMap, MemoryRegion, NamedError,
+    ProcReExport, ProcedureAst, ReExportedProcMap, MAX_DOCS_LEN, MAX_EXPORTED_CONSTANTS,
+    MAX_LOCAL_PROCS, MAX_MEMORY_REGIONS, MAX_NAMED_ERRORS, MAX_REEXPORTED_PROCS,
     {
         ByteReader, ByteWriter, Deserializable, DeserializationError, ParsingError, SliceReader,
         Token, TokenStream,
     },
 };
 
+use alloc::collections::BTreeMap;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use core::{fmt, str::from_utf8};
@@ -23,12 +25,19 @@ use vm_core::utils::Serializable;
 /// An abstract syntax tree of a Miden module.
 ///
 /// A module AST consists of a list of procedure ASTs, a list of re-exported procedures, a list of
-/// imports, and module documentation. Local procedures could be internal or exported.
+/// imports, a map of exported constants, a list of declared memory regions, a list of declared
+/// named errors, and module documentation. Local procedures could be internal or exported.
+///
+/// Constants which are not exported are substituted with their values during parsing, and thus
+/// are not a part of the AST.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ModuleAst {
     pub(super) local_procs: Vec<ProcedureAst>,
     pub(super) reexported_procs: Vec<ProcReExport>,
     pub(super) import_info: ModuleImports,
+    pub(super) exported_constants: BTreeMap<String, u64>,
+    pub(super) memory_regions: Vec<MemoryRegion>,
+    pub(super) named_errors: Vec<NamedError>,
     pub(super) docs: Option<String>,
 }
 
@@ -61,10 +70,49 @@ impl ModuleAst {
             local_procs,
             reexported_procs,
             import_info: Default::default(),
+            exported_constants: BTreeMap::new(),
+            memory_regions: Vec::new(),
+            named_errors: Vec::new(),
             docs,
         })
     }
 
+    /// Adds the provided exported constants to the module.
+    ///
+    /// # Panics
+    /// Panics if exported constants have already been added, or if the number of constants is
+    /// greater than the maximum number of constants a module can export.
+    pub fn with_exported_constants(mut self, constants: BTreeMap<String, u64>) -> Self {
+        assert!(self.exported_constants.is_empty(), "exported constants have already been added");
+        assert!(constants.len() <= MAX_EXPORTED_CONSTANTS, "too many exported constants");
+        self.exported_constants = constants;
+        self
+    }
+
+    /// Adds the provided memory regions to the module.
+    ///
+    /// # Panics
+    /// Panics if memory regions have already been added, or if the number of regions is greater
+    /// than the maximum number of regions a module can declare.
+    pub fn with_memory_regions(mut self, regions: Vec<MemoryRegion>) -> Self {
+        assert!(self.memory_regions.is_empty(), "memory regions have already been added");
+        assert!(regions.len() <= MAX_MEMORY_REGIONS, "too many memory regions");
+        self.memory_regions = regions;
+        self
+    }
+
+    /// Adds the provided named errors to the module.
+    ///
+    /// # Panics
+    /// Panics if named errors have already been added, or if the number of errors is greater than
+    /// the maximum number of errors a module can declare.
+    pub fn with_named_errors(mut self, errors: Vec<NamedError>) -> Self {
+        assert!(self.named_errors.is_empty(), "named errors have already been added");
+        assert!(errors.len() <= MAX_NAMED_ERRORS, "too many named errors");
+        self.named_errors = errors;
+        self
+    }
+
     /// Adds the provided import information to the module.
     ///
     /// # Panics
@@ -80,16 +128,30 @@ impl ModuleAst {
     /// Parses the provided source into a [ModuleAst].
     ///
     /// A module consists of internal and exported procedures but does not contain a body.
+    ///
+    /// Constants exported from imported modules cannot be referenced by the parsed module; use
+    /// [ModuleAst::parse_with_constants()] to parse modules which reference such constants.
     pub fn parse(source: &str) -> Result<Self, ParsingError> {
+        Self::parse_with_constants(source, &BTreeMap::<LibraryPath, ModuleAst>::new())
+    }
+
+    /// Parses the provided source into a [ModuleAst], resolving references to constants exported
+    /// from imported modules (e.g., `push.layout::BASE_PTR`) via the provided constant provider.
+    pub fn parse_with_constants(
+        source: &str,
+        constants: &dyn ConstantProvider,
+    ) -> Result<Self, ParsingError> {
         let mut tokens = TokenStream::new(source)?;
         let mut import_info = ModuleImports::parse(&mut tokens)?;
-        let local_constants = parse_constants(&mut tokens)?;
+        let declarations = parse_constants(&mut tokens, &mut import_info, constants, true)?;
         let mut context = ParserContext {
             import_info: &mut import_info,
             local_procs: LocalProcMap::default(),
             reexported_procs: ReExportedProcMap::default(),
-            local_constants,
+            local_constants: declarations.constants,
+            constant_provider: constants,
             num_proc_locals: 0,
+            templates: TemplateRegistry::default(),
         };
         context.parse_procedures(&mut tokens, true)?;
 
@@ -113,7 +175,11 @@ impl ModuleAst {
 
         check_unused_imports(context.import_info);
 
-        Ok(Self::new(local_procs, reexported_procs, docs)?.with_import_info(import_info))
+        Ok(Self::new(local_procs, reexported_procs, docs)?
+            .with_import_info(import_info)
+            .with_exported_constants(declarations.exported_constants)
+            .with_memory_regions(declarations.memory_regions)
+            .with_named_errors(declarations.named_errors))
     }
 
     // PUBLIC ACCESSORS
@@ -139,6 +205,25 @@ impl ModuleAst {
         &self.import_info
     }
 
+    /// Returns a map of constants exported from this module.
+    ///
+    /// Names of exported memory regions and exported named errors are included in this map as
+    /// constants which resolve to the first addresses of the regions and to the error codes
+    /// respectively.
+    pub fn exported_constants(&self) -> &BTreeMap<String, u64> {
+        &self.exported_constants
+    }
+
+    /// Returns a list of memory regions declared in this module.
+    pub fn memory_regions(&self) -> &[MemoryRegion] {
+        &self.memory_regions
+    }
+
+    /// Returns a list of named errors declared in this module.
+    pub fn named_errors(&self) -> &[NamedError] {
+        &self.named_errors
+    }
+
     // STATE MUTATORS
     // --------------------------------------------------------------------------------------------
 
@@ -175,6 +260,28 @@ impl ModuleAst {
             self.import_info.write_into(target);
         }
 
+        // serialize exported constants
+        assert!(
+            self.exported_constants.len() <= MAX_EXPORTED_CONSTANTS,
+            "too many exported constants"
+        );
+        target.write_u16(self.exported_constants.len() as u16);
+        for (name, value) in self.exported_constants.iter() {
+            target.write_u8(name.len() as u8);
+            target.write_bytes(name.as_bytes());
+            target.write_u64(*value);
+        }
+
+        // serialize memory regions
+        assert!(self.memory_regions.len() <= MAX_MEMORY_REGIONS, "too many memory regions");
+        target.write_u16(self.memory_regions.len() as u16);
+        target.write_many(&self.memory_regions);
+
+        // serialize named errors
+        assert!(self.named_errors.len() <= MAX_NAMED_ERRORS, "too many named errors");
+        target.write_u16(self.named_errors.len() as u16);
+        target.write_many(&self.named_errors);
+
         // serialize procedures
         assert!(self.local_procs.len() <= u16::MAX as usize, "too many local procs");
         assert!(
@@ -212,6 +319,25 @@ impl ModuleAst {
             ModuleImports::default()
         };
 
+        // deserialize exported constants
+        let num_exported_constants = source.read_u16()? as usize;
+        let mut exported_constants = BTreeMap::new();
+        for _ in 0..num_exported_constants {
+            let name_len = source.read_u8()? as usize;
+            let name = source.read_vec(name_len)?;
+            let name =
+                from_utf8(&name).map_err(|e| DeserializationError::InvalidValue(e.to_string()))?;
+            exported_constants.insert(name.to_string(), source.read_u64()?);
+        }
+
+        // deserialize memory regions
+        let num_memory_regions = source.read_u16()? as usize;
+        let memory_regions = source.read_many::<MemoryRegion>(num_memory_regions)?;
+
+        // deserialize named errors
+        let num_named_errors = source.read_u16()? as usize;
+        let named_errors = source.read_many::<NamedError>(num_named_errors)?;
+
         // deserialize re-exports
         let num_reexported_procs = source.read_u16()? as usize;
         let reexported_procs = source.read_many::<ProcReExport>(num_reexported_procs)?;
@@ -222,7 +348,11 @@ impl ModuleAst {
 
         match Self::new(local_procs, reexported_procs, docs) {
             Err(err) => Err(DeserializationError::UnknownError(err.message().clone())),
-            Ok(res) => Ok(res.with_import_info(import_info)),
+            Ok(res) => Ok(res
+                .with_import_info(import_info)
+                .with_exported_constants(exported_constants)
+                .with_memory_regions(memory_regions)
+                .with_named_errors(named_errors)),
         }
     }
 
@@ -304,6 +434,35 @@ impl fmt::Display for ModuleAst {
             writeln!(f)?;
         }
 
+        // Exported constants; names of exported memory regions and named errors are printed with
+        // the regions and the errors
+        let mut num_declarations = 0;
+        for (name, value) in self.exported_constants.iter() {
+            let is_region = self
+                .memory_regions
+                .iter()
+                .any(|region| region.is_export() && region.name() == name);
+            let is_error =
+                self.named_errors.iter().any(|error| error.is_export() && error.name() == name);
+            if !is_region && !is_error {
+                writeln!(f, "export.const.{name}={value}")?;
+                num_declarations += 1;
+            }
+        }
+
+        // Memory regions and named errors
+        for region in self.memory_regions.iter() {
+            writeln!(f, "{region}")?;
+            num_declarations += 1;
+        }
+        for error in self.named_errors.iter() {
+            writeln!(f, "{error}")?;
+            num_declarations += 1;
+        }
+        if num_declarations != 0 {
+            writeln!(f)?;
+        }
+
         // Re-exports
         for proc in self.reexported_procs.iter() {
             writeln!(f, "export.{}", proc.name())?;
@@ -0,0 +1,121 @@
+use super::{
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, Token,
+    MAX_DOCS_LEN, MAX_LABEL_LEN,
+};
+use alloc::string::{String, ToString};
+use core::{fmt, str::from_utf8};
+
+// NAMED ERROR
+// ================================================================================================
+
+/// An error code with a name and a message declared at the top of a module or a program.
+///
+/// A named error is declared via `error.<NAME>=<CODE>` (or via `export.error.<NAME>=<CODE>` to
+/// make the error available to other modules), and the message of the error is specified via the
+/// doc comment preceding the declaration. For example:
+///
+/// ```masm
+/// #! Account balance is insufficient to cover the transfer.
+/// export.error.INSUFFICIENT_BALANCE=0x00020001
+/// ```
+///
+/// The name of an error is also a constant which resolves to the error code, and thus can be used
+/// as an error code of assertions (e.g., `assert.err=INSUFFICIENT_BALANCE`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedError {
+    name: String,
+    code: u32,
+    message: String,
+    is_export: bool,
+}
+
+impl NamedError {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new named error with the specified name, code, and message.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - The name is longer than [MAX_LABEL_LEN] bytes.
+    /// - The code is zero (i.e., the code of assertions without an explicit error code).
+    /// - The message is longer than [MAX_DOCS_LEN] bytes.
+    pub fn new(name: String, code: u32, message: String, is_export: bool) -> Self {
+        assert!(name.len() <= MAX_LABEL_LEN, "error name too long");
+        assert_ne!(code, 0, "error code cannot be zero");
+        assert!(message.len() <= MAX_DOCS_LEN, "error message too long");
+        Self {
+            name,
+            code,
+            message,
+            is_export,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the name of this error.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the code of this error.
+    pub fn code(&self) -> u32 {
+        self.code
+    }
+
+    /// Returns the message of this error.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns true if this error can be referenced from other modules.
+    pub fn is_export(&self) -> bool {
+        self.is_export
+    }
+}
+
+impl fmt::Display for NamedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in self.message.lines() {
+            writeln!(f, "#! {line}")?;
+        }
+        if self.is_export {
+            write!(f, "{}.", Token::EXPORT)?;
+        }
+        write!(f, "{}.{}={}", Token::ERROR, self.name, self.code)
+    }
+}
+
+impl Serializable for NamedError {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.name.len() as u8);
+        target.write_bytes(self.name.as_bytes());
+        target.write_u32(self.code);
+        target.write_u16(self.message.len() as u16);
+        target.write_bytes(self.message.as_bytes());
+        target.write_bool(self.is_export);
+    }
+}
+
+impl Deserializable for NamedError {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let name_len = source.read_u8()? as usize;
+        let name = source.read_vec(name_len)?;
+        let name =
+            from_utf8(&name).map_err(|e| DeserializationError::InvalidValue(e.to_string()))?;
+        let code = source.read_u32()?;
+        let message_len = source.read_u16()? as usize;
+        let message = source.read_vec(message_len)?;
+        let message =
+            from_utf8(&message).map_err(|e| DeserializationError::InvalidValue(e.to_string()))?;
+        let is_export = source.read_bool()?;
+
+        if name.len() > MAX_LABEL_LEN || code == 0 {
+            let reason = format!("named error '{name}' is invalid");
+            return Err(DeserializationError::InvalidValue(reason));
+        }
+        Ok(Self::new(name.to_string(), code, message.to_string(), is_export))
+    }
+}
@@ -1,8 +1,54 @@
-use super::{Felt, LocalConstMap, ParsingError, Token};
+use super::{
+    ConstantProvider, Felt, LibraryPath, LocalConstMap, ModuleImports, ParsingError, Token,
+};
 use alloc::string::String;
 use alloc::vec::Vec;
 use core::fmt::Display;
 
+// CONSTANT RESOLVER
+// ================================================================================================
+
+/// Resolves references to constants in instruction parameters and constant expressions.
+///
+/// A reference without a module prefix (e.g., `FOO`) resolves to a constant declared in the
+/// source being parsed, or to a define if no such constant has been declared. A reference with a
+/// module prefix (e.g., `layout::BASE_PTR`) resolves to a constant exported from the imported
+/// module with the specified name, and the module is recorded as used in the module imports.
+pub struct ConstantResolver<'a> {
+    local_constants: &'a LocalConstMap,
+    import_info: &'a mut ModuleImports,
+    provider: &'a dyn ConstantProvider,
+}
+
+impl<'a> ConstantResolver<'a> {
+    /// Returns a new instance of the [ConstantResolver].
+    pub fn new(
+        local_constants: &'a LocalConstMap,
+        import_info: &'a mut ModuleImports,
+        provider: &'a dyn ConstantProvider,
+    ) -> Self {
+        Self {
+            local_constants,
+            import_info,
+            provider,
+        }
+    }
+
+    /// Returns the value of the referenced constant, or None if the reference cannot be resolved.
+    pub fn get(&mut self, reference: &str) -> Option<u64> {
+        match reference.split_once(LibraryPath::PATH_DELIM) {
+            Some((module_name, const_name)) => {
+                self.import_info.get_constant(module_name, const_name, self.provider)
+            }
+            None => self
+                .local_constants
+                .get(reference)
+                .copied()
+                .or_else(|| self.provider.get_define(reference)),
+        }
+    }
+}
+
 // CONSTANT VALUE EXPRESSIONS
 // ================================================================================================
 
@@ -42,7 +88,7 @@ impl Display for Operation {
 pub fn calculate_const_value(
     op: &Token,
     expression: &str,
-    constants: &LocalConstMap,
+    constants: &mut ConstantResolver,
 ) -> Result<Felt, ParsingError> {
     let postfix_expression = build_postfix_expression(op, expression, constants)?;
     evaluate_postfix_expression(op, expression, postfix_expression)
@@ -52,7 +98,7 @@ pub fn calculate_const_value(
 fn build_postfix_expression(
     op: &Token,
     expression: &str,
-    constants: &LocalConstMap,
+    constants: &mut ConstantResolver,
 ) -> Result<Vec<Operation>, ParsingError> {
     let mut stack = Vec::new();
     let mut postfix_expression = Vec::new();
@@ -144,16 +190,20 @@ fn evaluate_postfix_expression(
 ///
 /// `original_expression` stay unchanged during `next` method. It is used to obtain original
 /// expression for ParsingError.
-struct OperationIterator<'a> {
+struct OperationIterator<'a, 'c> {
     op: &'a Token<'a>,
     original_expression: &'a str,
     expression: &'a str,
-    constants: &'a LocalConstMap,
+    constants: &'a mut ConstantResolver<'c>,
 }
 
-impl<'a> OperationIterator<'a> {
+impl<'a, 'c> OperationIterator<'a, 'c> {
     /// Returns a new instance of the [OperationIterator].
-    pub fn new(op: &'a Token<'a>, expression: &'a str, constants: &'a LocalConstMap) -> Self {
+    pub fn new(
+        op: &'a Token<'a>,
+        expression: &'a str,
+        constants: &'a mut ConstantResolver<'c>,
+    ) -> Self {
         OperationIterator {
             op,
             original_expression: expression,
@@ -209,7 +259,7 @@ impl<'a> OperationIterator<'a> {
                 Ok(Some(parse_operand(
                     self.op,
                     self.original_expression,
-                    self.constants,
+                    &mut *self.constants,
                     parsed_value,
                 )?))
             }
@@ -218,11 +268,11 @@ impl<'a> OperationIterator<'a> {
     }
 }
 
-/// Returns the number in `value` or the constant value if the value is the name of the constant.
+/// Returns the number in `value` or the constant value if the value is a reference to a constant.
 fn parse_operand(
     op: &Token,
     expression: &str,
-    constants: &LocalConstMap,
+    constants: &mut ConstantResolver,
     value: String,
 ) -> Result<Operation, ParsingError> {
     let parsed_number = value.parse::<u64>();
@@ -230,7 +280,7 @@ fn parse_operand(
     if let Ok(parsed_number) = parsed_number {
         Ok(Operation::Value(Felt::new(parsed_number)))
     }
-    // if it is a name of the constant get its value from the `constants` map
+    // if it is a reference to a constant get its value from the constant resolver
     else {
         let parsed_number = constants.get(&value).ok_or_else(|| {
             ParsingError::invalid_const_value(
@@ -239,7 +289,7 @@ fn parse_operand(
                 &format!("constant with name {} was not initialized", value),
             )
         })?;
-        Ok(Operation::Value(Felt::new(*parsed_number)))
+        Ok(Operation::Value(Felt::new(parsed_number)))
     }
 }
 
@@ -292,28 +342,36 @@ fn compute_statement(
 // ================================================================================================
 #[cfg(test)]
 mod tests {
-    use super::{Felt, LocalConstMap, Token};
+    use super::{ConstantResolver, Felt, LibraryPath, LocalConstMap, ModuleImports, Token};
     use crate::{
-        ast::parsers::constants::{
-            build_postfix_expression, evaluate_postfix_expression, Operation,
+        ast::{
+            parsers::constants::{
+                build_postfix_expression, evaluate_postfix_expression, Operation,
+            },
+            ModuleAst,
         },
         ONE,
     };
-    use alloc::string::ToString;
+    use alloc::{collections::BTreeMap, string::ToString};
     use Operation::*;
 
     #[test]
     fn test_build_postfix_expression() {
-        let constants = LocalConstMap::from([("A".to_string(), 3), ("B".to_string(), 10)]);
+        let local_constants = LocalConstMap::from([("A".to_string(), 3), ("B".to_string(), 10)]);
+        let mut import_info = ModuleImports::default();
+        let provider = BTreeMap::<LibraryPath, ModuleAst>::new();
+        let mut constants = ConstantResolver::new(&local_constants, &mut import_info, &provider);
 
         let expression = "51-A+22";
-        let result = build_postfix_expression(&Token::new_dummy(), expression, &constants).unwrap();
+        let result =
+            build_postfix_expression(&Token::new_dummy(), expression, &mut constants).unwrap();
         let expected =
             vec![Value(Felt::new(51)), Value(Felt::new(3)), Sub, Value(Felt::new(22)), Add];
         assert_eq!(result, expected);
 
         let expression = "12*3+(2*B-(A/3+1))-2*3";
-        let result = build_postfix_expression(&Token::new_dummy(), expression, &constants).unwrap();
+        let result =
+            build_postfix_expression(&Token::new_dummy(), expression, &mut constants).unwrap();
         let expected = vec![
             Value(Felt::new(12)),
             Value(Felt::new(3)),
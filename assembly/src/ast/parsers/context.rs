@@ -1,10 +1,16 @@
 use super::{
-    super::ProcReExport, adv_ops, debug, events, field_ops, io_ops, stack_ops, sys_ops, u32_ops,
-    CodeBody, Instruction, InvocationTarget, LibraryPath, LocalConstMap, LocalProcMap,
-    ModuleImports, Node, ParsingError, ProcedureAst, ProcedureId, ProcedureName, ReExportedProcMap,
-    Token, TokenStream, MAX_BODY_LEN, MAX_DOCS_LEN,
+    super::ProcReExport,
+    adv_ops, debug, events, field_ops, io_ops, parse_const_value, stack_ops, sys_ops,
+    templates::{
+        instance_name, is_template_name, parse_template_invocation, ProcedureTemplate,
+        TemplateRegistry,
+    },
+    u32_ops, CodeBody, ConstantProvider, ConstantResolver, Instruction, InvocationTarget,
+    LibraryPath, LocalConstMap, LocalProcMap, ModuleImports, Node, ParsingError, ProcedureAst,
+    ProcedureId, ProcedureName, ReExportedProcMap, Token, TokenStream, CONSTANT_LABEL_PARSER,
+    MAX_BODY_LEN, MAX_DOCS_LEN, MAX_TEMPLATE_DEPTH,
 };
-use alloc::string::ToString;
+use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 
 // PARSER CONTEXT
@@ -16,10 +22,17 @@ pub struct ParserContext<'a> {
     pub local_procs: LocalProcMap,
     pub reexported_procs: ReExportedProcMap,
     pub local_constants: LocalConstMap,
+    pub constant_provider: &'a dyn ConstantProvider,
     pub num_proc_locals: u16,
+    pub templates: TemplateRegistry,
 }
 
 impl ParserContext<'_> {
+    /// Returns a resolver of the constants which can be referenced from the source being parsed.
+    fn constants(&mut self) -> ConstantResolver<'_> {
+        ConstantResolver::new(&self.local_constants, self.import_info, self.constant_provider)
+    }
+
     // STATEMENT PARSERS
     // --------------------------------------------------------------------------------------------
 
@@ -134,7 +147,7 @@ impl ParserContext<'_> {
         // record start of the repeat block and consume the 'repeat' token
         let repeat_start = tokens.pos();
         let repeat_token = tokens.read().expect("no repeat token");
-        let times = repeat_token.parse_repeat(&self.local_constants)?;
+        let times = repeat_token.parse_repeat(&mut self.constants())?;
         tokens.advance();
 
         // read the loop body
@@ -160,11 +173,79 @@ impl ParserContext<'_> {
         Ok(Node::Repeat { times, body })
     }
 
+    /// Parses a conditional compilation block (i.e., `cfg.<condition>` followed by an optional
+    /// `else` clause and terminated by `end`) from the provided token stream, and returns the body
+    /// of the clause selected by the condition.
+    ///
+    /// Both clauses are parsed, and thus errors in the clause which is left out are reported as
+    /// well.
+    fn parse_cfg(&mut self, tokens: &mut TokenStream) -> Result<CodeBody, ParsingError> {
+        // record start of the cfg block, evaluate the condition, and consume the 'cfg' token
+        let cfg_start = tokens.pos();
+        let cfg_token = tokens.read().expect("no cfg token");
+        let is_enabled = evaluate_cfg_condition(cfg_token, &mut self.constants())?;
+        tokens.advance();
+
+        // read the clause used when the condition holds
+        let true_case = self.parse_body(tokens, true)?;
+
+        // read the `else` clause, if it is present
+        let false_case = match tokens.read() {
+            Some(token) => match token.parts()[0] {
+                Token::ELSE => {
+                    // record start of the `else` block and consume the `else` token
+                    token.validate_else()?;
+                    let else_start = tokens.pos();
+                    tokens.advance();
+
+                    // parse the clause used when the condition does not hold
+                    let false_case = self.parse_body(tokens, false)?;
+
+                    // consume the `end` token
+                    match tokens.read() {
+                        None => {
+                            let token = tokens.read_at(else_start).expect("no else token");
+                            Err(ParsingError::unmatched_else(token))
+                        }
+                        Some(token) => match token.parts()[0] {
+                            Token::END => token.validate_end(),
+                            Token::ELSE => Err(ParsingError::dangling_else(token)),
+                            _ => {
+                                let token = tokens.read_at(else_start).expect("no else token");
+                                Err(ParsingError::unmatched_else(token))
+                            }
+                        },
+                    }?;
+                    tokens.advance();
+                    false_case
+                }
+                Token::END => {
+                    token.validate_end()?;
+                    tokens.advance();
+                    CodeBody::default()
+                }
+                _ => {
+                    let token = tokens.read_at(cfg_start).expect("no cfg token");
+                    return Err(ParsingError::unmatched_cfg(token));
+                }
+            },
+            None => {
+                let token = tokens.read_at(cfg_start).expect("no cfg token");
+                return Err(ParsingError::unmatched_cfg(token));
+            }
+        };
+
+        Ok(if is_enabled { true_case } else { false_case })
+    }
+
     // CALL PARSERS
     // --------------------------------------------------------------------------------------------
 
     /// Parse an `exec` token into an instruction node.
     fn parse_exec(&mut self, token: &Token) -> Result<Node, ParsingError> {
+        if let Some(index) = self.instantiate_template(token)? {
+            return Ok(Node::Instruction(Instruction::ExecLocal(index)));
+        }
         match token.parse_invocation(token.parts()[0])? {
             InvocationTarget::MastRoot(_) => Err(ParsingError::exec_with_mast_root(token)),
             InvocationTarget::ProcedureName(proc_name) => {
@@ -182,6 +263,9 @@ impl ParserContext<'_> {
 
     /// Parse a `call` token into an instruction node.
     fn parse_call(&mut self, token: &Token) -> Result<Node, ParsingError> {
+        if let Some(index) = self.instantiate_template(token)? {
+            return Ok(Node::Instruction(Instruction::CallLocal(index)));
+        }
         match token.parse_invocation(token.parts()[0])? {
             InvocationTarget::MastRoot(root_hash) => {
                 let inner = Instruction::CallMastRoot(root_hash);
@@ -220,6 +304,9 @@ impl ParserContext<'_> {
 
     /// Parse a `procref` token into an instruction node.
     pub fn parse_procref(&mut self, token: &Token) -> Result<Node, ParsingError> {
+        if let Some(index) = self.instantiate_template(token)? {
+            return Ok(Node::Instruction(Instruction::ProcRefLocal(index)));
+        }
         match token.parse_invocation(token.parts()[0])? {
             InvocationTarget::ProcedureName(proc_name) => {
                 let index = self.get_local_proc_index(proc_name, token)?;
@@ -235,6 +322,70 @@ impl ParserContext<'_> {
         }
     }
 
+    // TEMPLATE INSTANTIATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Instantiates the procedure template invoked by the provided token (e.g., `exec.foo<8>`)
+    /// and returns the index of the local procedure containing the instance, or None if the token
+    /// does not invoke a template.
+    ///
+    /// Every instance is parsed only once per module into a local procedure named after the
+    /// instance (e.g., `foo<8>`). Instances with identical bodies are not merged here: they
+    /// compile to the same MAST root, and thus share a single code block in the compiled program.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The template is not declared in this context or the invocation is malformed.
+    /// - The instance cannot be parsed; the location of such an error points to the source of the
+    ///   template.
+    /// - The instance invokes itself, or instances are nested too deep.
+    fn instantiate_template(&mut self, token: &Token) -> Result<Option<u16>, ParsingError> {
+        let target = match token.parts().get(1) {
+            Some(target) if is_template_name(target) => *target,
+            _ => return Ok(None),
+        };
+        let (name, args) = parse_template_invocation(token, target, &mut self.constants())?;
+        let template = self
+            .templates
+            .declared
+            .get(name)
+            .ok_or_else(|| ParsingError::undefined_template(token, name))?;
+        if args.len() != template.num_params() {
+            let expected = template.num_params();
+            return Err(ParsingError::invalid_template_args(token, name, expected, args.len()));
+        }
+
+        let instance = instance_name(name, &args);
+        if let Some(&index) = self.templates.instances.get(&instance) {
+            return Ok(Some(index));
+        }
+        if self.templates.pending.contains(&instance) {
+            return Err(ParsingError::recursive_template_instance(token, &instance));
+        }
+        if self.templates.pending.len() == MAX_TEMPLATE_DEPTH {
+            return Err(ParsingError::template_instances_too_deep(token, MAX_TEMPLATE_DEPTH));
+        }
+
+        // parse the instance as a regular procedure; the number of locals is saved because the
+        // instance may be parsed while parsing the body of another procedure
+        let (instance_tokens, locations) = template.instantiate(&instance, &args);
+        let mut instance_stream = TokenStream::from_tokens(
+            instance_tokens.iter().map(String::as_str).collect(),
+            locations,
+        );
+        let num_proc_locals = self.num_proc_locals;
+        self.templates.pending.push(instance.clone());
+        let proc = self.parse_procedure(&mut instance_stream);
+        self.templates.pending.pop();
+        self.num_proc_locals = num_proc_locals;
+        let proc = proc.map_err(|err| err.in_template_instance(token, &instance))?;
+
+        let index = self.local_procs.len() as u16;
+        self.local_procs.insert(proc.name.clone(), (index, proc));
+        self.templates.instances.insert(instance, index);
+        Ok(Some(index))
+    }
+
     // PROCEDURE PARSERS
     // --------------------------------------------------------------------------------------------
 
@@ -246,8 +397,18 @@ impl ParserContext<'_> {
     ) -> Result<(), ParsingError> {
         // parse procedures until all `proc` or `exec` tokens have been consumed
         while let Some(token) = tokens.read() {
+            let is_template = token.parts().get(1).is_some_and(|name| is_template_name(name));
             let is_reexport = match token.parts()[0] {
                 Token::EXPORT => {
+                    if matches!(
+                        token.parts().get(1),
+                        Some(&Token::CONST | &Token::REGION | &Token::ERROR)
+                    ) {
+                        return Err(ParsingError::const_invalid_scope(token));
+                    }
+                    if is_template {
+                        return Err(ParsingError::template_export_not_allowed(token));
+                    }
                     if !allow_export {
                         let proc_name = token.parts()[1];
                         return Err(ParsingError::proc_export_not_allowed(token, proc_name));
@@ -261,7 +422,17 @@ impl ParserContext<'_> {
                 _ => break,
             };
 
-            if is_reexport {
+            if is_template {
+                // store the template to be instantiated when it is invoked
+                let template_start = tokens.pos();
+                let template = ProcedureTemplate::parse(tokens)?;
+                let name = ProcedureName::try_from(template.name()).expect("invalid template name");
+                if self.contains_proc_name(&name) {
+                    let header = tokens.read_at(template_start).expect("no template header");
+                    return Err(ParsingError::duplicate_proc_name(header, template.name()));
+                }
+                self.templates.declared.insert(template.name().to_string(), template);
+            } else if is_reexport {
                 // parse procedure re-export and add it to the list of re-exported procedures
                 let proc = self.parse_reexported_procedure(tokens)?;
                 self.reexported_procs.insert(proc.name.clone(), proc);
@@ -391,7 +562,8 @@ impl ParserContext<'_> {
     /// Parses AST tokens from the token stream and add them to the nodes vector.
     ///
     /// Nodes are added to the list until `if`, `else`, `while`, `repeat`, `end`, `export`, `proc`,
-    /// or `begin` tokens are encountered, or an error occurs.
+    /// or `begin` tokens are encountered, or an error occurs. Nodes of the selected clause of a
+    /// `cfg` block are added to the list directly.
     pub fn parse_body(
         &mut self,
         tokens: &mut TokenStream,
@@ -425,6 +597,12 @@ impl ParserContext<'_> {
                     let body = self.parse_repeat(tokens)?;
                     nodes.push(body);
                 }
+                Token::CFG => {
+                    // nodes of the selected clause are added directly to this body
+                    let (cfg_nodes, cfg_locations) = self.parse_cfg(tokens)?.into_parts();
+                    locations.extend(cfg_locations.into_iter().take(cfg_nodes.len()));
+                    nodes.extend(cfg_nodes);
+                }
                 Token::END => {
                     locations.push(*token.location());
                     token.validate_end()?;
@@ -464,10 +642,10 @@ impl ParserContext<'_> {
         // based on the instruction, invoke the correct parser for the operation
         match op.parts()[0] {
             // ----- field operations -------------------------------------------------------------
-            "assert" => sys_ops::parse_assert(op, &self.local_constants),
-            "assertz" => sys_ops::parse_assertz(op, &self.local_constants),
-            "assert_eq" => sys_ops::parse_assert_eq(op, &self.local_constants),
-            "assert_eqw" => sys_ops::parse_assert_eqw(op, &self.local_constants),
+            "assert" => sys_ops::parse_assert(op, &mut self.constants()),
+            "assertz" => sys_ops::parse_assertz(op, &mut self.constants()),
+            "assert_eq" => sys_ops::parse_assert_eq(op, &mut self.constants()),
+            "assert_eqw" => sys_ops::parse_assert_eqw(op, &mut self.constants()),
 
             "add" => field_ops::parse_add(op),
             "sub" => field_ops::parse_sub(op),
@@ -505,9 +683,9 @@ impl ParserContext<'_> {
             // ----- u32 operations ---------------------------------------------------------------
             "u32test" => simple_instruction(op, U32Test),
             "u32testw" => simple_instruction(op, U32TestW),
-            "u32assert" => u32_ops::parse_u32assert(op, &self.local_constants),
-            "u32assert2" => u32_ops::parse_u32assert2(op, &self.local_constants),
-            "u32assertw" => u32_ops::parse_u32assertw(op, &self.local_constants),
+            "u32assert" => u32_ops::parse_u32assert(op, &mut self.constants()),
+            "u32assert2" => u32_ops::parse_u32assert2(op, &mut self.constants()),
+            "u32assertw" => u32_ops::parse_u32assertw(op, &mut self.constants()),
             "u32cast" => simple_instruction(op, U32Cast),
             "u32split" => simple_instruction(op, U32Split),
 
@@ -578,24 +756,24 @@ impl ParserContext<'_> {
             "cdropw" => simple_instruction(op, CDropW),
 
             // ----- input / output operations ----------------------------------------------------
-            "push" => io_ops::parse_push(op, &self.local_constants),
+            "push" => io_ops::parse_push(op, &mut self.constants()),
 
             "sdepth" => simple_instruction(op, Sdepth),
-            "locaddr" => io_ops::parse_locaddr(op, &self.local_constants),
+            "locaddr" => io_ops::parse_locaddr(op, &mut self.constants()),
             "caller" => simple_instruction(op, Caller), // TODO: error if not in SYSCALL (issue #551)
             "clk" => simple_instruction(op, Clk),
 
-            "mem_load" => io_ops::parse_mem_load(op, &self.local_constants),
-            "loc_load" => io_ops::parse_loc_load(op, &self.local_constants),
+            "mem_load" => io_ops::parse_mem_load(op, &mut self.constants()),
+            "loc_load" => io_ops::parse_loc_load(op, &mut self.constants()),
 
-            "mem_loadw" => io_ops::parse_mem_loadw(op, &self.local_constants),
-            "loc_loadw" => io_ops::parse_loc_loadw(op, &self.local_constants),
+            "mem_loadw" => io_ops::parse_mem_loadw(op, &mut self.constants()),
+            "loc_loadw" => io_ops::parse_loc_loadw(op, &mut self.constants()),
 
-            "mem_store" => io_ops::parse_mem_store(op, &self.local_constants),
-            "loc_store" => io_ops::parse_loc_store(op, &self.local_constants),
+            "mem_store" => io_ops::parse_mem_store(op, &mut self.constants()),
+            "loc_store" => io_ops::parse_loc_store(op, &mut self.constants()),
 
-            "mem_storew" => io_ops::parse_mem_storew(op, &self.local_constants),
-            "loc_storew" => io_ops::parse_loc_storew(op, &self.local_constants),
+            "mem_storew" => io_ops::parse_mem_storew(op, &mut self.constants()),
+            "loc_storew" => io_ops::parse_loc_storew(op, &mut self.constants()),
 
             "mem_stream" => simple_instruction(op, MemStream),
             "adv_pipe" => simple_instruction(op, AdvPipe),
@@ -628,15 +806,15 @@ impl ParserContext<'_> {
             "procref" => self.parse_procref(op),
 
             // ----- constant statements ----------------------------------------------------------
-            "const" => Err(ParsingError::const_invalid_scope(op)),
+            "const" | "region" | "error" => Err(ParsingError::const_invalid_scope(op)),
 
             // ----- debug decorators -------------------------------------------------------------
             "breakpoint" => simple_instruction(op, Breakpoint),
             "debug" => debug::parse_debug(op, self.num_proc_locals),
 
             // ----- event decorators -------------------------------------------------------------
-            "emit" => events::parse_emit(op, &self.local_constants),
-            "trace" => events::parse_trace(op, &self.local_constants),
+            "emit" => events::parse_emit(op, &mut self.constants()),
+            "trace" => events::parse_trace(op, &mut self.constants()),
 
             // ----- catch all --------------------------------------------------------------------
             _ => Err(ParsingError::invalid_op(op)),
@@ -659,15 +837,55 @@ impl ParserContext<'_> {
     }
 
     /// Returns true if a procedure with the specified name is present in the set of local or
-    /// re-exported procedures.
+    /// re-exported procedures, or if a procedure template with this name has been declared.
     fn contains_proc_name(&self, proc_name: &ProcedureName) -> bool {
-        self.local_procs.contains_key(proc_name) || self.reexported_procs.contains_key(proc_name)
+        self.local_procs.contains_key(proc_name)
+            || self.reexported_procs.contains_key(proc_name)
+            || self.templates.declared.contains_key(proc_name.as_str())
     }
 }
 
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Evaluates the condition of a `cfg` token against the provided constants (which include the
+/// defines).
+///
+/// The condition `cfg.<NAME>` holds if the value of the constant or define with the specified
+/// name is not zero, and the condition `cfg.<NAME>=<VALUE>` holds if this value is equal to the
+/// specified value.
+///
+/// # Errors
+/// Returns an error if neither a constant nor a define with the specified name is present, so
+/// that a misspelled name is not silently treated as a disabled condition.
+fn evaluate_cfg_condition(
+    token: &Token,
+    constants: &mut ConstantResolver,
+) -> Result<bool, ParsingError> {
+    debug_assert_eq!(token.parts()[0], Token::CFG);
+    const EXPECTED_FORMAT: &str = "cfg.<name>[=<value>]";
+    match token.num_parts() {
+        0 => unreachable!(),
+        1 => Err(ParsingError::missing_param(token, EXPECTED_FORMAT)),
+        2 => {
+            let (name, expected) = match token.parts()[1].split_once('=') {
+                Some((name, value)) => (name, Some(parse_const_value(token, value, constants)?)),
+                None => (token.parts()[1], None),
+            };
+            let name = CONSTANT_LABEL_PARSER
+                .parse_label(name)
+                .map_err(|err| ParsingError::invalid_const_name(token, err))?;
+            let value =
+                constants.get(name).ok_or_else(|| ParsingError::unknown_cfg_name(token, name))?;
+            Ok(match expected {
+                Some(expected) => value == expected,
+                None => value != 0,
+            })
+        }
+        _ => Err(ParsingError::extra_param(token)),
+    }
+}
+
 /// Validates that the provided token does not contain any immediate parameters and returns a node
 /// for the specified instruction.
 ///
@@ -1,7 +1,6 @@
 use super::{
-    parse_param_with_constant_lookup,
+    parse_param_with_constant_lookup, ConstantResolver,
     Instruction::*,
-    LocalConstMap,
     Node::{self, Instruction},
     ParsingError, Token,
 };
@@ -15,7 +14,7 @@ use super::{
 ///
 /// # Errors
 /// Returns an error if the constant does not exist or if the value is not a u32.
-pub fn parse_emit(op: &Token, constants: &LocalConstMap) -> Result<Node, ParsingError> {
+pub fn parse_emit(op: &Token, constants: &mut ConstantResolver) -> Result<Node, ParsingError> {
     debug_assert_eq!(op.parts()[0], "emit");
     match op.num_parts() {
         0 => unreachable!(),
@@ -37,7 +36,7 @@ pub fn parse_emit(op: &Token, constants: &LocalConstMap) -> Result<Node, Parsing
 ///
 /// # Errors
 /// Returns an error if the constant does not exist or if the value is not a u32.
-pub fn parse_trace(op: &Token, constants: &LocalConstMap) -> Result<Node, ParsingError> {
+pub fn parse_trace(op: &Token, constants: &mut ConstantResolver) -> Result<Node, ParsingError> {
     debug_assert_eq!(op.parts()[0], "trace");
     match op.num_parts() {
         0 => unreachable!(),
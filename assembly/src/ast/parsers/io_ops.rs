@@ -1,9 +1,9 @@
 use super::{
-    parse_checked_param, parse_hex_value, parse_param_with_constant_lookup, Endianness, Felt,
+    parse_checked_param, parse_hex_value, parse_param_with_constant_lookup, try_get_constant_value,
+    ConstantResolver, Endianness, Felt,
     Instruction::*,
-    LocalConstMap,
     Node::{self, Instruction},
-    ParsingError, Token, CONSTANT_LABEL_PARSER, HEX_CHUNK_SIZE,
+    ParsingError, Token, HEX_CHUNK_SIZE,
 };
 use crate::{StarkField, ADVICE_READ_LIMIT, MAX_PUSH_INPUTS};
 use alloc::vec::Vec;
@@ -24,7 +24,7 @@ const MAX_PUSH_PARTS: usize = MAX_PUSH_INPUTS + 1;
 /// # Errors
 /// Returns an error if the instruction token has invalid values or inappropriate number of
 /// values.
-pub fn parse_push(op: &Token, constants: &LocalConstMap) -> Result<Node, ParsingError> {
+pub fn parse_push(op: &Token, constants: &mut ConstantResolver) -> Result<Node, ParsingError> {
     debug_assert_eq!(op.parts()[0], "push");
     match op.num_parts() {
         0 => unreachable!("missing token"),
@@ -62,7 +62,7 @@ pub fn parse_push(op: &Token, constants: &LocalConstMap) -> Result<Node, Parsing
 /// # Errors
 /// Returns an error if the instruction token contains a wrong number of parameters, or if
 /// the provided parameter is not a u16 value.
-pub fn parse_locaddr(op: &Token, constants: &LocalConstMap) -> Result<Node, ParsingError> {
+pub fn parse_locaddr(op: &Token, constants: &mut ConstantResolver) -> Result<Node, ParsingError> {
     debug_assert_eq!(op.parts()[0], "locaddr");
     match op.num_parts() {
         0 => unreachable!(),
@@ -99,7 +99,7 @@ pub fn parse_adv_push(op: &Token) -> Result<Node, ParsingError> {
 /// # Errors
 /// Returns an error if the instruction token contains a wrong number of parameters, or if
 /// the provided parameter is not a u32 value.
-pub fn parse_mem_load(op: &Token, constants: &LocalConstMap) -> Result<Node, ParsingError> {
+pub fn parse_mem_load(op: &Token, constants: &mut ConstantResolver) -> Result<Node, ParsingError> {
     debug_assert_eq!(op.parts()[0], "mem_load");
     match op.num_parts() {
         0 => unreachable!(),
@@ -117,7 +117,7 @@ pub fn parse_mem_load(op: &Token, constants: &LocalConstMap) -> Result<Node, Par
 /// # Errors
 /// Returns an error if the instruction token contains a wrong number of parameters, or if
 /// the provided parameter is not a u16 value.
-pub fn parse_loc_load(op: &Token, constants: &LocalConstMap) -> Result<Node, ParsingError> {
+pub fn parse_loc_load(op: &Token, constants: &mut ConstantResolver) -> Result<Node, ParsingError> {
     debug_assert_eq!(op.parts()[0], "loc_load");
     match op.num_parts() {
         0 => unreachable!(),
@@ -136,7 +136,7 @@ pub fn parse_loc_load(op: &Token, constants: &LocalConstMap) -> Result<Node, Par
 /// # Errors
 /// Returns an error if the instruction token contains a wrong number of parameters, or if
 /// the provided parameter is not a u32 value.
-pub fn parse_mem_loadw(op: &Token, constants: &LocalConstMap) -> Result<Node, ParsingError> {
+pub fn parse_mem_loadw(op: &Token, constants: &mut ConstantResolver) -> Result<Node, ParsingError> {
     debug_assert_eq!(op.parts()[0], "mem_loadw");
     match op.num_parts() {
         0 => unreachable!(),
@@ -154,7 +154,7 @@ pub fn parse_mem_loadw(op: &Token, constants: &LocalConstMap) -> Result<Node, Pa
 /// # Errors
 /// Returns an error if the instruction token contains a wrong number of parameters, or if
 /// the provided parameter is not a u16 value.
-pub fn parse_loc_loadw(op: &Token, constants: &LocalConstMap) -> Result<Node, ParsingError> {
+pub fn parse_loc_loadw(op: &Token, constants: &mut ConstantResolver) -> Result<Node, ParsingError> {
     debug_assert_eq!(op.parts()[0], "loc_loadw");
     match op.num_parts() {
         0 => unreachable!(),
@@ -173,7 +173,7 @@ pub fn parse_loc_loadw(op: &Token, constants: &LocalConstMap) -> Result<Node, Pa
 /// # Errors
 /// Returns an error if the instruction token contains a wrong number of parameters, or if
 /// the provided parameter is not a u32 value.
-pub fn parse_mem_store(op: &Token, constants: &LocalConstMap) -> Result<Node, ParsingError> {
+pub fn parse_mem_store(op: &Token, constants: &mut ConstantResolver) -> Result<Node, ParsingError> {
     debug_assert_eq!(op.parts()[0], "mem_store");
     match op.num_parts() {
         0 => unreachable!(),
@@ -191,7 +191,7 @@ pub fn parse_mem_store(op: &Token, constants: &LocalConstMap) -> Result<Node, Pa
 /// # Errors
 /// Returns an error if the instruction token contains a wrong number of parameters, or if
 /// the provided parameter is not a u16 value.
-pub fn parse_loc_store(op: &Token, constants: &LocalConstMap) -> Result<Node, ParsingError> {
+pub fn parse_loc_store(op: &Token, constants: &mut ConstantResolver) -> Result<Node, ParsingError> {
     debug_assert_eq!(op.parts()[0], "loc_store");
     match op.num_parts() {
         0 => unreachable!(),
@@ -210,7 +210,10 @@ pub fn parse_loc_store(op: &Token, constants: &LocalConstMap) -> Result<Node, Pa
 /// # Errors
 /// Returns an error if the instruction token contains a wrong number of parameters, or if
 /// the provided parameter is not a u32 value.
-pub fn parse_mem_storew(op: &Token, constants: &LocalConstMap) -> Result<Node, ParsingError> {
+pub fn parse_mem_storew(
+    op: &Token,
+    constants: &mut ConstantResolver,
+) -> Result<Node, ParsingError> {
     debug_assert_eq!(op.parts()[0], "mem_storew");
     match op.num_parts() {
         0 => unreachable!(),
@@ -228,7 +231,10 @@ pub fn parse_mem_storew(op: &Token, constants: &LocalConstMap) -> Result<Node, P
 /// # Errors
 /// Returns an error if the instruction token contains a wrong number of parameters, or if
 /// the provided parameter is not a u16 value.
-pub fn parse_loc_storew(op: &Token, constants: &LocalConstMap) -> Result<Node, ParsingError> {
+pub fn parse_loc_storew(
+    op: &Token,
+    constants: &mut ConstantResolver,
+) -> Result<Node, ParsingError> {
     debug_assert_eq!(op.parts()[0], "loc_storew");
     match op.num_parts() {
         0 => unreachable!(),
@@ -246,7 +252,7 @@ pub fn parse_loc_storew(op: &Token, constants: &LocalConstMap) -> Result<Node, P
 
 /// Parses a list of parameters (each of which could be in decimal or hexadecimal form) and returns
 /// an appropriate push instruction node.
-fn parse_param_list(op: &Token, constants: &LocalConstMap) -> Result<Node, ParsingError> {
+fn parse_param_list(op: &Token, constants: &mut ConstantResolver) -> Result<Node, ParsingError> {
     let values =
         op.parts().iter().enumerate().skip(1).map(|(param_idx, &param_str)| {
             match param_str.strip_prefix("0x") {
@@ -260,25 +266,25 @@ fn parse_param_list(op: &Token, constants: &LocalConstMap) -> Result<Node, Parsi
             }
         });
 
-    build_push_many_instruction(values)
+    // the values are collected since resolving constant references requires mutable access to
+    // the constant resolver
+    let values: Vec<_> = values.collect();
+    build_push_many_instruction(values.into_iter())
 }
 
 /// Parses a non hexadecimal parameter and returns the value. Takes as argument a constant map
 /// for constant lookup.
 fn parse_non_hex_param_with_constants_lookup<R: RangeBounds<u64>>(
     op: &Token,
-    constants: &LocalConstMap,
+    constants: &mut ConstantResolver,
     param_idx: usize,
     range: R,
 ) -> Result<u64, ParsingError> {
     let param_str = op.parts()[param_idx];
-    // if we have a valid constant label then try and fetch it
-    match CONSTANT_LABEL_PARSER.parse_label(param_str) {
-        Ok(_) => constants
-            .get(param_str)
-            .cloned()
-            .ok_or_else(|| ParsingError::const_not_found(op)),
-        Err(_) => parse_checked_param(op, param_idx, range),
+    // if we have a valid constant reference then try and fetch it
+    match try_get_constant_value(op, param_str, constants)? {
+        Some(value) => Ok(value),
+        None => parse_checked_param(op, param_idx, range),
     }
 }
 
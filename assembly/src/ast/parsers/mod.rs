@@ -1,11 +1,13 @@
 use super::{
-    bound_into_included_u64, AdviceInjectorNode, CodeBody, Deserializable, Felt, Instruction,
-    InvocationTarget, LabelError, LibraryPath, LocalConstMap, LocalProcMap, ModuleImports, Node,
-    ParsingError, ProcedureAst, ProcedureId, ProcedureName, ReExportedProcMap, RpoDigest,
-    SliceReader, StarkField, Token, TokenStream, MAX_BODY_LEN, MAX_DOCS_LEN, MAX_LABEL_LEN,
-    MAX_STACK_WORD_OFFSET,
+    bound_into_included_u64, AdviceInjectorNode, CodeBody, ConstantProvider, Deserializable, Felt,
+    Instruction, InvocationTarget, LabelError, LibraryPath, LocalConstMap, LocalProcMap,
+    MemoryRegion, ModuleImports, NamedError, Node, ParsingError, ProcedureAst, ProcedureId,
+    ProcedureName, ReExportedProcMap, RpoDigest, SliceReader, SourceLocation, StarkField, Token,
+    TokenStream, MAX_BODY_LEN, MAX_DOCS_LEN, MAX_EXPORTED_CONSTANTS, MAX_LABEL_LEN,
+    MAX_MEMORY_REGIONS, MAX_NAMED_ERRORS, MAX_STACK_WORD_OFFSET, MAX_TEMPLATE_DEPTH,
 };
 use crate::HEX_CHUNK_SIZE;
+use alloc::collections::BTreeSet;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use core::{fmt::Display, ops::RangeBounds};
@@ -21,10 +23,14 @@ mod u32_ops;
 
 mod constants;
 use constants::calculate_const_value;
+pub use constants::ConstantResolver;
 
 mod context;
 pub use context::ParserContext;
 
+mod templates;
+pub use templates::TemplateRegistry;
+
 mod labels;
 pub use labels::{
     decode_hex_rpo_digest_label, CONSTANT_LABEL_PARSER, NAMESPACE_LABEL_PARSER,
@@ -41,38 +47,243 @@ pub enum Endianness {
 // PARSERS FUNCTIONS
 // ================================================================================================
 
-/// Parses all `const` statements into a map which maps a const name to a value
-pub fn parse_constants(tokens: &mut TokenStream) -> Result<LocalConstMap, ParsingError> {
-    // instantiate new constant map for this module
+/// Constants, memory regions, and named errors declared at the top of a module or a program.
+pub struct ConstantDeclarations {
+    /// All constants declared in the module, including the first addresses of the declared memory
+    /// regions and the codes of the named errors.
+    pub constants: LocalConstMap,
+    /// Constants (including the names of memory regions and named errors) exported from the module.
+    pub exported_constants: LocalConstMap,
+    /// Memory regions declared in the module, in the order of their declaration.
+    pub memory_regions: Vec<MemoryRegion>,
+    /// Named errors declared in the module, in the order of their declaration.
+    pub named_errors: Vec<NamedError>,
+}
+
+/// Parses all `const`, `region`, and `error` statements (as well as their `export.` variants) and
+/// adds the declared constants to the provided map of constants.
+///
+/// The name of a memory region is added to the constants as a constant which resolves to the
+/// first address of the region, and the name of a named error is added to the constants as a
+/// constant which resolves to the error code.
+///
+/// Constants exported from the imported modules and defines are resolved via the provided constant
+/// provider. A constant declared with the same name as a define is a default value of the define:
+/// the declaration is validated, but the constant resolves to the value of the define.
+///
+/// # Errors
+/// Returns an error if a declaration is malformed, if a constant, a region, or an error with the
+/// same name has already been declared, if a region or an error has the same name as a define, if
+/// two errors have the same code, or if exported declarations are used while `allow_export` is
+/// false.
+pub fn parse_constants(
+    tokens: &mut TokenStream,
+    import_info: &mut ModuleImports,
+    provider: &dyn ConstantProvider,
+    allow_export: bool,
+) -> Result<ConstantDeclarations, ParsingError> {
     let mut constants = LocalConstMap::new();
+    let mut exported_constants = LocalConstMap::new();
+    let mut memory_regions = Vec::new();
+    let mut named_errors: Vec<NamedError> = Vec::new();
+    let mut declared_names = BTreeSet::new();
 
-    // iterate over tokens until we find a const declaration
-    while let Some(token) = tokens.read() {
-        match token.parts()[0] {
-            Token::CONST => {
-                let (name, value) = parse_constant(token, &constants)?;
+    // iterate over tokens until we find a declaration of something other than a constant
+    loop {
+        // messages of named errors are specified via doc comments, which have to be taken from
+        // the stream before the declaration is read
+        let is_named_error = matches!(
+            tokens.read().map(Token::parts),
+            Some([Token::ERROR, ..] | [Token::EXPORT, Token::ERROR, ..])
+        );
+        let message = if is_named_error {
+            tokens.take_doc_comment_at(tokens.pos())
+        } else {
+            None
+        };
 
-                if constants.contains_key(&name) {
-                    return Err(ParsingError::duplicate_const_name(token, &name));
+        let Some(token) = tokens.read() else {
+            break;
+        };
+        let is_export = token.parts()[0] == Token::EXPORT;
+        let declaration = if is_export { &token.parts()[1..] } else { token.parts() };
+        let kind = match declaration.first() {
+            Some(&kind) if matches!(kind, Token::CONST | Token::REGION | Token::ERROR) => kind,
+            _ => break,
+        };
+
+        if is_export && !allow_export {
+            return Err(ParsingError::const_export_not_allowed(token));
+        }
+
+        let mut resolver = ConstantResolver::new(&constants, import_info, provider);
+        let (name, mut value) = match kind {
+            Token::REGION => {
+                if memory_regions.len() == MAX_MEMORY_REGIONS {
+                    return Err(ParsingError::too_many_memory_regions(
+                        memory_regions.len() + 1,
+                        MAX_MEMORY_REGIONS,
+                    ));
+                }
+                let region = parse_memory_region(token, declaration, &mut resolver, is_export)?;
+                let declared = (region.name().to_string(), region.address() as u64);
+                memory_regions.push(region);
+                declared
+            }
+            Token::ERROR => {
+                if named_errors.len() == MAX_NAMED_ERRORS {
+                    return Err(ParsingError::too_many_named_errors(
+                        named_errors.len() + 1,
+                        MAX_NAMED_ERRORS,
+                    ));
+                }
+                let error =
+                    parse_named_error(token, declaration, &mut resolver, message, is_export)?;
+                if let Some(other) = named_errors.iter().find(|other| other.code() == error.code())
+                {
+                    let reason =
+                        format!("code {} is already used by error {}", error.code(), other.name());
+                    return Err(ParsingError::invalid_named_error(token, &reason));
                 }
+                let declared = (error.name().to_string(), error.code() as u64);
+                named_errors.push(error);
+                declared
+            }
+            _ => parse_constant(token, declaration, &mut resolver)?,
+        };
 
-                constants.insert(name, value);
-                tokens.advance();
+        if !declared_names.insert(name.clone()) {
+            return Err(ParsingError::duplicate_const_name(token, &name));
+        }
+        if let Some(define) = provider.get_define(&name) {
+            if kind != Token::CONST {
+                return Err(ParsingError::duplicate_const_name(token, &name));
             }
-            _ => break,
+            value = define;
+        }
+
+        if is_export {
+            if exported_constants.len() == MAX_EXPORTED_CONSTANTS {
+                return Err(ParsingError::too_many_exported_constants(
+                    exported_constants.len() + 1,
+                    MAX_EXPORTED_CONSTANTS,
+                ));
+            }
+            exported_constants.insert(name.clone(), value);
+        }
+        constants.insert(name, value);
+        tokens.advance();
+    }
+
+    Ok(ConstantDeclarations {
+        constants,
+        exported_constants,
+        memory_regions,
+        named_errors,
+    })
+}
+
+/// Parses a named error declaration (the `error.<name>=<code>` parts of the token) and returns the
+/// declared error with the provided message.
+///
+/// The code can be specified via a constant expression, and must be a non-zero 32-bit integer.
+fn parse_named_error(
+    token: &Token,
+    declaration: &[&str],
+    constants: &mut ConstantResolver,
+    message: Option<String>,
+    is_export: bool,
+) -> Result<NamedError, ParsingError> {
+    const EXPECTED_FORMAT: &str = "error.<name>=<code>";
+    match declaration.len() {
+        0 => unreachable!(),
+        1 => Err(ParsingError::missing_param(token, EXPECTED_FORMAT)),
+        2 => {
+            let (name, code) = declaration[1]
+                .split_once('=')
+                .ok_or_else(|| ParsingError::missing_param(token, EXPECTED_FORMAT))?;
+            let name = CONSTANT_LABEL_PARSER
+                .parse_label(name)
+                .map_err(|err| ParsingError::invalid_const_name(token, err))?;
+
+            let code = parse_const_value(token, code, constants)?;
+            if code == 0 || code > u32::MAX as u64 {
+                let reason = "error code must be a non-zero 32-bit integer";
+                return Err(ParsingError::invalid_named_error(token, reason));
+            }
+
+            let message = match message {
+                Some(message) if message.len() <= MAX_DOCS_LEN => message,
+                Some(_) => {
+                    let reason = format!("error message is over {MAX_DOCS_LEN} bytes long");
+                    return Err(ParsingError::invalid_named_error(token, &reason));
+                }
+                None => {
+                    let reason = "error message must be specified via a doc comment";
+                    return Err(ParsingError::invalid_named_error(token, reason));
+                }
+            };
+
+            Ok(NamedError::new(name.to_string(), code as u32, message, is_export))
         }
+        _ => Err(ParsingError::extra_param(token)),
     }
+}
+
+/// Parses a memory region declaration (the `region.<name>.<num_words>=<address>` parts of the
+/// token) and returns the declared region.
+///
+/// The address can be specified via a constant expression, while the number of words must be a
+/// decimal number.
+fn parse_memory_region(
+    token: &Token,
+    declaration: &[&str],
+    constants: &mut ConstantResolver,
+    is_export: bool,
+) -> Result<MemoryRegion, ParsingError> {
+    const EXPECTED_FORMAT: &str = "region.<name>.<num_words>=<address>";
+    match declaration.len() {
+        0 => unreachable!(),
+        1 | 2 => Err(ParsingError::missing_param(token, EXPECTED_FORMAT)),
+        3 => {
+            let name = CONSTANT_LABEL_PARSER
+                .parse_label(declaration[1])
+                .map_err(|err| ParsingError::invalid_const_name(token, err))?;
+            let (num_words, address) = declaration[2]
+                .split_once('=')
+                .ok_or_else(|| ParsingError::missing_param(token, EXPECTED_FORMAT))?;
 
-    Ok(constants)
+            let num_words = match num_words.parse::<u32>() {
+                Ok(num_words) if num_words > 0 => num_words,
+                _ => {
+                    let reason = "number of words must be a positive 32-bit integer";
+                    return Err(ParsingError::invalid_memory_region(token, reason));
+                }
+            };
+            let address = parse_const_value(token, address, constants)?;
+            if address + num_words as u64 > MemoryRegion::ADDRESS_SPACE_SIZE {
+                let reason = "region extends beyond the last memory address";
+                return Err(ParsingError::invalid_memory_region(token, reason));
+            }
+
+            Ok(MemoryRegion::new(name.to_string(), address as u32, num_words, is_export))
+        }
+        _ => Err(ParsingError::extra_param(token)),
+    }
 }
 
-/// Parses a constant token and returns a (constant_name, constant_value) tuple
-fn parse_constant(token: &Token, constants: &LocalConstMap) -> Result<(String, u64), ParsingError> {
-    match token.num_parts() {
+/// Parses a constant declaration (the `const.<name>=<value>` parts of the token) and returns a
+/// (constant_name, constant_value) tuple
+fn parse_constant(
+    token: &Token,
+    declaration: &[&str],
+    constants: &mut ConstantResolver,
+) -> Result<(String, u64), ParsingError> {
+    match declaration.len() {
         0 => unreachable!(),
         1 => Err(ParsingError::missing_param(token, "const.<name>=<value>")),
         2 => {
-            let const_declaration: Vec<&str> = token.parts()[1].split('=').collect();
+            let const_declaration: Vec<&str> = declaration[1].split('=').collect();
             match const_declaration.len() {
                 0 => unreachable!(),
                 1 => Err(ParsingError::missing_param(token, "const.<name>=<value>")),
@@ -100,22 +311,39 @@ fn parse_constant(token: &Token, constants: &LocalConstMap) -> Result<(String, u
 fn try_get_constant_value(
     op: &Token,
     const_name: &str,
-    constants: &LocalConstMap,
+    constants: &mut ConstantResolver,
 ) -> Result<Option<u64>, ParsingError> {
-    match CONSTANT_LABEL_PARSER.parse_label(const_name) {
-        Ok(_) => constants
+    if is_constant_reference(const_name) {
+        constants
             .get(const_name)
             .ok_or_else(|| ParsingError::const_not_found(op))
-            .map(|v| Some(*v)),
-        Err(_) => Ok(None),
+            .map(Some)
+    } else {
+        Ok(None)
     }
 }
 
+/// Returns true if the provided string is a reference to a constant: either a name of a constant
+/// declared in the current module (e.g., `FOO`), or a name of a constant exported from an
+/// imported module (e.g., `bar::FOO`).
+fn is_constant_reference(reference: &str) -> bool {
+    let const_name = match reference.split_once(LibraryPath::PATH_DELIM) {
+        Some((module_name, const_name)) => {
+            if NAMESPACE_LABEL_PARSER.parse_label(module_name).is_err() {
+                return false;
+            }
+            const_name
+        }
+        None => reference,
+    };
+    CONSTANT_LABEL_PARSER.parse_label(const_name).is_ok()
+}
+
 /// Parses a constant value and ensures it falls within bounds specified by the caller.
 fn parse_const_value(
     op: &Token,
     const_value: &str,
-    constants: &LocalConstMap,
+    constants: &mut ConstantResolver,
 ) -> Result<u64, ParsingError> {
     let result = match const_value.parse::<u64>() {
         Ok(value) => value,
@@ -138,7 +366,7 @@ fn parse_const_value(
 pub(crate) fn parse_param_with_constant_lookup<R>(
     op: &Token,
     param_idx: usize,
-    constants: &LocalConstMap,
+    constants: &mut ConstantResolver,
 ) -> Result<R, ParsingError>
 where
     R: TryFrom<u64> + core::str::FromStr,
@@ -209,7 +437,7 @@ fn check_div_by_zero(value: u64, op: &Token, param_idx: usize) -> Result<(), Par
 ///
 /// The code is expected to be specified via the first instruction parameter and have the form
 /// `err=<code>`.
-fn parse_error_code(token: &Token, constants: &LocalConstMap) -> Result<u32, ParsingError> {
+fn parse_error_code(token: &Token, constants: &mut ConstantResolver) -> Result<u32, ParsingError> {
     let inst = token.parts()[0];
     let err_code_parts: Vec<&str> = token.parts()[1].split('=').collect();
     match err_code_parts.len() {
@@ -1,7 +1,6 @@
 use super::{
-    parse_error_code,
+    parse_error_code, ConstantResolver,
     Instruction::*,
-    LocalConstMap,
     Node::{self, Instruction},
     ParsingError, Token,
 };
@@ -12,7 +11,7 @@ use super::{
 /// # Errors
 /// Returns an error if the instruction token contains a wrong number of parameters, or if
 /// the provided parameter is not a u32 value.
-pub fn parse_assert(op: &Token, constants: &LocalConstMap) -> Result<Node, ParsingError> {
+pub fn parse_assert(op: &Token, constants: &mut ConstantResolver) -> Result<Node, ParsingError> {
     debug_assert_eq!(op.parts()[0], "assert");
     match op.num_parts() {
         0 => unreachable!(),
@@ -35,7 +34,7 @@ pub fn parse_assert(op: &Token, constants: &LocalConstMap) -> Result<Node, Parsi
 /// # Errors
 /// Returns an error if the instruction token contains a wrong number of parameters, or if
 /// the provided parameter is not a u32 value.
-pub fn parse_assertz(op: &Token, constants: &LocalConstMap) -> Result<Node, ParsingError> {
+pub fn parse_assertz(op: &Token, constants: &mut ConstantResolver) -> Result<Node, ParsingError> {
     debug_assert_eq!(op.parts()[0], "assertz");
     match op.num_parts() {
         0 => unreachable!(),
@@ -58,7 +57,7 @@ pub fn parse_assertz(op: &Token, constants: &LocalConstMap) -> Result<Node, Pars
 /// # Errors
 /// Returns an error if the instruction token contains a wrong number of parameters, or if
 /// the provided parameter is not a u32 value.
-pub fn parse_assert_eq(op: &Token, constants: &LocalConstMap) -> Result<Node, ParsingError> {
+pub fn parse_assert_eq(op: &Token, constants: &mut ConstantResolver) -> Result<Node, ParsingError> {
     debug_assert_eq!(op.parts()[0], "assert_eq");
     match op.num_parts() {
         0 => unreachable!(),
@@ -81,7 +80,10 @@ pub fn parse_assert_eq(op: &Token, constants: &LocalConstMap) -> Result<Node, Pa
 /// # Errors
 /// Returns an error if the instruction token contains a wrong number of parameters, or if
 /// the provided parameter is not a u32 value.
-pub fn parse_assert_eqw(op: &Token, constants: &LocalConstMap) -> Result<Node, ParsingError> {
+pub fn parse_assert_eqw(
+    op: &Token,
+    constants: &mut ConstantResolver,
+) -> Result<Node, ParsingError> {
     debug_assert_eq!(op.parts()[0], "assert_eqw");
     match op.num_parts() {
         0 => unreachable!(),
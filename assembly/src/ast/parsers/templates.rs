@@ -0,0 +1,258 @@
+use super::{
+    parse_const_value, ConstantResolver, LibraryPath, ParsingError, SourceLocation, Token,
+    TokenStream, CONSTANT_LABEL_PARSER, PROCEDURE_LABEL_PARSER,
+};
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+// PROCEDURE TEMPLATE
+// ================================================================================================
+
+/// A procedure parametrized by a list of constants, e.g. `proc.rotl_word<N>`.
+///
+/// A template is not parsed into an AST when it is declared. Instead, the tokens of its body are
+/// stored, and every time the template is invoked with a distinct list of arguments (e.g.,
+/// `exec.rotl_word<8>`), the parameters in these tokens are replaced with the arguments, and the
+/// resulting tokens are parsed into a regular local procedure. Source locations of the stored
+/// tokens are preserved, and thus errors in an instance point to the source of the template.
+#[derive(Debug, Clone)]
+pub struct ProcedureTemplate {
+    name: String,
+    params: Vec<String>,
+    num_locals: Option<String>,
+    header_location: SourceLocation,
+    body: Vec<(String, SourceLocation)>,
+}
+
+impl ProcedureTemplate {
+    // PARSER
+    // --------------------------------------------------------------------------------------------
+
+    /// Parses a template declaration (i.e., `proc.<name><<params>>[.<num_locals>]` followed by the
+    /// template body and the matching `end`) from the token stream.
+    ///
+    /// The body is not validated beyond making sure that it is terminated by a matching `end`.
+    pub fn parse(tokens: &mut TokenStream) -> Result<Self, ParsingError> {
+        let template_start = tokens.pos();
+        let header = tokens.read().expect("missing template header");
+        let header_location = *header.location();
+        let (name, params, num_locals) = parse_template_header(header)?;
+        tokens.advance();
+
+        // store the tokens of the body (including the `end` token) while keeping track of nested
+        // blocks to find the `end` token which terminates the template
+        let mut body = Vec::new();
+        let mut depth = 0_usize;
+        loop {
+            let Some(token) = tokens.read() else {
+                let header = tokens.read_at(template_start).expect("no template header");
+                return Err(ParsingError::unmatched_proc(header, &name));
+            };
+            body.push((token.to_string(), *token.location()));
+            match token.parts()[0] {
+                Token::IF | Token::WHILE | Token::REPEAT | Token::CFG => depth += 1,
+                Token::END if depth == 0 => break,
+                Token::END => depth -= 1,
+                Token::EXPORT | Token::PROC | Token::BEGIN => {
+                    let header = tokens.read_at(template_start).expect("no template header");
+                    return Err(ParsingError::unmatched_proc(header, &name));
+                }
+                _ => (),
+            }
+            tokens.advance();
+        }
+        tokens.advance();
+
+        Ok(Self {
+            name,
+            params,
+            num_locals,
+            header_location,
+            body,
+        })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the name of this template.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the number of parameters of this template.
+    pub fn num_params(&self) -> usize {
+        self.params.len()
+    }
+
+    // INSTANTIATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the tokens of a procedure declaration with the specified name (header, body, and
+    /// the terminating `end`) in which all parameters of this template are replaced with the
+    /// provided arguments, together with the source locations of these tokens.
+    ///
+    /// # Panics
+    /// Panics if the number of arguments is not equal to the number of template parameters.
+    pub fn instantiate(&self, proc_name: &str, args: &[u64]) -> (Vec<String>, Vec<SourceLocation>) {
+        assert_eq!(self.params.len(), args.len(), "invalid number of template arguments");
+
+        let header = match &self.num_locals {
+            Some(num_locals) => {
+                format!(
+                    "{}.{proc_name}.{}",
+                    Token::PROC,
+                    substitute(num_locals, &self.params, args)
+                )
+            }
+            None => format!("{}.{proc_name}", Token::PROC),
+        };
+
+        let mut tokens = Vec::with_capacity(self.body.len() + 1);
+        let mut locations = Vec::with_capacity(self.body.len() + 1);
+        tokens.push(header);
+        locations.push(self.header_location);
+        for (token, location) in self.body.iter() {
+            tokens.push(substitute(token, &self.params, args));
+            locations.push(*location);
+        }
+        (tokens, locations)
+    }
+}
+
+// TEMPLATE REGISTRY
+// ================================================================================================
+
+/// Procedure templates declared in a module or a program together with their instances.
+#[derive(Debug, Default)]
+pub struct TemplateRegistry {
+    /// Templates declared so far, keyed by their names.
+    pub declared: BTreeMap<String, ProcedureTemplate>,
+    /// Indexes of local procedures containing the instances of templates, keyed by the instance
+    /// names (e.g., `rotl_word<8>`).
+    pub instances: BTreeMap<String, u16>,
+    /// Names of the instances which are being parsed; a template invoked from the body of one of
+    /// its own instances is pushed on top of the instance which invoked it.
+    pub pending: Vec<String>,
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns true if the provided procedure name (e.g., `rotl_word<N>` or `rotl_word<8>`) refers to
+/// a procedure template.
+pub fn is_template_name(name: &str) -> bool {
+    name.contains('<')
+}
+
+/// Parses a template invocation target (e.g., `rotl_word<8>`) into the name of the template and
+/// the list of arguments. Arguments can be constant expressions referencing the provided
+/// constants.
+pub fn parse_template_invocation<'a>(
+    token: &Token,
+    target: &'a str,
+    constants: &mut ConstantResolver,
+) -> Result<(&'a str, Vec<u64>), ParsingError> {
+    let (name, args) = split_template_name(token, target)?;
+    if name.contains(LibraryPath::PATH_DELIM) {
+        let reason = "templates declared in other modules cannot be instantiated";
+        return Err(ParsingError::invalid_template(token, reason));
+    }
+
+    let args = args
+        .iter()
+        .map(|arg| parse_const_value(token, arg, constants))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((name, args))
+}
+
+/// Returns the name of the instance of the specified template with the provided arguments, e.g.
+/// `rotl_word<8>`. This is also the name of the local procedure containing the instance.
+pub fn instance_name(template_name: &str, args: &[u64]) -> String {
+    let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+    format!("{template_name}<{}>", args.join(","))
+}
+
+/// Parses the header of a template declaration and returns the name of the template, the names of
+/// its parameters, and the (unresolved) number of its locals.
+fn parse_template_header(
+    token: &Token,
+) -> Result<(String, Vec<String>, Option<String>), ParsingError> {
+    debug_assert_eq!(token.parts()[0], Token::PROC);
+    let num_locals = match token.num_parts() {
+        0 | 1 => unreachable!(),
+        2 => None,
+        3 => Some(token.parts()[2].to_string()),
+        _ => return Err(ParsingError::extra_param(token)),
+    };
+
+    let (name, params) = split_template_name(token, token.parts()[1])?;
+    let name = PROCEDURE_LABEL_PARSER
+        .parse_label(name)
+        .map_err(|err| ParsingError::invalid_proc_name(token, err))?;
+
+    let mut param_names: Vec<String> = Vec::with_capacity(params.len());
+    for param in params {
+        let param = CONSTANT_LABEL_PARSER
+            .parse_label(param)
+            .map_err(|err| ParsingError::invalid_const_name(token, err))?;
+        if param_names.iter().any(|name| name == param) {
+            let reason = format!("duplicate parameter name {param}");
+            return Err(ParsingError::invalid_template(token, &reason));
+        }
+        param_names.push(param.to_string());
+    }
+
+    Ok((name.to_string(), param_names, num_locals))
+}
+
+/// Splits a template name with a list of parameters or arguments (e.g., `rotl_word<N>`) into the
+/// name and the list.
+fn split_template_name<'a>(
+    token: &Token,
+    target: &'a str,
+) -> Result<(&'a str, Vec<&'a str>), ParsingError> {
+    let (name, params) = target
+        .strip_suffix('>')
+        .and_then(|target| target.split_once('<'))
+        .ok_or_else(|| ParsingError::invalid_template(token, "expected `<name><<params>>`"))?;
+
+    let params: Vec<&str> = params.split(',').collect();
+    if params.iter().any(|param| param.is_empty()) {
+        let reason = "template parameters and arguments cannot be empty";
+        return Err(ParsingError::invalid_template(token, reason));
+    }
+    Ok((name, params))
+}
+
+/// Replaces all occurrences of the specified parameters in the provided token with the values of
+/// the corresponding arguments.
+///
+/// A parameter is replaced only if it is a complete identifier (i.e., `N` is replaced in `push.N`
+/// and `push.N*2` but not in `push.NUM`), and is not a constant exported from another module
+/// (e.g., `layout::N`).
+fn substitute(token: &str, params: &[String], args: &[u64]) -> String {
+    let mut result = String::with_capacity(token.len());
+    let mut rest = token;
+    while let Some(start) = rest.find(is_identifier_char) {
+        let (prefix, tail) = rest.split_at(start);
+        let end = tail.find(|c: char| !is_identifier_char(c)).unwrap_or(tail.len());
+        let (identifier, tail) = tail.split_at(end);
+
+        result.push_str(prefix);
+        match params.iter().position(|param| param == identifier) {
+            Some(idx) if !result.ends_with(':') => result.push_str(&args[idx].to_string()),
+            _ => result.push_str(identifier),
+        }
+        rest = tail;
+    }
+    result.push_str(rest);
+    result
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
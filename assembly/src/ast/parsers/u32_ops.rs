@@ -1,7 +1,6 @@
 use super::{
-    check_div_by_zero, parse_checked_param, parse_error_code, parse_param,
+    check_div_by_zero, parse_checked_param, parse_error_code, parse_param, ConstantResolver,
     Instruction::*,
-    LocalConstMap,
     Node::{self, Instruction},
     ParsingError, Token,
 };
@@ -16,7 +15,7 @@ use crate::{MAX_U32_ROTATE_VALUE, MAX_U32_SHIFT_VALUE};
 /// # Errors
 /// Returns an error if the instruction token contains wrong number of parameters, or if the
 /// provided parameter is not a u32 value.
-pub fn parse_u32assert(op: &Token, constants: &LocalConstMap) -> Result<Node, ParsingError> {
+pub fn parse_u32assert(op: &Token, constants: &mut ConstantResolver) -> Result<Node, ParsingError> {
     debug_assert_eq!(op.parts()[0], "u32assert");
     match op.num_parts() {
         0 => unreachable!(),
@@ -39,7 +38,10 @@ pub fn parse_u32assert(op: &Token, constants: &LocalConstMap) -> Result<Node, Pa
 /// # Errors
 /// Returns an error if the instruction token contains wrong number of parameters, or if the
 /// provided parameter is not a u32 value.
-pub fn parse_u32assert2(op: &Token, constants: &LocalConstMap) -> Result<Node, ParsingError> {
+pub fn parse_u32assert2(
+    op: &Token,
+    constants: &mut ConstantResolver,
+) -> Result<Node, ParsingError> {
     debug_assert_eq!(op.parts()[0], "u32assert2");
     match op.num_parts() {
         0 => unreachable!(),
@@ -62,7 +64,10 @@ pub fn parse_u32assert2(op: &Token, constants: &LocalConstMap) -> Result<Node, P
 /// # Errors
 /// Returns an error if the instruction token contains wrong number of parameters, or if the
 /// provided parameter is not a u32 value.
-pub fn parse_u32assertw(op: &Token, constants: &LocalConstMap) -> Result<Node, ParsingError> {
+pub fn parse_u32assertw(
+    op: &Token,
+    constants: &mut ConstantResolver,
+) -> Result<Node, ParsingError> {
     debug_assert_eq!(op.parts()[0], "u32assertw");
     match op.num_parts() {
         0 => unreachable!(),
@@ -1,3 +1,4 @@
+use alloc::collections::BTreeMap;
 use alloc::vec::Vec;
 
 use crate::ast::MAX_BODY_LEN;
@@ -9,11 +10,12 @@ use super::{
     imports::ModuleImports,
     instrument,
     nodes::Node,
-    parsers::{parse_constants, ParserContext},
+    parsers::{parse_constants, ParserContext, TemplateRegistry},
     serde::AstSerdeOptions,
     {
-        format::*, sort_procs_into_vec, LocalProcMap, ProcedureAst, ReExportedProcMap,
-        MAX_LOCAL_PROCS,
+        format::*, sort_procs_into_vec, ConstantProvider, LibraryPath, LocalProcMap, MemoryRegion,
+        ModuleAst, NamedError, ProcedureAst, ReExportedProcMap, MAX_LOCAL_PROCS,
+        MAX_MEMORY_REGIONS, MAX_NAMED_ERRORS,
     },
     {
         ByteReader, ByteWriter, Deserializable, DeserializationError, ParsingError, Serializable,
@@ -31,12 +33,15 @@ use std::{fs, io, path::Path};
 ///
 /// A program AST consists of a body of the program, a list of internal procedure ASTs, a list of
 /// imported libraries, a map from procedure ids to procedure names for imported procedures used in
-/// the module, and the source location of the program.
+/// the module, a list of declared memory regions, a list of declared named errors, and the source
+/// location of the program.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ProgramAst {
     pub(super) body: CodeBody,
     pub(super) local_procs: Vec<ProcedureAst>,
     pub(super) import_info: ModuleImports,
+    pub(super) memory_regions: Vec<MemoryRegion>,
+    pub(super) named_errors: Vec<NamedError>,
     pub(super) start: SourceLocation,
 }
 
@@ -66,6 +71,8 @@ impl ProgramAst {
             body,
             local_procs,
             import_info: Default::default(),
+            memory_regions: Vec::new(),
+            named_errors: Vec::new(),
             start,
         })
     }
@@ -80,6 +87,30 @@ impl ProgramAst {
         self
     }
 
+    /// Adds the provided memory regions to the program.
+    ///
+    /// # Panics
+    /// Panics if memory regions have already been added, or if the number of regions is greater
+    /// than the maximum number of regions a program can declare.
+    pub fn with_memory_regions(mut self, regions: Vec<MemoryRegion>) -> Self {
+        assert!(self.memory_regions.is_empty(), "memory regions have already been added");
+        assert!(regions.len() <= MAX_MEMORY_REGIONS, "too many memory regions");
+        self.memory_regions = regions;
+        self
+    }
+
+    /// Adds the provided named errors to the program.
+    ///
+    /// # Panics
+    /// Panics if named errors have already been added, or if the number of errors is greater than
+    /// the maximum number of errors a program can declare.
+    pub fn with_named_errors(mut self, errors: Vec<NamedError>) -> Self {
+        assert!(self.named_errors.is_empty(), "named errors have already been added");
+        assert!(errors.len() <= MAX_NAMED_ERRORS, "too many named errors");
+        self.named_errors = errors;
+        self
+    }
+
     /// Binds the provided `locations` to the nodes of this program's body.
     ///
     /// The `start` location points to the `begin` token which does not have its own node.
@@ -119,23 +150,47 @@ impl ProgramAst {
         &self.import_info
     }
 
+    /// Returns a list of memory regions declared in this program.
+    pub fn memory_regions(&self) -> &[MemoryRegion] {
+        &self.memory_regions
+    }
+
+    /// Returns a list of named errors declared in this program.
+    pub fn named_errors(&self) -> &[NamedError] {
+        &self.named_errors
+    }
+
     // PARSER
     // --------------------------------------------------------------------------------------------
     /// Parses the provided source into a [ProgramAst].
     ///
     /// A program consist of a body and a set of internal (i.e., not exported) procedures.
-    #[instrument(name = "parse_program", skip_all)]
+    ///
+    /// Constants exported from imported modules cannot be referenced by the parsed program; use
+    /// [ProgramAst::parse_with_constants()] to parse programs which reference such constants.
     pub fn parse(source: &str) -> Result<ProgramAst, ParsingError> {
+        Self::parse_with_constants(source, &BTreeMap::<LibraryPath, ModuleAst>::new())
+    }
+
+    /// Parses the provided source into a [ProgramAst], resolving references to constants exported
+    /// from imported modules (e.g., `push.layout::BASE_PTR`) via the provided constant provider.
+    #[instrument(name = "parse_program", skip_all)]
+    pub fn parse_with_constants(
+        source: &str,
+        constants: &dyn ConstantProvider,
+    ) -> Result<ProgramAst, ParsingError> {
         let mut tokens = TokenStream::new(source)?;
         let mut import_info = ModuleImports::parse(&mut tokens)?;
-        let local_constants = parse_constants(&mut tokens)?;
+        let declarations = parse_constants(&mut tokens, &mut import_info, constants, false)?;
 
         let mut context = ParserContext {
             import_info: &mut import_info,
             local_procs: LocalProcMap::default(),
             reexported_procs: ReExportedProcMap::default(),
-            local_constants,
+            local_constants: declarations.constants,
+            constant_provider: constants,
             num_proc_locals: 0,
+            templates: TemplateRegistry::default(),
         };
 
         context.parse_procedures(&mut tokens, false)?;
@@ -189,7 +244,9 @@ impl ProgramAst {
         let (nodes, locations) = body.into_parts();
         Ok(Self::new(nodes, local_procs)?
             .with_source_locations(locations, start)
-            .with_import_info(import_info))
+            .with_import_info(import_info)
+            .with_memory_regions(declarations.memory_regions)
+            .with_named_errors(declarations.named_errors))
     }
 
     // SERIALIZATION / DESERIALIZATION
@@ -211,6 +268,16 @@ impl ProgramAst {
             self.import_info.write_into(target);
         }
 
+        // serialize memory regions
+        assert!(self.memory_regions.len() <= MAX_MEMORY_REGIONS, "too many memory regions");
+        target.write_u16(self.memory_regions.len() as u16);
+        target.write_many(&self.memory_regions);
+
+        // serialize named errors
+        assert!(self.named_errors.len() <= MAX_NAMED_ERRORS, "too many named errors");
+        target.write_u16(self.named_errors.len() as u16);
+        target.write_many(&self.named_errors);
+
         // serialize procedures
         assert!(self.local_procs.len() <= MAX_LOCAL_PROCS, "too many local procs");
         target.write_u16(self.local_procs.len() as u16);
@@ -246,6 +313,14 @@ impl ProgramAst {
             ModuleImports::default()
         };
 
+        // deserialize memory regions
+        let num_memory_regions = source.read_u16()? as usize;
+        let memory_regions = source.read_many::<MemoryRegion>(num_memory_regions)?;
+
+        // deserialize named errors
+        let num_named_errors = source.read_u16()? as usize;
+        let named_errors = source.read_many::<NamedError>(num_named_errors)?;
+
         // deserialize local procs
         let num_local_procs = source.read_u16()?.into();
         let local_procs = source.read_many::<ProcedureAst>(num_local_procs)?;
@@ -256,7 +331,10 @@ impl ProgramAst {
 
         match Self::new(nodes, local_procs) {
             Err(err) => Err(DeserializationError::UnknownError(err.message().clone())),
-            Ok(res) => Ok(res.with_import_info(import_info)),
+            Ok(res) => Ok(res
+                .with_import_info(import_info)
+                .with_memory_regions(memory_regions)
+                .with_named_errors(named_errors)),
         }
     }
 
@@ -346,6 +424,17 @@ impl fmt::Display for ProgramAst {
             writeln!(f)?;
         }
 
+        // Memory regions and named errors
+        for region in self.memory_regions.iter() {
+            writeln!(f, "{region}")?;
+        }
+        for error in self.named_errors.iter() {
+            writeln!(f, "{error}")?;
+        }
+        if !self.memory_regions.is_empty() || !self.named_errors.is_empty() {
+            writeln!(f)?;
+        }
+
         let invoked_procs = self.import_info.invoked_procs();
         let context = AstFormatterContext::new(&self.local_procs, invoked_procs);
 
@@ -3,10 +3,33 @@
 //! Structs in this module are used to serialize and deserialize ASTs into a binary format.
 
 use super::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+use alloc::format;
+
+/// Version of the binary format used for serializing ASTs. This must be incremented every time
+/// the encoding of ASTs or any of their components changes.
+///
+/// Versions 0 and 1 are not used, since ASTs serialized before the format was versioned start
+/// with a boolean serialization option instead of the format version.
+pub(crate) const AST_FORMAT_VERSION: u8 = 3;
+
+/// Reads the format version from the specified source, and returns an error if it does not match
+/// [AST_FORMAT_VERSION].
+pub(crate) fn read_format_version<R: ByteReader>(
+    source: &mut R,
+) -> Result<(), DeserializationError> {
+    let version = source.read_u8()?;
+    if version != AST_FORMAT_VERSION {
+        return Err(DeserializationError::InvalidValue(format!(
+            "unsupported AST format version {version}, expected {AST_FORMAT_VERSION}"
+        )));
+    }
+    Ok(())
+}
 
 /// Serialization options
 /// Used to enable or disable serialization of parts of the AST.  Serialization options are
-/// serialized along with the AST to make the serialization format self-contained.
+/// serialized along with the AST to make the serialization format self-contained, and are
+/// preceded by the version of the serialization format.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct AstSerdeOptions {
     pub serialize_imports: bool,
@@ -20,12 +43,14 @@ impl AstSerdeOptions {
 
 impl Serializable for AstSerdeOptions {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(AST_FORMAT_VERSION);
         target.write_bool(self.serialize_imports);
     }
 }
 
 impl Deserializable for AstSerdeOptions {
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        read_format_version(source)?;
         let serialize_imports = source.read_bool()?;
         Ok(Self::new(serialize_imports))
     }
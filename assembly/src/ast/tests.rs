@@ -1,13 +1,14 @@
 use super::{
-    AstSerdeOptions, CodeBody, Felt, Instruction, LocalProcMap, ModuleAst, Node, ParsingError,
-    ProcedureAst, ProcedureId, ProcedureName, ProgramAst, SourceLocation, Token,
+    AstSerdeOptions, CodeBody, Felt, Instruction, LibraryPath, LocalProcMap, MemoryRegion,
+    ModuleAst, NamedError, Node, ParsingError, ProcedureAst, ProcedureId, ProcedureName,
+    ProgramAst, SourceLocation, Token,
 };
 use alloc::{
     collections::BTreeMap,
     string::{String, ToString},
     vec::Vec,
 };
-use vm_core::utils::SliceReader;
+use vm_core::{utils::SliceReader, DebugOptions};
 
 // UNIT TESTS
 // ================================================================================================
@@ -228,9 +229,12 @@ fn test_ast_parsing_adv_injection() {
     use super::AdviceInjectorNode::*;
     use Instruction::AdvInject;
 
-    let source = "begin adv.push_u64div adv.push_mapval adv.push_smtget adv.insert_mem end";
+    let source = "begin adv.push_u64div adv.push_u256div adv.push_lower_bound adv.push_mapval \
+        adv.push_smtget adv.insert_mem end";
     let nodes: Vec<Node> = vec![
         Node::Instruction(AdvInject(PushU64Div)),
+        Node::Instruction(AdvInject(PushU256Div)),
+        Node::Instruction(AdvInject(PushLowerBound)),
         Node::Instruction(AdvInject(PushMapVal)),
         Node::Instruction(AdvInject(PushSmtGet)),
         Node::Instruction(AdvInject(InsertMem)),
@@ -971,6 +975,18 @@ fn test_ast_module_serde_imports_not_serialized() {
     assert_correct_module_serialization(source, false);
 }
 
+#[test]
+fn test_ast_module_serde_format_version() {
+    let module = ModuleAst::parse("export.foo add end").unwrap();
+    let mut bytes = module.to_bytes(AstSerdeOptions::new(true));
+    assert_eq!(ModuleAst::from_bytes(&bytes).unwrap(), module);
+
+    // ASTs serialized before the format was versioned start with a serialization option
+    bytes[0] = 1;
+    let err = ModuleAst::from_bytes(&bytes).unwrap_err();
+    assert!(err.to_string().contains("unsupported AST format version 1"), "{err}");
+}
+
 #[test]
 fn test_repeat_with_constant_count() {
     let source = "\
@@ -1003,6 +1019,477 @@ fn test_repeat_with_constant_count() {
     assert_program_output(source, BTreeMap::new(), nodes);
 }
 
+// EXPORTED CONSTANTS
+// ================================================================================================
+
+#[test]
+fn test_ast_parsing_exported_constants() {
+    let source = "\
+    export.const.A=3
+    const.B=A*2
+    export.const.C=B+1
+
+    export.foo
+        push.A
+        push.C
+    end";
+
+    let module = ModuleAst::parse(source).unwrap();
+    let expected = BTreeMap::from([("A".to_string(), 3), ("C".to_string(), 7)]);
+    assert_eq!(module.exported_constants(), &expected);
+    assert_correct_module_serialization(source, true);
+    assert_correct_module_serialization(source, false);
+
+    // constants cannot be exported from programs
+    let source = "\
+    export.const.A=3
+
+    begin
+        push.A
+    end";
+    let err = ProgramAst::parse(source).unwrap_err();
+    assert!(err.to_string().contains("exported constants not allowed"));
+}
+
+#[test]
+fn test_ast_parsing_imported_constants() {
+    let layout = ModuleAst::parse("export.const.BASE_PTR=100\nexport.const.SIZE=4").unwrap();
+    let modules = BTreeMap::from([(LibraryPath::new("test::layout").unwrap(), layout)]);
+
+    let source = "\
+    use.test::layout
+
+    const.END_PTR=layout::BASE_PTR+layout::SIZE
+
+    begin
+        push.layout::BASE_PTR
+        mem_load.END_PTR
+    end";
+
+    let program = ProgramAst::parse_with_constants(source, &modules).unwrap();
+    let nodes = vec![
+        Node::Instruction(Instruction::PushU8(100)),
+        Node::Instruction(Instruction::MemLoadImm(104)),
+    ];
+    assert_eq!(program.body().nodes(), nodes);
+
+    // the module is recorded as used, and this information survives serialization
+    let layout_path = LibraryPath::new("test::layout").unwrap();
+    assert_eq!(program.import_info().const_module_paths(), vec![&layout_path]);
+    let bytes = program.to_bytes(AstSerdeOptions::new(true));
+    let program_deserialized = ProgramAst::from_bytes(&bytes).unwrap();
+    assert_eq!(program.import_info(), program_deserialized.import_info());
+
+    // imported constants cannot be resolved without a constant provider
+    let err = ProgramAst::parse(source).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("constant with name layout::BASE_PTR was not initialized"));
+
+    // constants can be imported only from imported modules
+    let source = "\
+    begin
+        push.layout::BASE_PTR
+    end";
+    let err = ProgramAst::parse_with_constants(source, &modules).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("constant used in operation `push.layout::BASE_PTR` not found"));
+}
+
+// MEMORY REGIONS
+// ================================================================================================
+
+#[test]
+fn test_ast_parsing_memory_regions() {
+    let source = "\
+    const.BASE=1000
+    export.region.NOTES.64=BASE
+    region.SCRATCH.4=NOTES+64
+
+    export.foo
+        mem_storew.NOTES
+        mem_loadw.SCRATCH
+    end";
+
+    let module = ModuleAst::parse(source).unwrap();
+    let expected = vec![
+        MemoryRegion::new("NOTES".to_string(), 1000, 64, true),
+        MemoryRegion::new("SCRATCH".to_string(), 1064, 4, false),
+    ];
+    assert_eq!(module.memory_regions(), &expected);
+    assert_eq!(module.exported_constants(), &BTreeMap::from([("NOTES".to_string(), 1000)]));
+    assert!(expected[0].contains(1063) && !expected[0].contains(1064));
+    assert!(!expected[0].overlaps(&expected[1]));
+    assert_correct_module_serialization(source, true);
+    assert_correct_module_serialization(source, false);
+
+    // region names resolve to the first addresses of the regions
+    let body = module.procs()[0].body.nodes();
+    assert_eq!(body[0], Node::Instruction(Instruction::MemStoreWImm(1000)));
+    assert_eq!(body[1], Node::Instruction(Instruction::MemLoadWImm(1064)));
+
+    // regions can be declared in programs, but cannot be exported from them
+    let source = "\
+    region.BUFFER.8=100
+
+    begin
+        mem_loadw.BUFFER
+    end";
+    let program = ProgramAst::parse(source).unwrap();
+    assert_eq!(
+        program.memory_regions(),
+        &[MemoryRegion::new("BUFFER".to_string(), 100, 8, false)]
+    );
+    assert_correct_program_serialization(source, true);
+    let err = ProgramAst::parse(&format!("export.{source}")).unwrap_err();
+    assert!(err.to_string().contains("exported constants not allowed"));
+}
+
+#[test]
+fn test_ast_parsing_memory_regions_fail() {
+    let sources = [
+        ("region.EMPTY.0=100", "number of words must be a positive 32-bit integer"),
+        ("region.LAST.2=4294967295", "region extends beyond the last memory address"),
+        ("region.lower.2=100", "invalid constant name"),
+        ("region.NO_SIZE=100", "expected format `region.<name>.<num_words>=<address>`"),
+    ];
+    for (source, message) in sources {
+        let err = ModuleAst::parse(source).unwrap_err();
+        assert!(err.to_string().contains(message), "{err}");
+    }
+
+    // regions cannot be declared inside procedures
+    let source = "\
+    export.foo
+        region.BUFFER.8=100
+    end";
+    assert!(ModuleAst::parse(source).is_err());
+}
+
+// NAMED ERRORS
+// ================================================================================================
+
+#[test]
+fn test_ast_parsing_named_errors() {
+    let source = "\
+    const.BASE=0x00010000
+
+    #! Account balance is insufficient
+    #! to cover the transfer.
+    export.error.INSUFFICIENT_BALANCE=BASE+1
+    #! Note is not valid.
+    error.INVALID_NOTE=BASE+2
+
+    export.foo
+        assert.err=INSUFFICIENT_BALANCE
+        assert_eq.err=INVALID_NOTE
+    end";
+
+    let module = ModuleAst::parse(source).unwrap();
+    let expected = vec![
+        NamedError::new(
+            "INSUFFICIENT_BALANCE".to_string(),
+            0x10001,
+            "Account balance is insufficient\nto cover the transfer.".to_string(),
+            true,
+        ),
+        NamedError::new(
+            "INVALID_NOTE".to_string(),
+            0x10002,
+            "Note is not valid.".to_string(),
+            false,
+        ),
+    ];
+    assert_eq!(module.named_errors(), &expected);
+    assert_eq!(
+        module.exported_constants(),
+        &BTreeMap::from([("INSUFFICIENT_BALANCE".to_string(), 0x10001)])
+    );
+    assert_correct_module_serialization(source, true);
+    assert_correct_module_serialization(source, false);
+
+    // error names resolve to the error codes
+    let body = module.procs()[0].body.nodes();
+    assert_eq!(body[0], Node::Instruction(Instruction::AssertWithError(0x10001)));
+    assert_eq!(body[1], Node::Instruction(Instruction::AssertEqWithError(0x10002)));
+
+    // the module is formatted with the error messages
+    let formatted = ModuleAst::parse(&module.to_string()).unwrap();
+    assert_eq!(formatted.named_errors(), &expected);
+
+    // errors can be declared in programs, but cannot be exported from them
+    let source = "\
+    #! Value is out of range.
+    error.OUT_OF_RANGE=7
+
+    begin
+        u32assert.err=OUT_OF_RANGE
+    end";
+    let program = ProgramAst::parse(source).unwrap();
+    assert_eq!(
+        program.named_errors(),
+        &[NamedError::new(
+            "OUT_OF_RANGE".to_string(),
+            7,
+            "Value is out of range.".to_string(),
+            false
+        )]
+    );
+    assert_correct_program_serialization(source, true);
+    let err = ProgramAst::parse(&source.replace("error.", "export.error.")).unwrap_err();
+    assert!(err.to_string().contains("exported constants not allowed"));
+}
+
+#[test]
+fn test_ast_parsing_named_errors_fail() {
+    let sources = [
+        ("#! Message.\nerror.ZERO=0", "error code must be a non-zero 32-bit integer"),
+        (
+            "#! Message.\nerror.LARGE=4294967296",
+            "error code must be a non-zero 32-bit integer",
+        ),
+        ("error.NO_MESSAGE=1", "error message must be specified via a doc comment"),
+        ("#! Message.\nerror.lower=1", "invalid constant name"),
+        ("#! Message.\nerror.NO_CODE", "expected format `error.<name>=<code>`"),
+        ("#! A.\nerror.A=1\n#! B.\nerror.B=1", "code 1 is already used by error A"),
+        ("#! A.\nerror.A=1\nconst.A=2", "duplicate constant name"),
+        ("#! Message.\nconst.A=1", "comment is not immediately followed"),
+    ];
+    for (source, message) in sources {
+        let err = ModuleAst::parse(source).unwrap_err();
+        assert!(err.to_string().contains(message), "{err}");
+    }
+
+    // errors cannot be declared inside procedures
+    let source = "\
+    export.foo
+        error.ERR=1
+    end";
+    assert!(ModuleAst::parse(source).is_err());
+}
+
+// PROCEDURE TEMPLATES
+// ================================================================================================
+
+#[test]
+fn test_ast_parsing_procedure_templates() {
+    let source = "\
+    const.SHIFT=8
+
+    proc.rotl_word<N>
+        u32rotl.N
+        push.N
+    end
+
+    proc.mix<A,B>.1
+        loc_store.0
+        exec.rotl_word<A>
+        exec.rotl_word<B+1>
+    end
+
+    proc.drop_one<N>
+        push.1
+        drop
+    end
+
+    export.foo
+        exec.rotl_word<8>
+        exec.rotl_word<SHIFT>
+        exec.mix<3,8>
+        call.rotl_word<4>
+        exec.drop_one<1>
+        exec.drop_one<2>
+    end";
+
+    // each distinct instance is added to the local procedures once, before the procedure which
+    // invokes it for the first time
+    let module = ModuleAst::parse(source).unwrap();
+    let names: Vec<&str> = module.procs().iter().map(|proc| proc.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "rotl_word<8>",
+            "rotl_word<3>",
+            "rotl_word<9>",
+            "mix<3,8>",
+            "rotl_word<4>",
+            "drop_one<1>",
+            "drop_one<2>",
+            "foo"
+        ]
+    );
+
+    let procs = module.procs();
+    assert_eq!(
+        procs[0].body.nodes(),
+        [
+            Node::Instruction(Instruction::U32RotlImm(8)),
+            Node::Instruction(Instruction::PushU8(8)),
+        ]
+    );
+    assert_eq!(procs[3].num_locals, 1);
+    assert_eq!(
+        procs[3].body.nodes(),
+        [
+            Node::Instruction(Instruction::LocStore(0)),
+            Node::Instruction(Instruction::ExecLocal(1)),
+            Node::Instruction(Instruction::ExecLocal(2)),
+        ]
+    );
+    assert_eq!(
+        procs[7].body.nodes(),
+        [
+            Node::Instruction(Instruction::ExecLocal(0)),
+            Node::Instruction(Instruction::ExecLocal(0)),
+            Node::Instruction(Instruction::ExecLocal(3)),
+            Node::Instruction(Instruction::CallLocal(4)),
+            Node::Instruction(Instruction::ExecLocal(5)),
+            Node::Instruction(Instruction::ExecLocal(6)),
+        ]
+    );
+
+    // instances are regular procedures, and thus modules with templates can be serialized
+    assert_correct_module_serialization(source, true);
+
+    // names of instances cannot collide with the names of regular procedures
+    let source = "\
+    proc.rotl_word<N>
+        u32rotl.N
+    end
+
+    proc.rotl_word__8
+        push.1
+    end
+
+    export.foo
+        exec.rotl_word<8>
+        exec.rotl_word__8
+    end";
+    let module = ModuleAst::parse(source).unwrap();
+    let names: Vec<&str> = module.procs().iter().map(|proc| proc.name.as_str()).collect();
+    assert_eq!(names, vec!["rotl_word__8", "rotl_word<8>", "foo"]);
+}
+
+#[test]
+fn test_ast_parsing_procedure_templates_fail() {
+    // errors in an instance point to the source of the template
+    let source = "\
+    proc.shift<N>
+        u32shl.N
+    end
+
+    begin
+        exec.shift<40>
+    end";
+    let err = ProgramAst::parse(source).unwrap_err().to_string();
+    assert!(err.starts_with("parsing error at [2:9]"), "{err}");
+    assert!(err.ends_with("(in instance 'shift<40>' invoked at [6:9])"), "{err}");
+
+    let sources = [
+        ("begin exec.shift<1> end", "undefined procedure template: shift"),
+        ("proc.shift<N> u32shl.N end begin exec.shift<1,2> end", "expects 1 arguments"),
+        (
+            "proc.shift<N,N> u32shl.N end begin exec.shift<1,2> end",
+            "duplicate parameter name N",
+        ),
+        ("proc.shift<> push.1 end begin push.1 end", "cannot be empty"),
+        (
+            "proc.shift<N> push.1 end proc.shift push.1 end begin push.1 end",
+            "duplicate procedure",
+        ),
+        (
+            "proc.loop<N> exec.loop<N> end begin exec.loop<1> end",
+            "'loop<1>' invokes itself",
+        ),
+        (
+            "proc.loop<N> exec.loop<N+1> end begin exec.loop<1> end",
+            "nested more than 64 levels",
+        ),
+        (
+            "proc.shift<N> push.1 end begin exec.lib::shift<1> end",
+            "declared in other modules",
+        ),
+    ];
+    for (source, message) in sources {
+        let err = ProgramAst::parse(source).unwrap_err();
+        assert!(err.to_string().contains(message), "{err}");
+    }
+
+    // templates cannot be exported
+    let err = ModuleAst::parse("export.shift<N> u32shl.N end").unwrap_err();
+    assert!(err.to_string().contains("procedure templates cannot be exported"));
+}
+
+#[test]
+fn test_ast_parsing_cfg_blocks() {
+    let source = "\
+    const.DEBUG=1
+    const.MODE=2
+    const.TRACE=0
+
+    begin
+        push.1
+        cfg.DEBUG
+            debug.stack
+            cfg.MODE=2
+                push.2
+            else
+                push.3
+            end
+        else
+            push.4
+        end
+        cfg.MODE=DEBUG
+            push.5
+        end
+        cfg.TRACE
+            push.6
+        else
+            if.true
+                push.7
+            end
+        end
+    end";
+    let expected = [
+        Node::Instruction(Instruction::PushU8(1)),
+        Node::Instruction(Instruction::Debug(DebugOptions::StackAll)),
+        Node::Instruction(Instruction::PushU8(2)),
+        Node::IfElse {
+            true_case: CodeBody::new([Node::Instruction(Instruction::PushU8(7))])
+                .with_source_locations([SourceLocation::new(24, 17), SourceLocation::new(25, 13)]),
+            false_case: CodeBody::default(),
+        },
+    ];
+
+    let program = ProgramAst::parse(source).unwrap();
+    assert_eq!(program.body().nodes(), expected);
+    assert_eq!(program.body().source_locations().len(), program.body().nodes().len() + 1);
+
+    // both clauses of a cfg block are checked for errors
+    let sources = [
+        ("const.DEBUG=1 begin cfg.DEBUG push.1 else push.a end end", "push.a"),
+        (
+            "const.DEBUG=1 proc.foo cfg.DEBUG push.1 begin push.1 end",
+            "cfg without matching else/end",
+        ),
+        ("begin cfg push.1 end end", "expected format `cfg.<name>[=<value>]`"),
+        ("begin cfg.debug push.1 end end", "invalid constant name"),
+        (
+            "const.DEBUG=1 begin cfg.DEBUG cfg.DEBGU push.1 end end end",
+            "unknown cfg name: DEBGU",
+        ),
+        (
+            "const.DEBUG=1 begin cfg.DEBUG push.1 else push.2 else push.3 end end",
+            "else without matching if",
+        ),
+    ];
+    for (source, message) in sources {
+        let err = ProgramAst::parse(source).unwrap_err();
+        assert!(err.to_string().contains(message), "{err}");
+    }
+}
+
 fn assert_program_output(source: &str, procedures: LocalProcMap, body: Vec<Node>) {
     let program = ProgramAst::parse(source).unwrap();
     assert_eq!(program.body.nodes(), body);
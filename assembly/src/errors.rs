@@ -1,6 +1,6 @@
 use super::{
     ast::ProcReExport, crypto::hash::RpoDigest, tokens::SourceLocation, KernelError,
-    LibraryNamespace, ProcedureId, ProcedureName, Token,
+    LibraryNamespace, ProcedureId, ProcedureName, StackEffect, Token, Version,
 };
 use alloc::{
     string::{String, ToString},
@@ -18,6 +18,7 @@ pub enum AssemblyError {
     CallSetProcedureNotFound(RpoDigest),
     CallerOutOKernel,
     CircularModuleDependency(Vec<String>),
+    ConflictingErrorCodes(String, String),
     ConflictingNumLocals(String),
     DivisionByZero,
     DuplicateProcId(ProcedureId),
@@ -26,17 +27,20 @@ pub enum AssemblyError {
     ImportedProcModuleNotFound(ProcedureId, String),
     ImportedProcNotFoundInModule(ProcedureId, String),
     InvalidCacheLock,
+    InvalidDefine(String, String),
     InvalidProgramAssemblyContext,
     Io(String),
     KernelError(KernelError),
     KernelProcNotFound(ProcedureId),
     LibraryError(String),
     LocalProcNotFound(u16, String),
+    OverlappingMemoryRegions(String, String),
     ParamOutOfBounds(u64, u64, u64),
     ParsingError(String),
     PhantomCallsNotAllowed(RpoDigest),
     ProcedureNameError(String),
     ReExportedProcModuleNotFound(ProcReExport),
+    StackEffectError(StackEffectError),
     SysCallInKernel(String),
 }
 
@@ -56,6 +60,10 @@ impl AssemblyError {
         Self::CircularModuleDependency(dep_chain.to_vec())
     }
 
+    pub fn conflicting_error_codes(first_error: &str, second_error: &str) -> Self {
+        Self::ConflictingErrorCodes(first_error.to_string(), second_error.to_string())
+    }
+
     pub fn conflicting_num_locals(proc_name: &str) -> Self {
         Self::ConflictingNumLocals(proc_name.to_string())
     }
@@ -99,6 +107,10 @@ impl AssemblyError {
         Self::LocalProcNotFound(proc_idx, module_path.to_string())
     }
 
+    pub fn overlapping_memory_regions(first_region: &str, second_region: &str) -> Self {
+        Self::OverlappingMemoryRegions(first_region.to_string(), second_region.to_string())
+    }
+
     pub fn param_out_of_bounds(value: u64, min: u64, max: u64) -> Self {
         Self::ParamOutOfBounds(value, min, max)
     }
@@ -114,6 +126,10 @@ impl AssemblyError {
     pub fn invalid_cache_lock() -> Self {
         Self::InvalidCacheLock
     }
+
+    pub fn invalid_define(name: &str, reason: &str) -> Self {
+        Self::InvalidDefine(name.to_string(), reason.to_string())
+    }
 }
 
 impl From<ParsingError> for AssemblyError {
@@ -128,6 +144,12 @@ impl From<LibraryError> for AssemblyError {
     }
 }
 
+impl From<StackEffectError> for AssemblyError {
+    fn from(err: StackEffectError) -> Self {
+        Self::StackEffectError(err)
+    }
+}
+
 impl From<LabelError> for AssemblyError {
     fn from(err: LabelError) -> Self {
         Self::ProcedureNameError(format!("invalid procedure name: {err}"))
@@ -142,6 +164,7 @@ impl fmt::Display for AssemblyError {
             CallSetProcedureNotFound(mast_root) => write!(f, "callset procedure not found in assembler cache for procedure with MAST root {mast_root}"),
             CallerOutOKernel => write!(f, "caller instruction used outside of kernel"),
             CircularModuleDependency(dep_chain) => write!(f, "circular module dependency in the following chain: {dep_chain:?}"),
+            ConflictingErrorCodes(first, second) => write!(f, "error {first} has the same code as error {second}"),
             ConflictingNumLocals(proc_name) => write!(f, "procedure `{proc_name}` has the same MAST as another procedure but different number of locals"),
             DivisionByZero => write!(f, "division by zero"),
             DuplicateProcId(proc_id) => write!(f, "duplicate proc id {proc_id}"),
@@ -150,15 +173,18 @@ impl fmt::Display for AssemblyError {
             ImportedProcModuleNotFound(proc_id, proc_name) => write!(f, "module for imported procedure `{proc_name}` with ID {proc_id} not found"),
             ImportedProcNotFoundInModule(proc_id, module_path) => write!(f, "imported procedure {proc_id} not found in module {module_path}"),
             InvalidCacheLock => write!(f, "an attempt was made to lock a borrowed procedures cache"),
+            InvalidDefine(name, reason) => write!(f, "invalid define '{name}': {reason}"),
             InvalidProgramAssemblyContext => write!(f, "assembly context improperly initialized for program compilation"),
             Io(description) => write!(f, "I/O error: {description}"),
             KernelError(error) => write!(f, "{}", error),
             KernelProcNotFound(proc_id) => write!(f, "procedure {proc_id} not found in kernel"),
             LibraryError(err) | ParsingError(err) | ProcedureNameError(err) => write!(f, "{err}"),
             LocalProcNotFound(proc_idx, module_path) => write!(f, "procedure at index {proc_idx} not found in module {module_path}"),
+            OverlappingMemoryRegions(first, second) => write!(f, "memory region {first} overlaps with memory region {second}"),
             ParamOutOfBounds(value, min, max) => write!(f, "parameter value must be greater than or equal to {min} and less than or equal to {max}, but was {value}"),
             PhantomCallsNotAllowed(mast_root) => write!(f, "cannot call phantom procedure with MAST root {mast_root}: phantom calls not allowed"),
             ReExportedProcModuleNotFound(reexport) => write!(f, "re-exported proc {} with id {} not found", reexport.name(), reexport.proc_id()),
+            StackEffectError(err) => write!(f, "{err}"),
             SysCallInKernel(proc_name) => write!(f, "syscall instruction used in kernel procedure '{proc_name}'"),
         }
     }
@@ -215,6 +241,14 @@ impl ParsingError {
 
     // CONSTANTS DECLARATION
     // --------------------------------------------------------------------------------------------
+    pub fn const_export_not_allowed(token: &Token) -> Self {
+        ParsingError {
+            message: format!("exported constants not allowed in this context: `{token}`"),
+            location: *token.location(),
+            op: token.to_string(),
+        }
+    }
+
     pub fn duplicate_const_name(token: &Token, label: &str) -> Self {
         ParsingError {
             message: format!("duplicate constant name: '{label}'"),
@@ -249,6 +283,22 @@ impl ParsingError {
         }
     }
 
+    pub fn invalid_memory_region(token: &Token, reason: &str) -> Self {
+        ParsingError {
+            message: format!("malformed memory region `{token}` - reason: {reason}"),
+            location: *token.location(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn invalid_named_error(token: &Token, reason: &str) -> Self {
+        ParsingError {
+            message: format!("malformed named error `{token}` - reason: {reason}"),
+            location: *token.location(),
+            op: token.to_string(),
+        }
+    }
+
     pub fn const_not_found(token: &Token) -> Self {
         ParsingError {
             message: format!("constant used in operation `{token}` not found"),
@@ -346,6 +396,24 @@ impl ParsingError {
         }
     }
 
+    pub fn unmatched_cfg(token: &Token) -> Self {
+        ParsingError {
+            message: "cfg without matching else/end".to_string(),
+            location: *token.location(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn unknown_cfg_name(token: &Token, name: &str) -> Self {
+        ParsingError {
+            message: format!(
+                "unknown cfg name: {name} - declare a constant with the default value of the define"
+            ),
+            location: *token.location(),
+            op: token.to_string(),
+        }
+    }
+
     pub fn unmatched_while(token: &Token) -> Self {
         ParsingError {
             message: "while without matching end".to_string(),
@@ -602,6 +670,82 @@ impl ParsingError {
         }
     }
 
+    // PROCEDURE TEMPLATES
+    // --------------------------------------------------------------------------------------------
+
+    pub fn invalid_template(token: &Token, reason: &str) -> Self {
+        ParsingError {
+            message: format!("malformed procedure template `{token}` - reason: {reason}"),
+            location: *token.location(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn template_export_not_allowed(token: &Token) -> Self {
+        ParsingError {
+            message: format!("procedure templates cannot be exported: `{token}`"),
+            location: *token.location(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn undefined_template(token: &Token, label: &str) -> Self {
+        ParsingError {
+            message: format!("undefined procedure template: {label}"),
+            location: *token.location(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn invalid_template_args(
+        token: &Token,
+        label: &str,
+        expected: usize,
+        actual: usize,
+    ) -> Self {
+        ParsingError {
+            message: format!(
+                "procedure template '{label}' expects {expected} arguments, but {actual} were provided"
+            ),
+            location: *token.location(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn recursive_template_instance(token: &Token, instance: &str) -> Self {
+        ParsingError {
+            message: format!("procedure template instance '{instance}' invokes itself"),
+            location: *token.location(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn template_instances_too_deep(token: &Token, max_depth: usize) -> Self {
+        ParsingError {
+            message: format!(
+                "procedure template instances cannot be nested more than {max_depth} levels deep"
+            ),
+            location: *token.location(),
+            op: token.to_string(),
+        }
+    }
+
+    /// Returns this error (which occurred while parsing an instance of a procedure template)
+    /// extended with the name of the instance and the location of the token which invoked it.
+    ///
+    /// The location of the error is left unchanged, and thus points to the template source.
+    pub fn in_template_instance(self, token: &Token, instance: &str) -> Self {
+        ParsingError {
+            message: format!(
+                "{} (in instance '{instance}' invoked at {})",
+                self.message,
+                token.location()
+            ),
+            location: self.location,
+            op: self.op,
+        }
+    }
+
     // IMPORTS AND MODULES
     // --------------------------------------------------------------------------------------------
 
@@ -645,6 +789,36 @@ impl ParsingError {
         }
     }
 
+    pub fn too_many_exported_constants(num_constants: usize, max_constants: usize) -> Self {
+        ParsingError {
+            message: format!(
+                "a module cannot export more than {max_constants} constants, but had {num_constants}"
+            ),
+            location: SourceLocation::default(),
+            op: "".to_string(),
+        }
+    }
+
+    pub fn too_many_memory_regions(num_regions: usize, max_regions: usize) -> Self {
+        ParsingError {
+            message: format!(
+                "a module cannot declare more than {max_regions} memory regions, but had {num_regions}"
+            ),
+            location: SourceLocation::default(),
+            op: "".to_string(),
+        }
+    }
+
+    pub fn too_many_named_errors(num_errors: usize, max_errors: usize) -> Self {
+        ParsingError {
+            message: format!(
+                "a module cannot declare more than {max_errors} named errors, but had {num_errors}"
+            ),
+            location: SourceLocation::default(),
+            op: "".to_string(),
+        }
+    }
+
     pub fn too_many_imports(num_imports: usize, max_imports: usize) -> Self {
         ParsingError {
             message: format!(
@@ -764,8 +938,9 @@ impl fmt::Display for LabelError {
 // LIBRARY ERROR
 // ================================================================================================
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum LibraryError {
+    ConflictingErrorCodes(String, String),
     DeserializationFailed(String, String),
     DuplicateModulePath(String),
     DuplicateNamespace(String),
@@ -804,6 +979,10 @@ pub enum LibraryError {
 }
 
 impl LibraryError {
+    pub fn conflicting_error_codes(first_error: &str, second_error: &str) -> Self {
+        Self::ConflictingErrorCodes(first_error.into(), second_error.into())
+    }
+
     pub fn deserialization_error(path: &str, message: &str) -> Self {
         Self::DeserializationFailed(path.into(), message.into())
     }
@@ -884,6 +1063,9 @@ impl fmt::Display for LibraryError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use LibraryError::*;
         match self {
+            ConflictingErrorCodes(first, second) => {
+                write!(f, "error {first} has the same code as error {second}")
+            }
             DeserializationFailed(path, message) => {
                 write!(f, "library deserialization failed - '{path}': {message}")
             }
@@ -956,6 +1138,203 @@ impl From<PathError> for LibraryError {
     }
 }
 
+// LINKER ERROR
+// ================================================================================================
+
+/// An error which can be generated while linking a program against a set of libraries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkerError {
+    LibraryError(LibraryError),
+    LocalProcNotFound(u16, String),
+    MissingDependency {
+        library: String,
+        dependency: String,
+    },
+    UnresolvedProcedure(String),
+    VersionConflict {
+        namespace: String,
+        existing: Version,
+        new: Version,
+    },
+}
+
+impl LinkerError {
+    pub fn local_proc_not_found(proc_idx: u16, module_path: &str) -> Self {
+        Self::LocalProcNotFound(proc_idx, module_path.into())
+    }
+
+    pub fn missing_dependency(library: &LibraryNamespace, dependency: &LibraryNamespace) -> Self {
+        Self::MissingDependency {
+            library: library.as_str().into(),
+            dependency: dependency.as_str().into(),
+        }
+    }
+
+    pub fn unresolved_procedure(proc_name: &str) -> Self {
+        Self::UnresolvedProcedure(proc_name.into())
+    }
+
+    pub fn version_conflict(namespace: &LibraryNamespace, existing: Version, new: Version) -> Self {
+        Self::VersionConflict {
+            namespace: namespace.as_str().into(),
+            existing,
+            new,
+        }
+    }
+}
+
+impl From<LibraryError> for LinkerError {
+    fn from(err: LibraryError) -> Self {
+        Self::LibraryError(err)
+    }
+}
+
+impl fmt::Display for LinkerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use LinkerError::*;
+        match self {
+            LibraryError(err) => write!(f, "{err}"),
+            LocalProcNotFound(proc_idx, module_path) => {
+                write!(f, "procedure at index {proc_idx} not found in module {module_path}")
+            }
+            MissingDependency {
+                library,
+                dependency,
+            } => {
+                write!(
+                    f,
+                    "library '{library}' depends on library '{dependency}' which was not provided"
+                )
+            }
+            UnresolvedProcedure(proc_name) => {
+                write!(f, "procedure '{proc_name}' not found in any of the provided libraries")
+            }
+            VersionConflict {
+                namespace,
+                existing,
+                new,
+            } => {
+                write!(
+                    f,
+                    "library '{namespace}' is provided in incompatible versions {existing} and {new}"
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LinkerError {}
+
+// STACK EFFECT ERROR
+// ================================================================================================
+
+/// A problem which can be found while inferring the stack effects of procedures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StackEffectError {
+    InvalidSignature {
+        procedure: String,
+        signature: String,
+    },
+    SignatureMismatch {
+        procedure: String,
+        declared: StackEffect,
+        inferred: StackEffect,
+    },
+    UnbalancedBranches {
+        procedure: String,
+        true_case: StackEffect,
+        false_case: StackEffect,
+    },
+    UnbalancedLoop {
+        procedure: String,
+        body: StackEffect,
+    },
+}
+
+impl StackEffectError {
+    pub fn invalid_signature(procedure: &str, signature: &str) -> Self {
+        Self::InvalidSignature {
+            procedure: procedure.into(),
+            signature: signature.into(),
+        }
+    }
+
+    pub fn signature_mismatch(
+        procedure: &str,
+        declared: StackEffect,
+        inferred: StackEffect,
+    ) -> Self {
+        Self::SignatureMismatch {
+            procedure: procedure.into(),
+            declared,
+            inferred,
+        }
+    }
+
+    pub fn unbalanced_branches(
+        procedure: &str,
+        true_case: StackEffect,
+        false_case: StackEffect,
+    ) -> Self {
+        Self::UnbalancedBranches {
+            procedure: procedure.into(),
+            true_case,
+            false_case,
+        }
+    }
+
+    pub fn unbalanced_loop(procedure: &str, body: StackEffect) -> Self {
+        Self::UnbalancedLoop {
+            procedure: procedure.into(),
+            body,
+        }
+    }
+}
+
+impl fmt::Display for StackEffectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use StackEffectError::*;
+        match self {
+            InvalidSignature {
+                procedure,
+                signature,
+            } => {
+                write!(f, "invalid stack signature '{signature}' of procedure '{procedure}'")
+            }
+            SignatureMismatch {
+                procedure,
+                declared,
+                inferred,
+            } => {
+                write!(
+                    f,
+                    "procedure '{procedure}' declares stack effect {declared} but has stack effect {inferred}"
+                )
+            }
+            UnbalancedBranches {
+                procedure,
+                true_case,
+                false_case,
+            } => {
+                write!(
+                    f,
+                    "branches of if-else block in procedure '{procedure}' have different stack effects {true_case} and {false_case}"
+                )
+            }
+            UnbalancedLoop { procedure, body } => {
+                write!(
+                    f,
+                    "body of while loop in procedure '{procedure}' must push exactly one element, but has stack effect {body}"
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StackEffectError {}
+
 // PATH ERROR
 // ================================================================================================
 
@@ -0,0 +1,263 @@
+use super::{
+    ast::{ConstantProvider, ModuleAst, ProgramAst},
+    tokens::{LineInfo, LineTokenizer},
+    LibraryPath, ParsingError, Token,
+};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// String used to indent a single level of nested blocks.
+const INDENT_STRING: &str = "    ";
+
+// SOURCE FORMATTER
+// ================================================================================================
+
+/// Formats the provided Miden assembly source (a program or a module) into its canonical form.
+///
+/// Unlike the AST formatter, this works directly on the source lines and thus preserves all
+/// comments, as well as the way instructions are grouped into lines. In the canonical form:
+/// - the contents of every block (procedures, program body, and control flow blocks) are indented
+///   by four spaces per nesting level;
+/// - tokens on the same line are separated by a single space;
+/// - comments and doc comments have a single space after the comment prefix, and trailing
+///   comments are separated from the instructions by a single space;
+/// - consecutive blank lines are collapsed into one, blank lines at the start and at the end of a
+///   block are removed, and top-level blocks are separated by a blank line;
+/// - trailing whitespace is removed and the source ends with a single newline.
+///
+/// Formatting does not change the meaning of the source: the formatted source parses into the
+/// same AST as the original one.
+///
+/// # Errors
+/// Returns an error if the source is not a valid program or module.
+pub fn format_source(source: &str) -> Result<String, ParsingError> {
+    let lines: Vec<_> = source.lines().map(SourceLine::new).collect();
+
+    // make sure the source is valid, as formatting relies on blocks being properly delimited
+    if lines.iter().any(|line| line.first_keyword() == Some(Token::BEGIN)) {
+        ProgramAst::parse_with_constants(source, &AnyConstant)?;
+    } else {
+        ModuleAst::parse_with_constants(source, &AnyConstant)?;
+    }
+
+    let mut formatter = SourceFormatter::default();
+    for line in lines.iter() {
+        formatter.write_line(line);
+    }
+    Ok(formatter.output)
+}
+
+// SOURCE FORMATTER STATE
+// ================================================================================================
+
+/// Kind of the last non-blank line written by the [SourceFormatter].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineKind {
+    /// No lines were written yet.
+    None,
+    /// The line ends with a token which opens a block (e.g., `begin`, `if.true`, or `else`).
+    BlockStart,
+    /// The line ends with an `end` token which closes a top-level block.
+    TopLevelEnd,
+    /// Any other line.
+    Other,
+}
+
+/// Incrementally builds the canonical form of a source, line by line.
+#[derive(Debug)]
+struct SourceFormatter {
+    output: String,
+    depth: usize,
+    pending_blank: bool,
+    last_line: LineKind,
+}
+
+impl Default for SourceFormatter {
+    fn default() -> Self {
+        Self {
+            output: String::new(),
+            depth: 0,
+            pending_blank: false,
+            last_line: LineKind::None,
+        }
+    }
+}
+
+impl SourceFormatter {
+    /// Appends the canonical form of the provided line to the output.
+    fn write_line(&mut self, line: &SourceLine) {
+        if line.is_blank() {
+            self.pending_blank = true;
+            return;
+        }
+
+        // lines starting with `end` or `else` belong to the enclosing block
+        let closes_block = matches!(line.first_keyword(), Some(Token::END | Token::ELSE));
+
+        let write_blank = match self.last_line {
+            LineKind::None | LineKind::BlockStart => false,
+            _ if closes_block => false,
+            LineKind::TopLevelEnd => true,
+            LineKind::Other => self.pending_blank,
+        };
+        self.pending_blank = false;
+        if write_blank {
+            self.output.push('\n');
+        }
+
+        let indent = if closes_block {
+            self.depth.saturating_sub(1)
+        } else {
+            self.depth
+        };
+        for _ in 0..indent {
+            self.output.push_str(INDENT_STRING);
+        }
+        self.output.push_str(&line.tokens.join(" "));
+        if let Some(comment) = line.comment {
+            if !line.tokens.is_empty() {
+                self.output.push(' ');
+            }
+            self.output.push_str(&format_comment(comment));
+        }
+        self.output.push('\n');
+
+        // update the nesting depth and remember how the line ended
+        self.last_line = LineKind::Other;
+        for token in line.tokens.iter() {
+            match BlockToken::parse(token) {
+                Some(BlockToken::Start) => {
+                    self.depth += 1;
+                    self.last_line = LineKind::BlockStart;
+                }
+                Some(BlockToken::Else) => self.last_line = LineKind::BlockStart,
+                Some(BlockToken::End) => {
+                    self.depth = self.depth.saturating_sub(1);
+                    self.last_line = if self.depth == 0 {
+                        LineKind::TopLevelEnd
+                    } else {
+                        LineKind::Other
+                    };
+                }
+                None => self.last_line = LineKind::Other,
+            }
+        }
+    }
+}
+
+// SOURCE LINE
+// ================================================================================================
+
+/// A single line of the source split into tokens and an optional trailing comment.
+#[derive(Debug)]
+struct SourceLine<'a> {
+    tokens: Vec<&'a str>,
+    comment: Option<&'a str>,
+}
+
+impl<'a> SourceLine<'a> {
+    fn new(line: &'a str) -> Self {
+        let line = line.trim();
+
+        // a comment starts with a comment prefix at the start of a token
+        let comment_start = line.char_indices().find_map(|(i, c)| {
+            let at_token_start = line[..i].ends_with(char::is_whitespace) || i == 0;
+            (c == Token::COMMENT_PREFIX && at_token_start).then_some(i)
+        });
+        let (code, comment) = match comment_start {
+            Some(i) => (line[..i].trim_end(), Some(line[i..].trim_end())),
+            None => (line, None),
+        };
+
+        let info = LineInfo::new(0, 0).with_contents(code);
+        let tokens = LineTokenizer::new(&info)
+            .expect("line contents are present")
+            .map(|(token, _)| token)
+            .collect();
+
+        Self { tokens, comment }
+    }
+
+    /// Returns true if this line contains neither tokens nor comments.
+    fn is_blank(&self) -> bool {
+        self.tokens.is_empty() && self.comment.is_none()
+    }
+
+    /// Returns the first part of the first token in this line, if any.
+    fn first_keyword(&self) -> Option<&'a str> {
+        self.tokens.first().and_then(|token| token.split('.').next())
+    }
+}
+
+// BLOCK TOKENS
+// ================================================================================================
+
+/// Tokens which affect the nesting of blocks.
+enum BlockToken {
+    Start,
+    Else,
+    End,
+}
+
+impl BlockToken {
+    fn parse(token: &str) -> Option<Self> {
+        match token.split('.').next()? {
+            Token::BEGIN | Token::PROC | Token::IF | Token::WHILE | Token::REPEAT | Token::CFG => {
+                Some(Self::Start)
+            }
+            // re-exported procedures (e.g., `export.foo::bar`), exported constants, exported
+            // memory regions, and exported named errors do not have a body
+            Token::EXPORT
+                if !token.contains(LibraryPath::PATH_DELIM)
+                    && !matches!(
+                        token.split('.').nth(1),
+                        Some(Token::CONST | Token::REGION | Token::ERROR)
+                    ) =>
+            {
+                Some(Self::Start)
+            }
+            Token::ELSE => Some(Self::Else),
+            Token::END => Some(Self::End),
+            _ => None,
+        }
+    }
+}
+
+// CONSTANT PROVIDER
+// ================================================================================================
+
+/// A constant provider which resolves every imported constant.
+///
+/// The formatter does not have access to the imported modules, and the values of constants do not
+/// affect formatting; thus, references to imported constants are assumed to be valid.
+struct AnyConstant;
+
+impl ConstantProvider for AnyConstant {
+    fn get_constant(&self, _module_path: &LibraryPath, _name: &str) -> Option<u64> {
+        Some(1)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Makes sure the comment text is separated from the comment prefix by a whitespace.
+fn format_comment(comment: &str) -> String {
+    let (prefix, text) = match comment.strip_prefix(Token::DOC_COMMENT_PREFIX) {
+        Some(text) => (Token::DOC_COMMENT_PREFIX, text),
+        None => ("#", &comment[Token::COMMENT_PREFIX.len_utf8()..]),
+    };
+    if text.is_empty() || text.starts_with(|c: char| c.is_whitespace() || c == '#') {
+        comment.to_string()
+    } else {
+        format!("{prefix} {text}")
+    }
+}
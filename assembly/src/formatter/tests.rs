@@ -0,0 +1,135 @@
+use super::{
+    super::ast::{AstSerdeOptions, ModuleAst, ProgramAst},
+    format_source,
+};
+
+// TEST SOURCES
+// ================================================================================================
+
+const MODULE_SOURCE: &str = "\
+#! Module docs
+
+
+use.std::math::u64
+   # import comment
+const.A=1
+
+#! proc docs
+proc.foo.2     # trailing
+  push.1   push.2 add   #no space
+
+
+  loc_store.0
+if.true
+push.A
+   else
+
+ # in else
+   push.3
+ end
+
+end
+#!exported
+export.bar
+exec.foo
+end
+export.u64::checked_add->add
+";
+
+const PROGRAM_SOURCE: &str = "
+proc.foo
+push.1 drop
+end
+begin
+  repeat.2 push.1 end
+while.true
+ push.0
+     end
+    if.true push.1 else push.2 end
+  exec.foo
+
+
+end
+
+
+";
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn format_module() {
+    let expected = "\
+#! Module docs
+
+use.std::math::u64
+# import comment
+const.A=1
+
+#! proc docs
+proc.foo.2 # trailing
+    push.1 push.2 add # no space
+
+    loc_store.0
+    if.true
+        push.A
+    else
+        # in else
+        push.3
+    end
+end
+
+#! exported
+export.bar
+    exec.foo
+end
+
+export.u64::checked_add->add
+";
+    assert_eq!(format_source(MODULE_SOURCE).unwrap(), expected);
+}
+
+#[test]
+fn format_program() {
+    let expected = "\
+proc.foo
+    push.1 drop
+end
+
+begin
+    repeat.2 push.1 end
+    while.true
+        push.0
+    end
+    if.true push.1 else push.2 end
+    exec.foo
+end
+";
+    assert_eq!(format_source(PROGRAM_SOURCE).unwrap(), expected);
+}
+
+#[test]
+fn format_is_idempotent_and_preserves_ast() {
+    let options = AstSerdeOptions::new(true);
+
+    let formatted = format_source(MODULE_SOURCE).unwrap();
+    assert_eq!(format_source(&formatted).unwrap(), formatted);
+    assert_eq!(
+        ModuleAst::parse(&formatted).unwrap().to_bytes(options),
+        ModuleAst::parse(MODULE_SOURCE).unwrap().to_bytes(options)
+    );
+
+    let formatted = format_source(PROGRAM_SOURCE).unwrap();
+    assert_eq!(format_source(&formatted).unwrap(), formatted);
+    assert_eq!(
+        ProgramAst::parse(&formatted).unwrap().to_bytes(options),
+        ProgramAst::parse(PROGRAM_SOURCE).unwrap().to_bytes(options)
+    );
+}
+
+#[test]
+fn format_invalid_source() {
+    assert!(format_source("begin push.1").is_err());
+    assert!(format_source("proc.foo push.1 end end").is_err());
+    assert!(format_source("").is_err());
+}
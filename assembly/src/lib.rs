@@ -17,7 +17,9 @@ use vm_core::{
 };
 
 mod library;
-pub use library::{Library, LibraryNamespace, LibraryPath, MaslLibrary, Module, Version};
+pub use library::{
+    ErrorTable, Library, LibraryNamespace, LibraryPath, MaslLibrary, MemoryLayout, Module, Version,
+};
 
 mod procedures;
 use procedures::{CallSet, NamedProcedure, Procedure};
@@ -30,10 +32,26 @@ mod tokens;
 use tokens::{Token, TokenStream};
 
 mod errors;
-pub use errors::{AssemblyError, LabelError, LibraryError, ParsingError, PathError};
+pub use errors::{
+    AssemblyError, LabelError, LibraryError, LinkerError, ParsingError, PathError, StackEffectError,
+};
 
 mod assembler;
-pub use assembler::{Assembler, AssemblyContext};
+pub use assembler::{Assembler, AssemblyContext, ModuleProvider};
+#[cfg(feature = "std")]
+pub use assembler::{ModuleCache, ModuleFingerprint};
+
+mod linker;
+pub use linker::{LinkedProgram, Linker};
+
+mod linter;
+pub use linter::{Lint, LintConfig, LintLevel, LintRule, Linter};
+
+mod formatter;
+pub use formatter::format_source;
+
+mod stack_effect;
+pub use stack_effect::{StackEffect, StackEffectPolicy};
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,116 @@
+use super::{LibraryPath, NamedError};
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+// ERROR TABLE
+// ================================================================================================
+
+/// A table of the named errors declared in a set of modules.
+///
+/// Errors are kept sorted by their codes, and each error is associated with the path of the
+/// module which declares it. A table may contain distinct errors with the same code (e.g., errors
+/// declared in unrelated libraries); [ErrorTable::find_conflict()] can be used to check whether
+/// this is the case. The assembler rejects programs which can raise distinct errors with the same
+/// code.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ErrorTable {
+    errors: Vec<(LibraryPath, NamedError)>,
+}
+
+impl ErrorTable {
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds the provided errors declared in the module with the specified path to this table.
+    pub fn add_errors(&mut self, path: &LibraryPath, errors: &[NamedError]) {
+        for error in errors {
+            let idx = self.errors.partition_point(|(_, e)| e.code() <= error.code());
+            self.errors.insert(idx, (path.clone(), error.clone()));
+        }
+    }
+
+    /// Adds all errors of the other table to this table.
+    pub fn extend(&mut self, other: &ErrorTable) {
+        for (path, error) in other.errors.iter() {
+            self.add_errors(path, core::slice::from_ref(error));
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if this table does not contain any errors.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns the number of errors in this table.
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Returns an iterator over the errors of this table (together with the paths of the modules
+    /// declaring them) sorted by their codes.
+    pub fn errors(&self) -> impl Iterator<Item = (&LibraryPath, &NamedError)> {
+        self.errors.iter().map(|(path, error)| (path, error))
+    }
+
+    /// Returns the first error with the specified code, or None if no error has this code.
+    pub fn get(&self, code: u32) -> Option<(&LibraryPath, &NamedError)> {
+        let idx = self.errors.partition_point(|(_, error)| error.code() < code);
+        self.errors
+            .get(idx)
+            .filter(|(_, error)| error.code() == code)
+            .map(|(path, error)| (path, error))
+    }
+
+    /// Returns an iterator over the codes of the errors of this table together with their
+    /// messages.
+    ///
+    /// Codes shared by distinct errors (see [ErrorTable::find_conflict()]) are skipped, since a
+    /// failed assertion with such a code cannot be attributed to any of these errors.
+    pub fn messages(&self) -> impl Iterator<Item = (u32, String)> + '_ {
+        let mut errors = self.errors.as_slice();
+        core::iter::from_fn(move || loop {
+            // errors are sorted by their codes, and thus errors with the same code are adjacent
+            let ((first_path, first), rest) = errors.split_first()?;
+            let num_errors = 1 + rest.iter().take_while(|(_, e)| e.code() == first.code()).count();
+            let (same_code, tail) = errors.split_at(num_errors);
+            errors = tail;
+            if same_code.iter().all(|(path, e)| path == first_path && e.name() == first.name()) {
+                return Some((first.code(), String::from(first.message())));
+            }
+        })
+    }
+
+    /// Returns a pair of distinct errors with the same code, or None if all codes are unique.
+    ///
+    /// The same error declared in the same module is allowed to appear in the table more than
+    /// once (e.g., if the table is built from libraries which share a dependency).
+    pub fn find_conflict(&self) -> Option<[(&LibraryPath, &NamedError); 2]> {
+        self.errors.windows(2).find_map(|pair| {
+            let ((first_path, first), (second_path, second)) = (&pair[0], &pair[1]);
+            let is_conflict = first.code() == second.code()
+                && (first_path != second_path || first.name() != second.name());
+            is_conflict.then_some([(first_path, first), (second_path, second)])
+        })
+    }
+}
+
+impl fmt::Display for ErrorTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (path, error) in self.errors() {
+            writeln!(f, "{}: {}", format_error(path, error), error.message())?;
+        }
+        Ok(())
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a human-readable description of the error declared in the module with the specified
+/// path, e.g., `wallet::errors::INSUFFICIENT_BALANCE (131073)`.
+pub(crate) fn format_error(path: &LibraryPath, error: &NamedError) -> String {
+    format!("{path}{}{} ({})", LibraryPath::PATH_DELIM, error.name(), error.code())
+}
@@ -0,0 +1,99 @@
+use super::{LibraryPath, MemoryRegion};
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+// MEMORY LAYOUT
+// ================================================================================================
+
+/// A map of the memory regions declared in a set of modules.
+///
+/// Regions are kept sorted by their first address, and each region is associated with the path of
+/// the module which declares it. A layout may contain overlapping regions (e.g., regions declared
+/// in modules which are never used together); [MemoryLayout::find_overlap()] can be used to check
+/// whether this is the case.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryLayout {
+    regions: Vec<(LibraryPath, MemoryRegion)>,
+}
+
+impl MemoryLayout {
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds the provided regions declared in the module with the specified path to this layout.
+    pub fn add_regions(&mut self, path: &LibraryPath, regions: &[MemoryRegion]) {
+        for region in regions {
+            let idx = self.regions.partition_point(|(_, r)| r.address() <= region.address());
+            self.regions.insert(idx, (path.clone(), region.clone()));
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if this layout does not contain any regions.
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    /// Returns the number of regions in this layout.
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    /// Returns an iterator over the regions of this layout (together with the paths of the modules
+    /// declaring them) sorted by their first address.
+    pub fn regions(&self) -> impl Iterator<Item = (&LibraryPath, &MemoryRegion)> {
+        self.regions.iter().map(|(path, region)| (path, region))
+    }
+
+    /// Returns the region which contains the specified address, or None if the address does not
+    /// belong to any region.
+    pub fn find_region(&self, address: u32) -> Option<(&LibraryPath, &MemoryRegion)> {
+        self.regions().find(|(_, region)| region.contains(address))
+    }
+
+    /// Returns a pair of overlapping regions of this layout, or None if no regions overlap.
+    ///
+    /// The first region of the returned pair starts at or before the second one.
+    pub fn find_overlap(&self) -> Option<[(&LibraryPath, &MemoryRegion); 2]> {
+        // since the regions are sorted by their first address, a region overlaps with one of the
+        // preceding regions iff it starts before the end of the preceding region ending last
+        let mut last_ending: Option<&(LibraryPath, MemoryRegion)> = None;
+        for entry in self.regions.iter() {
+            if let Some(previous) = last_ending {
+                if (entry.1.address() as u64) < previous.1.end() {
+                    return Some([(&previous.0, &previous.1), (&entry.0, &entry.1)]);
+                }
+            }
+            if last_ending.map_or(true, |previous| entry.1.end() > previous.1.end()) {
+                last_ending = Some(entry);
+            }
+        }
+        None
+    }
+}
+
+impl fmt::Display for MemoryLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (path, region) in self.regions() {
+            writeln!(f, "{}", format_region(path, region))?;
+        }
+        Ok(())
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a human-readable description of the region declared in the module with the specified
+/// path, e.g., `kernel::layout::NOTES [1000..1064)`.
+pub(crate) fn format_region(path: &LibraryPath, region: &MemoryRegion) -> String {
+    format!(
+        "{path}{}{} [{}..{})",
+        LibraryPath::PATH_DELIM,
+        region.name(),
+        region.address(),
+        region.end()
+    )
+}
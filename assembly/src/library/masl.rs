@@ -1,8 +1,9 @@
 use super::{
-    AstSerdeOptions, ByteReader, ByteWriter, Deserializable, DeserializationError, Library,
-    LibraryError, LibraryNamespace, LibraryPath, Module, ModuleAst, Serializable, Version,
+    format_error, AstSerdeOptions, ByteReader, ByteWriter, Deserializable, DeserializationError,
+    Library, LibraryError, LibraryNamespace, LibraryPath, Module, ModuleAst, Serializable, Version,
     MAX_DEPENDENCIES, MAX_MODULES,
 };
+use crate::ast::{read_format_version, AST_FORMAT_VERSION};
 use alloc::{collections::BTreeSet, vec::Vec};
 use core::slice::Iter;
 
@@ -16,6 +17,9 @@ const AST_DEFAULT_SERDE_OPTIONS: AstSerdeOptions = AstSerdeOptions {
     serialize_imports: true,
 };
 
+/// Magic bytes at the start of a serialized [MaslLibrary].
+const MASL_MAGIC: &[u8; 4] = b"MASL";
+
 // LIBRARY IMPLEMENTATION FOR MASL FILES
 // ================================================================================================
 
@@ -73,7 +77,7 @@ impl MaslLibrary {
     ///
     /// # Errors
     /// Returns an error if the provided `modules` vector is empty or contains more than
-    /// [u16::MAX] elements.
+    /// [u16::MAX] elements, or if two named errors declared in the modules have the same code.
     pub fn new(
         namespace: LibraryNamespace,
         version: Version,
@@ -99,13 +103,33 @@ impl MaslLibrary {
             ));
         }
 
-        Ok(Self {
+        let library = Self {
             namespace,
             version,
             has_source_locations,
             modules,
             dependencies,
-        })
+        };
+
+        // error codes must identify errors unambiguously within a library
+        if let Some([(first_path, first), (second_path, second)]) =
+            library.error_table().find_conflict()
+        {
+            return Err(LibraryError::conflicting_error_codes(
+                &format_error(first_path, first),
+                &format_error(second_path, second),
+            ));
+        }
+
+        Ok(library)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if source locations are serialized with this library.
+    pub fn has_source_locations(&self) -> bool {
+        self.has_source_locations
     }
 
     // STATE MUTATORS
@@ -119,9 +143,16 @@ impl MaslLibrary {
 
 #[cfg(feature = "std")]
 mod use_std {
-    use alloc::{collections::BTreeMap, string::ToString};
-
-    use super::{super::super::ast::instrument, *};
+    use alloc::{
+        collections::BTreeMap,
+        string::{String, ToString},
+    };
+
+    use super::{
+        super::super::ast::{instrument, ConstantProvider, ModuleImports},
+        *,
+    };
+    use crate::tokens::TokenStream;
     use std::{fs, io, path::Path};
 
     impl MaslLibrary {
@@ -137,6 +168,10 @@ mod use_std {
         /// - ./crypto/hash.masm    -> ("crypto::hash", ast(./crypto/hash.masm))
         /// - ./math/u32.masm       -> ("math::u32",    ast(./math/u32.masm))
         /// - ./math/u64.masm       -> ("math::u64",    ast(./math/u64.masm))
+        ///
+        /// Only constants exported from the modules of this library can be referenced by its
+        /// modules; use [Self::read_from_dir_with_constants] to reference constants exported from
+        /// other libraries.
         pub fn read_from_dir<P>(
             path: P,
             namespace: LibraryNamespace,
@@ -145,6 +180,33 @@ mod use_std {
         ) -> io::Result<Self>
         where
             P: AsRef<Path>,
+        {
+            let no_constants = BTreeMap::<LibraryPath, ModuleAst>::new();
+            Self::read_from_dir_with_constants(
+                path,
+                namespace,
+                with_source_locations,
+                version,
+                &no_constants,
+            )
+        }
+
+        /// Read a directory and recursively create modules from its `masm` files in the same way
+        /// as [Self::read_from_dir] does.
+        ///
+        /// References to constants exported from modules of other libraries (e.g., the
+        /// dependencies of this library) are resolved via the provided constant provider, such as
+        /// an [Assembler](crate::Assembler) with these libraries loaded.
+        pub fn read_from_dir_with_constants<P, C>(
+            path: P,
+            namespace: LibraryNamespace,
+            with_source_locations: bool,
+            version: Version,
+            constants: &C,
+        ) -> io::Result<Self>
+        where
+            P: AsRef<Path>,
+            C: ConstantProvider,
         {
             if !path.as_ref().is_dir() {
                 return Err(io::Error::new(
@@ -168,15 +230,11 @@ mod use_std {
                 ));
             }
 
-            let modules = read_from_dir_helper(
-                Default::default(),
-                path,
-                &module_path,
-                &mut dependencies_set,
-            )?
-            .into_iter()
-            .map(|(path, ast)| Module { path, ast })
-            .collect();
+            let sources = read_from_dir_helper(Default::default(), path, &module_path)?;
+            let modules = parse_modules(sources, &mut dependencies_set, constants)?
+                .into_iter()
+                .map(|(path, ast)| Module { path, ast })
+                .collect();
 
             let dependencies =
                 dependencies_set.into_iter().filter(|dep| dep != &namespace).collect();
@@ -221,15 +279,14 @@ mod use_std {
     // HELPER FUNCTIONS
     // --------------------------------------------------------------------------------------------
 
-    /// Read a directory and recursively feed the state map with path->ast tuples.
+    /// Read a directory and recursively feed the state map with path->source tuples.
     ///
     /// Helper for [`Self::read_from_dir`].
     fn read_from_dir_helper<P>(
-        mut state: BTreeMap<LibraryPath, ModuleAst>,
+        mut state: BTreeMap<LibraryPath, String>,
         dir: P,
         module_path: &LibraryPath,
-        deps: &mut BTreeSet<LibraryNamespace>,
-    ) -> io::Result<BTreeMap<LibraryPath, ModuleAst>>
+    ) -> io::Result<BTreeMap<LibraryPath, String>>
     where
         P: AsRef<Path>,
     {
@@ -246,8 +303,8 @@ mod use_std {
                 let module_path = module_path
                     .append(name)
                     .map_err(|err| io::Error::new(io::ErrorKind::Other, format!("{err}")))?;
-                state = read_from_dir_helper(state, path, &module_path, deps)?;
-            // if file, check if `masm`, parse & append; skip otherwise
+                state = read_from_dir_helper(state, path, &module_path)?;
+            // if file, check if `masm`, read & append; skip otherwise
             } else if ty.is_file() {
                 let path = entry.path();
 
@@ -270,15 +327,8 @@ mod use_std {
                         ));
                     }
 
-                    // read & parse file
+                    // read file
                     let contents = fs::read_to_string(&path)?;
-                    let ast = ModuleAst::parse(&contents)?;
-
-                    // add dependencies of this module to the dependencies of this library
-                    for path in ast.import_info().import_paths() {
-                        let ns = LibraryNamespace::new(path.first())?;
-                        deps.insert(ns);
-                    }
 
                     // build module path and add it to the map of modules
                     let module = if name == MaslLibrary::MOD {
@@ -289,7 +339,7 @@ mod use_std {
                             .map_err(|err| io::Error::new(io::ErrorKind::Other, format!("{err}")))?
                     };
 
-                    if state.insert(module, ast).is_some() {
+                    if state.insert(module, contents).is_some() {
                         unreachable!(
                             "the filesystem is inconsistent as it produced duplicated module paths"
                         );
@@ -299,10 +349,98 @@ mod use_std {
         }
         Ok(state)
     }
+
+    /// Parses the provided module sources and feeds the dependency set with the namespaces of all
+    /// modules imported by them.
+    ///
+    /// Modules are parsed in the order of their imports so that constants exported from a module
+    /// of this library can be referenced by the modules which import it. If the remaining modules
+    /// import each other in a cycle, they are all parsed at once, and thus cannot reference
+    /// constants exported from each other. Constants exported from modules of other libraries are
+    /// resolved via the provided constant provider.
+    ///
+    /// Helper for [`Self::read_from_dir`].
+    fn parse_modules<C: ConstantProvider>(
+        mut sources: BTreeMap<LibraryPath, String>,
+        deps: &mut BTreeSet<LibraryNamespace>,
+        constants: &C,
+    ) -> io::Result<BTreeMap<LibraryPath, ModuleAst>> {
+        // collect the imports of every module before parsing the modules
+        let mut imports = BTreeMap::new();
+        for (path, source) in sources.iter() {
+            let mut tokens = TokenStream::new(source)?;
+            let import_paths = ModuleImports::parse(&mut tokens)?
+                .import_paths()
+                .into_iter()
+                .filter(|import| sources.contains_key(*import))
+                .cloned()
+                .collect::<Vec<_>>();
+            imports.insert(path.clone(), import_paths);
+        }
+
+        let mut modules: BTreeMap<LibraryPath, ModuleAst> = BTreeMap::new();
+        while !sources.is_empty() {
+            // pick the modules whose imports from this library have already been parsed; if there
+            // are no such modules, the remaining modules form an import cycle
+            let mut ready: Vec<LibraryPath> = sources
+                .keys()
+                .filter(|path| imports[*path].iter().all(|import| modules.contains_key(import)))
+                .cloned()
+                .collect();
+            if ready.is_empty() {
+                ready = sources.keys().cloned().collect();
+            }
+
+            for path in ready {
+                let source = sources.remove(&path).expect("module source not found");
+                let provider = LibraryConstants {
+                    modules: &modules,
+                    dependencies: constants,
+                };
+                let ast = ModuleAst::parse_with_constants(&source, &provider)?;
+
+                // add dependencies of this module to the dependencies of this library
+                for path in ast.import_info().import_paths() {
+                    let ns = LibraryNamespace::new(path.first())?;
+                    deps.insert(ns);
+                }
+
+                modules.insert(path, ast);
+            }
+        }
+
+        Ok(modules)
+    }
+
+    /// A constant provider which resolves constants exported from the already parsed modules of
+    /// a library, and falls back to the provider of the library dependencies for other modules.
+    /// Defines are resolved via the provider of the library dependencies.
+    ///
+    /// Helper for [`parse_modules`].
+    struct LibraryConstants<'a, C> {
+        modules: &'a BTreeMap<LibraryPath, ModuleAst>,
+        dependencies: &'a C,
+    }
+
+    impl<C: ConstantProvider> ConstantProvider for LibraryConstants<'_, C> {
+        fn get_constant(&self, module_path: &LibraryPath, name: &str) -> Option<u64> {
+            self.modules
+                .get_constant(module_path, name)
+                .or_else(|| self.dependencies.get_constant(module_path, name))
+        }
+
+        fn get_define(&self, name: &str) -> Option<u64> {
+            self.dependencies.get_define(name)
+        }
+    }
 }
 
 impl Serializable for MaslLibrary {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        // the header consists of the magic bytes followed by the version of the AST format
+        target.write_bytes(MASL_MAGIC);
+        target.write_u8(AST_FORMAT_VERSION);
+
         self.namespace.write_into(target);
         self.version.write_into(target);
 
@@ -334,6 +472,14 @@ impl Serializable for MaslLibrary {
 
 impl Deserializable for MaslLibrary {
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let magic: [u8; 4] = source.read_array()?;
+        if &magic != MASL_MAGIC {
+            return Err(DeserializationError::InvalidValue(
+                "input is not a serialized library (invalid magic bytes)".into(),
+            ));
+        }
+        read_format_version(source)?;
+
         let namespace = LibraryNamespace::read_from(source)?;
         let version = Version::read_from(source)?;
 
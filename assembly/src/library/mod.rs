@@ -1,5 +1,5 @@
 use super::{
-    ast::{AstSerdeOptions, ModuleAst},
+    ast::{AstSerdeOptions, MemoryRegion, ModuleAst, NamedError},
     ByteReader, ByteWriter, Deserializable, DeserializationError, LibraryError, PathError,
     Serializable, MAX_LABEL_LEN, NAMESPACE_LABEL_PARSER,
 };
@@ -12,6 +12,14 @@ pub use masl::MaslLibrary;
 mod path;
 pub use path::LibraryPath;
 
+mod layout;
+pub(crate) use layout::format_region;
+pub use layout::MemoryLayout;
+
+mod error_table;
+pub(crate) use error_table::format_error;
+pub use error_table::ErrorTable;
+
 #[cfg(test)]
 mod tests;
 
@@ -47,6 +55,24 @@ pub trait Library {
     fn get_module_ast(&self, path: &LibraryPath) -> Option<&ModuleAst> {
         self.modules().find(|&module| module.path == *path).map(|module| &module.ast)
     }
+
+    /// Returns the memory regions declared in the modules of this library.
+    fn memory_layout(&self) -> MemoryLayout {
+        let mut layout = MemoryLayout::default();
+        for module in self.modules() {
+            layout.add_regions(&module.path, module.ast.memory_regions());
+        }
+        layout
+    }
+
+    /// Returns the named errors declared in the modules of this library.
+    fn error_table(&self) -> ErrorTable {
+        let mut table = ErrorTable::default();
+        for module in self.modules() {
+            table.add_errors(&module.path, module.ast.named_errors());
+        }
+        table
+    }
 }
 
 impl<T> Library for &T
@@ -76,6 +102,14 @@ where
     fn get_module_ast(&self, path: &LibraryPath) -> Option<&ModuleAst> {
         T::get_module_ast(self, path)
     }
+
+    fn memory_layout(&self) -> MemoryLayout {
+        T::memory_layout(self)
+    }
+
+    fn error_table(&self) -> ErrorTable {
+        T::error_table(self)
+    }
 }
 
 // MODULE
@@ -1,5 +1,5 @@
 use super::{Library, LibraryNamespace, LibraryPath, MaslLibrary, Module, ModuleAst, Version};
-use alloc::vec::Vec;
+use alloc::{string::ToString, vec, vec::Vec};
 use vm_core::utils::{Deserializable, Serializable, SliceReader};
 
 #[test]
@@ -58,6 +58,26 @@ fn masl_locations_serialization() {
     assert_eq!(bundle, deserialized);
 }
 
+#[test]
+fn masl_format_version() {
+    let path = LibraryPath::new("test::foo").unwrap();
+    let ast = ModuleAst::parse("export.foo add end").unwrap();
+    let namespace = LibraryNamespace::new("test").unwrap();
+    let modules = vec![Module::new(path, ast)];
+    let bundle = MaslLibrary::new(namespace, Version::MIN, false, modules, Vec::new()).unwrap();
+    let bytes = bundle.to_bytes();
+
+    // libraries serialized before the format was versioned do not start with the magic bytes
+    let err = MaslLibrary::read_from_bytes(&bytes[5..]).unwrap_err();
+    assert!(err.to_string().contains("invalid magic bytes"), "{err}");
+
+    // libraries serialized with a different version of the AST format are rejected
+    let mut bytes = bytes;
+    bytes[4] -= 1;
+    let err = MaslLibrary::read_from_bytes(&bytes).unwrap_err();
+    assert!(err.to_string().contains("unsupported AST format version"), "{err}");
+}
+
 #[test]
 fn get_module_by_path() {
     // declare foo module
@@ -91,3 +111,77 @@ end
 
     assert!(bundle.get_module_ast(&LibraryPath::new("test::bar").unwrap()).is_none());
 }
+
+#[test]
+fn error_table() {
+    let errors = "\
+    #! Account balance is insufficient.
+    export.error.INSUFFICIENT_BALANCE=2
+
+    #! Note is not valid.
+    export.error.INVALID_NOTE=1";
+    let wallet = "\
+    #! Wallet is locked.
+    error.LOCKED=3
+
+    export.foo
+        push.1
+    end";
+    let errors =
+        Module::new(LibraryPath::new("test::errors").unwrap(), ModuleAst::parse(errors).unwrap());
+    let wallet =
+        Module::new(LibraryPath::new("test::wallet").unwrap(), ModuleAst::parse(wallet).unwrap());
+
+    let namespace = LibraryNamespace::new("test").unwrap();
+    let modules = vec![errors.clone(), wallet.clone()];
+    let library =
+        MaslLibrary::new(namespace.clone(), Version::MIN, false, modules, Vec::new()).unwrap();
+
+    // errors are sorted by their codes
+    let table = library.error_table();
+    let names: Vec<&str> = table.errors().map(|(_, error)| error.name()).collect();
+    assert_eq!(names, vec!["INVALID_NOTE", "INSUFFICIENT_BALANCE", "LOCKED"]);
+    let (path, error) = table.get(3).unwrap();
+    assert_eq!((path.path(), error.message()), ("test::wallet", "Wallet is locked."));
+    assert!(table.get(4).is_none());
+    assert_eq!(
+        table.messages().collect::<Vec<_>>(),
+        vec![
+            (1, "Note is not valid.".to_string()),
+            (2, "Account balance is insufficient.".to_string()),
+            (3, "Wallet is locked.".to_string())
+        ]
+    );
+
+    // named errors are serialized with the library
+    let bytes = library.to_bytes();
+    let deserialized = MaslLibrary::read_from(&mut SliceReader::new(&bytes)).unwrap();
+    assert_eq!(deserialized.error_table(), table);
+
+    // the same error may appear in a table more than once, but distinct errors cannot have the
+    // same code within a library
+    let mut merged = table.clone();
+    merged.extend(&table);
+    assert!(merged.find_conflict().is_none());
+    assert_eq!(merged.messages().count(), 3);
+
+    let conflicting = "\
+    #! Another error.
+    export.error.OTHER=3";
+    let conflicting = Module::new(
+        LibraryPath::new("test::other").unwrap(),
+        ModuleAst::parse(conflicting).unwrap(),
+    );
+    // messages are not returned for codes shared by distinct errors
+    let mut merged = table.clone();
+    merged.add_errors(&conflicting.path, conflicting.ast.named_errors());
+    assert!(merged.find_conflict().is_some());
+    assert_eq!(merged.messages().map(|(code, _)| code).collect::<Vec<_>>(), vec![1, 2]);
+
+    let modules = vec![errors, wallet, conflicting];
+    let err = MaslLibrary::new(namespace, Version::MIN, false, modules, Vec::new()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "error test::wallet::LOCKED (3) has the same code as error test::other::OTHER (3)"
+    );
+}
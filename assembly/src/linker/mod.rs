@@ -0,0 +1,586 @@
+use super::{
+    ast::{CodeBody, Instruction, ModuleAst, Node, ProcedureAst, ProgramAst},
+    Library, LibraryNamespace, LinkerError, MaslLibrary, Module, ProcedureId,
+};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::cmp::Ordering;
+
+#[cfg(test)]
+mod tests;
+
+// LINKER
+// ================================================================================================
+
+/// Links a program against a set of [MaslLibrary] dependencies.
+///
+/// Linking resolves every procedure invoked by the program (directly or transitively through
+/// other library procedures), and produces a [LinkedProgram] which contains only the procedures
+/// reachable from the program body. Procedures which are not reachable are removed from both the
+/// program and the libraries, and modules (or whole libraries) left without reachable procedures
+/// are dropped from the bundle.
+///
+/// Libraries are keyed by their namespace. When a library with an already registered namespace is
+/// added, the two versions must be compatible: they must share the same major version and, for
+/// `0.x` versions, the same minor version as well. The newer of two compatible versions is kept.
+#[derive(Debug, Default, Clone)]
+pub struct Linker {
+    libraries: BTreeMap<LibraryNamespace, MaslLibrary>,
+}
+
+impl Linker {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds the provided library to the set of libraries the program is linked against.
+    ///
+    /// # Errors
+    /// Returns an error if a library with the same namespace but an incompatible version has
+    /// already been added to this linker.
+    pub fn with_library(mut self, library: &MaslLibrary) -> Result<Self, LinkerError> {
+        self.add_library(library)?;
+        Ok(self)
+    }
+
+    /// Adds all of the provided libraries to the set of libraries the program is linked against.
+    ///
+    /// # Errors
+    /// Returns an error if any two libraries share a namespace but have incompatible versions.
+    pub fn with_libraries<'a, I>(self, mut libraries: I) -> Result<Self, LinkerError>
+    where
+        I: Iterator<Item = &'a MaslLibrary>,
+    {
+        libraries.try_fold(self, |slf, library| slf.with_library(library))
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an iterator over the libraries currently registered with this linker.
+    pub fn libraries(&self) -> impl Iterator<Item = &MaslLibrary> {
+        self.libraries.values()
+    }
+
+    // LINKER
+    // --------------------------------------------------------------------------------------------
+
+    /// Links the provided program against the libraries of this linker.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - A dependency of one of the libraries has not been provided to the linker.
+    /// - A procedure invoked by the program or by any reachable library procedure cannot be
+    ///   resolved.
+    pub fn link(&self, program: &ProgramAst) -> Result<LinkedProgram, LinkerError> {
+        self.check_dependencies()?;
+
+        let modules: Vec<&Module> =
+            self.libraries.values().flat_map(|library| library.modules()).collect();
+        let index = build_procedure_index(&modules);
+
+        // walk the call graph starting from the program body
+        let mut reachable = ReachableProcs::new(&modules);
+        let mut worklist = Vec::new();
+        let program_caller = Caller::Program(program);
+        program_caller.collect_invocations(
+            program.body(),
+            &index,
+            &mut reachable,
+            &mut worklist,
+        )?;
+
+        while let Some(proc_ref) = worklist.pop() {
+            if !reachable.insert(proc_ref) {
+                continue;
+            }
+            match proc_ref {
+                ProcRef::Program(proc_idx) => {
+                    let proc = &program.procedures()[proc_idx as usize];
+                    program_caller.collect_invocations(
+                        &proc.body,
+                        &index,
+                        &mut reachable,
+                        &mut worklist,
+                    )?;
+                }
+                ProcRef::Local {
+                    module,
+                    index: proc_idx,
+                } => {
+                    let proc = &modules[module].ast.procs()[proc_idx as usize];
+                    Caller::Module(module, modules[module]).collect_invocations(
+                        &proc.body,
+                        &index,
+                        &mut reachable,
+                        &mut worklist,
+                    )?;
+                }
+                ProcRef::ReExport {
+                    module,
+                    index: reexport_idx,
+                } => {
+                    let reexport = &modules[module].ast.reexported_procs()[reexport_idx];
+                    let target = index.get(&reexport.proc_id()).copied().ok_or_else(|| {
+                        LinkerError::unresolved_procedure(
+                            &modules[module].path.append_unchecked(reexport.name()),
+                        )
+                    })?;
+                    worklist.push(target);
+                }
+            }
+        }
+
+        // build the pruned program and libraries
+        let unused_exports = reachable.unused_exports(&modules);
+        let program = prune_program(program, &reachable.program_procs);
+        let libraries = self.prune_libraries(&modules, &reachable)?;
+
+        Ok(LinkedProgram {
+            program,
+            libraries,
+            unused_exports,
+            has_dynamic_calls: reachable.has_dynamic_calls,
+        })
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Registers the provided library, resolving a possible namespace collision with a library
+    /// added previously.
+    fn add_library(&mut self, library: &MaslLibrary) -> Result<(), LinkerError> {
+        let namespace = library.root_ns();
+        match self.libraries.get(namespace) {
+            Some(existing) => {
+                let existing_version = existing.version();
+                let new_version = library.version();
+                let compatible = existing_version.cmp_major(new_version) == Ordering::Equal
+                    && (existing_version.major != 0
+                        || existing_version.cmp_minor(new_version) == Ordering::Equal);
+                if !compatible {
+                    return Err(LinkerError::version_conflict(
+                        namespace,
+                        *existing_version,
+                        *new_version,
+                    ));
+                }
+                if existing_version.cmp_patch(new_version) == Ordering::Less {
+                    self.libraries.insert(namespace.clone(), library.clone());
+                }
+            }
+            None => {
+                self.libraries.insert(namespace.clone(), library.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// Makes sure that all dependencies of the registered libraries have been provided.
+    fn check_dependencies(&self) -> Result<(), LinkerError> {
+        for library in self.libraries.values() {
+            for dependency in library.dependencies() {
+                if !self.libraries.contains_key(dependency) {
+                    return Err(LinkerError::missing_dependency(library.root_ns(), dependency));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Builds the libraries containing only the reachable procedures.
+    ///
+    /// `modules` is expected to be the flattened list of modules of all registered libraries, in
+    /// the order of `self.libraries`.
+    fn prune_libraries(
+        &self,
+        modules: &[&Module],
+        reachable: &ReachableProcs,
+    ) -> Result<Vec<MaslLibrary>, LinkerError> {
+        let mut pruned_modules = Vec::with_capacity(self.libraries.len());
+        let mut module_idx = 0;
+        for library in self.libraries.values() {
+            let mut library_modules = Vec::new();
+            for _ in library.modules() {
+                if let Some(module) = prune_module(
+                    modules[module_idx],
+                    &reachable.module_procs[module_idx],
+                    &reachable.module_reexports[module_idx],
+                ) {
+                    library_modules.push(module);
+                }
+                module_idx += 1;
+            }
+            pruned_modules.push((library, library_modules));
+        }
+
+        let namespaces: BTreeSet<LibraryNamespace> = pruned_modules
+            .iter()
+            .filter(|(_, modules)| !modules.is_empty())
+            .map(|(library, _)| library.root_ns().clone())
+            .collect();
+
+        pruned_modules
+            .into_iter()
+            .filter(|(_, modules)| !modules.is_empty())
+            .map(|(library, modules)| {
+                let dependencies = library
+                    .dependencies()
+                    .iter()
+                    .filter(|dependency| namespaces.contains(dependency))
+                    .cloned()
+                    .collect();
+                MaslLibrary::new(
+                    library.root_ns().clone(),
+                    *library.version(),
+                    library.has_source_locations(),
+                    modules,
+                    dependencies,
+                )
+                .map_err(LinkerError::from)
+            })
+            .collect()
+    }
+}
+
+// LINKED PROGRAM
+// ================================================================================================
+
+/// The result of linking a program against a set of libraries.
+///
+/// Contains the program and the libraries with all unreachable procedures removed, together with
+/// the list of exported library procedures which are never invoked by the program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkedProgram {
+    program: ProgramAst,
+    libraries: Vec<MaslLibrary>,
+    unused_exports: Vec<String>,
+    has_dynamic_calls: bool,
+}
+
+impl LinkedProgram {
+    /// Returns the program with all unreachable local procedures removed.
+    pub fn program(&self) -> &ProgramAst {
+        &self.program
+    }
+
+    /// Returns the libraries containing only the procedures reachable from the program.
+    pub fn libraries(&self) -> &[MaslLibrary] {
+        &self.libraries
+    }
+
+    /// Returns fully-qualified names of exported library procedures which are not reachable from
+    /// the program.
+    pub fn unused_exports(&self) -> &[String] {
+        &self.unused_exports
+    }
+
+    /// Returns true if the program or any of the reachable procedures invokes a procedure by its
+    /// MAST root (i.e., via `call.<root>`, `dynexec` or `dyncall`).
+    ///
+    /// Targets of such invocations cannot be resolved statically, and thus, procedures which are
+    /// reported as unreachable may still be required at runtime.
+    pub fn has_dynamic_calls(&self) -> bool {
+        self.has_dynamic_calls
+    }
+
+    /// Decomposes this linked program into the pruned program and libraries.
+    pub fn into_parts(self) -> (ProgramAst, Vec<MaslLibrary>) {
+        (self.program, self.libraries)
+    }
+}
+
+// PROCEDURE REFERENCES
+// ================================================================================================
+
+/// A reference to a procedure which can be reached from the program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProcRef {
+    /// A local procedure of the program being linked.
+    Program(u16),
+    /// A procedure at `index` of the module at `module` in the flattened module list.
+    Local { module: usize, index: u16 },
+    /// A re-exported procedure at `index` of the module at `module` in the flattened module list.
+    ReExport { module: usize, index: usize },
+}
+
+/// Builds a map from procedure IDs to the procedures they refer to.
+///
+/// Similarly to how the assembler resolves imports, all procedures (including re-exported ones)
+/// are indexed by the ID derived from their fully-qualified name.
+fn build_procedure_index(modules: &[&Module]) -> BTreeMap<ProcedureId, ProcRef> {
+    let mut index = BTreeMap::new();
+    for (module_idx, module) in modules.iter().enumerate() {
+        for (proc_idx, proc) in module.ast.procs().iter().enumerate() {
+            let proc_id = ProcedureId::from_name(&proc.name, &module.path);
+            index.insert(
+                proc_id,
+                ProcRef::Local {
+                    module: module_idx,
+                    index: proc_idx as u16,
+                },
+            );
+        }
+        for (reexport_idx, reexport) in module.ast.reexported_procs().iter().enumerate() {
+            index.insert(
+                reexport.get_alias_id(&module.path),
+                ProcRef::ReExport {
+                    module: module_idx,
+                    index: reexport_idx,
+                },
+            );
+        }
+    }
+    index
+}
+
+/// The entity whose procedure bodies are being traversed.
+#[derive(Clone, Copy)]
+enum Caller<'a> {
+    Program(&'a ProgramAst),
+    /// A library module together with its index in the flattened module list.
+    Module(usize, &'a Module),
+}
+
+impl Caller<'_> {
+    /// Pushes references to all procedures invoked from the provided code body into `worklist`.
+    fn collect_invocations(
+        &self,
+        body: &CodeBody,
+        index: &BTreeMap<ProcedureId, ProcRef>,
+        reachable: &mut ReachableProcs,
+        worklist: &mut Vec<ProcRef>,
+    ) -> Result<(), LinkerError> {
+        for node in body.nodes() {
+            match node {
+                Node::Instruction(instruction) => match instruction {
+                    Instruction::ExecLocal(idx)
+                    | Instruction::CallLocal(idx)
+                    | Instruction::ProcRefLocal(idx) => worklist.push(self.local_proc(*idx)?),
+                    Instruction::ExecImported(id)
+                    | Instruction::CallImported(id)
+                    | Instruction::ProcRefImported(id) => {
+                        let proc_ref = index.get(id).copied().ok_or_else(|| {
+                            LinkerError::unresolved_procedure(&self.imported_proc_name(id))
+                        })?;
+                        worklist.push(proc_ref);
+                    }
+                    Instruction::CallMastRoot(_) | Instruction::DynExec | Instruction::DynCall => {
+                        reachable.has_dynamic_calls = true;
+                    }
+                    _ => (),
+                },
+                Node::IfElse {
+                    true_case,
+                    false_case,
+                } => {
+                    self.collect_invocations(true_case, index, reachable, worklist)?;
+                    self.collect_invocations(false_case, index, reachable, worklist)?;
+                }
+                Node::Repeat { body, .. } | Node::While { body } => {
+                    self.collect_invocations(body, index, reachable, worklist)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns a reference to the local procedure at the specified index.
+    fn local_proc(&self, proc_idx: u16) -> Result<ProcRef, LinkerError> {
+        match self {
+            Self::Program(program) => {
+                if proc_idx as usize >= program.procedures().len() {
+                    return Err(LinkerError::local_proc_not_found(proc_idx, "#exec"));
+                }
+                Ok(ProcRef::Program(proc_idx))
+            }
+            Self::Module(module_idx, module) => {
+                if proc_idx as usize >= module.ast.procs().len() {
+                    return Err(LinkerError::local_proc_not_found(proc_idx, &module.path));
+                }
+                Ok(ProcRef::Local {
+                    module: *module_idx,
+                    index: proc_idx,
+                })
+            }
+        }
+    }
+
+    /// Returns the fully-qualified name of the imported procedure with the specified ID, if this
+    /// information is available, or a string representation of the ID otherwise.
+    fn imported_proc_name(&self, proc_id: &ProcedureId) -> String {
+        let import_info = match self {
+            Self::Program(program) => program.import_info(),
+            Self::Module(_, module) => module.ast.import_info(),
+        };
+        match import_info.get_procedure_info(proc_id) {
+            Some((name, path)) => path.append_unchecked(name),
+            None => proc_id.to_string(),
+        }
+    }
+}
+
+// REACHABLE PROCEDURES
+// ================================================================================================
+
+/// Tracks the set of procedures reachable from the program body.
+struct ReachableProcs {
+    program_procs: BTreeSet<u16>,
+    module_procs: Vec<BTreeSet<u16>>,
+    module_reexports: Vec<BTreeSet<usize>>,
+    has_dynamic_calls: bool,
+}
+
+impl ReachableProcs {
+    fn new(modules: &[&Module]) -> Self {
+        Self {
+            program_procs: BTreeSet::new(),
+            module_procs: vec![BTreeSet::new(); modules.len()],
+            module_reexports: vec![BTreeSet::new(); modules.len()],
+            has_dynamic_calls: false,
+        }
+    }
+
+    /// Marks the referenced procedure as reachable; returns false if it was already marked.
+    fn insert(&mut self, proc_ref: ProcRef) -> bool {
+        match proc_ref {
+            ProcRef::Program(index) => self.program_procs.insert(index),
+            ProcRef::Local { module, index } => self.module_procs[module].insert(index),
+            ProcRef::ReExport { module, index } => self.module_reexports[module].insert(index),
+        }
+    }
+
+    /// Returns fully-qualified names of all exported and re-exported procedures which have not
+    /// been marked as reachable.
+    fn unused_exports(&self, modules: &[&Module]) -> Vec<String> {
+        let mut unused = Vec::new();
+        for (module_idx, module) in modules.iter().enumerate() {
+            for (proc_idx, proc) in module.ast.procs().iter().enumerate() {
+                if proc.is_export && !self.module_procs[module_idx].contains(&(proc_idx as u16)) {
+                    unused.push(module.path.append_unchecked(&proc.name));
+                }
+            }
+            for (reexport_idx, reexport) in module.ast.reexported_procs().iter().enumerate() {
+                if !self.module_reexports[module_idx].contains(&reexport_idx) {
+                    unused.push(module.path.append_unchecked(reexport.name()));
+                }
+            }
+        }
+        unused
+    }
+}
+
+// PRUNING
+// ================================================================================================
+
+/// Returns a copy of the program which contains only the specified local procedures.
+fn prune_program(program: &ProgramAst, reachable: &BTreeSet<u16>) -> ProgramAst {
+    let remap = build_index_remap(program.procedures().len(), reachable);
+    let procs = prune_procs(program.procedures(), &remap);
+
+    let mut locations = program.source_locations();
+    let start = *locations.next().expect("program start location is always present");
+    let body = remap_body(program.body(), &remap);
+    let (nodes, _) = body.into_parts();
+
+    ProgramAst::new(nodes, procs)
+        .expect("pruned program cannot be larger than the original program")
+        .with_import_info(program.import_info().clone())
+        .with_memory_regions(program.memory_regions().to_vec())
+        .with_named_errors(program.named_errors().to_vec())
+        .with_source_locations(locations.copied(), start)
+}
+
+/// Returns a copy of the module which contains only the specified local and re-exported
+/// procedures, or None if none of the module's procedures are reachable.
+fn prune_module(
+    module: &Module,
+    reachable_procs: &BTreeSet<u16>,
+    reachable_reexports: &BTreeSet<usize>,
+) -> Option<Module> {
+    if reachable_procs.is_empty() && reachable_reexports.is_empty() {
+        return None;
+    }
+
+    let remap = build_index_remap(module.ast.procs().len(), reachable_procs);
+    let procs = prune_procs(module.ast.procs(), &remap);
+    let reexports = module
+        .ast
+        .reexported_procs()
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| reachable_reexports.contains(idx))
+        .map(|(_, reexport)| reexport.clone())
+        .collect();
+
+    let ast = ModuleAst::new(procs, reexports, module.ast.docs().cloned())
+        .expect("pruned module cannot be larger than the original module")
+        .with_import_info(module.ast.import_info().clone())
+        .with_exported_constants(module.ast.exported_constants().clone())
+        .with_memory_regions(module.ast.memory_regions().to_vec())
+        .with_named_errors(module.ast.named_errors().to_vec());
+    Some(Module::new(module.path.clone(), ast))
+}
+
+/// Builds a map from the original local procedure indexes to the indexes these procedures will
+/// have once all unreachable procedures are removed.
+fn build_index_remap(num_procs: usize, reachable: &BTreeSet<u16>) -> Vec<Option<u16>> {
+    let mut next_idx = 0;
+    (0..num_procs)
+        .map(|idx| {
+            reachable.contains(&(idx as u16)).then(|| {
+                next_idx += 1;
+                next_idx - 1
+            })
+        })
+        .collect()
+}
+
+/// Returns the retained procedures with their local invocations updated to the new indexes.
+fn prune_procs(procs: &[ProcedureAst], remap: &[Option<u16>]) -> Vec<ProcedureAst> {
+    procs
+        .iter()
+        .zip(remap)
+        .filter(|(_, new_idx)| new_idx.is_some())
+        .map(|(proc, _)| ProcedureAst {
+            body: remap_body(&proc.body, remap),
+            ..proc.clone()
+        })
+        .collect()
+}
+
+/// Rewrites local procedure invocations in the provided code body according to `remap`.
+///
+/// Source locations of the body are preserved as the number of nodes does not change.
+fn remap_body(body: &CodeBody, remap: &[Option<u16>]) -> CodeBody {
+    let remap_idx = |idx: &u16| remap[*idx as usize].expect("invoked procedure must be reachable");
+    let nodes = body.nodes().iter().map(|node| match node {
+        Node::Instruction(Instruction::ExecLocal(idx)) => {
+            Node::Instruction(Instruction::ExecLocal(remap_idx(idx)))
+        }
+        Node::Instruction(Instruction::CallLocal(idx)) => {
+            Node::Instruction(Instruction::CallLocal(remap_idx(idx)))
+        }
+        Node::Instruction(Instruction::ProcRefLocal(idx)) => {
+            Node::Instruction(Instruction::ProcRefLocal(remap_idx(idx)))
+        }
+        Node::Instruction(instruction) => Node::Instruction(instruction.clone()),
+        Node::IfElse {
+            true_case,
+            false_case,
+        } => Node::IfElse {
+            true_case: remap_body(true_case, remap),
+            false_case: remap_body(false_case, remap),
+        },
+        Node::Repeat { times, body } => Node::Repeat {
+            times: *times,
+            body: remap_body(body, remap),
+        },
+        Node::While { body } => Node::While {
+            body: remap_body(body, remap),
+        },
+    });
+    CodeBody::new(nodes).with_source_locations(body.source_locations().iter().copied())
+}
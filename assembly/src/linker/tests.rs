@@ -0,0 +1,214 @@
+use super::{
+    super::{
+        ast::{ModuleAst, ProgramAst},
+        Assembler, Library, LibraryNamespace, LibraryPath, LinkerError, MaslLibrary, Module,
+        Version,
+    },
+    Linker,
+};
+use alloc::{string::ToString, vec::Vec};
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_library(namespace: &str, version: Version, modules: &[(&str, &str)]) -> MaslLibrary {
+    let modules = modules
+        .iter()
+        .map(|(path, source)| {
+            let path = LibraryPath::new(path).unwrap();
+            Module::new(path, ModuleAst::parse(source).unwrap())
+        })
+        .collect();
+    let namespace = LibraryNamespace::new(namespace).unwrap();
+    MaslLibrary::new(namespace, version, false, modules, Vec::new()).unwrap()
+}
+
+fn test_library(version: Version) -> MaslLibrary {
+    let foo = "\
+        proc.helper
+            push.3 add
+        end
+        proc.unused_helper
+            push.4 mul
+        end
+        export.foo
+            exec.helper
+        end
+        export.foo_mul
+            exec.unused_helper
+        end";
+    let bar = "\
+        export.bar
+            mul
+        end";
+    build_library("test", version, &[("test::foo", foo), ("test::bar", bar)])
+}
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn link_removes_unreachable_procedures() {
+    let library = test_library(Version::MIN);
+    let source = "\
+        use.test::foo
+        proc.unused
+            push.1
+        end
+        proc.local
+            exec.foo::foo
+        end
+        begin
+            exec.local
+        end";
+    let program = ProgramAst::parse(source).unwrap();
+
+    let linked = Linker::default().with_library(&library).unwrap().link(&program).unwrap();
+
+    // only the reachable program procedure is retained
+    let names: Vec<_> = linked.program().procedures().iter().map(|p| p.name.to_string()).collect();
+    assert_eq!(names, ["local"]);
+
+    // only `test::foo` remains in the bundle, containing `foo` and its helper
+    assert_eq!(linked.libraries().len(), 1);
+    let modules: Vec<_> = linked.libraries()[0].modules().collect();
+    assert_eq!(modules.len(), 1);
+    assert_eq!(modules[0].path.as_str(), "test::foo");
+    let names: Vec<_> = modules[0].ast.procs().iter().map(|p| p.name.to_string()).collect();
+    assert_eq!(names, ["helper", "foo"]);
+
+    assert_eq!(linked.unused_exports(), ["test::foo::foo_mul", "test::bar::bar"]);
+    assert!(!linked.has_dynamic_calls());
+
+    // the linked program compiles to the same MAST as the original one
+    let expected = Assembler::default()
+        .with_library(&library)
+        .unwrap()
+        .compile_ast(&program)
+        .unwrap();
+    let (program, libraries) = linked.into_parts();
+    let actual = Assembler::default()
+        .with_libraries(libraries.iter())
+        .unwrap()
+        .compile_ast(&program)
+        .unwrap();
+    assert_eq!(expected.hash(), actual.hash());
+}
+
+#[test]
+fn link_resolves_reexported_procedures() {
+    let library = test_library(Version::MIN);
+    let baz = "\
+        use.test::foo
+        export.foo::foo->baz";
+    let reexporting = build_library("other", Version::MIN, &[("other::baz", baz)]);
+    let source = "\
+        use.other::baz
+        begin
+            exec.baz::baz
+        end";
+    let program = ProgramAst::parse(source).unwrap();
+
+    let linked = Linker::default()
+        .with_library(&library)
+        .unwrap()
+        .with_library(&reexporting)
+        .unwrap()
+        .link(&program)
+        .unwrap();
+
+    assert_eq!(linked.libraries().len(), 2);
+    assert_eq!(linked.unused_exports(), ["test::foo::foo_mul", "test::bar::bar"]);
+}
+
+#[test]
+fn link_unresolved_procedure() {
+    let library = test_library(Version::MIN);
+    let source = "\
+        use.test::baz
+        begin
+            exec.baz::baz
+        end";
+    let program = ProgramAst::parse(source).unwrap();
+
+    let result = Linker::default().with_library(&library).unwrap().link(&program);
+    assert_eq!(result, Err(LinkerError::unresolved_procedure("test::baz::baz")));
+}
+
+#[test]
+fn link_dynamic_calls() {
+    let library = test_library(Version::MIN);
+    let source = "\
+        use.test::foo
+        begin
+            procref.foo::foo_mul dynexec
+        end";
+    let program = ProgramAst::parse(source).unwrap();
+
+    let linked = Linker::default().with_library(&library).unwrap().link(&program).unwrap();
+    assert!(linked.has_dynamic_calls());
+    assert_eq!(linked.unused_exports(), ["test::foo::foo", "test::bar::bar"]);
+}
+
+#[test]
+fn link_version_conflicts() {
+    let v0_1 = Version::MIN;
+    let v0_1_1 = v0_1.inc_patch();
+    let v0_2 = v0_1.inc_minor();
+    let v1_0 = v0_1.inc_major();
+    let v1_1 = v1_0.inc_minor();
+    let v2_0 = v1_0.inc_major();
+    let namespace = LibraryNamespace::new("test").unwrap();
+
+    // patch versions are compatible and the newer version is kept
+    let linker = Linker::default()
+        .with_library(&test_library(v0_1_1))
+        .unwrap()
+        .with_library(&test_library(v0_1))
+        .unwrap();
+    let versions: Vec<_> = linker.libraries().map(|library| *library.version()).collect();
+    assert_eq!(versions, [v0_1_1]);
+
+    // minor versions are compatible only for non-zero major versions
+    let linker = Linker::default()
+        .with_library(&test_library(v1_0))
+        .unwrap()
+        .with_library(&test_library(v1_1))
+        .unwrap();
+    let versions: Vec<_> = linker.libraries().map(|library| *library.version()).collect();
+    assert_eq!(versions, [v1_1]);
+
+    let result = Linker::default()
+        .with_library(&test_library(v0_1))
+        .unwrap()
+        .with_library(&test_library(v0_2));
+    assert_eq!(result.unwrap_err(), LinkerError::version_conflict(&namespace, v0_1, v0_2));
+
+    // major versions are never compatible
+    let result = Linker::default()
+        .with_library(&test_library(v1_1))
+        .unwrap()
+        .with_library(&test_library(v2_0));
+    assert_eq!(result.unwrap_err(), LinkerError::version_conflict(&namespace, v1_1, v2_0));
+}
+
+#[test]
+fn link_missing_dependency() {
+    let library = test_library(Version::MIN);
+    let dependent = MaslLibrary::new(
+        LibraryNamespace::new("other").unwrap(),
+        Version::MIN,
+        false,
+        library.modules().cloned().collect(),
+        vec![LibraryNamespace::new("missing").unwrap()],
+    )
+    .unwrap();
+    let program = ProgramAst::parse("begin push.1 end").unwrap();
+
+    let result = Linker::default().with_library(&dependent).unwrap().link(&program);
+    let expected = LinkerError::missing_dependency(
+        &LibraryNamespace::new("other").unwrap(),
+        &LibraryNamespace::new("missing").unwrap(),
+    );
+    assert_eq!(result, Err(expected));
+}
@@ -0,0 +1,679 @@
+use super::{
+    assembler::check_define,
+    ast::{
+        CodeBody, ConstantProvider, Instruction, ModuleAst, ModuleImports, Node, ProcedureAst,
+        ProgramAst,
+    },
+    tokens::SourceLocation,
+    AssemblyError, Library, LibraryError, LibraryPath, ModuleProvider, ParsingError, Token,
+    TokenStream,
+};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+#[cfg(test)]
+mod tests;
+
+// LINT RULE
+// ================================================================================================
+
+/// A rule checked by the [Linter].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LintRule {
+    /// A local procedure is not reachable from any exported procedure or from the program body.
+    UnusedProcedure,
+    /// An imported module is neither invoked nor re-exported from.
+    UnusedImport,
+    /// A constant is declared but never referenced.
+    UnusedConstant,
+    /// Instructions follow an assertion which always fails.
+    UnreachableCode,
+    /// A sequence of two stack manipulation instructions has no effect (e.g., `swap swap`).
+    RedundantStackOps,
+    /// A procedure declares more locals than it accesses.
+    UnusedLocals,
+    /// An imported procedure which is not exported from its module is invoked.
+    NonExportedInvocation,
+}
+
+impl LintRule {
+    /// All rules checked by the linter.
+    pub const ALL: [Self; 7] = [
+        Self::UnusedProcedure,
+        Self::UnusedImport,
+        Self::UnusedConstant,
+        Self::UnreachableCode,
+        Self::RedundantStackOps,
+        Self::UnusedLocals,
+        Self::NonExportedInvocation,
+    ];
+
+    /// Returns the rule with the specified name, if any.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|rule| rule.as_str() == name)
+    }
+
+    /// Returns the name of this rule.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::UnusedProcedure => "unused_procedure",
+            Self::UnusedImport => "unused_import",
+            Self::UnusedConstant => "unused_constant",
+            Self::UnreachableCode => "unreachable_code",
+            Self::RedundantStackOps => "redundant_stack_ops",
+            Self::UnusedLocals => "unused_locals",
+            Self::NonExportedInvocation => "non_exported_invocation",
+        }
+    }
+}
+
+impl fmt::Display for LintRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+// LINT LEVEL
+// ================================================================================================
+
+/// Specifies how violations of a lint rule are reported.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LintLevel {
+    /// Violations are not reported.
+    Allow,
+    /// Violations are reported as warnings.
+    #[default]
+    Warn,
+    /// Violations are reported as errors.
+    Deny,
+}
+
+impl LintLevel {
+    /// Returns the level with the specified name (`allow`, `warn`, or `deny`), if any.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "allow" => Some(Self::Allow),
+            "warn" => Some(Self::Warn),
+            "deny" => Some(Self::Deny),
+            _ => None,
+        }
+    }
+}
+
+// LINT CONFIG
+// ================================================================================================
+
+/// Specifies the level of every lint rule; rules without an explicitly set level are reported as
+/// warnings.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LintConfig {
+    levels: BTreeMap<LintRule, LintLevel>,
+}
+
+impl LintConfig {
+    /// Sets the level of the specified rule.
+    pub fn with_level(mut self, rule: LintRule, level: LintLevel) -> Self {
+        self.set_level(rule, level);
+        self
+    }
+
+    /// Sets the level of the specified rule.
+    pub fn set_level(&mut self, rule: LintRule, level: LintLevel) {
+        self.levels.insert(rule, level);
+    }
+
+    /// Returns the level of the specified rule.
+    pub fn level(&self, rule: LintRule) -> LintLevel {
+        self.levels.get(&rule).copied().unwrap_or_default()
+    }
+}
+
+// LINT
+// ================================================================================================
+
+/// A violation of a lint rule found in a source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lint {
+    rule: LintRule,
+    level: LintLevel,
+    location: SourceLocation,
+    message: String,
+}
+
+impl Lint {
+    /// Returns the rule which was violated.
+    pub fn rule(&self) -> LintRule {
+        self.rule
+    }
+
+    /// Returns the level at which the violation is reported.
+    pub fn level(&self) -> LintLevel {
+        self.level
+    }
+
+    /// Returns the location of the violation in the source.
+    pub fn location(&self) -> &SourceLocation {
+        &self.location
+    }
+
+    /// Returns the description of the violation.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns true if this lint is reported as an error.
+    pub fn is_error(&self) -> bool {
+        self.level == LintLevel::Deny
+    }
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = if self.is_error() { "error" } else { "warning" };
+        write!(f, "{level}[{}] {}: {}", self.rule, self.location, self.message)
+    }
+}
+
+// LINTER
+// ================================================================================================
+
+/// Checks Miden assembly sources (programs or modules) for common issues.
+///
+/// Libraries added to the linter are used to check invocations of imported procedures; when the
+/// module of an imported procedure is not provided by any of the libraries, the invocation is not
+/// checked. Defines set on the linter are available to the linted sources as constants.
+#[derive(Debug, Default, Clone)]
+pub struct Linter {
+    config: LintConfig,
+    module_provider: ModuleProvider,
+    defines: BTreeMap<String, u64>,
+}
+
+impl Linter {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new linter which reports violations according to the provided config.
+    pub fn new(config: LintConfig) -> Self {
+        Self {
+            config,
+            module_provider: ModuleProvider::default(),
+            defines: BTreeMap::new(),
+        }
+    }
+
+    /// Adds the library used to resolve imported procedures.
+    pub fn with_library<L>(mut self, library: &L) -> Result<Self, LibraryError>
+    where
+        L: Library,
+    {
+        self.module_provider.add_library(library)?;
+        Ok(self)
+    }
+
+    /// Sets a define with the specified name and value.
+    ///
+    /// # Errors
+    /// Returns an error if the name is not a valid constant name, or if the value is not a valid
+    /// field element.
+    pub fn with_define(mut self, name: &str, value: u64) -> Result<Self, AssemblyError> {
+        check_define(name, value)?;
+        self.defines.insert(name.to_string(), value);
+        Ok(self)
+    }
+
+    // LINTING
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks the provided source and returns all violations of rules which are not allowed,
+    /// ordered by their location in the source.
+    ///
+    /// The source is treated as a program if it contains a `begin` block, and as a module
+    /// otherwise.
+    ///
+    /// # Errors
+    /// Returns an error if the source is not a valid program or module.
+    pub fn lint_source(&self, source: &str) -> Result<Vec<Lint>, ParsingError> {
+        let tokens = SourceTokens::new(source)?;
+        let mut lints = LintCollector::new(&self.config);
+
+        if tokens.iter().any(|(parts, _)| parts[0] == Token::BEGIN) {
+            let program = ProgramAst::parse_with_constants(source, self)?;
+            let source = LintSource {
+                procs: program.procedures(),
+                body: Some(program.body()),
+                import_info: program.import_info(),
+                tokens,
+            };
+            self.lint(&source, &mut lints);
+        } else {
+            let module = ModuleAst::parse_with_constants(source, self)?;
+            let source = LintSource {
+                procs: module.procs(),
+                body: None,
+                import_info: module.import_info(),
+                tokens,
+            };
+            self.lint(&source, &mut lints);
+        }
+
+        Ok(lints.into_lints())
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    fn lint(&self, source: &LintSource, lints: &mut LintCollector) {
+        check_unused_procedures(source, lints);
+        check_unused_imports(source, lints);
+        check_unused_constants(source, lints);
+        check_unused_locals(source, lints);
+        for body in source.bodies() {
+            check_unreachable_code(body, lints);
+            check_redundant_stack_ops(body, lints);
+            self.check_imported_invocations(body, source.import_info, lints);
+        }
+    }
+
+    /// Reports invocations of imported procedures which are not exported from their modules.
+    fn check_imported_invocations(
+        &self,
+        body: &CodeBody,
+        import_info: &ModuleImports,
+        lints: &mut LintCollector,
+    ) {
+        for (node, location) in body_nodes(body) {
+            let proc_id = match node {
+                Node::Instruction(Instruction::ExecImported(proc_id))
+                | Node::Instruction(Instruction::CallImported(proc_id))
+                | Node::Instruction(Instruction::ProcRefImported(proc_id)) => proc_id,
+                _ => continue,
+            };
+            let Some((proc_name, module_path)) = import_info.get_procedure_info(proc_id) else {
+                continue;
+            };
+            let Some(module) = self.module_provider.get_module_by_path(module_path) else {
+                continue;
+            };
+
+            let is_exported =
+                module.ast.procs().iter().any(|proc| proc.is_export && &proc.name == proc_name)
+                    || module.ast.reexported_procs().iter().any(|proc| proc.name() == proc_name);
+            if !is_exported {
+                let message =
+                    format!("procedure '{proc_name}' is not exported from module '{module_path}'");
+                lints.report(LintRule::NonExportedInvocation, location, message);
+            }
+        }
+    }
+}
+
+impl ConstantProvider for Linter {
+    fn get_constant(&self, module_path: &LibraryPath, name: &str) -> Option<u64> {
+        self.module_provider.get_constant(module_path, name)
+    }
+
+    fn get_define(&self, name: &str) -> Option<u64> {
+        self.defines.get(name).copied()
+    }
+}
+
+// LINT SOURCE
+// ================================================================================================
+
+/// The parsed contents of a source being linted.
+struct LintSource<'a> {
+    procs: &'a [ProcedureAst],
+    /// Body of the program; `None` if the source is a module.
+    body: Option<&'a CodeBody>,
+    import_info: &'a ModuleImports,
+    tokens: SourceTokens,
+}
+
+impl<'a> LintSource<'a> {
+    /// Returns all code bodies of the source, including the nested ones.
+    fn bodies(&self) -> Vec<&'a CodeBody> {
+        let mut bodies = Vec::new();
+        for body in self.procs.iter().map(|proc| &proc.body).chain(self.body) {
+            collect_bodies(body, &mut bodies);
+        }
+        bodies
+    }
+}
+
+/// All tokens of a source together with their locations.
+struct SourceTokens(Vec<(Vec<String>, SourceLocation)>);
+
+impl SourceTokens {
+    fn new(source: &str) -> Result<Self, ParsingError> {
+        let mut stream = TokenStream::new(source)?;
+        let mut tokens = Vec::new();
+        while let Some(token) = stream.read() {
+            let parts = token.parts().iter().map(|part| part.to_string()).collect();
+            tokens.push((parts, *token.location()));
+            stream.advance();
+        }
+        Ok(Self(tokens))
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (&[String], SourceLocation)> {
+        self.0.iter().map(|(parts, location)| (parts.as_slice(), *location))
+    }
+}
+
+// LINT COLLECTOR
+// ================================================================================================
+
+/// Collects violations of rules which are not allowed by the config.
+struct LintCollector<'a> {
+    config: &'a LintConfig,
+    lints: Vec<Lint>,
+}
+
+impl<'a> LintCollector<'a> {
+    fn new(config: &'a LintConfig) -> Self {
+        Self {
+            config,
+            lints: Vec::new(),
+        }
+    }
+
+    fn report(&mut self, rule: LintRule, location: SourceLocation, message: String) {
+        let level = self.config.level(rule);
+        if level != LintLevel::Allow {
+            self.lints.push(Lint {
+                rule,
+                level,
+                location,
+                message,
+            });
+        }
+    }
+
+    fn into_lints(mut self) -> Vec<Lint> {
+        self.lints.sort_by_key(|lint| (lint.location, lint.rule));
+        self.lints
+    }
+}
+
+// LINT RULES
+// ================================================================================================
+
+/// Reports local procedures which cannot be reached from exported procedures or from the program
+/// body.
+fn check_unused_procedures(source: &LintSource, lints: &mut LintCollector) {
+    let mut reachable = BTreeSet::new();
+    let mut to_visit: Vec<&CodeBody> = source.body.into_iter().collect();
+    for (index, proc) in source.procs.iter().enumerate() {
+        if proc.is_export {
+            reachable.insert(index);
+            to_visit.push(&proc.body);
+        }
+    }
+
+    while let Some(body) = to_visit.pop() {
+        let mut bodies = Vec::new();
+        collect_bodies(body, &mut bodies);
+        for (node, _) in bodies.into_iter().flat_map(body_nodes) {
+            if let Node::Instruction(
+                Instruction::ExecLocal(index)
+                | Instruction::CallLocal(index)
+                | Instruction::ProcRefLocal(index),
+            ) = node
+            {
+                let index = *index as usize;
+                if reachable.insert(index) {
+                    to_visit.push(&source.procs[index].body);
+                }
+            }
+        }
+    }
+
+    for (index, proc) in source.procs.iter().enumerate() {
+        if !reachable.contains(&index) {
+            let message = format!("procedure '{}' is never used", proc.name);
+            lints.report(LintRule::UnusedProcedure, proc.start, message);
+        }
+    }
+}
+
+/// Reports imported modules from which no procedures are invoked or re-exported, and no constants
+/// are referenced.
+fn check_unused_imports(source: &LintSource, lints: &mut LintCollector) {
+    let import_info = source.import_info;
+    let mut used_paths: BTreeSet<&LibraryPath> = import_info
+        .get_imported_procedures()
+        .keys()
+        .filter_map(|proc_id| import_info.get_procedure_path(proc_id))
+        .chain(import_info.const_module_paths())
+        .collect();
+
+    // re-exported procedures are not tracked as invoked procedures, so the modules they are
+    // re-exported from are determined from the `export.<module>::<procedure>` tokens
+    for (parts, _) in source.tokens.iter() {
+        if parts[0] == Token::EXPORT && parts.len() == 2 {
+            if let Some((module_name, _)) = parts[1].split_once("::") {
+                used_paths.extend(import_info.get_module_path(module_name));
+            }
+        }
+    }
+
+    for (parts, location) in source.tokens.iter() {
+        if parts[0] != Token::USE || parts.len() != 2 {
+            continue;
+        }
+        let module_path = parts[1].split_once(Token::ALIAS_DELIM).map_or(&*parts[1], |(p, _)| p);
+        if !used_paths.iter().any(|path| path.path() == module_path) {
+            let message = format!("module '{module_path}' is imported but never used");
+            lints.report(LintRule::UnusedImport, location, message);
+        }
+    }
+}
+
+/// Reports constants which are not referenced by any instruction or other constant.
+fn check_unused_constants(source: &LintSource, lints: &mut LintCollector) {
+    let mut constants = Vec::new();
+    let mut references = BTreeSet::new();
+    for (parts, location) in source.tokens.iter() {
+        let expressions: Vec<&str> = if parts[0] == Token::CONST && parts.len() >= 2 {
+            // the constant declaration is `const.NAME=EXPRESSION`
+            let (name, expression) = parts[1].split_once('=').unwrap_or((parts[1].as_str(), ""));
+            constants.push((name, location));
+            core::iter::once(expression)
+                .chain(parts[2..].iter().map(String::as_str))
+                .collect()
+        } else {
+            parts.iter().map(String::as_str).collect()
+        };
+
+        for expression in expressions {
+            let identifiers = expression.split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'));
+            references.extend(identifiers.filter(|identifier| !identifier.is_empty()));
+        }
+    }
+
+    for (name, location) in constants {
+        if !references.contains(name) {
+            let message = format!("constant '{name}' is never used");
+            lints.report(LintRule::UnusedConstant, location, message);
+        }
+    }
+}
+
+/// Reports procedures which declare more locals than they access.
+///
+/// Procedures which take the address of any of their locals are not checked, as the locals may be
+/// accessed indirectly via the address.
+fn check_unused_locals(source: &LintSource, lints: &mut LintCollector) {
+    for proc in source.procs.iter().filter(|proc| proc.num_locals > 0) {
+        let mut bodies = Vec::new();
+        collect_bodies(&proc.body, &mut bodies);
+
+        let mut num_used_locals = 0;
+        for (node, _) in bodies.into_iter().flat_map(body_nodes) {
+            match node {
+                Node::Instruction(Instruction::Locaddr(_)) => {
+                    num_used_locals = proc.num_locals;
+                    break;
+                }
+                Node::Instruction(
+                    Instruction::LocLoad(index)
+                    | Instruction::LocLoadW(index)
+                    | Instruction::LocStore(index)
+                    | Instruction::LocStoreW(index),
+                ) => num_used_locals = num_used_locals.max(index + 1),
+                _ => (),
+            }
+        }
+
+        if num_used_locals < proc.num_locals {
+            let message = format!(
+                "procedure '{}' declares {} locals but accesses only {}",
+                proc.name, proc.num_locals, num_used_locals
+            );
+            lints.report(LintRule::UnusedLocals, proc.start, message);
+        }
+    }
+}
+
+/// Reports instructions which follow an assertion on a constant value which always fails.
+fn check_unreachable_code(body: &CodeBody, lints: &mut LintCollector) {
+    let nodes: Vec<_> = body_nodes(body).collect();
+    for (index, pair) in nodes.windows(2).enumerate() {
+        let (Node::Instruction(push), Node::Instruction(assert)) = (pair[0].0, pair[1].0) else {
+            continue;
+        };
+        let value = match push {
+            Instruction::PushU8(value) => *value as u64,
+            Instruction::PushU16(value) => *value as u64,
+            Instruction::PushU32(value) => *value as u64,
+            Instruction::PushFelt(value) => value.as_int(),
+            _ => continue,
+        };
+        let always_fails = match assert {
+            Instruction::Assert | Instruction::AssertWithError(_) => value == 0,
+            Instruction::Assertz | Instruction::AssertzWithError(_) => value != 0,
+            _ => false,
+        };
+
+        if let (true, Some((_, location))) = (always_fails, nodes.get(index + 2)) {
+            let message = "code after an assertion which always fails is unreachable".to_string();
+            lints.report(LintRule::UnreachableCode, *location, message);
+            return;
+        }
+    }
+}
+
+/// Reports pairs of consecutive stack manipulation instructions which cancel each other out.
+fn check_redundant_stack_ops(body: &CodeBody, lints: &mut LintCollector) {
+    let nodes: Vec<_> = body_nodes(body).collect();
+    let mut index = 0;
+    while index + 1 < nodes.len() {
+        if let (Node::Instruction(first), Node::Instruction(second)) =
+            (nodes[index].0, nodes[index + 1].0)
+        {
+            if cancel_out(first, second) {
+                let message = format!("'{first} {second}' has no effect on the stack");
+                lints.report(LintRule::RedundantStackOps, nodes[index].1, message);
+                // skip the second instruction so that overlapping pairs are not reported
+                index += 1;
+            }
+        }
+        index += 1;
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Adds the provided body and all bodies nested in it to the list of bodies.
+fn collect_bodies<'a>(body: &'a CodeBody, bodies: &mut Vec<&'a CodeBody>) {
+    bodies.push(body);
+    for node in body.nodes() {
+        match node {
+            Node::Instruction(_) => (),
+            Node::IfElse {
+                true_case,
+                false_case,
+            } => {
+                collect_bodies(true_case, bodies);
+                collect_bodies(false_case, bodies);
+            }
+            Node::Repeat { body, .. } | Node::While { body } => collect_bodies(body, bodies),
+        }
+    }
+}
+
+/// Returns an iterator over the nodes of the provided body together with their locations.
+fn body_nodes(body: &CodeBody) -> impl Iterator<Item = (&Node, SourceLocation)> {
+    let locations = body.source_locations();
+    body.nodes()
+        .iter()
+        .enumerate()
+        .map(move |(index, node)| (node, locations.get(index).copied().unwrap_or_default()))
+}
+
+/// Returns true if executing the `second` instruction right after the `first` one leaves the
+/// stack unchanged.
+fn cancel_out(first: &Instruction, second: &Instruction) -> bool {
+    use Instruction::*;
+
+    match (first, second) {
+        (Swap1 | Swap2 | Swap3 | Swap4 | Swap5 | Swap6 | Swap7 | Swap8, _)
+        | (Swap9 | Swap10 | Swap11 | Swap12 | Swap13 | Swap14 | Swap15, _)
+        | (SwapW1 | SwapW2 | SwapW3 | SwapDw, _) => first == second,
+        (Dup0 | Dup1 | Dup2 | Dup3 | Dup4 | Dup5 | Dup6 | Dup7, Drop)
+        | (Dup8 | Dup9 | Dup10 | Dup11 | Dup12 | Dup13 | Dup14 | Dup15, Drop)
+        | (DupW0 | DupW1 | DupW2 | DupW3, DropW) => true,
+        (MovUpW2, MovDnW2) | (MovDnW2, MovUpW2) | (MovUpW3, MovDnW3) | (MovDnW3, MovUpW3) => true,
+        _ => match (element_movement(first), element_movement(second)) {
+            (Some((depth, up)), Some((other_depth, other_up))) => {
+                depth == other_depth && up != other_up
+            }
+            _ => false,
+        },
+    }
+}
+
+/// Returns the depth of the element moved by a `movup` or `movdn` instruction, and whether the
+/// element is moved up.
+fn element_movement(instruction: &Instruction) -> Option<(u8, bool)> {
+    use Instruction::*;
+
+    let movement = match instruction {
+        MovUp2 => (2, true),
+        MovUp3 => (3, true),
+        MovUp4 => (4, true),
+        MovUp5 => (5, true),
+        MovUp6 => (6, true),
+        MovUp7 => (7, true),
+        MovUp8 => (8, true),
+        MovUp9 => (9, true),
+        MovUp10 => (10, true),
+        MovUp11 => (11, true),
+        MovUp12 => (12, true),
+        MovUp13 => (13, true),
+        MovUp14 => (14, true),
+        MovUp15 => (15, true),
+        MovDn2 => (2, false),
+        MovDn3 => (3, false),
+        MovDn4 => (4, false),
+        MovDn5 => (5, false),
+        MovDn6 => (6, false),
+        MovDn7 => (7, false),
+        MovDn8 => (8, false),
+        MovDn9 => (9, false),
+        MovDn10 => (10, false),
+        MovDn11 => (11, false),
+        MovDn12 => (12, false),
+        MovDn13 => (13, false),
+        MovDn14 => (14, false),
+        MovDn15 => (15, false),
+        _ => return None,
+    };
+    Some(movement)
+}
@@ -0,0 +1,191 @@
+use super::{
+    super::{
+        ast::ModuleAst, tokens::SourceLocation, LibraryNamespace, LibraryPath, MaslLibrary, Module,
+        Version,
+    },
+    LintConfig, LintLevel, LintRule, Linter,
+};
+use alloc::{string::ToString, vec::Vec};
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the rules and lines of all lints reported for the provided source.
+fn lint(linter: &Linter, source: &str) -> Vec<(LintRule, u32)> {
+    let lints = linter.lint_source(source).unwrap();
+    lints.iter().map(|lint| (lint.rule(), lint.location().line())).collect()
+}
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn lint_module() {
+    let source = "\
+use.std::math::u64
+use.std::crypto::hashes::blake3
+use.std::sys
+
+const.USED=2
+const.DERIVED=USED*2
+const.UNUSED=3
+
+proc.helper.2
+    loc_store.0 push.DERIVED
+end
+
+proc.unused
+    exec.helper
+end
+
+export.foo.1
+    exec.helper swap swap
+    dup.2 drop
+    movup.3 movdn.3
+    exec.u64::wrapping_add
+end
+
+export.bar
+    push.0 assert
+    push.1
+end
+
+export.sys::truncate_stack->truncate
+";
+    let expected = vec![
+        (LintRule::UnusedImport, 2),
+        (LintRule::UnusedConstant, 7),
+        (LintRule::UnusedLocals, 9),
+        (LintRule::UnusedProcedure, 13),
+        (LintRule::UnusedLocals, 17),
+        (LintRule::RedundantStackOps, 18),
+        (LintRule::RedundantStackOps, 19),
+        (LintRule::RedundantStackOps, 20),
+        (LintRule::UnreachableCode, 26),
+    ];
+    assert_eq!(lint(&Linter::default(), source), expected);
+}
+
+#[test]
+fn lint_program() {
+    let source = "\
+proc.used
+    push.1 assertz
+    if.true
+        push.1
+    end
+end
+
+proc.unused
+    exec.used
+end
+
+begin
+    exec.used
+end";
+    let expected = vec![(LintRule::UnreachableCode, 3), (LintRule::UnusedProcedure, 8)];
+    assert_eq!(lint(&Linter::default(), source), expected);
+}
+
+#[test]
+fn lint_levels() {
+    let source = "\
+proc.unused
+    swap swap
+end
+
+begin
+    push.1
+end";
+
+    let config = LintConfig::default()
+        .with_level(LintRule::UnusedProcedure, LintLevel::Deny)
+        .with_level(LintRule::RedundantStackOps, LintLevel::Allow);
+    let lints = Linter::new(config).lint_source(source).unwrap();
+    assert_eq!(lints.len(), 1);
+    assert!(lints[0].is_error());
+    assert_eq!(lints[0].location(), &SourceLocation::new(1, 1));
+    assert_eq!(
+        lints[0].to_string(),
+        "error[unused_procedure] [1:1]: procedure 'unused' is never used"
+    );
+
+    assert_eq!(LintRule::from_name("unused_import"), Some(LintRule::UnusedImport));
+    assert_eq!(LintRule::from_name("unknown"), None);
+    assert_eq!(LintLevel::from_name("deny"), Some(LintLevel::Deny));
+}
+
+#[test]
+fn lint_imported_invocations() {
+    let module = "\
+proc.internal
+    push.1
+end
+
+export.public
+    exec.internal
+end";
+    let path = LibraryPath::new("test::math").unwrap();
+    let module = Module::new(path, ModuleAst::parse(module).unwrap());
+    let namespace = LibraryNamespace::new("test").unwrap();
+    let library =
+        MaslLibrary::new(namespace, Version::MIN, false, vec![module], Vec::new()).unwrap();
+
+    let source = "\
+use.test::math
+
+begin
+    exec.math::public
+    exec.math::internal
+end";
+
+    // invocations of imported procedures are checked only if their modules are available
+    assert_eq!(lint(&Linter::default(), source), Vec::new());
+    let linter = Linter::default().with_library(&library).unwrap();
+    assert_eq!(lint(&linter, source), vec![(LintRule::NonExportedInvocation, 5)]);
+}
+
+#[test]
+fn lint_imported_constants() {
+    let path = LibraryPath::new("test::layout").unwrap();
+    let module = Module::new(path, ModuleAst::parse("export.const.SIZE=4").unwrap());
+    let namespace = LibraryNamespace::new("test").unwrap();
+    let library =
+        MaslLibrary::new(namespace, Version::MIN, false, vec![module], Vec::new()).unwrap();
+
+    let source = "\
+use.test::layout
+use.std::math::u64
+
+const.DOUBLE_SIZE=layout::SIZE*2
+
+begin
+    push.DOUBLE_SIZE
+end";
+
+    // modules from which only constants are imported are not reported as unused
+    let linter = Linter::default().with_library(&library).unwrap();
+    assert_eq!(lint(&linter, source), vec![(LintRule::UnusedImport, 2)]);
+}
+
+#[test]
+fn lint_defines() {
+    let source = "\
+begin
+    cfg.DEBUG
+        push.LEVEL drop
+    end
+end";
+
+    // defines referenced by the source must be set on the linter
+    assert!(Linter::default().lint_source(source).is_err());
+
+    let linter = Linter::default()
+        .with_define("DEBUG", 1)
+        .unwrap()
+        .with_define("LEVEL", 3)
+        .unwrap();
+    assert_eq!(lint(&linter, source), Vec::new());
+
+    assert!(Linter::default().with_define("debug", 1).is_err());
+}
@@ -1,7 +1,7 @@
 use super::{
     crypto::hash::{Blake3_160, RpoDigest},
     ByteReader, ByteWriter, CodeBlock, Deserializable, DeserializationError, LabelError,
-    LibraryPath, Serializable, PROCEDURE_LABEL_PARSER,
+    LibraryPath, Serializable, MAX_LABEL_LEN, PROCEDURE_LABEL_PARSER,
 };
 use alloc::{
     collections::BTreeSet,
@@ -146,8 +146,11 @@ impl NamedProcedure {
 /// - It must start with a ASCII letter.
 /// - It must consist of only ASCII letters, numbers, and underscores.
 ///
-/// The only exception from the above rules is the name for the main procedure of an executable
-/// module which is set to `#main`.
+/// The only exceptions from the above rules are the name for the main procedure of an executable
+/// module which is set to `#main`, and the names of procedures instantiated from procedure
+/// templates, which are followed by the list of template arguments in angle brackets (e.g.,
+/// `rotl_word<8>`). Since angle brackets cannot appear in other procedure names, the names of
+/// template instances cannot collide with the names of regular procedures.
 ///
 /// # Type-safety
 /// Any instance of this type can be created only via the checked [`Self::try_from`].
@@ -194,8 +197,14 @@ impl TryFrom<&str> for ProcedureName {
     type Error = LabelError;
 
     fn try_from(name: &str) -> Result<Self, Self::Error> {
+        let label = match split_instance_name(name) {
+            Some(label) if name.len() <= MAX_LABEL_LEN => label,
+            Some(_) => return Err(LabelError::label_too_long(name, MAX_LABEL_LEN)),
+            None => name,
+        };
+        PROCEDURE_LABEL_PARSER.parse_label(label)?;
         Ok(Self {
-            name: (PROCEDURE_LABEL_PARSER.parse_label(name)?).to_string(),
+            name: name.to_string(),
         })
     }
 }
@@ -217,7 +226,7 @@ impl AsRef<str> for ProcedureName {
 impl Serializable for ProcedureName {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         debug_assert!(
-            PROCEDURE_LABEL_PARSER.parse_label(&self.name).is_ok(),
+            self.name.len() <= MAX_LABEL_LEN,
             "The constructor should ensure the length is within limits"
         );
 
@@ -366,9 +375,21 @@ impl ops::Deref for CallSet {
     }
 }
 
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the name of the template if the provided procedure name is the name of a template
+/// instance (e.g., `rotl_word` for `rotl_word<8,16>`), or None otherwise.
+fn split_instance_name(name: &str) -> Option<&str> {
+    let (label, args) = name.strip_suffix('>')?.split_once('<')?;
+    args.split(',')
+        .all(|arg| !arg.is_empty() && arg.bytes().all(|byte| byte.is_ascii_digit()))
+        .then_some(label)
+}
+
 #[cfg(test)]
 mod test {
-    use super::{super::MAX_LABEL_LEN, LabelError, ProcedureName};
+    use super::{LabelError, ProcedureName, MAX_LABEL_LEN};
     use alloc::borrow::ToOwned;
 
     #[test]
@@ -381,4 +402,24 @@ mod test {
             Err(LabelError::LabelTooLong(long, MAX_LABEL_LEN))
         );
     }
+
+    #[test]
+    fn test_procedure_name_template_instance() {
+        assert!(ProcedureName::try_from("rotl_word<8>").is_ok());
+        assert!(ProcedureName::try_from("rotl_word<8,16>").is_ok());
+
+        // only numeric arguments are allowed, and the name of the template must be a valid label
+        assert!(ProcedureName::try_from("rotl_word<N>").is_err());
+        assert!(ProcedureName::try_from("rotl_word<8,>").is_err());
+        assert!(ProcedureName::try_from("rotl_word<>").is_err());
+        assert!(ProcedureName::try_from("rotl_word<8").is_err());
+        assert!(ProcedureName::try_from("8rotl<8>").is_err());
+
+        // the arguments count towards the length of the name
+        let long = format!("{}<8>", "a".repeat(253));
+        assert_eq!(
+            ProcedureName::try_from(long.clone()),
+            Err(LabelError::LabelTooLong(long, MAX_LABEL_LEN))
+        );
+    }
 }
@@ -0,0 +1,452 @@
+use super::{
+    ast::{CodeBody, Instruction, Node, ProcedureAst},
+    LibraryPath, ProcedureId, ProcedureName, StackEffectError,
+};
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::fmt;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// The prefix of a doc comment line which declares the stack signature of a procedure.
+const SIGNATURE_PREFIX: &str = "@stack";
+
+/// The marker which denotes the rest of the stack in a stack signature.
+const STACK_REST: &str = "...";
+
+// STACK EFFECT
+// ================================================================================================
+
+/// Describes the effect of a sequence of instructions on the operand stack.
+///
+/// The effect consists of the number of elements at the top of the stack which the sequence
+/// requires to be present (`inputs`), and the number of elements it leaves in their place
+/// (`outputs`). For example, `add` has an effect of `2 -> 1`, while `dup.1` has an effect of
+/// `2 -> 3` because it reads the second element of the stack and leaves it unchanged.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct StackEffect {
+    inputs: u32,
+    outputs: u32,
+}
+
+impl StackEffect {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new stack effect which consumes `inputs` elements and produces `outputs`
+    /// elements.
+    pub const fn new(inputs: u32, outputs: u32) -> Self {
+        Self { inputs, outputs }
+    }
+
+    /// Parses a stack signature of the form `[a, b, ...] -> [c, ...]` into a stack effect.
+    ///
+    /// Every named item of the signature denotes a single stack element, and the optional `...`
+    /// marker denotes the rest of the stack (which must be the last item of the list).
+    ///
+    /// Returns `None` if the signature is malformed.
+    pub fn from_signature(signature: &str) -> Option<Self> {
+        let (inputs, outputs) = signature.split_once("->")?;
+        Some(Self::new(parse_stack_items(inputs)?, parse_stack_items(outputs)?))
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of elements at the top of the stack required by this effect.
+    pub const fn inputs(&self) -> u32 {
+        self.inputs
+    }
+
+    /// Returns the number of elements left at the top of the stack by this effect.
+    pub const fn outputs(&self) -> u32 {
+        self.outputs
+    }
+
+    /// Returns the change in the depth of the stack caused by this effect.
+    pub const fn net(&self) -> i64 {
+        self.outputs as i64 - self.inputs as i64
+    }
+
+    /// Returns true if a procedure with this (inferred) effect satisfies the declared effect.
+    ///
+    /// The effects must change the depth of the stack by the same amount, and the procedure must
+    /// not access elements deeper than declared.
+    pub const fn satisfies(&self, declared: &Self) -> bool {
+        self.net() == declared.net() && self.inputs <= declared.inputs
+    }
+
+    // COMPOSITION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the effect of executing this effect followed by the `next` effect.
+    pub fn then(self, next: Self) -> Self {
+        if self.outputs >= next.inputs {
+            Self::new(self.inputs, self.outputs - next.inputs + next.outputs)
+        } else {
+            let missing = next.inputs - self.outputs;
+            Self::new(self.inputs.saturating_add(missing), next.outputs)
+        }
+    }
+
+    /// Returns the effect of executing this effect the specified number of times in a row.
+    pub fn repeat(self, times: u32) -> Self {
+        if times == 0 {
+            return Self::default();
+        }
+
+        let (inputs, outputs) = if self.outputs >= self.inputs {
+            // each iteration leaves enough elements for the next one
+            let growth = (self.outputs - self.inputs).saturating_mul(times - 1);
+            (self.inputs, self.outputs.saturating_add(growth))
+        } else {
+            // each iteration consumes elements which were below the inputs of the previous one
+            let shrink = (self.inputs - self.outputs).saturating_mul(times - 1);
+            (self.inputs.saturating_add(shrink), self.outputs)
+        };
+        Self::new(inputs, outputs)
+    }
+}
+
+impl fmt::Display for StackEffect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} -> {}", self.inputs, self.outputs)
+    }
+}
+
+// STACK EFFECT POLICY
+// ================================================================================================
+
+/// Specifies how the assembler reports problems found while inferring stack effects of
+/// procedures.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum StackEffectPolicy {
+    /// Problems are not reported.
+    #[default]
+    Ignore,
+    /// Problems are logged as warnings, and compilation proceeds.
+    Warn,
+    /// The first problem found is returned as an error, and compilation fails.
+    Deny,
+}
+
+// STACK EFFECT ANALYZER
+// ================================================================================================
+
+/// Infers stack effects of the procedures of a single module (or program).
+///
+/// Procedures must be analyzed in the order in which they are defined, as local procedures can
+/// only be invoked after their definition. Effects of imported procedures are looked up in the
+/// provided map; invocations of procedures with unknown effects (e.g., dynamic calls) make the
+/// effect of the invoking code unknown, which disables the checks depending on it.
+pub(crate) struct StackEffectAnalyzer<'a> {
+    module_path: &'a LibraryPath,
+    imported_effects: &'a BTreeMap<ProcedureId, StackEffect>,
+    local_effects: Vec<Option<StackEffect>>,
+    errors: Vec<StackEffectError>,
+    current_proc: String,
+}
+
+impl<'a> StackEffectAnalyzer<'a> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new analyzer for the module with the specified path.
+    pub fn new(
+        module_path: &'a LibraryPath,
+        imported_effects: &'a BTreeMap<ProcedureId, StackEffect>,
+    ) -> Self {
+        Self {
+            module_path,
+            imported_effects,
+            local_effects: Vec::new(),
+            errors: Vec::new(),
+            current_proc: String::new(),
+        }
+    }
+
+    // ANALYSIS
+    // --------------------------------------------------------------------------------------------
+
+    /// Infers the stack effect of the specified procedure and checks it against the signature
+    /// declared in the procedure's doc comment (if any).
+    ///
+    /// When a signature is declared, it is used as the effect of the procedure for subsequent
+    /// invocations; otherwise the inferred effect is used.
+    pub fn analyze_procedure(&mut self, proc: &ProcedureAst) {
+        self.current_proc = format!("{}::{}", self.module_path, proc.name);
+        let inferred = self.infer_body(&proc.body);
+
+        let declared = match proc.docs.as_deref().and_then(find_signature) {
+            Some(signature) => match StackEffect::from_signature(signature) {
+                Some(declared) => Some(declared),
+                None => {
+                    let err = StackEffectError::invalid_signature(&self.current_proc, signature);
+                    self.errors.push(err);
+                    None
+                }
+            },
+            None => None,
+        };
+
+        if let (Some(declared), Some(inferred)) = (declared, inferred) {
+            if !inferred.satisfies(&declared) {
+                let err =
+                    StackEffectError::signature_mismatch(&self.current_proc, declared, inferred);
+                self.errors.push(err);
+            }
+        }
+
+        self.local_effects.push(declared.or(inferred));
+    }
+
+    /// Infers the stack effect of the body of a program.
+    pub fn analyze_program_body(&mut self, body: &CodeBody) -> Option<StackEffect> {
+        self.current_proc = format!("{}::{}", self.module_path, ProcedureName::main());
+        self.infer_body(body)
+    }
+
+    /// Returns effects of all procedures analyzed so far, in the order of analysis, together with
+    /// the problems found during the analysis.
+    pub fn into_parts(self) -> (Vec<Option<StackEffect>>, Vec<StackEffectError>) {
+        (self.local_effects, self.errors)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the effect of the specified code body, or `None` if the effect cannot be
+    /// determined.
+    fn infer_body(&mut self, body: &CodeBody) -> Option<StackEffect> {
+        let mut effect = Some(StackEffect::default());
+        for node in body.nodes() {
+            // nodes are always analyzed so that problems in nested blocks are reported even if
+            // the effect of the preceding code is unknown
+            let node_effect = self.infer_node(node);
+            effect = effect.zip(node_effect).map(|(effect, next)| effect.then(next));
+        }
+        effect
+    }
+
+    fn infer_node(&mut self, node: &Node) -> Option<StackEffect> {
+        match node {
+            Node::Instruction(instruction) => self.infer_instruction(instruction),
+            Node::IfElse {
+                true_case,
+                false_case,
+            } => {
+                let true_effect = self.infer_body(true_case);
+                let false_effect = self.infer_body(false_case);
+                let (true_effect, false_effect) = (true_effect?, false_effect?);
+                if true_effect.net() != false_effect.net() {
+                    let err = StackEffectError::unbalanced_branches(
+                        &self.current_proc,
+                        true_effect,
+                        false_effect,
+                    );
+                    self.errors.push(err);
+                    return None;
+                }
+
+                // the condition is popped before either of the branches is executed
+                let inputs = true_effect.inputs.max(false_effect.inputs);
+                let outputs = (inputs as i64 + true_effect.net()) as u32;
+                Some(StackEffect::new(1, 0).then(StackEffect::new(inputs, outputs)))
+            }
+            Node::Repeat { times, body } => self.infer_body(body).map(|body| body.repeat(*times)),
+            Node::While { body } => {
+                let body = self.infer_body(body)?;
+                if body.net() != 1 {
+                    let err = StackEffectError::unbalanced_loop(&self.current_proc, body);
+                    self.errors.push(err);
+                    return None;
+                }
+
+                // the condition is popped before every iteration; the body must push the condition
+                // for the next iteration in addition to preserving the depth of the stack
+                Some(StackEffect::new(1, 0).then(StackEffect::new(body.inputs, body.inputs)))
+            }
+        }
+    }
+
+    fn infer_instruction(&self, instruction: &Instruction) -> Option<StackEffect> {
+        match instruction {
+            Instruction::ExecLocal(index) | Instruction::CallLocal(index) => {
+                self.local_effects.get(*index as usize).copied().flatten()
+            }
+            Instruction::ExecImported(proc_id)
+            | Instruction::CallImported(proc_id)
+            | Instruction::SysCall(proc_id) => self.imported_effects.get(proc_id).copied(),
+            Instruction::CallMastRoot(_) | Instruction::DynExec | Instruction::DynCall => None,
+            instruction => Some(instruction_effect(instruction)),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the stack signature declared in the provided doc comment, if any.
+///
+/// The signature is declared on a separate line starting with `@stack`, e.g.:
+/// `@stack [b, a, ...] -> [c, ...]`.
+fn find_signature(docs: &str) -> Option<&str> {
+    docs.lines()
+        .find_map(|line| line.trim().strip_prefix(SIGNATURE_PREFIX))
+        .map(str::trim)
+}
+
+/// Returns the number of stack elements in a list of the form `[a, b, ...]`.
+fn parse_stack_items(items: &str) -> Option<u32> {
+    let items = items.trim().strip_prefix('[')?.strip_suffix(']')?.trim();
+    if items.is_empty() {
+        return Some(0);
+    }
+
+    let items: Vec<&str> = items.split(',').map(str::trim).collect();
+    let (last, named) = items.split_last()?;
+    let num_items = if *last == STACK_REST { named.len() } else { items.len() };
+
+    let is_valid = items[..num_items]
+        .iter()
+        .all(|item| !item.is_empty() && *item != STACK_REST && !item.contains(['[', ']']));
+    is_valid.then_some(num_items as u32)
+}
+
+/// Returns the stack effect of an instruction which does not invoke other procedures.
+fn instruction_effect(instruction: &Instruction) -> StackEffect {
+    use Instruction::*;
+
+    let (inputs, outputs) = match instruction {
+        Assert | AssertWithError(_) | Assertz | AssertzWithError(_) => (1, 0),
+        AssertEq | AssertEqWithError(_) => (2, 0),
+        AssertEqw | AssertEqwWithError(_) => (8, 0),
+
+        // ----- field operations -----------------------------------------------------------------
+        Add | Sub | Mul | Div | Exp | ExpBitLength(_) => (2, 1),
+        AddImm(_) | SubImm(_) | MulImm(_) | DivImm(_) | ExpImm(_) => (1, 1),
+        Neg | Inv | Incr | Pow2 | ILog2 | Not | IsOdd => (1, 1),
+        And | Or | Xor | Eq | Neq | Lt | Lte | Gt | Gte => (2, 1),
+        EqImm(_) | NeqImm(_) => (1, 1),
+        Eqw => (8, 9),
+
+        // ----- ext2 operations ------------------------------------------------------------------
+        Ext2Add | Ext2Sub | Ext2Mul | Ext2Div => (4, 2),
+        Ext2Neg | Ext2Inv => (2, 2),
+
+        // ----- u32 operations -------------------------------------------------------------------
+        U32Test => (1, 2),
+        U32TestW => (4, 5),
+        U32Assert | U32AssertWithError(_) | U32Cast => (1, 1),
+        U32Assert2 | U32Assert2WithError(_) => (2, 2),
+        U32AssertW | U32AssertWWithError(_) => (4, 4),
+        U32Split => (1, 2),
+        U32WrappingAdd | U32WrappingSub | U32WrappingMul | U32Div | U32Mod => (2, 1),
+        U32WrappingAddImm(_) | U32WrappingSubImm(_) | U32WrappingMulImm(_) => (1, 1),
+        U32DivImm(_) | U32ModImm(_) => (1, 1),
+        U32OverflowingAdd | U32OverflowingSub | U32OverflowingMul | U32DivMod => (2, 2),
+        U32OverflowingAddImm(_) | U32OverflowingSubImm(_) | U32OverflowingMulImm(_) => (1, 2),
+        U32DivModImm(_) => (1, 2),
+        U32OverflowingAdd3 | U32OverflowingMadd => (3, 2),
+        U32WrappingAdd3 | U32WrappingMadd => (3, 1),
+        U32And | U32Or | U32Xor | U32Shr | U32Shl | U32Rotr | U32Rotl => (2, 1),
+        U32ShrImm(_) | U32ShlImm(_) | U32RotrImm(_) | U32RotlImm(_) => (1, 1),
+        U32Not | U32Popcnt | U32Clz | U32Ctz | U32Clo | U32Cto => (1, 1),
+        U32Lt | U32Lte | U32Gt | U32Gte | U32Min | U32Max => (2, 1),
+
+        // ----- stack manipulation ---------------------------------------------------------------
+        Drop => (1, 0),
+        DropW => (4, 0),
+        PadW => (0, 4),
+        Dup0 => (1, 2),
+        Dup1 => (2, 3),
+        Dup2 => (3, 4),
+        Dup3 => (4, 5),
+        Dup4 => (5, 6),
+        Dup5 => (6, 7),
+        Dup6 => (7, 8),
+        Dup7 => (8, 9),
+        Dup8 => (9, 10),
+        Dup9 => (10, 11),
+        Dup10 => (11, 12),
+        Dup11 => (12, 13),
+        Dup12 => (13, 14),
+        Dup13 => (14, 15),
+        Dup14 => (15, 16),
+        Dup15 => (16, 17),
+        DupW0 => (4, 8),
+        DupW1 => (8, 12),
+        DupW2 => (12, 16),
+        DupW3 => (16, 20),
+        Swap1 => (2, 2),
+        Swap2 | MovUp2 | MovDn2 => (3, 3),
+        Swap3 | MovUp3 | MovDn3 => (4, 4),
+        Swap4 | MovUp4 | MovDn4 => (5, 5),
+        Swap5 | MovUp5 | MovDn5 => (6, 6),
+        Swap6 | MovUp6 | MovDn6 => (7, 7),
+        Swap7 | MovUp7 | MovDn7 => (8, 8),
+        Swap8 | MovUp8 | MovDn8 => (9, 9),
+        Swap9 | MovUp9 | MovDn9 => (10, 10),
+        Swap10 | MovUp10 | MovDn10 => (11, 11),
+        Swap11 | MovUp11 | MovDn11 => (12, 12),
+        Swap12 | MovUp12 | MovDn12 => (13, 13),
+        Swap13 | MovUp13 | MovDn13 => (14, 14),
+        Swap14 | MovUp14 | MovDn14 => (15, 15),
+        Swap15 | MovUp15 | MovDn15 => (16, 16),
+        SwapW1 => (8, 8),
+        SwapW2 | MovUpW2 | MovDnW2 => (12, 12),
+        SwapW3 | MovUpW3 | MovDnW3 | SwapDw => (16, 16),
+        CSwap => (3, 2),
+        CSwapW => (9, 8),
+        CDrop => (3, 1),
+        CDropW => (9, 4),
+
+        // ----- input / output operations --------------------------------------------------------
+        PushU8(_) | PushU16(_) | PushU32(_) | PushFelt(_) => (0, 1),
+        PushWord(_) => (0, 4),
+        PushU8List(imms) => (0, imms.len() as u32),
+        PushU16List(imms) => (0, imms.len() as u32),
+        PushU32List(imms) => (0, imms.len() as u32),
+        PushFeltList(imms) => (0, imms.len() as u32),
+        Locaddr(_) | Sdepth | Clk => (0, 1),
+        Caller => (4, 4),
+        MemLoad => (1, 1),
+        MemLoadImm(_) | LocLoad(_) => (0, 1),
+        MemLoadW => (5, 4),
+        MemLoadWImm(_) | LocLoadW(_) => (4, 4),
+        MemStore => (2, 0),
+        MemStoreImm(_) | LocStore(_) => (1, 0),
+        MemStoreW => (5, 4),
+        MemStoreWImm(_) | LocStoreW(_) => (4, 4),
+        MemStream | AdvPipe => (13, 13),
+        AdvPush(n) => (0, *n as u32),
+        AdvLoadW => (4, 4),
+        AdvInject(_) => (0, 0),
+
+        // ----- cryptographic operations ---------------------------------------------------------
+        Hash => (4, 4),
+        HMerge => (8, 4),
+        HPerm => (12, 12),
+        MTreeGet => (6, 8),
+        MTreeSet => (10, 8),
+        MTreeMerge => (8, 4),
+        MTreeVerify => (10, 10),
+        FriExt2Fold4 | RCombBase => (16, 16),
+
+        // ----- procedure references -------------------------------------------------------------
+        ProcRefLocal(_) | ProcRefImported(_) => (0, 4),
+
+        // ----- debugging ------------------------------------------------------------------------
+        Breakpoint | Debug(_) | Emit(_) | Trace(_) => (0, 0),
+
+        // invocations are resolved by the analyzer
+        ExecLocal(_) | ExecImported(_) | CallLocal(_) | CallImported(_) | CallMastRoot(_)
+        | SysCall(_) | DynExec | DynCall => {
+            unreachable!("invocation {instruction} has no fixed stack effect")
+        }
+    };
+    StackEffect::new(inputs, outputs)
+}
@@ -0,0 +1,238 @@
+use super::{
+    super::{
+        ast::ModuleAst, Assembler, AssemblyError, LibraryNamespace, LibraryPath, MaslLibrary,
+        Module, ProcedureId, StackEffectError, Version,
+    },
+    StackEffect, StackEffectAnalyzer, StackEffectPolicy,
+};
+use alloc::{collections::BTreeMap, vec::Vec};
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Analyzes all procedures of the provided module source without any imported procedures.
+fn analyze(source: &str) -> (Vec<Option<StackEffect>>, Vec<StackEffectError>) {
+    let module = ModuleAst::parse(source).unwrap();
+    let path = LibraryPath::new("test::module").unwrap();
+    let imported = BTreeMap::new();
+    let mut analyzer = StackEffectAnalyzer::new(&path, &imported);
+    for proc in module.procs() {
+        analyzer.analyze_procedure(proc);
+    }
+    analyzer.into_parts()
+}
+
+fn effect(inputs: u32, outputs: u32) -> Option<StackEffect> {
+    Some(StackEffect::new(inputs, outputs))
+}
+
+// COMPOSITION
+// ================================================================================================
+
+#[test]
+fn compose_effects() {
+    // push.1 add
+    assert_eq!(StackEffect::new(0, 1).then(StackEffect::new(2, 1)), StackEffect::new(1, 1));
+    // dup.1 drop drop
+    assert_eq!(StackEffect::new(2, 3).then(StackEffect::new(2, 0)), StackEffect::new(2, 1));
+
+    assert_eq!(StackEffect::new(1, 2).repeat(0), StackEffect::new(0, 0));
+    assert_eq!(StackEffect::new(1, 2).repeat(3), StackEffect::new(1, 4));
+    assert_eq!(StackEffect::new(2, 1).repeat(3), StackEffect::new(4, 1));
+    assert_eq!(StackEffect::new(3, 3).repeat(5), StackEffect::new(3, 3));
+}
+
+#[test]
+fn parse_signatures() {
+    let signature = "[b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...]";
+    assert_eq!(StackEffect::from_signature(signature), effect(4, 2));
+    assert_eq!(StackEffect::from_signature("[] -> [a]"), effect(0, 1));
+    assert_eq!(StackEffect::from_signature("[...] -> [a, ...]"), effect(0, 1));
+
+    assert_eq!(StackEffect::from_signature("[a, b]"), None);
+    assert_eq!(StackEffect::from_signature("[a, ..., b] -> []"), None);
+    assert_eq!(StackEffect::from_signature("[a, , b] -> []"), None);
+    assert_eq!(StackEffect::from_signature("a, b -> c"), None);
+}
+
+// INFERENCE
+// ================================================================================================
+
+#[test]
+fn infer_procedure_effects() {
+    let source = "\
+    proc.add3
+        add add
+    end
+    proc.branches
+        if.true
+            push.1 add
+        else
+            dup.2 add
+        end
+    end
+    proc.counter
+        push.1
+        while.true
+            sub.1 dup neq.0
+        end
+    end
+    proc.repeated
+        repeat.3
+            mul
+        end
+    end
+    proc.calls
+        exec.add3 call.repeated
+    end
+    proc.dynamic
+        dynexec
+    end";
+    let (effects, errors) = analyze(source);
+
+    assert_eq!(errors, Vec::new());
+    assert_eq!(
+        effects,
+        vec![effect(3, 1), effect(4, 3), effect(1, 1), effect(4, 1), effect(6, 1), None]
+    );
+}
+
+#[test]
+fn infer_unbalanced_control_flow() {
+    let source = "\
+    proc.unbalanced_branches
+        if.true
+            drop
+        end
+    end
+    proc.unbalanced_loop
+        while.true
+            push.1 push.1
+        end
+    end";
+    let (effects, errors) = analyze(source);
+
+    assert_eq!(effects, vec![None, None]);
+    assert_eq!(
+        errors,
+        vec![
+            StackEffectError::unbalanced_branches(
+                "test::module::unbalanced_branches",
+                StackEffect::new(1, 0),
+                StackEffect::new(0, 0)
+            ),
+            StackEffectError::unbalanced_loop(
+                "test::module::unbalanced_loop",
+                StackEffect::new(0, 2)
+            ),
+        ]
+    );
+}
+
+#[test]
+fn check_declared_signatures() {
+    let source = "\
+    #! Adds two elements.
+    #!
+    #! @stack [b, a, ...] -> [c, ...]
+    export.matching
+        add
+    end
+    #! @stack [b, a, ...] -> [c, d, ...]
+    export.mismatching
+        add
+    end
+    #! @stack [b, a, ...] -> [...]
+    export.declared_dynamic
+        dynexec
+    end
+    #! @stack b, a -> c
+    export.invalid
+        add
+    end";
+    let (effects, errors) = analyze(source);
+
+    // declared signatures take precedence over inferred effects
+    assert_eq!(effects, vec![effect(2, 1), effect(2, 2), effect(2, 0), effect(2, 1)]);
+    assert_eq!(
+        errors,
+        vec![
+            StackEffectError::signature_mismatch(
+                "test::module::mismatching",
+                StackEffect::new(2, 2),
+                StackEffect::new(2, 1)
+            ),
+            StackEffectError::invalid_signature("test::module::invalid", "b, a -> c"),
+        ]
+    );
+}
+
+// ASSEMBLER INTEGRATION
+// ================================================================================================
+
+#[test]
+fn assembler_stack_effect_policy() {
+    let base = "\
+    #! @stack [b, a, ...] -> [c, ...]
+    export.add
+        add
+    end";
+    let math = "\
+    use.test::base
+
+    #! @stack [b, a, ...] -> [c, ...]
+    export.add_two
+        add
+    end
+    export.base::add->sum";
+    let base_path = LibraryPath::new("test::base").unwrap();
+    let base = Module::new(base_path, ModuleAst::parse(base).unwrap());
+    let path = LibraryPath::new("test::math").unwrap();
+    let module = Module::new(path.clone(), ModuleAst::parse(math).unwrap());
+    let namespace = LibraryNamespace::new("test").unwrap();
+    let library =
+        MaslLibrary::new(namespace, Version::MIN, false, vec![base, module], Vec::new()).unwrap();
+
+    // effects of imported procedures (including re-exported ones) are used for inference
+    let program = "\
+    use.test::math
+
+    proc.add_three
+        exec.math::add_two exec.math::sum
+    end
+
+    begin
+        push.1 push.2 push.3 exec.add_three
+        if.true
+            push.1
+        end
+    end";
+
+    let assembler = Assembler::default().with_library(&library).unwrap();
+    assert!(assembler.compile(program).is_ok());
+    assert_eq!(
+        assembler.stack_effect(&ProcedureId::from_name("sum", &path)),
+        Some(StackEffect::new(2, 1))
+    );
+
+    let assembler = Assembler::default()
+        .with_library(&library)
+        .unwrap()
+        .with_stack_effect_policy(StackEffectPolicy::Warn);
+    assert!(assembler.compile(program).is_ok());
+
+    let assembler = Assembler::default()
+        .with_library(&library)
+        .unwrap()
+        .with_stack_effect_policy(StackEffectPolicy::Deny);
+    let expected = StackEffectError::unbalanced_branches(
+        "#exec::#main",
+        StackEffect::new(0, 1),
+        StackEffect::new(0, 0),
+    );
+    let err = assembler.compile(program).unwrap_err();
+    assert_eq!(err, AssemblyError::StackEffectError(expected));
+
+    let program = program.replace("push.1\n", "push.1 drop\n");
+    assert!(assembler.compile(program).is_ok());
+}
@@ -1,10 +1,9 @@
 use super::{
-    ast::{parse_param_with_constant_lookup, InvocationTarget},
+    ast::{parse_param_with_constant_lookup, ConstantResolver, InvocationTarget},
     ByteReader, ByteWriter, Deserializable, DeserializationError, LibraryPath, ParsingError,
     ProcedureName, Serializable,
 };
 use alloc::{
-    collections::BTreeMap,
     string::{String, ToString},
     vec::Vec,
 };
@@ -40,10 +39,13 @@ impl<'a> Token<'a> {
     // DEFINITION TOKENS
     // --------------------------------------------------------------------------------------------
     pub const BEGIN: &'static str = "begin";
+    pub const CFG: &'static str = "cfg";
     pub const CONST: &'static str = "const";
     pub const END: &'static str = "end";
+    pub const ERROR: &'static str = "error";
     pub const EXPORT: &'static str = "export";
     pub const PROC: &'static str = "proc";
+    pub const REGION: &'static str = "region";
     pub const USE: &'static str = "use";
 
     // CONTROL FLOW TOKENS
@@ -244,7 +246,7 @@ impl<'a> Token<'a> {
         }
     }
 
-    pub fn parse_repeat(&self, constants: &BTreeMap<String, u64>) -> Result<u32, ParsingError> {
+    pub fn parse_repeat(&self, constants: &mut ConstantResolver) -> Result<u32, ParsingError> {
         assert_eq!(Self::REPEAT, self.parts[0], "not a repeat");
         match self.num_parts() {
             0 => unreachable!(),
@@ -30,8 +30,11 @@ impl<'a> TokenStream<'a> {
         for line_info in LinesStream::from(source) {
             match line_info.contents() {
                 Some(line) => {
-                    // fill the doc comments for procedures
-                    if line.starts_with(Token::EXPORT) || line.starts_with(Token::PROC) {
+                    // fill the doc comments for procedures and named errors
+                    if line.starts_with(Token::EXPORT)
+                        || line.starts_with(Token::PROC)
+                        || line.starts_with(Token::ERROR)
+                    {
                         let doc_comment = build_comment(line_info.docs());
                         proc_comments.insert(tokens.len(), doc_comment);
                     } else if !line_info.docs().is_empty() {
@@ -82,6 +85,28 @@ impl<'a> TokenStream<'a> {
         })
     }
 
+    /// Returns a new stream containing the provided tokens, each of which is linked to the
+    /// source location with the same index.
+    ///
+    /// The returned stream does not contain any doc comments.
+    ///
+    /// # Panics
+    /// Panics if no tokens are provided, or if the number of tokens and locations differ.
+    pub fn from_tokens(tokens: Vec<&'a str>, locations: Vec<SourceLocation>) -> Self {
+        assert!(!tokens.is_empty(), "token stream cannot be empty");
+        assert_eq!(tokens.len(), locations.len(), "every token must have a location");
+        let current = Token::new(tokens[0], locations[0]);
+        Self {
+            tokens,
+            locations,
+            current,
+            pos: 0,
+            temp: Token::default(),
+            proc_comments: BTreeMap::new(),
+            module_comment: None,
+        }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -90,6 +115,11 @@ impl<'a> TokenStream<'a> {
         self.pos
     }
 
+    /// Returns all tokens of this stream (including the ones which have already been read).
+    pub fn tokens(&self) -> &[&'a str] {
+        &self.tokens
+    }
+
     /// Returns the [SourceLocation] linked to the end-of-file of the source.
     pub fn eof_location(&self) -> &SourceLocation {
         let idx = self.pos.min(self.locations.len().saturating_sub(1));
@@ -49,7 +49,7 @@ pub use program::{blocks as code_blocks, CodeBlockTable, Kernel, Program, Progra
 mod operations;
 pub use operations::{
     AdviceInjector, AssemblyOp, DebugOptions, Decorator, DecoratorIterator, DecoratorList,
-    Operation, SignatureKind,
+    Location, Operation, SignatureKind,
 };
 
 pub mod stack;
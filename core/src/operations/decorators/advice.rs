@@ -1,5 +1,8 @@
 use super::SignatureKind;
-use crate::Felt;
+use crate::{
+    utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+    Felt,
+};
 use core::fmt;
 
 // ADVICE INJECTORS
@@ -105,6 +108,23 @@ pub enum AdviceInjector {
     /// the remainder respectively.
     U64Div,
 
+    /// Pushes the result of 256-bit unsigned integer division (both the quotient and the
+    /// remainder) onto the advice stack.
+    ///
+    /// Inputs:
+    ///   Operand stack: [b7, ..., b0, a7, ..., a0, ...]
+    ///   Advice stack: [...]
+    ///
+    /// Outputs:
+    ///   Operand stack: [b7, ..., b0, a7, ..., a0, ...]
+    ///   Advice stack: [q0, ..., q7, r0, ..., r7, ...]
+    ///
+    /// Where (a0, ..., a7) and (b0, ..., b7) are the 32-bit limbs of the dividend and the divisor
+    /// respectively (with a0 representing the 32 least significant bits and a7 representing the
+    /// 32 most significant bits). Similarly, (q0, ..., q7) and (r0, ..., r7) represent the
+    /// quotient and the remainder respectively.
+    U256Div,
+
     /// Given an element in a quadratic extension field on the top of the stack (i.e., a0, b1),
     /// computes its multiplicative inverse and push the result onto the advice stack.
     ///
@@ -219,6 +239,21 @@ pub enum AdviceInjector {
     ///   Advice stack: [ilog2(n), ...]
     ILog2,
 
+    /// Pushes the address of the first element not less than `value` in a sorted array of field
+    /// elements located in memory[start_addr..end_addr] onto the advice stack. The elements of the
+    /// array are expected to be stored in the first elements of the memory words.
+    ///
+    /// Inputs:
+    ///   Operand stack: [value, start_addr, end_addr, ...]
+    ///   Advice stack: [...]
+    ///
+    /// Outputs:
+    ///   Operand stack: [value, start_addr, end_addr, ...]
+    ///   Advice stack: [addr, ...]
+    ///
+    /// Where `addr` is equal to `end_addr` if all elements of the array are less than `value`.
+    LowerBound,
+
     // ADVICE MAP INJECTORS
     // --------------------------------------------------------------------------------------------
     /// Reads words from memory at the specified range and inserts them into the advice map under
@@ -300,6 +335,7 @@ impl fmt::Display for AdviceInjector {
                 }
             }
             Self::U64Div => write!(f, "div_u64"),
+            Self::U256Div => write!(f, "div_u256"),
             Self::Ext2Inv => write!(f, "ext2_inv"),
             Self::Ext2Intt => write!(f, "ext2_intt"),
             Self::SmtGet => write!(f, "smt_get"),
@@ -310,6 +346,7 @@ impl fmt::Display for AdviceInjector {
             Self::U32Clo => write!(f, "u32clo"),
             Self::U32Cto => write!(f, "u32cto"),
             Self::ILog2 => write!(f, "ilog2"),
+            Self::LowerBound => write!(f, "lower_bound"),
             Self::MemToMap => write!(f, "mem_to_map"),
             Self::HdwordToMap { domain } => write!(f, "hdword_to_map.{domain}"),
             Self::HpermToMap => write!(f, "hperm_to_map"),
@@ -317,3 +354,85 @@ impl fmt::Display for AdviceInjector {
         }
     }
 }
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for AdviceInjector {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::MerkleNodeMerge => target.write_u8(0),
+            Self::MerkleNodeToStack => target.write_u8(1),
+            Self::UpdateMerkleNode => target.write_u8(2),
+            Self::MapValueToStack {
+                include_len,
+                key_offset,
+            } => {
+                target.write_u8(3);
+                target.write_bool(*include_len);
+                target.write_usize(*key_offset);
+            }
+            Self::U64Div => target.write_u8(4),
+            Self::Ext2Inv => target.write_u8(5),
+            Self::Ext2Intt => target.write_u8(6),
+            Self::SmtGet => target.write_u8(7),
+            Self::SmtSet => target.write_u8(8),
+            Self::SmtPeek => target.write_u8(9),
+            Self::U32Clz => target.write_u8(10),
+            Self::U32Ctz => target.write_u8(11),
+            Self::U32Clo => target.write_u8(12),
+            Self::U32Cto => target.write_u8(13),
+            Self::ILog2 => target.write_u8(14),
+            Self::MemToMap => target.write_u8(15),
+            Self::HdwordToMap { domain } => {
+                target.write_u8(16);
+                domain.write_into(target);
+            }
+            Self::HpermToMap => target.write_u8(17),
+            Self::SigToStack { kind } => {
+                target.write_u8(18);
+                kind.write_into(target);
+            }
+            Self::U256Div => target.write_u8(19),
+            Self::LowerBound => target.write_u8(20),
+        }
+    }
+}
+
+impl Deserializable for AdviceInjector {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(Self::MerkleNodeMerge),
+            1 => Ok(Self::MerkleNodeToStack),
+            2 => Ok(Self::UpdateMerkleNode),
+            3 => Ok(Self::MapValueToStack {
+                include_len: source.read_bool()?,
+                key_offset: source.read_usize()?,
+            }),
+            4 => Ok(Self::U64Div),
+            5 => Ok(Self::Ext2Inv),
+            6 => Ok(Self::Ext2Intt),
+            7 => Ok(Self::SmtGet),
+            8 => Ok(Self::SmtSet),
+            9 => Ok(Self::SmtPeek),
+            10 => Ok(Self::U32Clz),
+            11 => Ok(Self::U32Ctz),
+            12 => Ok(Self::U32Clo),
+            13 => Ok(Self::U32Cto),
+            14 => Ok(Self::ILog2),
+            15 => Ok(Self::MemToMap),
+            16 => Ok(Self::HdwordToMap {
+                domain: source.read()?,
+            }),
+            17 => Ok(Self::HpermToMap),
+            18 => Ok(Self::SigToStack {
+                kind: source.read()?,
+            }),
+            19 => Ok(Self::U256Div),
+            20 => Ok(Self::LowerBound),
+            tag => Err(DeserializationError::InvalidValue(format!(
+                "invalid advice injector tag: {tag}"
+            ))),
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
 use alloc::string::String;
 use core::fmt;
 
@@ -11,6 +12,7 @@ pub struct AssemblyOp {
     num_cycles: u8,
     op: String,
     should_break: bool,
+    location: Option<Location>,
 }
 
 impl AssemblyOp {
@@ -22,9 +24,17 @@ impl AssemblyOp {
             num_cycles,
             op,
             should_break,
+            location: None,
         }
     }
 
+    /// Returns this [AssemblyOp] bound to the specified location of the instruction in the source
+    /// code.
+    pub fn with_location(mut self, location: Location) -> Self {
+        self.location = Some(location);
+        self
+    }
+
     /// Returns the context name for this operation.
     pub fn context_name(&self) -> &str {
         &self.context_name
@@ -45,6 +55,11 @@ impl AssemblyOp {
         self.should_break
     }
 
+    /// Returns the location of the assembly instruction in the source code, if known.
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+
     // STATE MUTATORS
     // --------------------------------------------------------------------------------------------
 
@@ -63,3 +78,93 @@ impl fmt::Display for AssemblyOp {
         )
     }
 }
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for AssemblyOp {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.context_name.write_into(target);
+        target.write_u8(self.num_cycles);
+        self.op.write_into(target);
+        target.write_bool(self.should_break);
+        self.location.write_into(target);
+    }
+}
+
+impl Deserializable for AssemblyOp {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let context_name = source.read()?;
+        let num_cycles = source.read_u8()?;
+        let op = source.read()?;
+        let should_break = source.read_bool()?;
+        let location = source.read()?;
+        Ok(Self {
+            context_name,
+            num_cycles,
+            op,
+            should_break,
+            location,
+        })
+    }
+}
+
+// LOCATION
+// ================================================================================================
+
+/// Location of an assembly instruction in the source code of a module.
+///
+/// The path is the fully qualified path of the module containing the instruction (e.g.,
+/// `std::math::u64`), or one of the reserved paths for executable (`#exec`) and kernel (`#sys`)
+/// modules. Lines and columns are 1-based.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Location {
+    path: String,
+    line: u32,
+    column: u32,
+}
+
+impl Location {
+    /// Returns a new [Location] instantiated with the specified module path, line and column.
+    pub fn new(path: String, line: u32, column: u32) -> Self {
+        Self { path, line, column }
+    }
+
+    /// Returns the path of the module containing the instruction.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the line of the instruction in the source code of the module.
+    pub const fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// Returns the column of the instruction in the source code of the module.
+    pub const fn column(&self) -> u32 {
+        self.column
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.path, self.line, self.column)
+    }
+}
+
+impl Serializable for Location {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.path.write_into(target);
+        target.write_u32(self.line);
+        target.write_u32(self.column);
+    }
+}
+
+impl Deserializable for Location {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let path = source.read()?;
+        let line = source.read_u32()?;
+        let column = source.read_u32()?;
+        Ok(Self::new(path, line, column))
+    }
+}
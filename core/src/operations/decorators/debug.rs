@@ -1,3 +1,4 @@
+use crate::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
 use core::fmt;
 
 // DEBUG OPTIONS
@@ -41,3 +42,47 @@ impl fmt::Display for DebugOptions {
         }
     }
 }
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for DebugOptions {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::StackAll => target.write_u8(0),
+            Self::StackTop(n) => {
+                target.write_u8(1);
+                target.write_u16(*n);
+            }
+            Self::MemAll => target.write_u8(2),
+            Self::MemInterval(start, end) => {
+                target.write_u8(3);
+                target.write_u32(*start);
+                target.write_u32(*end);
+            }
+            Self::LocalInterval(start, end, num_locals) => {
+                target.write_u8(4);
+                target.write_u16(*start);
+                target.write_u16(*end);
+                target.write_u16(*num_locals);
+            }
+        }
+    }
+}
+
+impl Deserializable for DebugOptions {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(Self::StackAll),
+            1 => Ok(Self::StackTop(source.read_u16()?)),
+            2 => Ok(Self::MemAll),
+            3 => Ok(Self::MemInterval(source.read_u32()?, source.read_u32()?)),
+            4 => {
+                Ok(Self::LocalInterval(source.read_u16()?, source.read_u16()?, source.read_u16()?))
+            }
+            tag => {
+                Err(DeserializationError::InvalidValue(format!("invalid debug options tag: {tag}")))
+            }
+        }
+    }
+}
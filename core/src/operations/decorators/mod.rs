@@ -1,3 +1,4 @@
+use crate::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
 use alloc::vec::Vec;
 use core::fmt;
 
@@ -5,7 +6,7 @@ mod advice;
 pub use advice::AdviceInjector;
 
 mod assembly_op;
-pub use assembly_op::AssemblyOp;
+pub use assembly_op::{AssemblyOp, Location};
 
 mod debug;
 pub use debug::DebugOptions;
@@ -50,6 +51,46 @@ impl fmt::Display for Decorator {
     }
 }
 
+impl Serializable for Decorator {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::Advice(injector) => {
+                target.write_u8(0);
+                injector.write_into(target);
+            }
+            Self::AsmOp(assembly_op) => {
+                target.write_u8(1);
+                assembly_op.write_into(target);
+            }
+            Self::Debug(options) => {
+                target.write_u8(2);
+                options.write_into(target);
+            }
+            Self::Event(event_id) => {
+                target.write_u8(3);
+                target.write_u32(*event_id);
+            }
+            Self::Trace(trace_id) => {
+                target.write_u8(4);
+                target.write_u32(*trace_id);
+            }
+        }
+    }
+}
+
+impl Deserializable for Decorator {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(Self::Advice(source.read()?)),
+            1 => Ok(Self::AsmOp(source.read()?)),
+            2 => Ok(Self::Debug(source.read()?)),
+            3 => Ok(Self::Event(source.read_u32()?)),
+            4 => Ok(Self::Trace(source.read_u32()?)),
+            tag => Err(DeserializationError::InvalidValue(format!("invalid decorator tag: {tag}"))),
+        }
+    }
+}
+
 /// Vector consisting of a tuple of operation index (within a span block) and decorator at that index
 pub type DecoratorList = Vec<(usize, Decorator)>;
 
@@ -108,3 +149,22 @@ impl fmt::Display for SignatureKind {
         }
     }
 }
+
+impl Serializable for SignatureKind {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::RpoFalcon512 => target.write_u8(0),
+        }
+    }
+}
+
+impl Deserializable for SignatureKind {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(Self::RpoFalcon512),
+            tag => Err(DeserializationError::InvalidValue(format!(
+                "invalid signature kind tag: {tag}"
+            ))),
+        }
+    }
+}
@@ -1,5 +1,8 @@
 use super::{hasher, Digest, Felt, Operation};
-use crate::DecoratorList;
+use crate::{
+    utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+    DecoratorList,
+};
 use alloc::vec::Vec;
 use core::fmt;
 
@@ -138,3 +141,73 @@ impl fmt::Display for CodeBlock {
         }
     }
 }
+
+// SERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+impl Serializable for CodeBlock {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            CodeBlock::Span(block) => {
+                target.write_u8(0);
+                block.write_into(target);
+            }
+            CodeBlock::Join(block) => {
+                target.write_u8(1);
+                block.first().write_into(target);
+                block.second().write_into(target);
+            }
+            CodeBlock::Split(block) => {
+                target.write_u8(2);
+                block.on_true().write_into(target);
+                block.on_false().write_into(target);
+            }
+            CodeBlock::Loop(block) => {
+                target.write_u8(3);
+                block.body().write_into(target);
+            }
+            CodeBlock::Call(block) => {
+                target.write_u8(4);
+                block.fn_hash().write_into(target);
+                target.write_bool(block.is_syscall());
+            }
+            CodeBlock::Dyn(_) => target.write_u8(5),
+            CodeBlock::Proxy(block) => {
+                target.write_u8(6);
+                block.hash().write_into(target);
+            }
+        }
+    }
+}
+
+impl Deserializable for CodeBlock {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(Self::Span(source.read()?)),
+            1 => {
+                let first = source.read()?;
+                let second = source.read()?;
+                Ok(Self::new_join([first, second]))
+            }
+            2 => {
+                let t_branch = source.read()?;
+                let f_branch = source.read()?;
+                Ok(Self::new_split(t_branch, f_branch))
+            }
+            3 => Ok(Self::new_loop(source.read()?)),
+            4 => {
+                let fn_hash = source.read()?;
+                if source.read_bool()? {
+                    Ok(Self::new_syscall(fn_hash))
+                } else {
+                    Ok(Self::new_call(fn_hash))
+                }
+            }
+            5 => Ok(Self::new_dyn()),
+            6 => Ok(Self::new_proxy(source.read()?)),
+            tag => {
+                Err(DeserializationError::InvalidValue(format!("invalid code block tag: {tag}")))
+            }
+        }
+    }
+}
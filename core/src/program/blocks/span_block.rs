@@ -1,4 +1,7 @@
-use super::{fmt, hasher, Digest, Felt, Operation};
+use super::{
+    fmt, hasher, ByteReader, ByteWriter, Deserializable, DeserializationError, Digest, Felt,
+    Operation, Serializable,
+};
 use crate::{DecoratorIterator, DecoratorList, ZERO};
 use alloc::vec::Vec;
 use winter_utils::flatten_slice_elements;
@@ -164,6 +167,54 @@ impl fmt::Display for Span {
     }
 }
 
+// SERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+impl Serializable for Span {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        // operation batches and the hash of the block are fully determined by the list of
+        // operations; so, we serialize only the operations and the decorators.
+        self.get_ops().write_into(target);
+
+        target.write_usize(self.decorators.len());
+        for (op_idx, decorator) in self.decorators.iter() {
+            target.write_usize(*op_idx);
+            decorator.write_into(target);
+        }
+    }
+}
+
+impl Deserializable for Span {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let operations: Vec<Operation> = source.read()?;
+        if operations.is_empty() {
+            return Err(DeserializationError::InvalidValue(
+                "span block must contain at least one operation".into(),
+            ));
+        }
+
+        let num_decorators = source.read_usize()?;
+        let mut decorators = DecoratorList::with_capacity(num_decorators);
+        for _ in 0..num_decorators {
+            let op_idx = source.read_usize()?;
+            if op_idx > operations.len() {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "decorator index {op_idx} is out of bounds for a span with {} operations",
+                    operations.len()
+                )));
+            }
+            if decorators.last().is_some_and(|(prev_idx, _)| *prev_idx > op_idx) {
+                return Err(DeserializationError::InvalidValue(
+                    "span block decorators are not sorted by operation index".into(),
+                ));
+            }
+            decorators.push((op_idx, source.read()?));
+        }
+
+        Ok(Self::with_decorators(operations, decorators))
+    }
+}
+
 // OPERATION BATCH
 // ================================================================================================
 
@@ -76,6 +76,53 @@ impl fmt::Display for Program {
     }
 }
 
+// SERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+/// Magic bytes at the start of a serialized [Program].
+const MAST_MAGIC: &[u8; 4] = b"MAST";
+
+/// Version of the binary format used for serializing [Program]s. This must be incremented every
+/// time the encoding of programs or any of their components changes.
+const MAST_VERSION: u8 = 0;
+
+impl Serializable for Program {
+    /// Serializes this program into a deterministic binary format.
+    ///
+    /// The encoding consists of a header (magic bytes followed by the format version), the
+    /// kernel, the code block table, and the full MAST of the program. Deserializing the result
+    /// yields a program with the same hash as this one.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(MAST_MAGIC);
+        target.write_u8(MAST_VERSION);
+        self.kernel.write_into(target);
+        self.cb_table.write_into(target);
+        self.root.write_into(target);
+    }
+}
+
+impl Deserializable for Program {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let magic: [u8; 4] = source.read_array()?;
+        if &magic != MAST_MAGIC {
+            return Err(DeserializationError::InvalidValue(
+                "input is not a serialized MAST program (invalid magic bytes)".into(),
+            ));
+        }
+        let version = source.read_u8()?;
+        if version != MAST_VERSION {
+            return Err(DeserializationError::InvalidValue(format!(
+                "unsupported MAST format version {version}, expected {MAST_VERSION}"
+            )));
+        }
+
+        let kernel = source.read()?;
+        let cb_table = source.read()?;
+        let root = source.read()?;
+        Ok(Self::with_kernel(root, kernel, cb_table))
+    }
+}
+
 // CODE BLOCK TABLE
 // ================================================================================================
 
@@ -113,6 +160,28 @@ impl CodeBlockTable {
     }
 }
 
+impl Serializable for CodeBlockTable {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        // blocks are written in the order of their hashes, and the hashes themselves are not
+        // written as they can be recomputed from the blocks
+        target.write_usize(self.0.len());
+        for block in self.0.values() {
+            block.write_into(target);
+        }
+    }
+}
+
+impl Deserializable for CodeBlockTable {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_blocks = source.read_usize()?;
+        let mut table = Self::default();
+        for _ in 0..num_blocks {
+            table.insert(source.read()?);
+        }
+        Ok(table)
+    }
+}
+
 // KERNEL
 // ================================================================================================
 
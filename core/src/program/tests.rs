@@ -1,6 +1,12 @@
-use super::{blocks::Dyn, Deserializable, Digest, Felt, Kernel, ProgramInfo, Serializable};
-use crate::{chiplets::hasher, Word};
-use alloc::vec::Vec;
+use super::{
+    blocks::{CodeBlock, Dyn},
+    CodeBlockTable, Deserializable, Digest, Felt, Kernel, Program, ProgramInfo, Serializable,
+};
+use crate::{
+    chiplets::hasher, utils::SliceReader, AdviceInjector, AssemblyOp, DebugOptions, Decorator,
+    Location, Operation, SignatureKind, Word, ONE,
+};
+use alloc::{string::ToString, vec::Vec};
 use proptest::prelude::*;
 use rand_utils::prng_array;
 
@@ -11,6 +17,120 @@ fn dyn_hash_is_correct() {
     assert_eq!(expected_constant, Dyn::new().hash());
 }
 
+#[test]
+fn program_serialization_roundtrip() {
+    let span = CodeBlock::new_span_with_decorators(
+        vec![
+            Operation::Push(Felt::new(7)),
+            Operation::Push(Felt::new(u64::MAX - 1000)),
+            Operation::Add,
+            Operation::Assert(17),
+            Operation::U32assert2(Felt::new(3)),
+            Operation::Drop,
+        ],
+        vec![
+            (
+                0,
+                Decorator::AsmOp(
+                    AssemblyOp::new("#main".to_string(), 2, "push.7".to_string(), false)
+                        .with_location(Location::new("#exec".to_string(), 3, 5)),
+                ),
+            ),
+            (
+                0,
+                Decorator::Advice(AdviceInjector::MapValueToStack {
+                    include_len: true,
+                    key_offset: 4,
+                }),
+            ),
+            (
+                2,
+                Decorator::Advice(AdviceInjector::HdwordToMap {
+                    domain: Felt::new(5),
+                }),
+            ),
+            (
+                3,
+                Decorator::Advice(AdviceInjector::SigToStack {
+                    kind: SignatureKind::RpoFalcon512,
+                }),
+            ),
+            (3, Decorator::Debug(DebugOptions::LocalInterval(1, 2, 3))),
+            (4, Decorator::Event(42)),
+            (6, Decorator::Trace(7)),
+        ],
+    );
+    let callee = CodeBlock::new_span(vec![Operation::Mul, Operation::Pad]);
+    let proxy_hash = digest_from_seed([3; 32]);
+
+    let body = CodeBlock::new_join([
+        CodeBlock::new_join([span.clone(), CodeBlock::new_call(callee.hash())]),
+        CodeBlock::new_join([
+            CodeBlock::new_split(
+                CodeBlock::new_loop(span),
+                CodeBlock::new_syscall(digest_from_seed([1; 32])),
+            ),
+            CodeBlock::new_join([
+                CodeBlock::new_dyn(),
+                CodeBlock::new_join([CodeBlock::new_dyncall(), CodeBlock::new_proxy(proxy_hash)]),
+            ]),
+        ]),
+    ]);
+
+    let kernel = Kernel::new(&[digest_from_seed([1; 32]), digest_from_seed([2; 32])]).unwrap();
+    let mut cb_table = CodeBlockTable::default();
+    cb_table.insert(callee.clone());
+    let program = Program::with_kernel(body, kernel, cb_table);
+
+    let bytes = program.to_bytes();
+    let deser = Program::read_from_bytes(&bytes).unwrap();
+
+    assert_eq!(program.hash(), deser.hash());
+    assert_eq!(program.root(), deser.root());
+    assert_eq!(program.kernel(), deser.kernel());
+    assert_eq!(Some(&callee), deser.cb_table().get(callee.hash()));
+
+    // the encoding must be deterministic
+    assert_eq!(bytes, deser.to_bytes());
+}
+
+#[test]
+fn program_deserialization_rejects_invalid_input() {
+    let program = Program::new(CodeBlock::new_span(vec![Operation::Noop]));
+    let bytes = program.to_bytes();
+
+    // invalid magic
+    let mut invalid = bytes.clone();
+    invalid[0] = b'X';
+    assert!(Program::read_from_bytes(&invalid).is_err());
+
+    // unsupported version
+    let mut invalid = bytes.clone();
+    invalid[4] = u8::MAX;
+    assert!(Program::read_from_bytes(&invalid).is_err());
+
+    // truncated input
+    assert!(Program::read_from_bytes(&bytes[..bytes.len() - 1]).is_err());
+}
+
+#[test]
+fn operation_serialization_covers_all_op_codes() {
+    let mut num_ops = 0;
+    for op_code in 0..=u8::MAX {
+        // every opcode is followed by an immediate value large enough for any operation
+        let mut bytes = vec![op_code];
+        bytes.extend_from_slice(&ONE.as_int().to_le_bytes());
+
+        if let Ok(op) = Operation::read_from(&mut SliceReader::new(&bytes)) {
+            let op_bytes = op.to_bytes();
+            assert_eq!(op_code, op.op_code());
+            assert_eq!(&bytes[..op_bytes.len()], op_bytes.as_slice());
+            num_ops += 1;
+        }
+    }
+    assert_eq!(89, num_ops);
+}
+
 proptest! {
     #[test]
     fn arbitrary_program_info_serialization_works(
@@ -0,0 +1,519 @@
+use super::{
+    document::{proc_declarations, Document, ParsedSource, SourceToken},
+    Workspace,
+};
+use assembly::{ast::ProcedureAst, LibraryPath, Module, ParsingError};
+use serde_json::{json, Value};
+use std::{collections::BTreeMap, path::Path};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Source reported for diagnostics generated by the language server.
+const DIAGNOSTIC_SOURCE: &str = "miden-assembly";
+
+/// LSP diagnostic severity of errors.
+const SEVERITY_ERROR: u32 = 1;
+
+/// LSP completion item kinds.
+const KIND_FUNCTION: u32 = 3;
+const KIND_MODULE: u32 = 9;
+const KIND_KEYWORD: u32 = 14;
+
+/// Keywords and instructions offered for completion outside of procedure invocations.
+const KEYWORDS: &[&str] = &[
+    "begin",
+    "end",
+    "proc",
+    "export",
+    "use",
+    "const",
+    "if.true",
+    "else",
+    "while.true",
+    "repeat",
+    "cfg",
+    "exec",
+    "call",
+    "syscall",
+    "procref",
+    "dynexec",
+    "dyncall",
+    // field operations
+    "assert",
+    "assertz",
+    "assert_eq",
+    "assert_eqw",
+    "add",
+    "sub",
+    "mul",
+    "div",
+    "neg",
+    "inv",
+    "pow2",
+    "exp",
+    "ilog2",
+    "not",
+    "and",
+    "or",
+    "xor",
+    "eq",
+    "neq",
+    "lt",
+    "lte",
+    "gt",
+    "gte",
+    "is_odd",
+    "eqw",
+    "ext2add",
+    "ext2sub",
+    "ext2mul",
+    "ext2div",
+    "ext2neg",
+    "ext2inv",
+    // u32 operations
+    "u32test",
+    "u32testw",
+    "u32assert",
+    "u32assert2",
+    "u32assertw",
+    "u32cast",
+    "u32split",
+    "u32wrapping_add",
+    "u32overflowing_add",
+    "u32overflowing_add3",
+    "u32wrapping_add3",
+    "u32wrapping_sub",
+    "u32overflowing_sub",
+    "u32wrapping_mul",
+    "u32overflowing_mul",
+    "u32overflowing_madd",
+    "u32wrapping_madd",
+    "u32div",
+    "u32mod",
+    "u32divmod",
+    "u32and",
+    "u32or",
+    "u32xor",
+    "u32not",
+    "u32shr",
+    "u32shl",
+    "u32rotr",
+    "u32rotl",
+    "u32popcnt",
+    "u32clz",
+    "u32ctz",
+    "u32clo",
+    "u32cto",
+    "u32lt",
+    "u32lte",
+    "u32gt",
+    "u32gte",
+    "u32min",
+    "u32max",
+    // stack manipulation
+    "drop",
+    "dropw",
+    "padw",
+    "dup",
+    "dupw",
+    "swap",
+    "swapw",
+    "swapdw",
+    "movup",
+    "movupw",
+    "movdn",
+    "movdnw",
+    "cswap",
+    "cswapw",
+    "cdrop",
+    "cdropw",
+    // input / output operations
+    "push",
+    "sdepth",
+    "locaddr",
+    "caller",
+    "clk",
+    "mem_load",
+    "loc_load",
+    "mem_loadw",
+    "loc_loadw",
+    "mem_store",
+    "loc_store",
+    "mem_storew",
+    "loc_storew",
+    "mem_stream",
+    "adv_pipe",
+    "adv_push",
+    "adv_loadw",
+    "adv",
+    // cryptographic operations
+    "hash",
+    "hmerge",
+    "hperm",
+    "mtree_get",
+    "mtree_set",
+    "mtree_merge",
+    "mtree_verify",
+    "fri_ext2fold4",
+    "rcomb_base",
+    // decorators
+    "breakpoint",
+    "debug",
+    "emit",
+    "trace",
+];
+
+// SYMBOLS
+// ================================================================================================
+
+/// A reference to a procedure or a module found in a source token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Symbol<'a> {
+    /// A procedure defined in the same document.
+    LocalProc(&'a str),
+    /// A procedure defined in the module imported under the specified name.
+    ImportedProc { module: &'a str, name: &'a str },
+    /// A module imported by a `use` statement.
+    Module(&'a str),
+}
+
+impl<'a> Symbol<'a> {
+    /// Returns the symbol referenced by the provided token, if any.
+    fn parse(token: &SourceToken<'a>) -> Option<Self> {
+        let label = token.label()?;
+        match token.keyword() {
+            "exec" | "call" | "syscall" | "procref" => Self::parse_proc(label),
+            "proc" => label.split('.').next().map(Self::LocalProc),
+            "export" if label.contains(LibraryPath::PATH_DELIM) => {
+                label.split("->").next().and_then(Self::parse_proc)
+            }
+            "export" => label.split('.').next().map(Self::LocalProc),
+            "use" => label.split("->").next().map(Self::Module),
+            _ => None,
+        }
+    }
+
+    fn parse_proc(label: &'a str) -> Option<Self> {
+        match label.rsplit_once(LibraryPath::PATH_DELIM) {
+            Some((module, name)) => Some(Self::ImportedProc { module, name }),
+            // invocations by MAST root do not reference any named procedure
+            None if label.starts_with("0x") => None,
+            None => Some(Self::LocalProc(label)),
+        }
+    }
+}
+
+// DIAGNOSTICS
+// ================================================================================================
+
+/// Returns the LSP diagnostics for the provided document.
+///
+/// Parsing errors are reported first; if the document is parsed successfully, all references to
+/// imported modules and procedures are checked against the workspace, and programs are compiled
+/// to report the remaining assembly errors.
+pub fn diagnostics(document: &Document, workspace: &Workspace) -> Vec<Value> {
+    let source = match document.source() {
+        Ok(source) => source,
+        Err(err) => return vec![parsing_diagnostic(document, err)],
+    };
+
+    let imports = document.imports();
+    let mut diagnostics = Vec::new();
+    for token in document.tokens() {
+        let message = match Symbol::parse(&token) {
+            Some(Symbol::Module(path)) => match LibraryPath::new(path) {
+                Ok(path) if workspace.get_module(&path).is_some() => continue,
+                _ => format!("module '{path}' was not found in any library"),
+            },
+            Some(Symbol::ImportedProc { module, name }) => {
+                match resolve_module_path(&imports, module) {
+                    // missing modules are reported at their `use` statements
+                    Some(path) if workspace.get_module(&path).is_none() => continue,
+                    Some(path) if workspace.resolve_procedure(&path, name).is_some() => continue,
+                    Some(path) => format!("procedure '{name}' was not found in module '{path}'"),
+                    None => format!("module '{module}' was not imported"),
+                }
+            }
+            _ => continue,
+        };
+        diagnostics.push(diagnostic(token.line, token.column, token.end_column(), &message));
+    }
+
+    // all imports must be resolved for the program to compile
+    if diagnostics.is_empty() {
+        if let ParsedSource::Program(program) = source {
+            if let Err((err, location)) = workspace.compile(program) {
+                // errors which cannot be attributed to any token are reported at the beginning of
+                // the document
+                let (line, start, end) = location
+                    .and_then(|location| document.token_range(&location))
+                    .unwrap_or_default();
+                diagnostics.push(diagnostic(line, start, end, &err.to_string()));
+            }
+        }
+    }
+
+    diagnostics
+}
+
+// GO TO DEFINITION
+// ================================================================================================
+
+/// Returns the LSP location of the definition of the symbol at the specified position.
+pub fn definition(
+    document: &Document,
+    uri: &str,
+    workspace: &Workspace,
+    line: u32,
+    column: u32,
+) -> Option<Value> {
+    let token = document.token_at(line, column)?;
+    match Symbol::parse(&token)? {
+        Symbol::LocalProc(name) => {
+            let (_, decl) =
+                document.proc_declarations().find(|(decl_name, _)| *decl_name == name)?;
+            Some(location(uri, decl.line, decl.column, decl.end_column()))
+        }
+        Symbol::ImportedProc { module, name } => {
+            let path = resolve_module_path(&document.imports(), module)?;
+            let (module, proc) = workspace.resolve_procedure(&path, name)?;
+            module_location(workspace, &module.path, Some(&proc.name))
+        }
+        Symbol::Module(path) => module_location(workspace, &LibraryPath::new(path).ok()?, None),
+    }
+}
+
+// HOVER
+// ================================================================================================
+
+/// Returns the LSP hover for the symbol at the specified position.
+///
+/// Hovering over a procedure shows its declaration, number of locals, and doc comments; hovering
+/// over an imported module shows the module docs.
+pub fn hover(document: &Document, workspace: &Workspace, line: u32, column: u32) -> Option<Value> {
+    let token = document.token_at(line, column)?;
+    let contents = match Symbol::parse(&token)? {
+        Symbol::LocalProc(name) => {
+            let docs = document.proc_docs(name);
+            proc_hover(document.local_proc(name)?, docs.as_deref(), None)
+        }
+        Symbol::ImportedProc { module, name } => {
+            let path = resolve_module_path(&document.imports(), module)?;
+            let (module, proc) = workspace.resolve_procedure(&path, name)?;
+            proc_hover(proc, proc.docs.as_deref(), Some(&module.path))
+        }
+        Symbol::Module(path) => {
+            let module = workspace.get_module(&LibraryPath::new(path).ok()?)?;
+            let num_exports = module.ast.procs().iter().filter(|proc| proc.is_export).count()
+                + module.ast.reexported_procs().len();
+            let mut contents =
+                format!("```masm\nuse.{path}\n```\nExported procedures: {num_exports}");
+            if let Some(docs) = module.ast.docs() {
+                contents.push_str("\n\n---\n\n");
+                contents.push_str(docs);
+            }
+            contents
+        }
+    };
+
+    Some(json!({
+        "contents": { "kind": "markdown", "value": contents },
+        "range": range(token.line, token.column, token.end_column()),
+    }))
+}
+
+// COMPLETION
+// ================================================================================================
+
+/// Returns the LSP completion items for the token ending at the specified position.
+///
+/// Invocation instructions are completed with local procedures, imported module names, and
+/// procedures exported from imported modules; `use` statements are completed with the paths of
+/// all modules in the workspace; any other token is completed with keywords and instructions.
+pub fn completion(document: &Document, workspace: &Workspace, line: u32, column: u32) -> Value {
+    let (prefix, start) = document.prefix_at(line, column);
+
+    let (keyword, candidates) = match prefix.split_once('.') {
+        Some(("use", _)) => {
+            let candidates = workspace
+                .modules()
+                .map(|module| (module.path.to_string(), KIND_MODULE, module.ast.docs().cloned()))
+                .collect();
+            ("use", candidates)
+        }
+        Some((keyword @ ("exec" | "call" | "syscall" | "procref"), label)) => {
+            let candidates = match label.rsplit_once(LibraryPath::PATH_DELIM) {
+                Some((module, _)) => resolve_module_path(&document.imports(), module)
+                    .and_then(|path| workspace.get_module(&path))
+                    .map(|module| module_exports(module, label))
+                    .unwrap_or_default(),
+                None => local_completions(document),
+            };
+            (keyword, candidates)
+        }
+        Some(_) => return json!([]),
+        None => {
+            let candidates =
+                KEYWORDS.iter().map(|kw| (kw.to_string(), KIND_KEYWORD, None)).collect();
+            ("", candidates)
+        }
+    };
+
+    let items: Vec<Value> = candidates
+        .into_iter()
+        .filter_map(|(label, kind, docs)| {
+            let text = if keyword.is_empty() {
+                label.clone()
+            } else {
+                format!("{keyword}.{label}")
+            };
+            text.starts_with(prefix).then(|| {
+                json!({
+                    "label": label,
+                    "kind": kind,
+                    "documentation": docs,
+                    "filterText": text,
+                    "textEdit": {
+                        "range": range(line, start, column),
+                        "newText": text,
+                    },
+                })
+            })
+        })
+        .collect();
+    json!(items)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+type Completion = (String, u32, Option<String>);
+
+/// Returns the completions for procedures defined in the document and for imported modules.
+fn local_completions(document: &Document) -> Vec<Completion> {
+    let procs = document
+        .proc_declarations()
+        .map(|(name, _)| (name.to_string(), KIND_FUNCTION, document.proc_docs(name)));
+    let modules = document
+        .imports()
+        .into_keys()
+        .map(|alias| (format!("{alias}{}", LibraryPath::PATH_DELIM), KIND_MODULE, None));
+    procs.chain(modules).collect()
+}
+
+/// Returns the completions for procedures exported from the provided module, where `label` is
+/// the invocation target typed so far (e.g., `u64::wrapping`).
+fn module_exports(module: &Module, label: &str) -> Vec<Completion> {
+    let (alias, _) = label.rsplit_once(LibraryPath::PATH_DELIM).expect("label is qualified");
+    let procs = module
+        .ast
+        .procs()
+        .iter()
+        .filter(|proc| proc.is_export)
+        .map(|proc| (proc.name.as_ref(), proc.docs.clone()));
+    let reexports = module
+        .ast
+        .reexported_procs()
+        .iter()
+        .map(|proc| (proc.name().as_ref(), proc.docs().map(String::from)));
+    procs
+        .chain(reexports)
+        .map(|(name, docs)| {
+            (format!("{alias}{}{name}", LibraryPath::PATH_DELIM), KIND_FUNCTION, docs)
+        })
+        .collect()
+}
+
+/// Returns the path of the module referenced by the provided name, which is either the name of a
+/// module imported by the document or a fully-qualified module path.
+fn resolve_module_path(imports: &BTreeMap<&str, LibraryPath>, module: &str) -> Option<LibraryPath> {
+    match imports.get(module) {
+        Some(path) => Some(path.clone()),
+        None if module.contains(LibraryPath::PATH_DELIM) => LibraryPath::new(module).ok(),
+        None => None,
+    }
+}
+
+/// Returns the markdown description of the provided procedure with the specified doc comments.
+fn proc_hover(
+    proc: &ProcedureAst,
+    docs: Option<&str>,
+    module_path: Option<&LibraryPath>,
+) -> String {
+    let keyword = if proc.is_export { "export" } else { "proc" };
+    let mut contents = format!("```masm\n{keyword}.{}\n```\n", proc.name);
+    if let Some(path) = module_path {
+        contents.push_str(&format!("Defined in `{path}`. "));
+    }
+    contents.push_str(&format!("Locals: {}", proc.num_locals));
+    if let Some(docs) = docs {
+        contents.push_str("\n\n---\n\n");
+        contents.push_str(docs);
+    }
+    contents
+}
+
+/// Returns the location of the module with the specified path, or of the procedure with the
+/// specified name in the module.
+fn module_location(
+    workspace: &Workspace,
+    path: &LibraryPath,
+    proc_name: Option<&str>,
+) -> Option<Value> {
+    let (file, source) = workspace.module_source(path).ok()?;
+    let (line, start, end) = match proc_name {
+        Some(name) => {
+            let (_, decl) = proc_declarations(&source).find(|(decl_name, _)| *decl_name == name)?;
+            (decl.line, decl.column, decl.end_column())
+        }
+        None => (0, 0, 0),
+    };
+    Some(location(&file_uri(&file), line, start, end))
+}
+
+fn parsing_diagnostic(document: &Document, err: &ParsingError) -> Value {
+    let (line, start, end) = document.token_range(err.location()).unwrap_or_else(|| {
+        // errors which are not located at any token are reported at the beginning of their line
+        let line = err.location().line().saturating_sub(1);
+        (line, 0, 0)
+    });
+    diagnostic(line, start, end, err.message())
+}
+
+fn diagnostic(line: u32, start: u32, end: u32, message: &str) -> Value {
+    json!({
+        "range": range(line, start, end),
+        "severity": SEVERITY_ERROR,
+        "source": DIAGNOSTIC_SOURCE,
+        "message": message,
+    })
+}
+
+fn location(uri: &str, line: u32, start: u32, end: u32) -> Value {
+    json!({ "uri": uri, "range": range(line, start, end) })
+}
+
+fn range(line: u32, start: u32, end: u32) -> Value {
+    json!({
+        "start": { "line": line, "character": start },
+        "end": { "line": line, "character": end },
+    })
+}
+
+/// Returns the `file` URI of the provided path.
+///
+/// All bytes of the path except for unreserved characters and path separators are
+/// percent-encoded.
+fn file_uri(path: &Path) -> String {
+    let mut uri = String::from("file://");
+    for byte in path.to_string_lossy().bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    uri
+}
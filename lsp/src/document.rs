@@ -0,0 +1,233 @@
+use assembly::{
+    ast::{ConstantProvider, ModuleAst, ProcedureAst, ProgramAst, SourceLocation},
+    LibraryPath, ParsingError,
+};
+use std::{collections::BTreeMap, iter};
+
+// CONSTANTS
+// ================================================================================================
+
+const BEGIN: &str = "begin";
+const EXPORT: &str = "export";
+const PROC: &str = "proc";
+const USE: &str = "use";
+const ALIAS_DELIM: &str = "->";
+const DOC_COMMENT_PREFIX: &str = "#!";
+
+// DOCUMENT
+// ================================================================================================
+
+/// A Miden assembly source file opened in the editor.
+///
+/// The document keeps both the raw text (used to locate tokens under the cursor) and the result
+/// of parsing the text into a program or a module AST.
+#[derive(Debug)]
+pub struct Document {
+    text: String,
+    source: Result<ParsedSource, ParsingError>,
+}
+
+/// The AST of a successfully parsed document.
+#[derive(Debug)]
+pub enum ParsedSource {
+    Program(ProgramAst),
+    Module(ModuleAst),
+}
+
+impl Document {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new document with the specified contents.
+    ///
+    /// The contents are parsed as a program if they contain a `begin` token, and as a module
+    /// otherwise. References to imported constants are resolved via the provided constants.
+    pub fn new(text: String, constants: &dyn ConstantProvider) -> Self {
+        let source = if tokens(&text).any(|token| token.text == BEGIN) {
+            ProgramAst::parse_with_constants(&text, constants).map(ParsedSource::Program)
+        } else {
+            ModuleAst::parse_with_constants(&text, constants).map(ParsedSource::Module)
+        };
+        Self { text, source }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the AST of this document, or the error encountered while parsing the document.
+    pub fn source(&self) -> Result<&ParsedSource, &ParsingError> {
+        self.source.as_ref()
+    }
+
+    /// Returns the procedure with the specified name defined in this document, if the document
+    /// was parsed successfully.
+    pub fn local_proc(&self, name: &str) -> Option<&ProcedureAst> {
+        let procs = match self.source.as_ref().ok()? {
+            ParsedSource::Program(program) => program.procedures(),
+            ParsedSource::Module(module) => module.procs(),
+        };
+        procs.iter().find(|proc| proc.name.as_ref() == name)
+    }
+
+    /// Returns the doc comments of the procedure with the specified name defined in this document.
+    ///
+    /// The parser attaches doc comments to exported procedures only, so doc comments of local
+    /// procedures are read from the lines preceding the procedure declaration.
+    pub fn proc_docs(&self, name: &str) -> Option<String> {
+        if let Some(docs) = self.local_proc(name).and_then(|proc| proc.docs.clone()) {
+            return Some(docs);
+        }
+        let (_, decl) = self.proc_declarations().find(|(decl_name, _)| *decl_name == name)?;
+        let lines: Vec<&str> = self.text.lines().take(decl.line as usize).collect();
+        let docs: Vec<&str> = lines
+            .iter()
+            .rev()
+            .map_while(|line| line.trim().strip_prefix(DOC_COMMENT_PREFIX))
+            .map(str::trim)
+            .collect();
+        (!docs.is_empty()).then(|| docs.into_iter().rev().collect::<Vec<_>>().join("\n"))
+    }
+
+    /// Returns an iterator over all tokens of this document, skipping comments.
+    pub fn tokens(&self) -> impl Iterator<Item = SourceToken<'_>> {
+        tokens(&self.text)
+    }
+
+    /// Returns the token which contains the specified position.
+    pub fn token_at(&self, line: u32, column: u32) -> Option<SourceToken<'_>> {
+        self.tokens().find(|token| {
+            token.line == line && token.column <= column && column <= token.end_column()
+        })
+    }
+
+    /// Returns the part of the token which precedes the specified position, together with the
+    /// column at which the token starts.
+    pub fn prefix_at(&self, line: u32, column: u32) -> (&str, u32) {
+        let line = self.text.lines().nth(line as usize).unwrap_or_default();
+        let line = &line[..byte_offset(line, column)];
+        let start = line.rfind(char::is_whitespace).map(|pos| pos + 1).unwrap_or_default();
+        (&line[start..], utf16_len(&line[..start]))
+    }
+
+    /// Returns the line and the start and end columns of the token at the specified location
+    /// reported by the assembler, or None if there is no token at the location.
+    ///
+    /// Source locations are one-based and their columns are measured in bytes.
+    pub fn token_range(&self, location: &SourceLocation) -> Option<(u32, u32, u32)> {
+        let line = location.line().checked_sub(1)?;
+        let text = self.text.lines().nth(line as usize)?;
+        let start = text.get(..location.column().checked_sub(1)? as usize)?;
+        let token = self.token_at(line, utf16_len(start))?;
+        Some((line, token.column, token.end_column()))
+    }
+
+    /// Returns a map of module aliases to the paths of modules imported by this document.
+    pub fn imports(&self) -> BTreeMap<&str, LibraryPath> {
+        self.tokens()
+            .filter_map(|token| {
+                let label = token.label().filter(|_| token.keyword() == USE)?;
+                let (path, alias) = match label.split_once(ALIAS_DELIM) {
+                    Some((path, alias)) => (LibraryPath::new(path).ok()?, alias),
+                    None => {
+                        let path = LibraryPath::new(label).ok()?;
+                        let alias = label.rsplit(LibraryPath::PATH_DELIM).next()?;
+                        (path, alias)
+                    }
+                };
+                Some((alias, path))
+            })
+            .collect()
+    }
+
+    /// Returns the declaration tokens (i.e., `proc.<name>` or `export.<name>`) of all procedures
+    /// defined in this document.
+    pub fn proc_declarations(&self) -> impl Iterator<Item = (&str, SourceToken<'_>)> {
+        proc_declarations(&self.text)
+    }
+}
+
+// SOURCE TOKEN
+// ================================================================================================
+
+/// A whitespace-delimited token of a source, with its zero-based line and column.
+///
+/// Columns are measured in UTF-16 code units, as are the character offsets of LSP positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceToken<'a> {
+    pub text: &'a str,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl<'a> SourceToken<'a> {
+    /// Returns the column immediately after the end of this token.
+    pub fn end_column(&self) -> u32 {
+        self.column + utf16_len(self.text)
+    }
+
+    /// Returns the first dot-separated part of this token (e.g., `exec` for `exec.foo`).
+    pub fn keyword(&self) -> &'a str {
+        self.text.split('.').next().unwrap_or_default()
+    }
+
+    /// Returns the part of this token after the first dot (e.g., `foo` for `exec.foo`), if any.
+    pub fn label(&self) -> Option<&'a str> {
+        self.text.split_once('.').map(|(_, label)| label)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns an iterator over all tokens of the provided source, skipping comments.
+pub fn tokens(source: &str) -> impl Iterator<Item = SourceToken<'_>> {
+    source.lines().enumerate().flat_map(|(line_idx, line)| {
+        let mut remainder = line;
+        let mut offset = 0;
+        iter::from_fn(move || {
+            let start = remainder.find(|c: char| !c.is_whitespace())?;
+            let tail = &remainder[start..];
+            if tail.starts_with('#') {
+                return None;
+            }
+            let len = tail.find(char::is_whitespace).unwrap_or(tail.len());
+            let token = SourceToken {
+                text: &tail[..len],
+                line: line_idx as u32,
+                column: offset + utf16_len(&remainder[..start]),
+            };
+            offset = token.end_column();
+            remainder = &tail[len..];
+            Some(token)
+        })
+    })
+}
+
+/// Returns the names and declaration tokens of all procedures defined in the provided source.
+///
+/// Re-exported procedures are skipped, as they are defined in other modules.
+pub fn proc_declarations(source: &str) -> impl Iterator<Item = (&str, SourceToken<'_>)> {
+    tokens(source).filter_map(|token| {
+        let label = token.label().filter(|_| matches!(token.keyword(), PROC | EXPORT))?;
+        let name = label.split('.').next()?;
+        (!name.contains(LibraryPath::PATH_DELIM)).then_some((name, token))
+    })
+}
+
+/// Returns the length of the provided string in UTF-16 code units.
+fn utf16_len(text: &str) -> u32 {
+    text.encode_utf16().count() as u32
+}
+
+/// Returns the byte offset in the provided line of the specified column measured in UTF-16 code
+/// units; columns past the end of the line are clamped to the length of the line.
+fn byte_offset(line: &str, column: u32) -> usize {
+    let mut units = 0;
+    for (offset, c) in line.char_indices() {
+        if units >= column {
+            return offset;
+        }
+        units += c.len_utf16() as u32;
+    }
+    line.len()
+}
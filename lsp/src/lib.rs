@@ -0,0 +1,12 @@
+mod analysis;
+
+mod document;
+use document::Document;
+
+pub mod protocol;
+
+mod server;
+pub use server::Server;
+
+mod workspace;
+pub use workspace::Workspace;
@@ -0,0 +1,81 @@
+use assembly::{LibraryNamespace, MaslLibrary};
+use miden_lsp::{Server, Workspace};
+use std::{env, fs, io, path::PathBuf, process};
+
+const USAGE: &str = "\
+Miden assembly language server
+
+Communicates with the editor over stdin and stdout.
+
+Usage: miden-lsp [OPTIONS]
+
+Options:
+  --library <PATH>            Path to a .masl library file to resolve imports against
+  --source <NAMESPACE>=<DIR>  Directory with .masm sources of the library with the given namespace
+  --help                      Print help";
+
+/// Executable entry point
+pub fn main() {
+    // modules are rendered into a directory of their own for each server instance, so that
+    // concurrently running servers do not overwrite each other's files
+    let cache_dir = env::temp_dir().join(format!("miden-lsp-{}", process::id()));
+    let workspace = match build_workspace(cache_dir.clone(), env::args().skip(1)) {
+        Ok(workspace) => workspace,
+        Err(err) => {
+            eprintln!("{err}\n\n{USAGE}");
+            process::exit(2);
+        }
+    };
+
+    let mut server = Server::new(workspace);
+    let result = server.run(io::stdin().lock(), io::stdout().lock());
+    // the cache directory may not exist if no modules were rendered
+    let _ = fs::remove_dir_all(&cache_dir);
+    if let Err(err) = result {
+        eprintln!("miden-lsp: {err}");
+        process::exit(1);
+    }
+
+    // per the LSP specification, the exit code is 1 if the server was not shut down first
+    process::exit(if server.shutdown_requested() { 0 } else { 1 });
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Builds the workspace which renders modules into the specified cache directory from the
+/// command-line arguments.
+fn build_workspace<I>(cache_dir: PathBuf, mut args: I) -> Result<Workspace, String>
+where
+    I: Iterator<Item = String>,
+{
+    let mut workspace = Workspace::new(cache_dir);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--library" => {
+                let path = args.next().ok_or("missing value for --library")?;
+                let library = MaslLibrary::read_from_file(&path)
+                    .map_err(|err| format!("failed to read library {path}: {err}"))?;
+                workspace = workspace
+                    .with_library(library)
+                    .map_err(|err| format!("failed to add library {path}: {err}"))?;
+            }
+            "--source" => {
+                let value = args.next().ok_or("missing value for --source")?;
+                let (namespace, dir) =
+                    value.split_once('=').ok_or("--source must have the form <NAMESPACE>=<DIR>")?;
+                let namespace = LibraryNamespace::new(namespace)
+                    .map_err(|err| format!("invalid library namespace {namespace}: {err}"))?;
+                workspace = workspace
+                    .with_source_dir(namespace, dir)
+                    .map_err(|err| format!("failed to read library sources from {dir}: {err}"))?;
+            }
+            "--help" | "-h" => {
+                println!("{USAGE}");
+                process::exit(0);
+            }
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+    Ok(workspace)
+}
@@ -0,0 +1,84 @@
+use serde_json::{json, Value};
+use std::{
+    error::Error,
+    io::{self, BufRead, Write},
+};
+
+// ERROR CODES
+// ================================================================================================
+
+/// JSON-RPC error code for requests with a method the server does not support.
+pub const METHOD_NOT_FOUND: i64 = -32601;
+
+/// JSON-RPC error code for requests with invalid parameters.
+pub const INVALID_PARAMS: i64 = -32602;
+
+// MESSAGE TRANSPORT
+// ================================================================================================
+
+/// Reads a single JSON-RPC message framed with a `Content-Length` header from the reader.
+///
+/// Returns `Ok(None)` if the reader is exhausted before the start of a message.
+pub fn read_message<R: BufRead>(reader: &mut R) -> io::Result<Option<Value>> {
+    let mut content_len = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                content_len = Some(value.trim().parse::<usize>().map_err(invalid_data)?);
+            }
+        }
+    }
+
+    let content_len = content_len.ok_or_else(|| invalid_data("missing Content-Length header"))?;
+    let mut content = vec![0; content_len];
+    reader.read_exact(&mut content)?;
+    serde_json::from_slice(&content).map(Some).map_err(invalid_data)
+}
+
+/// Writes the provided JSON-RPC message into the writer, framed with a `Content-Length` header.
+pub fn write_message<W: Write>(writer: &mut W, message: &Value) -> io::Result<()> {
+    let content = message.to_string();
+    write!(writer, "Content-Length: {}\r\n\r\n{content}", content.len())?;
+    writer.flush()
+}
+
+// MESSAGE BUILDERS
+// ================================================================================================
+
+/// Returns a request with the specified ID, method, and parameters.
+pub fn request(id: u64, method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+/// Returns a notification with the specified method and parameters.
+pub fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+/// Returns a successful response to the request with the specified ID.
+pub fn response(id: Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+/// Returns an error response to the request with the specified ID.
+pub fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn invalid_data<E>(err: E) -> io::Error
+where
+    E: Into<Box<dyn Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
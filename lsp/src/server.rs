@@ -0,0 +1,180 @@
+use super::{
+    analysis,
+    protocol::{
+        error_response, notification, read_message, response, write_message, INVALID_PARAMS,
+        METHOD_NOT_FOUND,
+    },
+    Document, Workspace,
+};
+use serde_json::{json, Value};
+use std::{
+    collections::BTreeMap,
+    io::{self, BufRead, Write},
+};
+
+// SERVER
+// ================================================================================================
+
+/// A Miden assembly language server.
+///
+/// The server keeps the contents of all documents opened in the editor, and analyzes them against
+/// the libraries of its [Workspace]. Documents are synchronized in full on every change.
+#[derive(Debug)]
+pub struct Server {
+    workspace: Workspace,
+    documents: BTreeMap<String, Document>,
+    shutdown_requested: bool,
+}
+
+impl Server {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new server which analyzes documents against the provided workspace.
+    pub fn new(workspace: Workspace) -> Self {
+        Self {
+            workspace,
+            documents: BTreeMap::new(),
+            shutdown_requested: false,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if the client requested the server to shut down before exiting.
+    pub fn shutdown_requested(&self) -> bool {
+        self.shutdown_requested
+    }
+
+    // MESSAGE HANDLING
+    // --------------------------------------------------------------------------------------------
+
+    /// Reads messages from the reader and writes replies into the writer until either the reader
+    /// is exhausted or the `exit` notification is received.
+    pub fn run<R: BufRead, W: Write>(&mut self, mut reader: R, mut writer: W) -> io::Result<()> {
+        while let Some(message) = read_message(&mut reader)? {
+            if message["method"] == "exit" {
+                break;
+            }
+            for reply in self.handle_message(&message) {
+                write_message(&mut writer, &reply)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Handles a single message received from the client, and returns the messages which should
+    /// be sent back to the client in response.
+    pub fn handle_message(&mut self, message: &Value) -> Vec<Value> {
+        let params = &message["params"];
+        match (message.get("id"), message["method"].as_str()) {
+            (Some(id), Some(method)) => vec![self.handle_request(id.clone(), method, params)],
+            (None, Some(method)) => self.handle_notification(method, params),
+            // responses to requests sent by the server are ignored
+            (_, None) => Vec::new(),
+        }
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    fn handle_request(&mut self, id: Value, method: &str, params: &Value) -> Value {
+        let result = match method {
+            "initialize" => Some(capabilities()),
+            "shutdown" => {
+                self.shutdown_requested = true;
+                Some(Value::Null)
+            }
+            "textDocument/definition" => self.with_position(params, |doc, uri, ws, line, col| {
+                analysis::definition(doc, uri, ws, line, col).unwrap_or_default()
+            }),
+            "textDocument/hover" => self.with_position(params, |doc, _, ws, line, col| {
+                analysis::hover(doc, ws, line, col).unwrap_or_default()
+            }),
+            "textDocument/completion" => self.with_position(params, |doc, _, ws, line, col| {
+                analysis::completion(doc, ws, line, col)
+            }),
+            _ => {
+                let message = format!("method '{method}' is not supported");
+                return error_response(id, METHOD_NOT_FOUND, &message);
+            }
+        };
+
+        match result {
+            Some(result) => response(id, result),
+            None => error_response(id, INVALID_PARAMS, "invalid text document position"),
+        }
+    }
+
+    fn handle_notification(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default().to_string();
+        let text = match method {
+            "textDocument/didOpen" => params["textDocument"]["text"].as_str(),
+            // documents are synchronized in full, so the last change contains the whole text
+            "textDocument/didChange" => params["contentChanges"]
+                .as_array()
+                .and_then(|changes| changes.last())
+                .and_then(|change| change["text"].as_str()),
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+                return vec![publish_diagnostics(&uri, Vec::new())];
+            }
+            _ => return Vec::new(),
+        };
+
+        match text {
+            Some(text) => {
+                let document = Document::new(text.to_string(), &self.workspace);
+                let diagnostics = analysis::diagnostics(&document, &self.workspace);
+                self.documents.insert(uri.clone(), document);
+                vec![publish_diagnostics(&uri, diagnostics)]
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Invokes the provided function with the document and the position referenced by the
+    /// request parameters.
+    ///
+    /// Returns `None` if the parameters do not contain a valid position, and a null result if the
+    /// document has not been opened.
+    fn with_position<F>(&self, params: &Value, f: F) -> Option<Value>
+    where
+        F: FnOnce(&Document, &str, &Workspace, u32, u32) -> Value,
+    {
+        let uri = params["textDocument"]["uri"].as_str()?;
+        let line = u32::try_from(params["position"]["line"].as_u64()?).ok()?;
+        let column = u32::try_from(params["position"]["character"].as_u64()?).ok()?;
+        Some(match self.documents.get(uri) {
+            Some(document) => f(document, uri, &self.workspace, line, column),
+            None => Value::Null,
+        })
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the result of the `initialize` request describing the capabilities of the server.
+fn capabilities() -> Value {
+    json!({
+        "capabilities": {
+            "textDocumentSync": 1,
+            "definitionProvider": true,
+            "hoverProvider": true,
+            "completionProvider": { "triggerCharacters": [".", ":"] },
+        },
+        "serverInfo": {
+            "name": "miden-lsp",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+    })
+}
+
+fn publish_diagnostics(uri: &str, diagnostics: Vec<Value>) -> Value {
+    notification(
+        "textDocument/publishDiagnostics",
+        json!({ "uri": uri, "diagnostics": diagnostics }),
+    )
+}
@@ -0,0 +1,199 @@
+use assembly::{
+    ast::{ConstantProvider, ProcedureAst, ProgramAst, SourceLocation},
+    Assembler, AssemblyContext, AssemblyError, Library, LibraryError, LibraryNamespace,
+    LibraryPath, MaslLibrary, Module, ModuleProvider, ProcedureId, Version,
+};
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+use stdlib::StdLibrary;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Maximum number of re-exports followed while resolving a procedure.
+const MAX_REEXPORT_DEPTH: usize = 16;
+
+// WORKSPACE
+// ================================================================================================
+
+/// The set of libraries against which documents opened in the editor are analyzed.
+///
+/// The standard library is always part of the workspace. Additional libraries can be loaded
+/// either from compiled `.masl` files or from directories with `.masm` sources; for the latter,
+/// go-to-definition navigates to the original source files. Modules without source files (e.g.,
+/// modules of the standard library) are rendered into the cache directory on demand.
+#[derive(Debug)]
+pub struct Workspace {
+    libraries: Vec<MaslLibrary>,
+    provider: ModuleProvider,
+    sources: BTreeMap<LibraryPath, PathBuf>,
+    cache_dir: PathBuf,
+}
+
+impl Workspace {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new workspace containing the standard library, which renders modules without
+    /// source files into the specified cache directory.
+    pub fn new<P: AsRef<Path>>(cache_dir: P) -> Self {
+        let stdlib = MaslLibrary::from(StdLibrary::default());
+        let mut provider = ModuleProvider::default();
+        provider.add_library(&stdlib).expect("failed to add the standard library");
+        Self {
+            libraries: vec![stdlib],
+            provider,
+            sources: BTreeMap::new(),
+            cache_dir: cache_dir.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Adds the provided library to this workspace.
+    ///
+    /// # Errors
+    /// Returns an error if the library contains a module with the same path as a module already
+    /// in the workspace.
+    pub fn with_library(mut self, library: MaslLibrary) -> Result<Self, LibraryError> {
+        self.provider.add_library(&library)?;
+        self.libraries.push(library);
+        Ok(self)
+    }
+
+    /// Adds the library with the specified namespace from a directory of `.masm` sources to this
+    /// workspace.
+    ///
+    /// # Errors
+    /// Returns an error if the library could not be read from the directory or added to the
+    /// workspace.
+    pub fn with_source_dir<P: AsRef<Path>>(
+        self,
+        namespace: LibraryNamespace,
+        dir: P,
+    ) -> io::Result<Self> {
+        let dir = dir.as_ref();
+        // constants exported from the libraries already in the workspace can be referenced
+        let library = MaslLibrary::read_from_dir_with_constants(
+            dir,
+            namespace,
+            false,
+            Version::MIN,
+            &self.provider,
+        )?;
+
+        let mut sources = BTreeMap::new();
+        for module in library.modules() {
+            let mut file = dir.to_path_buf();
+            file.extend(module.path.components().skip(1));
+            let file = if file.is_dir() {
+                file.join(MaslLibrary::MOD)
+            } else {
+                file
+            };
+            sources.insert(module.path.clone(), file.with_extension(MaslLibrary::MODULE_EXTENSION));
+        }
+
+        let mut workspace = self.with_library(library).map_err(io::Error::from)?;
+        workspace.sources.append(&mut sources);
+        Ok(workspace)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an iterator over all modules in this workspace.
+    pub fn modules(&self) -> impl Iterator<Item = &Module> {
+        self.provider.modules()
+    }
+
+    /// Returns the module with the specified path, if any.
+    pub fn get_module(&self, path: &LibraryPath) -> Option<&Module> {
+        self.provider.get_module_by_path(path)
+    }
+
+    /// Returns the procedure with the specified name exported from the module with the specified
+    /// path, together with the module in which the procedure is defined.
+    ///
+    /// Re-exported procedures are resolved to the procedures they refer to.
+    pub fn resolve_procedure(
+        &self,
+        module_path: &LibraryPath,
+        name: &str,
+    ) -> Option<(&Module, &ProcedureAst)> {
+        let mut proc_id = ProcedureId::from_name(name, module_path);
+        for _ in 0..MAX_REEXPORT_DEPTH {
+            let module = self.provider.get_module(&proc_id)?;
+            let proc = module
+                .ast
+                .procs()
+                .iter()
+                .find(|proc| ProcedureId::from_name(&proc.name, &module.path) == proc_id);
+            if let Some(proc) = proc {
+                return Some((module, proc));
+            }
+
+            let reexport = module
+                .ast
+                .reexported_procs()
+                .iter()
+                .find(|reexport| reexport.get_alias_id(&module.path) == proc_id)?;
+            proc_id = reexport.proc_id();
+        }
+        None
+    }
+
+    /// Returns the path and contents of the source file of the module with the specified path.
+    ///
+    /// If the module was not loaded from a source file, its source is rendered from the module AST
+    /// into the cache directory.
+    pub fn module_source(&self, path: &LibraryPath) -> io::Result<(PathBuf, String)> {
+        if let Some(file) = self.sources.get(path) {
+            let source = fs::read_to_string(file)?;
+            return Ok((file.clone(), source));
+        }
+
+        let module = self.get_module(path).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, format!("module '{path}' not found"))
+        })?;
+        let mut file = self.cache_dir.clone();
+        file.extend(path.components());
+        let file = file.with_extension(MaslLibrary::MODULE_EXTENSION);
+
+        let source = module.ast.to_string();
+        if fs::read_to_string(&file).ok().as_ref() != Some(&source) {
+            fs::create_dir_all(file.parent().expect("module file has a parent directory"))?;
+            fs::write(&file, &source)?;
+        }
+        Ok((file, source))
+    }
+
+    // COMPILATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Compiles the provided program against the libraries of this workspace.
+    ///
+    /// # Errors
+    /// Returns the error encountered while compiling the program, together with the location in
+    /// the program source of the code which failed to compile, if known.
+    pub fn compile(
+        &self,
+        program: &ProgramAst,
+    ) -> Result<(), (AssemblyError, Option<SourceLocation>)> {
+        let assembler = Assembler::default()
+            .with_libraries(self.libraries.iter())
+            .map_err(|err| (err, None))?;
+        let mut context = AssemblyContext::for_program(Some(program));
+        assembler
+            .compile_in_context(program, &mut context)
+            .map(|_| ())
+            .map_err(|err| (err, context.error_location().copied()))
+    }
+}
+
+impl ConstantProvider for Workspace {
+    fn get_constant(&self, module_path: &LibraryPath, name: &str) -> Option<u64> {
+        self.provider.get_constant(module_path, name)
+    }
+}
@@ -0,0 +1,221 @@
+use miden_lsp::protocol::{notification, read_message, request, write_message};
+use serde_json::{json, Value};
+use std::{
+    env, fs,
+    io::BufReader,
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+};
+
+// SCRIPTED CLIENT
+// ================================================================================================
+
+/// A minimal LSP client which drives the `miden-lsp` binary over stdio.
+struct Client {
+    process: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: u64,
+}
+
+impl Client {
+    fn start(args: &[&str]) -> Self {
+        let mut process = Command::new(env!("CARGO_BIN_EXE_miden-lsp"))
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("failed to start miden-lsp");
+        let stdin = process.stdin.take().unwrap();
+        let stdout = BufReader::new(process.stdout.take().unwrap());
+        Self {
+            process,
+            stdin,
+            stdout,
+            next_id: 1,
+        }
+    }
+
+    /// Sends a request and returns the result of the matching response.
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        let id = self.next_id;
+        self.next_id += 1;
+        write_message(&mut self.stdin, &request(id, method, params)).unwrap();
+        loop {
+            let message = read_message(&mut self.stdout).unwrap().expect("server closed stdout");
+            if message["id"] == id {
+                assert!(message.get("error").is_none(), "request failed: {message}");
+                return message["result"].clone();
+            }
+        }
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        write_message(&mut self.stdin, &notification(method, params)).unwrap();
+    }
+
+    /// Waits for the next diagnostics published by the server.
+    fn diagnostics(&mut self) -> Vec<Value> {
+        loop {
+            let message = read_message(&mut self.stdout).unwrap().expect("server closed stdout");
+            if message["method"] == "textDocument/publishDiagnostics" {
+                return message["params"]["diagnostics"].as_array().unwrap().clone();
+            }
+        }
+    }
+
+    fn position(&mut self, method: &str, uri: &str, line: u32, character: u32) -> Value {
+        let params = json!({
+            "textDocument": { "uri": uri },
+            "position": { "line": line, "character": character },
+        });
+        self.request(method, params)
+    }
+}
+
+// TEST SOURCES
+// ================================================================================================
+
+const LIBRARY_MODULE: &str = "\
+use.std::math::u64
+
+#! Adds two u64 values.
+export.add64
+    exec.u64::wrapping_add
+end
+
+export.u64::overflowing_add->add_overflowing
+";
+
+const PROGRAM: &str = "\
+use.std::math::u64
+use.proj::util
+
+#! Doubles the top stack element.
+proc.double.2
+    dup add
+end
+
+begin
+    exec.double
+    exec.u64::wrapping_add
+    exec.util::add64
+    exec.util::add_overflowing
+end
+";
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn scripted_session() {
+    // paths in URIs are percent-encoded
+    let source_dir = env::temp_dir().join(format!("miden lsp test {}", std::process::id()));
+    fs::create_dir_all(&source_dir).unwrap();
+    fs::write(source_dir.join("util.masm"), LIBRARY_MODULE).unwrap();
+    let source_arg = format!("proj={}", source_dir.display());
+
+    let mut client = Client::start(&["--source", &source_arg]);
+    let uri = "file:///workspace/main.masm";
+
+    let result = client.request("initialize", json!({ "capabilities": {} }));
+    assert_eq!(result["capabilities"]["hoverProvider"], true);
+    assert_eq!(result["capabilities"]["definitionProvider"], true);
+    client.notify("initialized", json!({}));
+
+    // parsing errors are reported at the location of the offending token
+    let params = json!({
+        "textDocument": { "uri": uri, "languageId": "masm", "version": 1, "text": "begin\npush.1" },
+    });
+    client.notify("textDocument/didOpen", params);
+    let diagnostics = client.diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0]["range"]["start"]["line"], 0);
+
+    // columns are measured in UTF-16 code units
+    let params = json!({
+        "textDocument": { "uri": uri, "version": 2 },
+        "contentChanges": [{ "text": "begin\n    exec.\u{fc}\u{1f600} push.1\nend" }],
+    });
+    client.notify("textDocument/didChange", params);
+    let diagnostics = client.diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    let expected = json!({
+        "start": { "line": 1, "character": 4 },
+        "end": { "line": 1, "character": 12 },
+    });
+    assert_eq!(diagnostics[0]["range"], expected);
+
+    // a valid program has no diagnostics
+    let params = json!({
+        "textDocument": { "uri": uri, "version": 3 },
+        "contentChanges": [{ "text": PROGRAM }],
+    });
+    client.notify("textDocument/didChange", params);
+    assert_eq!(client.diagnostics(), Vec::<Value>::new());
+
+    // hover shows doc comments and number of locals of local and imported procedures
+    let hover = client.position("textDocument/hover", uri, 9, 10);
+    let contents = hover["contents"]["value"].as_str().unwrap();
+    assert!(contents.contains("Doubles the top stack element."));
+    assert!(contents.contains("Locals: 2"));
+
+    let hover = client.position("textDocument/hover", uri, 10, 10);
+    let contents = hover["contents"]["value"].as_str().unwrap();
+    assert!(contents.contains("std::math::u64"));
+    assert!(contents.contains("This takes 7 cycles."));
+
+    // go-to-definition resolves local procedures, library sources, and re-exports
+    let location = client.position("textDocument/definition", uri, 9, 10);
+    assert_eq!(location["uri"], uri);
+    assert_eq!(location["range"]["start"]["line"], 4);
+
+    let location = client.position("textDocument/definition", uri, 11, 10);
+    let expected_uri =
+        format!("file://{}", source_dir.join("util.masm").display()).replace(' ', "%20");
+    assert_eq!(location["uri"], expected_uri.as_str());
+    assert_eq!(location["range"]["start"]["line"], 3);
+
+    let location = client.position("textDocument/definition", uri, 12, 10);
+    assert!(location["uri"].as_str().unwrap().ends_with("std/math/u64.masm"));
+
+    // completion offers procedures of imported modules, and local procedures and modules
+    let items = client.position("textDocument/completion", uri, 10, 22);
+    let labels: Vec<_> = items.as_array().unwrap().iter().map(|item| &item["label"]).collect();
+    assert!(labels.contains(&&json!("u64::wrapping_add")));
+    assert!(!labels.contains(&&json!("u64::overflowing_add")));
+
+    let items = client.position("textDocument/completion", uri, 9, 9);
+    let labels: Vec<_> = items.as_array().unwrap().iter().map(|item| &item["label"]).collect();
+    assert!(labels.contains(&&json!("double")));
+    assert!(labels.contains(&&json!("util::")));
+
+    // unknown imported procedures are reported
+    let params = json!({
+        "textDocument": { "uri": uri, "version": 4 },
+        "contentChanges": [{ "text": PROGRAM.replace("wrapping_add", "missing_proc") }],
+    });
+    client.notify("textDocument/didChange", params);
+    let diagnostics = client.diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0]["range"]["start"]["line"], 10);
+
+    // compilation errors are reported at the instruction which failed to compile
+    let params = json!({
+        "textDocument": { "uri": uri, "version": 5 },
+        "contentChanges": [{ "text": PROGRAM.replace("exec.double", "syscall.double") }],
+    });
+    client.notify("textDocument/didChange", params);
+    let diagnostics = client.diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    let expected = json!({
+        "start": { "line": 9, "character": 4 },
+        "end": { "line": 9, "character": 18 },
+    });
+    assert_eq!(diagnostics[0]["range"], expected);
+
+    assert_eq!(client.request("shutdown", Value::Null), Value::Null);
+    client.notify("exit", Value::Null);
+    assert!(client.process.wait().unwrap().success());
+
+    fs::remove_dir_all(&source_dir).unwrap();
+}
@@ -1,6 +1,8 @@
-use assembly::{LibraryNamespace, MaslLibrary, Version};
+use super::data::{parse_define, Libraries};
+use assembly::{Assembler, LibraryNamespace, MaslLibrary, Version};
 use clap::Parser;
 use std::path::PathBuf;
+use stdlib::StdLibrary;
 
 #[derive(Debug, Clone, Parser)]
 #[clap(
@@ -17,6 +19,14 @@ pub struct BundleCmd {
     /// Version of the library, defaults to `0.1.0`.
     #[clap(short, long, default_value = "0.1.0")]
     version: String,
+    /// Paths to .masl files of the libraries which the library depends on; constants exported
+    /// from these libraries and from the standard library can be referenced by the library
+    #[clap(short = 'l', long = "libraries", value_parser)]
+    library_paths: Vec<PathBuf>,
+    /// Defines available to the modules of the library as constants, specified as `NAME` or
+    /// `NAME=VALUE`; the value of a define specified without a value is 1
+    #[clap(short = 'D', long = "define", value_parser = parse_define)]
+    defines: Vec<(String, u64)>,
 }
 
 impl BundleCmd {
@@ -38,12 +48,25 @@ impl BundleCmd {
         let library_namespace =
             LibraryNamespace::try_from(namespace.clone()).expect("invalid base namespace");
         let version = Version::try_from(self.version.as_ref()).expect("invalid cargo version");
+        // constants exported from the dependencies and the defines are resolved via the assembler
+        let libraries = Libraries::new(&self.library_paths)?;
+        let mut dependencies = Assembler::default()
+            .with_library(&StdLibrary::default())
+            .and_then(|assembler| assembler.with_libraries(libraries.libraries.iter()))
+            .map_err(|e| format!("Failed to load libraries - {e}"))?;
+        for (name, value) in &self.defines {
+            dependencies = dependencies
+                .with_define(name, *value)
+                .map_err(|e| format!("Failed to set defines - {e}"))?;
+        }
+
         let with_source_locations = true;
-        let stdlib = MaslLibrary::read_from_dir(
+        let stdlib = MaslLibrary::read_from_dir_with_constants(
             self.dir.clone(),
             library_namespace,
             with_source_locations,
             version,
+            &dependencies,
         )
         .map_err(|e| e.to_string())?;
 
@@ -1,6 +1,6 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 
-use super::data::{Debug, Libraries, ProgramFile};
+use super::data::{parse_define, Debug, Libraries, MastFile, ProgramFile};
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Parser)]
@@ -12,9 +12,26 @@ pub struct CompileCmd {
     /// Paths to .masl library files
     #[clap(short = 'l', long = "libraries", value_parser)]
     library_paths: Vec<PathBuf>,
+    /// Defines available to the program as constants, specified as `NAME` or `NAME=VALUE`; the
+    /// value of a define specified without a value is 1
+    #[clap(short = 'D', long = "define", value_parser = parse_define)]
+    defines: Vec<(String, u64)>,
     /// Path to output file
     #[clap(short = 'o', long = "output", value_parser)]
     output_file: Option<PathBuf>,
+    /// Output format
+    #[clap(long = "emit", value_enum, default_value_t = EmitFormat::Ast)]
+    emit: EmitFormat,
+}
+
+/// Format of the output file of the compile command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum EmitFormat {
+    /// Serialized program AST
+    Ast,
+    /// Serialized MAST of the compiled program which can be executed directly by the `run` and
+    /// `prove` commands
+    Mast,
 }
 
 impl CompileCmd {
@@ -23,12 +40,12 @@ impl CompileCmd {
         println!("Compile program");
         println!("============================================================");
 
-        // load the program from file and parse it
-        let program = ProgramFile::read(&self.assembly_file)?;
-
         // load libraries from files
         let libraries = Libraries::new(&self.library_paths)?;
 
+        // load the program from file and parse it
+        let program = ProgramFile::read(&self.assembly_file, &libraries.libraries, &self.defines)?;
+
         // compile the program
         let compiled_program = program.compile(&Debug::Off, libraries.libraries)?;
 
@@ -37,6 +54,15 @@ impl CompileCmd {
         println!("program hash is {}", hex::encode(program_hash));
 
         // write the compiled file
-        program.write(self.output_file.clone())
+        match self.emit {
+            EmitFormat::Ast => program.write(self.output_file.clone()),
+            EmitFormat::Mast => {
+                let out_path = self
+                    .output_file
+                    .clone()
+                    .unwrap_or_else(|| self.assembly_file.with_extension(MastFile::EXTENSION));
+                MastFile::write(&compiled_program, &out_path)
+            }
+        }
     }
 }
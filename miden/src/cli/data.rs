@@ -1,8 +1,8 @@
-use assembly::{Library, MaslLibrary};
+use assembly::{ErrorTable, Library, MaslLibrary};
 use miden_vm::{
     crypto::{MerkleStore, MerkleTree, NodeIndex, PartialMerkleTree, RpoDigest, SimpleSmt},
     math::Felt,
-    utils::{Deserializable, SliceReader},
+    utils::{Deserializable, Serializable, SliceReader},
     AdviceInputs, Assembler, Digest, ExecutionProof, MemAdviceProvider, Program, ProgramAst,
     StackInputs, StackOutputs, Word,
 };
@@ -36,6 +36,19 @@ impl Debug {
     }
 }
 
+/// Parses a define specified as `NAME` or `NAME=VALUE` into its name and value.
+pub fn parse_define(define: &str) -> Result<(String, u64), String> {
+    match define.split_once('=') {
+        Some((name, value)) => {
+            let value = value
+                .parse::<u64>()
+                .map_err(|err| format!("invalid value of define `{name}` - {err}"))?;
+            Ok((name.to_string(), value))
+        }
+        None => Ok((define.to_string(), 1)),
+    }
+}
+
 // MERKLE DATA
 // ================================================================================================
 
@@ -384,15 +397,43 @@ pub struct ProgramFile {
 /// Helper methods to interact with masm program file.
 impl ProgramFile {
     /// Reads the masm file at the specified path and parses it into a [ProgramAst].
-    #[instrument(name = "read_program_file", fields(path = %path.display()))]
-    pub fn read(path: &PathBuf) -> Result<Self, String> {
+    ///
+    /// References to constants exported from the modules of the standard library and of the
+    /// provided libraries, as well as references to the provided defines, are resolved while
+    /// parsing.
+    #[instrument(name = "read_program_file", skip(libraries, defines), fields(path = %path.display()))]
+    pub fn read<'a, I, L>(
+        path: &PathBuf,
+        libraries: I,
+        defines: &[(String, u64)],
+    ) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = &'a L>,
+        L: Library + 'a,
+    {
         // read program file to string
         let source = fs::read_to_string(path).map_err(|err| {
             format!("Failed to open program file `{}` - {}\n", path.display(), err)
         })?;
 
+        // collect the modules which may export constants referenced by the program together with
+        // the defines
+        let mut constants = Assembler::default()
+            .with_library(&StdLibrary::default())
+            .map_err(|err| format!("Failed to load stdlib - {}", err))?;
+        for library in libraries {
+            constants = constants
+                .with_library(library)
+                .map_err(|err| format!("Failed to load libraries `{}`", err))?;
+        }
+        for (name, value) in defines {
+            constants = constants
+                .with_define(name, *value)
+                .map_err(|err| format!("Failed to set defines - {}", err))?;
+        }
+
         // parse the program into an AST
-        let ast = ProgramAst::parse(&source).map_err(|err| {
+        let ast = ProgramAst::parse_with_constants(&source, &constants).map_err(|err| {
             format!("Failed to parse program file `{}` - {}\n", path.display(), err)
         })?;
 
@@ -409,21 +450,42 @@ impl ProgramFile {
         I: IntoIterator<Item = L>,
         L: Library,
     {
-        // compile program
-        let mut assembler = Assembler::default()
-            .with_debug_mode(debug.is_on())
-            .with_library(&StdLibrary::default())
-            .map_err(|err| format!("Failed to load stdlib - {}", err))?;
-
-        assembler = assembler
-            .with_libraries(libraries.into_iter())
-            .map_err(|err| format!("Failed to load libraries `{}`", err))?;
+        self.assembler(debug, libraries)?
+            .compile_ast(&self.ast)
+            .map_err(|err| format!("Failed to compile program - {}", err))
+    }
 
+    /// Compiles this program file into a [Program], and returns it together with the table of
+    /// the named errors which can be raised by the program.
+    pub fn compile_with_errors<I, L>(
+        &self,
+        debug: &Debug,
+        libraries: I,
+    ) -> Result<(Program, ErrorTable), String>
+    where
+        I: IntoIterator<Item = L>,
+        L: Library,
+    {
+        let assembler = self.assembler(debug, libraries)?;
         let program = assembler
             .compile_ast(&self.ast)
             .map_err(|err| format!("Failed to compile program - {}", err))?;
+        Ok((program, assembler.error_table(&self.ast)))
+    }
 
-        Ok(program)
+    /// Returns an assembler which compiles programs against the standard library and the
+    /// provided libraries.
+    fn assembler<I, L>(&self, debug: &Debug, libraries: I) -> Result<Assembler, String>
+    where
+        I: IntoIterator<Item = L>,
+        L: Library,
+    {
+        Assembler::default()
+            .with_debug_mode(debug.is_on())
+            .with_library(&StdLibrary::default())
+            .map_err(|err| format!("Failed to load stdlib - {}", err))?
+            .with_libraries(libraries.into_iter())
+            .map_err(|err| format!("Failed to load libraries `{}`", err))
     }
 
     /// Writes this file into the specified path, if one is provided. If the path is not provided,
@@ -441,6 +503,78 @@ impl ProgramFile {
     }
 }
 
+// MAST FILE
+// ================================================================================================
+
+pub struct MastFile;
+
+/// Helper methods to interact with files containing serialized MAST of compiled programs.
+impl MastFile {
+    /// File extension for serialized programs.
+    pub const EXTENSION: &'static str = "masb";
+
+    /// Returns true if the specified path points to a file with compiled program extension.
+    pub fn is_mast_file(path: &Path) -> bool {
+        path.extension().is_some_and(|ext| ext == Self::EXTENSION)
+    }
+
+    /// Reads a compiled [Program] from the file at the specified path.
+    #[instrument(name = "read_mast_file", fields(path = %path.display()))]
+    pub fn read(path: &Path) -> Result<Program, String> {
+        // read the file to bytes
+        let bytes = fs::read(path).map_err(|err| {
+            format!("Failed to open compiled program file `{}` - {}", path.display(), err)
+        })?;
+
+        // deserialize bytes into a program
+        Program::read_from_bytes(&bytes).map_err(|err| {
+            format!("Failed to decode compiled program file `{}` - {}", path.display(), err)
+        })
+    }
+
+    /// Writes the serialized MAST of the specified program into the file at the specified path.
+    #[instrument(name = "write_mast_file", fields(path = %path.display()), skip_all)]
+    pub fn write(program: &Program, path: &Path) -> Result<(), String> {
+        fs::write(path, program.to_bytes()).map_err(|err| {
+            format!("Failed to write compiled program file `{}` - {}", path.display(), err)
+        })
+    }
+}
+
+/// Loads the program at the specified path together with the table of named errors which may be
+/// raised by the program.
+///
+/// If the path points to a `.masb` file, the program is deserialized from its MAST; otherwise,
+/// the file is parsed as Miden assembly source and compiled against the provided libraries and
+/// defines.
+///
+/// If the program is compiled from source, the returned table contains the errors declared in the
+/// program and in all modules it (transitively) imports; the assembler makes sure that codes of
+/// these errors are unique. Otherwise, the table contains the errors declared in the standard
+/// library and in the provided libraries, and codes shared by several errors of these libraries
+/// are not associated with any message.
+pub fn load_program<I, L>(
+    path: &PathBuf,
+    debug: &Debug,
+    libraries: I,
+    defines: &[(String, u64)],
+) -> Result<(Program, ErrorTable), String>
+where
+    I: IntoIterator<Item = L>,
+    L: Library,
+{
+    let libraries: Vec<L> = libraries.into_iter().collect();
+    if MastFile::is_mast_file(path) {
+        let mut errors = StdLibrary::default().error_table();
+        for library in libraries.iter() {
+            errors.extend(&library.error_table());
+        }
+        Ok((MastFile::read(path)?, errors))
+    } else {
+        ProgramFile::read(path, &libraries, defines)?.compile_with_errors(debug, libraries)
+    }
+}
+
 // PROOF FILE
 // ================================================================================================
 
@@ -1,8 +1,10 @@
 /// debug commands supported by the debugger
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum DebugCommand {
     Continue,
     Next(usize),
+    StepOver,
+    Finish,
     Rewind,
     Back(usize),
     PrintState,
@@ -11,10 +13,43 @@ pub enum DebugCommand {
     PrintMem,
     PrintMemAddress(u64),
     Clock,
+    Backtrace,
+    Break(Breakpoint),
+    Watch(Watchpoint),
     Quit,
     Help,
 }
 
+/// breakpoints supported by the debugger
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Breakpoint {
+    /// breaks when the procedure with the specified name is entered
+    Procedure(String),
+    /// breaks when an instruction at the specified line of the specified file starts executing
+    Line { file: String, line: u32 },
+}
+
+/// watchpoints supported by the debugger
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Watchpoint {
+    /// breaks when the memory address in the specified context is accessed in the specified way
+    Memory {
+        ctx: u32,
+        addr: u32,
+        access: WatchedAccess,
+    },
+    /// breaks when the stack item at the specified index changes
+    StackItem(usize),
+}
+
+/// memory accesses which trigger a memory watchpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WatchedAccess {
+    Read,
+    Write,
+    Any,
+}
+
 impl DebugCommand {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
@@ -35,10 +70,17 @@ impl DebugCommand {
         let command = match identifier {
             "n" | "next" => Self::parse_next(tokens.by_ref())?,
             "c" | "continue" => Self::Continue,
+            "o" | "over" => Self::StepOver,
+            "f" | "finish" => Self::Finish,
             "b" | "back" => Self::parse_back(tokens.by_ref())?,
             "r" | "rewind" => Self::Rewind,
             "p" | "print" => Self::parse_print(tokens.by_ref())?,
             "l" | "clock" => Self::Clock,
+            "bt" | "backtrace" => Self::Backtrace,
+            "break" => Self::parse_break(tokens.by_ref())?,
+            "watch" => Self::parse_watch(tokens.by_ref(), WatchedAccess::Write)?,
+            "rwatch" => Self::parse_watch(tokens.by_ref(), WatchedAccess::Read)?,
+            "awatch" => Self::parse_watch(tokens.by_ref(), WatchedAccess::Any)?,
             "h" | "?" | "help" => Self::Help,
             "q" | "quit" => Self::Quit,
             _ => {
@@ -125,7 +167,143 @@ impl DebugCommand {
             (Self::PrintMem, Some(arg)) => Ok(Self::PrintMemAddress(arg)),
             (Self::PrintStack, Some(arg)) => Ok(Self::PrintStackItem(arg as usize)),
             (_, Some(_)) => unreachable!("the command was previously parsed within this block"),
-            (_, None) => Ok(command),
+            (command, None) => Ok(command),
         }
     }
+
+    /// parse break command - break <proc> | break <file>:<line>
+    fn parse_break<'a, I>(mut tokens: I) -> Result<Self, String>
+    where
+        I: Iterator<Item = &'a str>,
+    {
+        let target = tokens
+            .next()
+            .ok_or("malformed `break` command - expected a procedure name or `<file>:<line>`")?;
+
+        // procedure paths are separated by `::`, so only a trailing line number after a single
+        // `:` denotes a source line
+        let breakpoint = match target.rsplit_once(':') {
+            Some((file, line)) if !file.is_empty() && !file.ends_with(':') => {
+                let line = line.parse::<u32>().map_err(|err| {
+                    format!("malformed `break` command - failed to parse line: `{line}` {err}")
+                })?;
+                Breakpoint::Line {
+                    file: file.to_string(),
+                    line,
+                }
+            }
+            _ => Breakpoint::Procedure(target.to_string()),
+        };
+
+        Ok(Self::Break(breakpoint))
+    }
+
+    /// parse watch command - [r|a]watch mem <addr> [ctx] | watch stack <i>
+    fn parse_watch<'a, I>(mut tokens: I, access: WatchedAccess) -> Result<Self, String>
+    where
+        I: Iterator<Item = &'a str>,
+    {
+        let target = tokens
+            .next()
+            .ok_or("malformed `watch` command - expected `mem <addr> [ctx]` or `stack <i>`")?;
+
+        let watchpoint = match (target, access) {
+            ("m" | "mem", _) => {
+                let addr = match tokens.next() {
+                    Some(addr) => addr.parse::<u32>().map_err(|err| {
+                        format!(
+                            "malformed `watch` command - failed to parse address: `{addr}` {err}"
+                        )
+                    })?,
+                    None => return Err("malformed `watch` command - expected an address".into()),
+                };
+                // memory of the root context is watched by default
+                let ctx =
+                    tokens.next().map(|ctx| ctx.parse::<u32>()).transpose().map_err(|err| {
+                        format!("malformed `watch` command - failed to parse context: {err}")
+                    })?;
+                Watchpoint::Memory {
+                    ctx: ctx.unwrap_or_default(),
+                    addr,
+                    access,
+                }
+            }
+            ("s" | "stack", WatchedAccess::Write) => {
+                let index = match tokens.next() {
+                    Some(index) => index.parse::<usize>().map_err(|err| {
+                        format!(
+                            "malformed `watch` command - failed to parse index: `{index}` {err}"
+                        )
+                    })?,
+                    None => return Err("malformed `watch` command - expected a stack index".into()),
+                };
+                Watchpoint::StackItem(index)
+            }
+            ("s" | "stack", _) => {
+                return Err("malformed `watch` command - only `watch` supports stack items".into())
+            }
+            _ => {
+                return Err(format!(
+                    "malformed `watch` command - unexpected subcommand: `{target}`"
+                ))
+            }
+        };
+
+        Ok(Self::Watch(watchpoint))
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{Breakpoint, DebugCommand, WatchedAccess, Watchpoint};
+
+    #[test]
+    fn parse_break() {
+        let command = DebugCommand::parse("break foo").unwrap();
+        assert_eq!(command, Some(DebugCommand::Break(Breakpoint::Procedure("foo".to_string()))));
+
+        let command = DebugCommand::parse("break std::math::u64::checked_add").unwrap();
+        let expected = Breakpoint::Procedure("std::math::u64::checked_add".to_string());
+        assert_eq!(command, Some(DebugCommand::Break(expected)));
+
+        let command = DebugCommand::parse("break examples/fib.masm:12").unwrap();
+        let expected = Breakpoint::Line {
+            file: "examples/fib.masm".to_string(),
+            line: 12,
+        };
+        assert_eq!(command, Some(DebugCommand::Break(expected)));
+
+        assert!(DebugCommand::parse("break").is_err());
+        assert!(DebugCommand::parse("break fib.masm:x").is_err());
+    }
+
+    #[test]
+    fn parse_watch() {
+        let command = DebugCommand::parse("watch mem 5").unwrap();
+        let expected = Watchpoint::Memory {
+            ctx: 0,
+            addr: 5,
+            access: WatchedAccess::Write,
+        };
+        assert_eq!(command, Some(DebugCommand::Watch(expected)));
+
+        let command = DebugCommand::parse("awatch m 5 16").unwrap();
+        let expected = Watchpoint::Memory {
+            ctx: 16,
+            addr: 5,
+            access: WatchedAccess::Any,
+        };
+        assert_eq!(command, Some(DebugCommand::Watch(expected)));
+
+        let command = DebugCommand::parse("watch stack 3").unwrap();
+        assert_eq!(command, Some(DebugCommand::Watch(Watchpoint::StackItem(3))));
+
+        assert!(DebugCommand::parse("rwatch stack 3").is_err());
+        assert!(DebugCommand::parse("watch mem").is_err());
+        assert!(DebugCommand::parse("watch mem 5 x").is_err());
+        assert!(DebugCommand::parse("watch foo 5").is_err());
+    }
 }
@@ -1,28 +1,38 @@
-use super::DebugCommand;
+use super::{Breakpoint, DebugCommand, WatchedAccess, Watchpoint};
+use assembly::LibraryPath;
 use miden_vm::{
     math::Felt, DefaultHost, MemAdviceProvider, Program, StackInputs, VmState, VmStateIterator,
 };
+use processor::{CallFrame, CallStack, ContextId, Location, MemoryAccessType};
+use std::path::PathBuf;
 
 /// Holds debugger state and iterator used for debugging.
 pub struct DebugExecutor {
     vm_state_iter: VmStateIterator,
     vm_state: VmState,
+    call_stack: CallStack,
+    breakpoints: Vec<Breakpoint>,
+    watchpoints: Vec<Watchpoint>,
+    program_path: PathBuf,
 }
 
 impl DebugExecutor {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Returns a new DebugExecutor for the specified program, inputs and advice provider.
+    /// Returns a new DebugExecutor for the specified program, inputs and host.
+    ///
+    /// The program path is used to resolve line breakpoints and source locations of the
+    /// instructions of the program.
     ///
     /// # Errors
     /// Returns an error if the command cannot be parsed.
     pub fn new(
         program: Program,
         stack_inputs: StackInputs,
-        advice_provider: MemAdviceProvider,
+        host: DefaultHost<MemAdviceProvider>,
+        program_path: PathBuf,
     ) -> Result<Self, String> {
-        let mut vm_state_iter =
-            processor::execute_iter(&program, stack_inputs, DefaultHost::new(advice_provider));
+        let mut vm_state_iter = processor::execute_iter(&program, stack_inputs, host);
         let vm_state = vm_state_iter
             .next()
             .ok_or(format!(
@@ -33,6 +43,10 @@ impl DebugExecutor {
         Ok(Self {
             vm_state_iter,
             vm_state,
+            call_stack: CallStack::default(),
+            breakpoints: Vec::new(),
+            watchpoints: Vec::new(),
+            program_path,
         })
     }
 
@@ -42,33 +56,33 @@ impl DebugExecutor {
     /// executes a debug command against the vm in it's current state.
     pub fn execute(&mut self, command: DebugCommand) -> bool {
         match command {
-            DebugCommand::Continue => {
-                while let Some(new_vm_state) = self.next_vm_state() {
-                    self.vm_state = new_vm_state;
-                    if self.should_break() {
-                        break;
-                    }
-                }
-                self.print_vm_state();
-            }
+            DebugCommand::Continue => self.run_until(|_| false),
             DebugCommand::Next(cycles) => {
                 for _cycle in 0..cycles {
-                    match self.next_vm_state() {
-                        Some(next_vm_state) => {
-                            self.vm_state = next_vm_state;
-                            if self.should_break() {
-                                break;
-                            }
-                        }
-                        None => break,
+                    match self.step() {
+                        Some(true) | None => break,
+                        Some(false) => (),
                     }
                 }
                 self.print_vm_state();
             }
+            DebugCommand::StepOver => {
+                let depth = self.call_stack.frames().len();
+                self.run_until(|executor| {
+                    executor.is_instruction_start() && executor.call_stack.frames().len() <= depth
+                });
+            }
+            DebugCommand::Finish => {
+                let depth = self.call_stack.frames().len();
+                self.run_until(|executor| {
+                    executor.is_instruction_start() && executor.call_stack.frames().len() < depth
+                });
+            }
             DebugCommand::Rewind => {
                 while let Some(new_vm_state) = self.vm_state_iter.back() {
                     self.vm_state = new_vm_state;
                 }
+                self.call_stack = self.vm_state_iter.call_stack_at(self.vm_state.clk);
                 self.print_vm_state();
             }
             DebugCommand::Back(cycles) => {
@@ -83,6 +97,7 @@ impl DebugExecutor {
                         None => break,
                     }
                 }
+                self.call_stack = self.vm_state_iter.call_stack_at(self.vm_state.clk);
                 self.print_vm_state()
             }
             DebugCommand::PrintState => self.print_vm_state(),
@@ -91,12 +106,83 @@ impl DebugExecutor {
             DebugCommand::PrintMem => self.print_memory(),
             DebugCommand::PrintMemAddress(address) => self.print_memory_entry(address),
             DebugCommand::Clock => println!("{}", self.vm_state.clk),
+            DebugCommand::Backtrace => self.print_backtrace(),
+            DebugCommand::Break(breakpoint) => self.add_breakpoint(breakpoint),
+            DebugCommand::Watch(watchpoint) => self.add_watchpoint(watchpoint),
             DebugCommand::Help => Self::print_help(),
             DebugCommand::Quit => return false,
         }
         true
     }
 
+    /// registers a new breakpoint.
+    fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        match &breakpoint {
+            Breakpoint::Procedure(name) => {
+                println!("Breakpoint {} set at procedure `{name}`", self.breakpoints.len())
+            }
+            Breakpoint::Line { file, line } => {
+                println!("Breakpoint {} set at {file}:{line}", self.breakpoints.len())
+            }
+        }
+        self.breakpoints.push(breakpoint);
+    }
+
+    /// registers a new watchpoint.
+    fn add_watchpoint(&mut self, watchpoint: Watchpoint) {
+        match &watchpoint {
+            Watchpoint::Memory { ctx, addr, access } => {
+                let access = match access {
+                    WatchedAccess::Read => "reads of",
+                    WatchedAccess::Write => "writes to",
+                    WatchedAccess::Any => "accesses to",
+                };
+                println!(
+                    "Watchpoint {} set on {access} memory address {addr} in context {ctx}",
+                    self.watchpoints.len()
+                )
+            }
+            Watchpoint::StackItem(index) => {
+                println!("Watchpoint {} set on stack item [{index}]", self.watchpoints.len())
+            }
+        }
+        self.watchpoints.push(watchpoint);
+    }
+
+    /// executes the program until the specified condition holds, a breakpoint is hit, or the
+    /// execution cannot advance any further.
+    fn run_until<F>(&mut self, condition: F)
+    where
+        F: Fn(&Self) -> bool,
+    {
+        while let Some(hit) = self.step() {
+            if hit || condition(self) {
+                break;
+            }
+        }
+        self.print_vm_state();
+    }
+
+    /// moves to the next clock cycle and updates the call stack.
+    ///
+    /// Returns whether a breakpoint or a watchpoint was hit, or None if the execution cannot
+    /// advance any further.
+    fn step(&mut self) -> Option<bool> {
+        let vm_state = self.next_vm_state()?;
+        let mut entered_frame = false;
+        for call_site in self.vm_state_iter.call_sites_at(vm_state.clk) {
+            entered_frame |= self.call_stack.record_asmop(call_site);
+        }
+        if let Some(asmop) = vm_state.asmop.as_ref().filter(|asmop| asmop.cycle_idx() == 1) {
+            entered_frame |= self.call_stack.record_asmop(asmop.asmop());
+        }
+        let prev_vm_state = std::mem::replace(&mut self.vm_state, vm_state);
+
+        // all watchpoints are checked so that each of the hit ones is reported
+        let hit_watchpoint = self.hits_watchpoint(&prev_vm_state);
+        Some(self.hits_breakpoint(entered_frame) || hit_watchpoint)
+    }
+
     /// iterates to the next clock cycle.
     fn next_vm_state(&mut self) -> Option<VmState> {
         match self.vm_state_iter.next() {
@@ -122,6 +208,29 @@ impl DebugExecutor {
         println!("{}", self.vm_state)
     }
 
+    /// print the procedure call stack, starting with the innermost frame.
+    fn print_backtrace(&self) {
+        for line in self.backtrace() {
+            println!("{line}");
+        }
+    }
+
+    /// Returns the lines of the backtrace, starting with the innermost frame.
+    fn backtrace(&self) -> Vec<String> {
+        self.call_stack
+            .frames()
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(depth, frame)| match frame.location() {
+                Some(location) => {
+                    format!("{depth}: {} at {}", frame.name(), self.format_location(location))
+                }
+                None => format!("{depth}: {}", frame.name()),
+            })
+            .collect()
+    }
+
     /// print all stack items.
     pub fn print_stack(&self) {
         println!(
@@ -183,6 +292,8 @@ impl DebugExecutor {
             ---------------------------------------------------------------------\n\
             next               moves to the next clock cycle\n\
             next <c>           moves `c` clock cycles forward\n\
+            over               moves to the next instruction of the current procedure\n\
+            finish             executes program until the current procedure returns\n\
             continue           executes program until completion or failure\n\
             back               rewinds `1` clock cycles\n\
             back <c>           rewinds `c` clock cycles\n\
@@ -193,11 +304,20 @@ impl DebugExecutor {
             print stack        displays the complete state of the stack\n\
             print stack <i>    displays the stack element at index `i`\n\
             clock              displays the current clock cycle\n\
+            backtrace          displays the procedure call stack\n\
+            break <p>          breaks when procedure `p` (or `<module>::p`) is entered\n\
+            break <f>:<l>      breaks when an instruction at line `l` of file `f` is executed\n\
+            watch mem <a> [c]  breaks when memory address `a` of context `c` is written\n\
+            rwatch mem <a> [c] breaks when memory address `a` of context `c` is read\n\
+            awatch mem <a> [c] breaks when memory address `a` of context `c` is accessed\n\
+            watch stack <i>    breaks when the stack element at index `i` changes\n\
             quit               quits the debugger\n\
             help               displays this message\n\
             \n\
             The following mappings are also available:\n\
             n -> next\n\
+            o -> over\n\
+            f -> finish\n\
             c -> continue\n\
             b -> back\n\
             r -> rewind\n\
@@ -205,6 +325,7 @@ impl DebugExecutor {
             m -> mem\n\
             s -> stack\n\
             l -> clock\n\
+            bt -> backtrace\n\
             q -> quit\n\
             h -> help\n\
             ? -> help";
@@ -216,4 +337,287 @@ impl DebugExecutor {
     fn should_break(&self) -> bool {
         self.vm_state.asmop.as_ref().map(|asm| asm.should_break()).unwrap_or(false)
     }
+
+    /// Returns `true` if the current state is the first cycle of an assembly instruction.
+    fn is_instruction_start(&self) -> bool {
+        self.vm_state.asmop.as_ref().map(|asm| asm.cycle_idx() == 1).unwrap_or(false)
+    }
+
+    /// Returns `true` if the current state hits a breakpoint instruction or one of the registered
+    /// breakpoints.
+    fn hits_breakpoint(&self, entered_frame: bool) -> bool {
+        if self.should_break() {
+            return true;
+        }
+        if !self.is_instruction_start() {
+            return false;
+        }
+
+        let frame = self.call_stack.current_frame();
+        self.breakpoints.iter().any(|breakpoint| match breakpoint {
+            Breakpoint::Procedure(name) => entered_frame && Self::is_frame_of(frame, name),
+            Breakpoint::Line { file, line } => frame
+                .location()
+                .map(|location| location.line() == *line && self.is_location_in(location, file))
+                .unwrap_or(false),
+        })
+    }
+
+    /// Returns `true` if the transition from the previous state to the current state hits one of
+    /// the registered watchpoints, printing a message for each watchpoint hit.
+    fn hits_watchpoint(&self, prev_vm_state: &VmState) -> bool {
+        let mut hit = false;
+        for (idx, watchpoint) in self.watchpoints.iter().enumerate() {
+            match watchpoint {
+                Watchpoint::Memory { ctx, addr, access } => {
+                    let mem_access = self.vm_state_iter.get_mem_access_at(
+                        ContextId::from(*ctx),
+                        *addr,
+                        self.vm_state.clk,
+                    );
+                    let message = match (mem_access, access) {
+                        (
+                            Some(MemoryAccessType::Read),
+                            WatchedAccess::Read | WatchedAccess::Any,
+                        ) => "read",
+                        (
+                            Some(MemoryAccessType::Write),
+                            WatchedAccess::Write | WatchedAccess::Any,
+                        ) => "written",
+                        _ => continue,
+                    };
+                    println!("Watchpoint {idx}: memory address {addr} in context {ctx} {message}");
+                    hit = true;
+                }
+                Watchpoint::StackItem(index) => {
+                    let prev_value = prev_vm_state.stack.get(*index);
+                    let value = self.vm_state.stack.get(*index);
+                    if prev_value != value {
+                        let prev_value = prev_value.map_or("-".to_string(), |v| v.to_string());
+                        let value = value.map_or("-".to_string(), |v| v.to_string());
+                        println!("Watchpoint {idx}: stack item [{index}] {prev_value} -> {value}");
+                        hit = true;
+                    }
+                }
+            }
+        }
+        hit
+    }
+
+    /// Returns `true` if the frame executes the procedure with the specified name.
+    ///
+    /// A name qualified with a module path (e.g., `std::math::u64::add`) matches only the procedure
+    /// of this module, which requires the location of the frame to be known. An unqualified name
+    /// matches the procedures with this name in all modules.
+    fn is_frame_of(frame: &CallFrame, name: &str) -> bool {
+        match name.rsplit_once(LibraryPath::PATH_DELIM) {
+            Some((module_path, proc_name)) => {
+                frame.name() == proc_name
+                    && frame
+                        .location()
+                        .map(|location| location.path() == module_path)
+                        .unwrap_or(false)
+            }
+            None => frame.name() == name,
+        }
+    }
+
+    /// Returns `true` if the location is in the specified file, which is either a path to the
+    /// program file or the path of a library module.
+    fn is_location_in(&self, location: &Location, file: &str) -> bool {
+        if location.path() == LibraryPath::EXEC_PATH {
+            self.program_path.ends_with(file)
+        } else {
+            location.path() == file
+        }
+    }
+
+    /// Formats the location, replacing the path of the executable module with the program path.
+    fn format_location(&self, location: &Location) -> String {
+        if location.path() == LibraryPath::EXEC_PATH {
+            format!("{}:{}:{}", self.program_path.display(), location.line(), location.column())
+        } else {
+            location.to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Breakpoint, DebugCommand, DebugExecutor, WatchedAccess, Watchpoint};
+    use assembly::Assembler;
+    use miden_vm::{DefaultHost, StackInputs};
+    use std::path::PathBuf;
+
+    const SOURCE: &str = "\
+proc.bar
+    push.3
+    drop
+end
+
+proc.foo
+    push.1
+    exec.bar
+    push.2
+    drop drop
+end
+
+begin
+    exec.foo
+    push.4
+    drop
+end";
+
+    fn debug_executor() -> DebugExecutor {
+        let program = Assembler::default().with_debug_mode(true).compile(SOURCE).unwrap();
+        let path = PathBuf::from("test.masm");
+        DebugExecutor::new(program, StackInputs::default(), DefaultHost::default(), path).unwrap()
+    }
+
+    fn current_op(executor: &DebugExecutor) -> String {
+        executor.vm_state.asmop.as_ref().unwrap().op().to_string()
+    }
+
+    fn memory(executor: &DebugExecutor) -> Vec<(u64, u64)> {
+        executor
+            .vm_state
+            .memory
+            .iter()
+            .map(|(addr, word)| (*addr, word[0].as_int()))
+            .collect()
+    }
+
+    #[test]
+    fn break_on_procedure() {
+        // unqualified names match procedures of any module
+        let mut executor = debug_executor();
+        executor.execute(DebugCommand::Break(Breakpoint::Procedure("bar".to_string())));
+        executor.execute(DebugCommand::Continue);
+        assert_eq!(current_op(&executor), "push.3");
+        assert_eq!(
+            executor.backtrace(),
+            [
+                "0: bar at test.masm:2:5",
+                "1: foo at test.masm:8:5",
+                "2: #main at test.masm:14:5"
+            ]
+        );
+
+        // qualified names match only procedures of the specified module
+        let mut executor = debug_executor();
+        executor.execute(DebugCommand::Break(Breakpoint::Procedure("#exec::bar".to_string())));
+        executor.execute(DebugCommand::Continue);
+        assert_eq!(current_op(&executor), "push.3");
+
+        let mut executor = debug_executor();
+        let breakpoint = Breakpoint::Procedure("std::math::u64::bar".to_string());
+        executor.execute(DebugCommand::Break(breakpoint));
+        executor.execute(DebugCommand::Continue);
+        assert_eq!(executor.backtrace(), ["0: #main at test.masm:16:5"]);
+    }
+
+    #[test]
+    fn break_on_line() {
+        let mut executor = debug_executor();
+        let breakpoint = Breakpoint::Line {
+            file: "test.masm".to_string(),
+            line: 9,
+        };
+        executor.execute(DebugCommand::Break(breakpoint));
+        executor.execute(DebugCommand::Continue);
+        assert_eq!(current_op(&executor), "push.2");
+        assert_eq!(executor.backtrace(), ["0: foo at test.masm:9:5", "1: #main at test.masm:14:5"]);
+    }
+
+    #[test]
+    fn step_over() {
+        let mut executor = debug_executor();
+        executor.execute(DebugCommand::Break(Breakpoint::Procedure("foo".to_string())));
+        executor.execute(DebugCommand::Continue);
+        assert_eq!(current_op(&executor), "push.1");
+
+        // the instructions of `bar` are stepped over
+        executor.execute(DebugCommand::StepOver);
+        assert_eq!(current_op(&executor), "push.2");
+        assert_eq!(executor.backtrace(), ["0: foo at test.masm:9:5", "1: #main at test.masm:14:5"]);
+
+        // stepping over the last instruction of `foo` (`push.2` is followed by two `drop`s) moves
+        // to its caller
+        for _ in 0..3 {
+            executor.execute(DebugCommand::StepOver);
+        }
+        assert_eq!(current_op(&executor), "push.4");
+        assert_eq!(executor.backtrace(), ["0: #main at test.masm:15:5"]);
+    }
+
+    #[test]
+    fn finish() {
+        let mut executor = debug_executor();
+        executor.execute(DebugCommand::Break(Breakpoint::Procedure("bar".to_string())));
+        executor.execute(DebugCommand::Continue);
+
+        // finishing `bar` stops at the next instruction of `foo`
+        executor.execute(DebugCommand::Finish);
+        assert_eq!(current_op(&executor), "push.2");
+        assert_eq!(executor.backtrace(), ["0: foo at test.masm:9:5", "1: #main at test.masm:14:5"]);
+
+        // finishing `foo` stops at the next instruction of the program entrypoint
+        executor.execute(DebugCommand::Finish);
+        assert_eq!(current_op(&executor), "push.4");
+        assert_eq!(executor.backtrace(), ["0: #main at test.masm:15:5"]);
+    }
+
+    #[test]
+    fn watch_memory_write() {
+        let source = "\
+begin
+    push.7 mem_store.5
+    push.8 mem_store.6
+    mem_load.5 push.9 mem_store.5
+    drop
+end";
+        let program = Assembler::default().with_debug_mode(true).compile(source).unwrap();
+        let path = PathBuf::from("test.masm");
+        let mut executor =
+            DebugExecutor::new(program, StackInputs::default(), DefaultHost::default(), path)
+                .unwrap();
+
+        let watchpoint = Watchpoint::Memory {
+            ctx: 0,
+            addr: 5,
+            access: WatchedAccess::Write,
+        };
+        executor.execute(DebugCommand::Watch(watchpoint));
+
+        // the execution stops at the instruction writing to the watched address once the value
+        // is written
+        executor.execute(DebugCommand::Continue);
+        assert_eq!(current_op(&executor), "mem_store.5");
+        assert_eq!(executor.backtrace(), ["0: #main at test.masm:2:12"]);
+        assert_eq!(memory(&executor), [(5, 7)]);
+
+        // writes to other addresses and reads of the watched address are ignored
+        executor.execute(DebugCommand::Continue);
+        assert_eq!(current_op(&executor), "mem_store.5");
+        assert_eq!(executor.backtrace(), ["0: #main at test.masm:4:23"]);
+        assert_eq!(memory(&executor), [(5, 9), (6, 8)]);
+    }
+
+    #[test]
+    fn error_messages() {
+        let source = "\
+begin
+    push.0
+    assert.err=7
+end";
+        let program = Assembler::default().with_debug_mode(true).compile(source).unwrap();
+        let host = DefaultHost::default().with_error_messages([(7, "balance too low".to_string())]);
+        let path = PathBuf::from("test.masm");
+        let mut executor = DebugExecutor::new(program, StackInputs::default(), host, path).unwrap();
+
+        // messages attached to the host are reported when the execution fails
+        executor.execute(DebugCommand::Continue);
+        let diagnostic = executor.vm_state_iter.error_diagnostic().unwrap();
+        assert!(diagnostic.error().to_string().ends_with("error code 7: balance too low"));
+    }
 }
@@ -1,10 +1,11 @@
-use super::data::{Debug, InputFile, Libraries, ProgramFile};
+use super::data::{load_program, parse_define, Debug, InputFile, Libraries};
 use clap::Parser;
+use miden_vm::DefaultHost;
 use rustyline::{error::ReadlineError, Config, DefaultEditor, EditMode};
 use std::path::PathBuf;
 
 mod command;
-use command::DebugCommand;
+use command::{Breakpoint, DebugCommand, WatchedAccess, Watchpoint};
 
 mod executor;
 use executor::DebugExecutor;
@@ -24,6 +25,10 @@ pub struct DebugCmd {
     /// Paths to .masl library files
     #[clap(short = 'l', long = "libraries", value_parser)]
     library_paths: Vec<PathBuf>,
+    /// Defines available to the program as constants, specified as `NAME` or `NAME=VALUE`; the
+    /// value of a define specified without a value is 1
+    #[clap(short = 'D', long = "define", value_parser = parse_define)]
+    defines: Vec<(String, u64)>,
 }
 
 impl DebugCmd {
@@ -36,8 +41,8 @@ impl DebugCmd {
         let libraries = Libraries::new(&self.library_paths)?;
 
         // load program from file and compile
-        let program =
-            ProgramFile::read(&self.assembly_file)?.compile(&Debug::On, libraries.libraries)?;
+        let (program, errors) =
+            load_program(&self.assembly_file, &Debug::On, libraries.libraries, &self.defines)?;
 
         let program_hash: [u8; 32] = program.hash().into();
         println!("Debugging program with hash {}...", hex::encode(program_hash));
@@ -49,8 +54,11 @@ impl DebugCmd {
         let stack_inputs = input_data.parse_stack_inputs()?;
         let advice_provider = input_data.parse_advice_provider()?;
 
+        let host = DefaultHost::new(advice_provider).with_error_messages(errors.messages());
+
         // Instantiate DebugExecutor
-        let mut debug_executor = DebugExecutor::new(program, stack_inputs, advice_provider)?;
+        let mut debug_executor =
+            DebugExecutor::new(program, stack_inputs, host, self.assembly_file.clone())?;
 
         // build readline config
         let mut rl_config = Config::builder().auto_add_history(true);
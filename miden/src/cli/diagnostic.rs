@@ -0,0 +1,160 @@
+use super::data::{load_program, Debug, InputFile, Libraries, MastFile};
+use assembly::LibraryPath;
+use processor::{DefaultHost, ErrorDiagnostic, ExecutionError, Location};
+use std::{
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+// EXECUTION ERROR REPORT
+// ================================================================================================
+
+/// Returns a report describing where in the source code of the program the execution failed.
+///
+/// Since programs are executed by the CLI without debug information, the program is re-compiled
+/// in debug mode and re-executed against the same inputs to build the report. If this is not
+/// possible, the report contains only the error itself.
+pub fn report_execution_error(
+    error: &ExecutionError,
+    program_path: &PathBuf,
+    library_paths: &[PathBuf],
+    defines: &[(String, u64)],
+    input_data: &InputFile,
+) -> String {
+    let diagnostic = replay_execution(program_path, library_paths, defines, input_data);
+    match diagnostic {
+        Some(diagnostic) if diagnostic.error() == error => {
+            // source code is available only for programs compiled from .masm files
+            let source = if MastFile::is_mast_file(program_path) {
+                None
+            } else {
+                fs::read_to_string(program_path).ok()
+            };
+            render_error_diagnostic(&diagnostic, program_path, source.as_deref())
+        }
+        _ => format!("{error}"),
+    }
+}
+
+/// Renders the diagnostic of an execution failure, including the snippet of the failing
+/// instruction if the source code of the program is provided.
+pub fn render_error_diagnostic(
+    diagnostic: &ErrorDiagnostic,
+    program_path: &Path,
+    source: Option<&str>,
+) -> String {
+    let mut report = format!("error: {}", diagnostic.error());
+
+    if let Some(location) = diagnostic.location() {
+        let _ = write!(report, "\n --> {}", format_location(location, program_path));
+
+        let line = source
+            .filter(|_| location.path() == LibraryPath::EXEC_PATH)
+            .and_then(|source| source.lines().nth(location.line() as usize - 1));
+        if let Some(line) = line {
+            // underline the instruction, i.e. all characters up to the next whitespace
+            let start = location.column() as usize - 1;
+            let len = line.chars().skip(start).take_while(|c| !c.is_whitespace()).count();
+            let line_num = location.line().to_string();
+            let margin = " ".repeat(line_num.len());
+            let _ = write!(
+                report,
+                "\n{margin} |\n{line_num} | {line}\n{margin} | {}{}",
+                " ".repeat(start),
+                "^".repeat(len.max(1))
+            );
+        }
+    }
+
+    report.push_str("\ncall stack:");
+    for (depth, frame) in diagnostic.call_stack().frames().iter().rev().enumerate() {
+        let _ = write!(report, "\n  {depth}: {}", frame.name());
+        if let Some(location) = frame.location() {
+            let _ = write!(report, " at {}", format_location(location, program_path));
+        }
+    }
+
+    report
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Executes the program in debug mode and returns the diagnostic of its failure, if any.
+fn replay_execution(
+    program_path: &PathBuf,
+    library_paths: &[PathBuf],
+    defines: &[(String, u64)],
+    input_data: &InputFile,
+) -> Option<ErrorDiagnostic> {
+    let libraries = Libraries::new(library_paths).ok()?;
+    let (program, errors) =
+        load_program(program_path, &Debug::On, libraries.libraries, defines).ok()?;
+    let stack_inputs = input_data.parse_stack_inputs().ok()?;
+    let host = DefaultHost::new(input_data.parse_advice_provider().ok()?)
+        .with_error_messages(errors.messages());
+
+    let vm_state_iterator = processor::execute_iter(&program, stack_inputs, host);
+    vm_state_iterator.error_diagnostic().cloned()
+}
+
+/// Formats the location, replacing the path of the executable module with the program path.
+fn format_location(location: &Location, program_path: &Path) -> String {
+    if location.path() == LibraryPath::EXEC_PATH {
+        format!("{}:{}:{}", program_path.display(), location.line(), location.column())
+    } else {
+        location.to_string()
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::render_error_diagnostic;
+    use assembly::Assembler;
+    use processor::{DefaultHost, StackInputs};
+    use std::path::PathBuf;
+
+    #[test]
+    fn render_failed_assertion() {
+        let source = "\
+proc.foo
+    push.1
+    assertz
+end
+
+begin
+    push.2 drop
+    exec.foo
+end
+";
+        let program = Assembler::default().with_debug_mode(true).compile(source).unwrap();
+        let vm_state_iterator =
+            processor::execute_iter(&program, StackInputs::default(), DefaultHost::default());
+        let diagnostic = vm_state_iterator.error_diagnostic().unwrap();
+
+        let path = PathBuf::from("test.masm");
+        let report = render_error_diagnostic(diagnostic, &path, Some(source));
+        let expected = "\
+error: Assertion failed at clock cycle 6 with error code 0
+ --> test.masm:3:5
+  |
+3 |     assertz
+  |     ^^^^^^^
+call stack:
+  0: foo at test.masm:3:5
+  1: #main at test.masm:8:5";
+        assert_eq!(report, expected);
+
+        // without source code, the snippet is omitted
+        let report = render_error_diagnostic(diagnostic, &path, None);
+        let expected = "\
+error: Assertion failed at clock cycle 6 with error code 0
+ --> test.masm:3:5
+call stack:";
+        assert!(report.starts_with(expected));
+    }
+}
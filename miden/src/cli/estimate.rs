@@ -0,0 +1,265 @@
+use super::{
+    data::{instrument, load_program, parse_define, Debug, InputFile, Libraries},
+    diagnostic::report_execution_error,
+};
+use air::trace::{AUX_TRACE_WIDTH, TRACE_WIDTH};
+use clap::Parser;
+use miden_vm::{HashFunction, ProvingOptions};
+use processor::{DefaultHost, ExecutionOptions, TraceLenSummary};
+use std::{path::PathBuf, time::Instant};
+
+#[derive(Debug, Clone, Parser)]
+#[clap(
+    about = "Estimate the execution trace size and proving cost of a miden program without building the trace"
+)]
+pub struct EstimateCmd {
+    /// Path to .masm assembly file or .masb compiled program file
+    #[clap(short = 'a', long = "assembly", value_parser)]
+    assembly_file: PathBuf,
+
+    /// Path to input file
+    #[clap(short = 'i', long = "input", value_parser)]
+    input_file: Option<PathBuf>,
+
+    /// Paths to .masl library files
+    #[clap(short = 'l', long = "libraries", value_parser)]
+    library_paths: Vec<PathBuf>,
+
+    /// Defines available to the program as constants, specified as `NAME` or `NAME=VALUE`; the
+    /// value of a define specified without a value is 1
+    #[clap(short = 'D', long = "define", value_parser = parse_define)]
+    defines: Vec<(String, u64)>,
+
+    /// Maximum number of cycles a program is allowed to consume
+    #[clap(short = 'm', long = "max-cycles", default_value = "4294967295")]
+    max_cycles: u32,
+
+    /// Estimate the cost of proofs suitable for recursive verification
+    #[clap(short = 'r', long = "recursive")]
+    recursive: bool,
+
+    /// Security level of the execution proof to estimate the cost of
+    #[clap(short = 's', long = "security", default_value = "96bits")]
+    security: String,
+}
+
+impl EstimateCmd {
+    pub fn get_proof_options(&self) -> ProvingOptions {
+        match self.security.as_str() {
+            "96bits" => ProvingOptions::with_96_bit_security(self.recursive),
+            "128bits" => ProvingOptions::with_128_bit_security(self.recursive),
+            other => panic!("{} is not a valid security setting", other),
+        }
+    }
+
+    pub fn execute(&self) -> Result<(), String> {
+        println!("===============================================================================");
+        println!("Estimate trace size: {}", self.assembly_file.display());
+        println!("-------------------------------------------------------------------------------");
+
+        let now = Instant::now();
+
+        let (summary, program_hash) = estimate_program(self)?;
+
+        println!(
+            "Executed the program with hash {} in {} ms",
+            hex::encode(program_hash),
+            now.elapsed().as_millis()
+        );
+
+        let chiplets = summary.chiplets_trace_len();
+        println!(
+            "Trace length: {} padded to {} rows ({}% padding).
+├── Stack and decoder rows: {}
+├── Range checker rows: {}
+└── Chiplets rows: {}
+    ├── Hash chiplet rows: {}
+    ├── Bitwise chiplet rows: {}
+    ├── Memory chiplet rows: {}
+    └── Kernel ROM rows: {}",
+            summary.trace_len(),
+            summary.padded_trace_len(),
+            summary.padding_percentage(),
+            summary.main_trace_len(),
+            summary.range_trace_len(),
+            chiplets.trace_len(),
+            chiplets.hash_chiplet_len(),
+            chiplets.bitwise_chiplet_len(),
+            chiplets.memory_chiplet_len(),
+            chiplets.kernel_rom_len(),
+        );
+        println!("Dominant segment: {}", dominant_segment(&summary));
+
+        let proving_options = self.get_proof_options();
+        let cost = ProvingCost::new(summary.padded_trace_len(), &proving_options);
+        println!(
+            "Estimated proving cost ({} security, {:?}):
+├── Low-degree extension: {} rows x {} columns
+├── Time: ~{} ms on a single thread
+└── Memory: ~{} MB",
+            self.security,
+            proving_options.hash_fn(),
+            cost.lde_len,
+            cost.lde_width,
+            cost.time_ms(),
+            cost.memory_mb(),
+        );
+        println!("The proving cost is a coarse estimate; actual figures depend on the machine.");
+
+        Ok(())
+    }
+}
+
+// PROVING COST
+// ================================================================================================
+
+/// Approximate number of bytes held in memory per cell of the low-degree extension of the trace.
+///
+/// Besides the extended trace itself, this accounts for the constraint composition polynomial and
+/// the Merkle trees committing to the extended trace.
+const BYTES_PER_LDE_CELL: usize = 16;
+
+/// Approximate single-threaded proving time per cell of the low-degree extension of the trace,
+/// in nanoseconds, when the proof is committed to with BLAKE3.
+const BLAKE3_NS_PER_LDE_CELL: usize = 330;
+
+/// Approximate single-threaded proving time per cell of the low-degree extension of the trace,
+/// in nanoseconds, when the proof is committed to with RPO.
+const RPO_NS_PER_LDE_CELL: usize = 3000;
+
+/// A coarse estimate of the time and memory required to prove a program.
+///
+/// Both scale linearly with the size of the low-degree extension of the trace: the padded trace
+/// length multiplied by the blowup factor, times the number of main trace columns plus the
+/// number of auxiliary trace columns in the extension field. The per-cell constants were measured
+/// on the prover without the `concurrent` feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ProvingCost {
+    lde_len: usize,
+    lde_width: usize,
+    ns_per_cell: usize,
+}
+
+impl ProvingCost {
+    fn new(padded_trace_len: usize, options: &ProvingOptions) -> Self {
+        let extension_degree = options.field_extension().degree() as usize;
+        let ns_per_cell = match options.hash_fn() {
+            HashFunction::Blake3_192 | HashFunction::Blake3_256 => BLAKE3_NS_PER_LDE_CELL,
+            HashFunction::Rpo256 => RPO_NS_PER_LDE_CELL,
+        };
+        Self {
+            lde_len: padded_trace_len * options.blowup_factor(),
+            lde_width: TRACE_WIDTH + AUX_TRACE_WIDTH * extension_degree,
+            ns_per_cell,
+        }
+    }
+
+    fn num_cells(&self) -> u64 {
+        (self.lde_len * self.lde_width) as u64
+    }
+
+    fn time_ms(&self) -> u64 {
+        self.num_cells() * self.ns_per_cell as u64 / 1_000_000
+    }
+
+    fn memory_mb(&self) -> u64 {
+        self.num_cells() * BYTES_PER_LDE_CELL as u64 / (1024 * 1024)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+#[instrument(name = "estimate_program", skip_all)]
+fn estimate_program(params: &EstimateCmd) -> Result<(TraceLenSummary, [u8; 32]), String> {
+    // load libraries from files
+    let libraries = Libraries::new(&params.library_paths)?;
+
+    // load program from file and compile it if needed
+    let (program, errors) =
+        load_program(&params.assembly_file, &Debug::Off, libraries.libraries, &params.defines)?;
+
+    // load input data from file
+    let input_data = InputFile::read(&params.input_file, &params.assembly_file)?;
+
+    // get execution options
+    let execution_options = ExecutionOptions::new(Some(params.max_cycles), 64, false)
+        .map_err(|err| format!("{err}"))?;
+
+    // fetch the stack and program inputs from the arguments
+    let stack_inputs = input_data.parse_stack_inputs()?;
+    let host = DefaultHost::new(input_data.parse_advice_provider()?)
+        .with_error_messages(errors.messages());
+
+    let program_hash: [u8; 32] = program.hash().into();
+
+    // execute the program without building the execution trace
+    let summary = processor::estimate_trace_len(&program, stack_inputs, host, execution_options)
+        .map_err(|err| {
+            let report = report_execution_error(
+                &err,
+                &params.assembly_file,
+                &params.library_paths,
+                &params.defines,
+                &input_data,
+            );
+            format!("Failed to estimate execution trace = {report}")
+        })?;
+
+    Ok((summary, program_hash))
+}
+
+/// Returns the name of the trace segment which determines the length of the trace.
+fn dominant_segment(summary: &TraceLenSummary) -> String {
+    if summary.trace_len() == summary.main_trace_len() {
+        return "stack and decoder".to_string();
+    }
+    if summary.trace_len() == summary.range_trace_len() {
+        return "range checker".to_string();
+    }
+
+    let chiplets = summary.chiplets_trace_len();
+    let (largest, _) = [
+        ("hash chiplet", chiplets.hash_chiplet_len()),
+        ("bitwise chiplet", chiplets.bitwise_chiplet_len()),
+        ("memory chiplet", chiplets.memory_chiplet_len()),
+        ("kernel ROM", chiplets.kernel_rom_len()),
+    ]
+    .into_iter()
+    .max_by_key(|(_, len)| *len)
+    .expect("there is always at least one chiplet");
+    format!("chiplets (largest: {largest})")
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::ProvingCost;
+    use miden_vm::ProvingOptions;
+
+    #[test]
+    fn proving_cost() {
+        // 70 main columns and 7 auxiliary columns in the quadratic extension field, extended by 8
+        let cost = ProvingCost::new(1 << 17, &ProvingOptions::with_96_bit_security(false));
+        assert_eq!(cost.lde_len, 1 << 20);
+        assert_eq!(cost.lde_width, 84);
+        assert_eq!(cost.memory_mb(), 1344);
+        assert_eq!(cost.time_ms(), 29_066);
+
+        // the cost doubles with the padded trace length
+        let half = ProvingCost::new(1 << 16, &ProvingOptions::with_96_bit_security(false));
+        assert_eq!(half.memory_mb() * 2, cost.memory_mb());
+
+        // recursive proofs use the same trace but are much slower to commit to
+        let recursive = ProvingCost::new(1 << 17, &ProvingOptions::with_96_bit_security(true));
+        assert_eq!(recursive.memory_mb(), cost.memory_mb());
+        assert!(recursive.time_ms() > 5 * cost.time_ms());
+
+        // 128-bit security doubles the blowup factor and uses the cubic extension field
+        let secure = ProvingCost::new(1 << 17, &ProvingOptions::with_128_bit_security(false));
+        assert_eq!(secure.lde_len, 1 << 21);
+        assert_eq!(secure.lde_width, 91);
+    }
+}
@@ -0,0 +1,88 @@
+use assembly::format_source;
+use clap::Parser;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Format miden assembly source files")]
+pub struct FmtCmd {
+    /// Paths to .masm files or to directories which are searched for .masm files recursively
+    #[clap(required = true, value_parser)]
+    paths: Vec<PathBuf>,
+
+    /// Check whether the files are formatted without modifying them; fails if any file would be
+    /// changed by formatting
+    #[clap(long = "check")]
+    check: bool,
+}
+
+impl FmtCmd {
+    pub fn execute(&self) -> Result<(), String> {
+        let mut files = Vec::new();
+        for path in self.paths.iter() {
+            collect_masm_files(path, &mut files)?;
+        }
+
+        let mut unformatted = Vec::new();
+        for file in files.iter() {
+            let source = fs::read_to_string(file)
+                .map_err(|err| format!("Failed to read file {} - {err}", file.display()))?;
+            let formatted = format_source(&source)
+                .map_err(|err| format!("Failed to format file {} - {err}", file.display()))?;
+            if formatted == source {
+                continue;
+            }
+
+            if self.check {
+                println!("Not formatted: {}", file.display());
+            } else {
+                fs::write(file, formatted)
+                    .map_err(|err| format!("Failed to write file {} - {err}", file.display()))?;
+                println!("Formatted: {}", file.display());
+            }
+            unformatted.push(file);
+        }
+
+        if self.check && !unformatted.is_empty() {
+            return Err(format!(
+                "{} of {} files are not formatted; run `miden fmt` to format them",
+                unformatted.len(),
+                files.len()
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Adds the specified file, or all .masm files in the specified directory and its subdirectories,
+/// to the provided list of files.
+pub(super) fn collect_masm_files(path: &Path, files: &mut Vec<PathBuf>) -> Result<(), String> {
+    if !path.is_dir() {
+        if !path.exists() {
+            return Err(format!("Path {} does not exist", path.display()));
+        }
+        files.push(path.to_path_buf());
+        return Ok(());
+    }
+
+    let entries = fs::read_dir(path)
+        .map_err(|err| format!("Failed to read directory {} - {err}", path.display()))?;
+    let mut paths = entries
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| format!("Failed to read directory {} - {err}", path.display()))?;
+    paths.sort();
+
+    for path in paths {
+        if path.is_dir() || path.extension().is_some_and(|ext| ext == "masm") {
+            collect_masm_files(&path, files)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use super::{
+    data::{parse_define, Libraries},
+    fmt::collect_masm_files,
+};
+use assembly::{LintConfig, LintLevel, LintRule, Linter};
+use clap::Parser;
+use serde_derive::Deserialize;
+use std::{fs, path::PathBuf};
+use stdlib::StdLibrary;
+
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Check miden assembly source files for common issues")]
+pub struct LintCmd {
+    /// Paths to .masm files or to directories which are searched for .masm files recursively
+    #[clap(required = true, value_parser)]
+    paths: Vec<PathBuf>,
+
+    /// Paths to .masl library files used to check invocations of imported procedures
+    #[clap(short = 'l', long = "libraries", value_parser)]
+    library_paths: Vec<PathBuf>,
+
+    /// Defines available to the sources as constants, specified as `NAME` or `NAME=VALUE`; the
+    /// value of a define specified without a value is 1
+    #[clap(short = 'D', long = "define", value_parser = parse_define)]
+    defines: Vec<(String, u64)>,
+
+    /// Path to a JSON config file with lists of `allow`, `warn`, and `deny` rules
+    #[clap(short = 'c', long = "config", value_parser)]
+    config_file: Option<PathBuf>,
+
+    /// Rules which are not checked; overrides the config file
+    #[clap(short = 'A', long = "allow")]
+    allow: Vec<String>,
+
+    /// Rules which are reported as errors; overrides the config file
+    #[clap(long = "deny")]
+    deny: Vec<String>,
+}
+
+impl LintCmd {
+    pub fn execute(&self) -> Result<(), String> {
+        let mut linter = Linter::new(self.build_config()?)
+            .with_library(&StdLibrary::default())
+            .map_err(|err| format!("Failed to load the standard library - {err}"))?;
+        for library in Libraries::new(&self.library_paths)?.libraries {
+            linter = linter
+                .with_library(&library)
+                .map_err(|err| format!("Failed to load library - {err}"))?;
+        }
+        for (name, value) in self.defines.iter() {
+            linter = linter
+                .with_define(name, *value)
+                .map_err(|err| format!("Failed to set defines - {err}"))?;
+        }
+
+        let mut files = Vec::new();
+        for path in self.paths.iter() {
+            collect_masm_files(path, &mut files)?;
+        }
+
+        let (mut num_warnings, mut num_errors) = (0, 0);
+        for file in files.iter() {
+            let source = fs::read_to_string(file)
+                .map_err(|err| format!("Failed to read file {} - {err}", file.display()))?;
+            let lints = linter
+                .lint_source(&source)
+                .map_err(|err| format!("Failed to parse file {} - {err}", file.display()))?;
+            for lint in lints {
+                println!("{}: {lint}", file.display());
+                if lint.is_error() {
+                    num_errors += 1;
+                } else {
+                    num_warnings += 1;
+                }
+            }
+        }
+
+        println!("{num_warnings} warnings and {num_errors} errors in {} files", files.len());
+        if num_errors > 0 {
+            return Err(format!("{num_errors} lint errors found"));
+        }
+
+        Ok(())
+    }
+
+    /// Builds the lint config from the config file (if any) and from the command line options.
+    fn build_config(&self) -> Result<LintConfig, String> {
+        let file = match &self.config_file {
+            Some(path) => {
+                let contents = fs::read_to_string(path).map_err(|err| {
+                    format!("Failed to read config file {} - {err}", path.display())
+                })?;
+                serde_json::from_str(&contents).map_err(|err| {
+                    format!("Failed to parse config file {} - {err}", path.display())
+                })?
+            }
+            None => ConfigFile::default(),
+        };
+
+        let mut config = LintConfig::default();
+        let levels = [
+            (&file.allow, LintLevel::Allow),
+            (&file.warn, LintLevel::Warn),
+            (&file.deny, LintLevel::Deny),
+            (&self.allow, LintLevel::Allow),
+            (&self.deny, LintLevel::Deny),
+        ];
+        for (rules, level) in levels {
+            for name in rules {
+                let rule = LintRule::from_name(name).ok_or_else(|| {
+                    let names: Vec<_> = LintRule::ALL.iter().map(LintRule::as_str).collect();
+                    format!("Unknown lint rule '{name}'; expected one of: {}", names.join(", "))
+                })?;
+                config.set_level(rule, level);
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+// CONFIG FILE
+// ================================================================================================
+
+/// Lint config file; rules which are not listed are reported as warnings.
+#[derive(Deserialize, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    allow: Vec<String>,
+    warn: Vec<String>,
+    deny: Vec<String>,
+}
@@ -2,6 +2,10 @@ mod bundle;
 mod compile;
 pub mod data;
 mod debug;
+mod diagnostic;
+mod estimate;
+mod fmt;
+mod lint;
 mod prove;
 mod repl;
 mod run;
@@ -11,6 +15,9 @@ pub use bundle::BundleCmd;
 pub use compile::CompileCmd;
 pub use data::InputFile;
 pub use debug::DebugCmd;
+pub use estimate::EstimateCmd;
+pub use fmt::FmtCmd;
+pub use lint::LintCmd;
 pub use prove::ProveCmd;
 pub use repl::ReplCmd;
 pub use run::RunCmd;
@@ -1,4 +1,10 @@
-use super::data::{instrument, Debug, InputFile, Libraries, OutputFile, ProgramFile, ProofFile};
+use super::{
+    data::{
+        instrument, load_program, parse_define, Debug, InputFile, Libraries, OutputFile, ProofFile,
+    },
+    diagnostic::report_execution_error,
+};
+use assembly::ErrorTable;
 use clap::Parser;
 use miden_vm::ProvingOptions;
 use processor::{DefaultHost, ExecutionOptions, ExecutionOptionsError, Program};
@@ -8,7 +14,7 @@ use std::{path::PathBuf, time::Instant};
 #[derive(Debug, Clone, Parser)]
 #[clap(about = "Prove a miden program")]
 pub struct ProveCmd {
-    /// Path to .masm assembly file
+    /// Path to .masm assembly file or .masb compiled program file
     #[clap(short = 'a', long = "assembly", value_parser)]
     assembly_file: PathBuf,
 
@@ -24,6 +30,11 @@ pub struct ProveCmd {
     #[clap(short = 'l', long = "libraries", value_parser)]
     library_paths: Vec<PathBuf>,
 
+    /// Defines available to the program as constants, specified as `NAME` or `NAME=VALUE`; the
+    /// value of a define specified without a value is 1
+    #[clap(short = 'D', long = "define", value_parser = parse_define)]
+    defines: Vec<(String, u64)>,
+
     /// Maximum number of cycles a program is allowed to consume
     #[clap(short = 'm', long = "max-cycles", default_value = "4294967295")]
     max_cycles: u32,
@@ -70,7 +81,7 @@ impl ProveCmd {
         println!("Prove program: {}", self.assembly_file.display());
         println!("-------------------------------------------------------------------------------");
 
-        let (program, input_data) = load_data(self)?;
+        let (program, errors, input_data) = load_data(self)?;
 
         let program_hash: [u8; 32] = program.hash().into();
         println!("Proving program with hash {}...", hex::encode(program_hash));
@@ -78,14 +89,23 @@ impl ProveCmd {
 
         // fetch the stack and program inputs from the arguments
         let stack_inputs = input_data.parse_stack_inputs()?;
-        let host = DefaultHost::new(input_data.parse_advice_provider()?);
+        let host = DefaultHost::new(input_data.parse_advice_provider()?)
+            .with_error_messages(errors.messages());
 
         let proving_options = self.get_proof_options().map_err(|err| format!("{err}"))?;
 
         // execute program and generate proof
-        let (stack_outputs, proof) =
-            prover::prove(&program, stack_inputs, host, proving_options)
-                .map_err(|err| format!("Failed to prove program - {:?}", err))?;
+        let (stack_outputs, proof) = prover::prove(&program, stack_inputs, host, proving_options)
+            .map_err(|err| {
+            let report = report_execution_error(
+                &err,
+                &self.assembly_file,
+                &self.library_paths,
+                &self.defines,
+                &input_data,
+            );
+            format!("Failed to prove program - {report}")
+        })?;
 
         println!(
             "Program with hash {} proved in {} ms",
@@ -119,16 +139,16 @@ impl ProveCmd {
 // ================================================================================================
 
 #[instrument(skip_all)]
-fn load_data(params: &ProveCmd) -> Result<(Program, InputFile), String> {
+fn load_data(params: &ProveCmd) -> Result<(Program, ErrorTable, InputFile), String> {
     // load libraries from files
     let libraries = Libraries::new(&params.library_paths)?;
 
-    // load program from file and compile
-    let program =
-        ProgramFile::read(&params.assembly_file)?.compile(&Debug::Off, libraries.libraries)?;
+    // load program from file and compile it if needed
+    let (program, errors) =
+        load_program(&params.assembly_file, &Debug::Off, libraries.libraries, &params.defines)?;
 
     // load input data from file
     let input_data = InputFile::read(&params.input_file, &params.assembly_file)?;
 
-    Ok((program, input_data))
+    Ok((program, errors, input_data))
 }
@@ -1,4 +1,7 @@
-use super::data::{instrument, Debug, InputFile, Libraries, OutputFile, ProgramFile};
+use super::{
+    data::{instrument, load_program, parse_define, Debug, InputFile, Libraries, OutputFile},
+    diagnostic::report_execution_error,
+};
 use clap::Parser;
 use processor::{DefaultHost, ExecutionOptions, ExecutionTrace};
 use std::{path::PathBuf, time::Instant};
@@ -6,7 +9,7 @@ use std::{path::PathBuf, time::Instant};
 #[derive(Debug, Clone, Parser)]
 #[clap(about = "Run a miden program")]
 pub struct RunCmd {
-    /// Path to .masm assembly file
+    /// Path to .masm assembly file or .masb compiled program file
     #[clap(short = 'a', long = "assembly", value_parser)]
     assembly_file: PathBuf,
 
@@ -22,6 +25,11 @@ pub struct RunCmd {
     #[clap(short = 'l', long = "libraries", value_parser)]
     library_paths: Vec<PathBuf>,
 
+    /// Defines available to the program as constants, specified as `NAME` or `NAME=VALUE`; the
+    /// value of a define specified without a value is 1
+    #[clap(short = 'D', long = "define", value_parser = parse_define)]
+    defines: Vec<(String, u64)>,
+
     /// Maximum number of cycles a program is allowed to consume
     #[clap(short = 'm', long = "max-cycles", default_value = "4294967295")]
     max_cycles: u32,
@@ -102,9 +110,9 @@ fn run_program(params: &RunCmd) -> Result<(ExecutionTrace, [u8; 32]), String> {
     // load libraries from files
     let libraries = Libraries::new(&params.library_paths)?;
 
-    // load program from file and compile
-    let program =
-        ProgramFile::read(&params.assembly_file)?.compile(&Debug::Off, libraries.libraries)?;
+    // load program from file and compile it if needed
+    let (program, errors) =
+        load_program(&params.assembly_file, &Debug::Off, libraries.libraries, &params.defines)?;
 
     // load input data from file
     let input_data = InputFile::read(&params.input_file, &params.assembly_file)?;
@@ -116,13 +124,23 @@ fn run_program(params: &RunCmd) -> Result<(ExecutionTrace, [u8; 32]), String> {
 
     // fetch the stack and program inputs from the arguments
     let stack_inputs = input_data.parse_stack_inputs()?;
-    let host = DefaultHost::new(input_data.parse_advice_provider()?);
+    let host = DefaultHost::new(input_data.parse_advice_provider()?)
+        .with_error_messages(errors.messages());
 
     let program_hash: [u8; 32] = program.hash().into();
 
     // execute program and generate outputs
-    let trace = processor::execute(&program, stack_inputs, host, execution_options)
-        .map_err(|err| format!("Failed to generate execution trace = {:?}", err))?;
+    let trace =
+        processor::execute(&program, stack_inputs, host, execution_options).map_err(|err| {
+            let report = report_execution_error(
+                &err,
+                &params.assembly_file,
+                &params.library_paths,
+                &params.defines,
+                &input_data,
+            );
+            format!("Failed to generate execution trace = {report}")
+        })?;
 
     Ok((trace, program_hash))
 }
@@ -9,9 +9,11 @@ pub use assembly::{
     Assembler, AssemblyError, ParsingError,
 };
 pub use processor::{
-    crypto, execute, execute_iter, utils, AdviceInputs, AdviceProvider, AsmOpInfo, DefaultHost,
-    ExecutionError, ExecutionTrace, Host, Kernel, MemAdviceProvider, Operation, Program,
-    ProgramInfo, StackInputs, VmState, VmStateIterator, ZERO,
+    crypto, estimate_trace_len, execute, execute_iter, utils, AdviceInputs, AdviceProvider,
+    AsmOpInfo, ChipletsLengths, DefaultHost, EventHandler, EventHandlerRegistry, ExecutionError,
+    ExecutionTrace, Host, HostResponse, Kernel, MemAdviceProvider, Operation, ProcessState,
+    Program, ProgramInfo, StackInputs, TraceLenSummary, UnhandledEventPolicy, VmState,
+    VmStateIterator, ZERO,
 };
 pub use prover::{
     math, prove, Digest, ExecutionProof, FieldExtension, HashFunction, InputError, ProvingOptions,
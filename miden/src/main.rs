@@ -27,7 +27,11 @@ pub enum Actions {
     Compile(cli::CompileCmd),
     Bundle(cli::BundleCmd),
     Debug(cli::DebugCmd),
+    Estimate(cli::EstimateCmd),
     Example(examples::ExampleOptions),
+    Fmt(cli::FmtCmd),
+    Lint(cli::LintCmd),
+    Profile(tools::Profile),
     Prove(cli::ProveCmd),
     Run(cli::RunCmd),
     Verify(cli::VerifyCmd),
@@ -43,7 +47,11 @@ impl Cli {
             Actions::Compile(compile) => compile.execute(),
             Actions::Bundle(compile) => compile.execute(),
             Actions::Debug(debug) => debug.execute(),
+            Actions::Estimate(estimate) => estimate.execute(),
             Actions::Example(example) => example.execute(),
+            Actions::Fmt(fmt) => fmt.execute(),
+            Actions::Lint(lint) => lint.execute(),
+            Actions::Profile(profile) => profile.execute(),
             Actions::Prove(prove) => prove.execute(),
             Actions::Run(run) => run.execute(),
             Actions::Verify(verify) => verify.execute(),
@@ -6,6 +6,9 @@ use processor::{AsmOpInfo, TraceLenSummary};
 use std::{fs, path::PathBuf};
 use stdlib::StdLibrary;
 
+mod profiler;
+pub use profiler::Profile;
+
 // CLI
 // ================================================================================================
 
@@ -0,0 +1,461 @@
+use crate::cli::data::{load_program, parse_define, Debug, InputFile, Libraries};
+use clap::Parser;
+use core::{cmp::Reverse, fmt};
+use miden_vm::{DefaultHost, ExecutionError, Host, Operation, Program, StackInputs};
+use processor::{AssemblyOp, CallStack, TraceLenSummary, VmState, ZERO};
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+// CLI
+// ================================================================================================
+
+/// Defines cli interface
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Profile execution of a miden program by procedure")]
+pub struct Profile {
+    /// Path to .masm assembly file or .masb compiled program file
+    #[clap(short = 'a', long = "assembly", value_parser)]
+    assembly_file: PathBuf,
+
+    /// Path to input file
+    #[clap(short = 'i', long = "input", value_parser)]
+    input_file: Option<PathBuf>,
+
+    /// Paths to .masl library files
+    #[clap(short = 'l', long = "libraries", value_parser)]
+    library_paths: Vec<PathBuf>,
+
+    /// Defines available to the program as constants, specified as `NAME` or `NAME=VALUE`; the
+    /// value of a define specified without a value is 1
+    #[clap(short = 'D', long = "define", value_parser = parse_define)]
+    defines: Vec<(String, u64)>,
+
+    /// Path to output file for folded stacks, consumable by flame graph tools
+    #[clap(short = 'o', long = "output", value_parser)]
+    output_file: Option<PathBuf>,
+
+    /// Metric used as sample counts in folded stacks: cycles, hasher, bitwise or memory
+    #[clap(short = 'm', long = "metric", default_value = "cycles")]
+    metric: String,
+}
+
+/// Implements CLI execution logic
+impl Profile {
+    pub fn execute(&self) -> Result<(), String> {
+        let metric = ProfileMetric::parse(&self.metric)?;
+
+        // load libraries from files
+        let libraries = Libraries::new(&self.library_paths)?;
+
+        // load program from file and compile it in debug mode if needed; programs loaded from
+        // .masb files carry procedure information only if they were compiled in debug mode
+        let (program, errors) =
+            load_program(&self.assembly_file, &Debug::On, libraries.libraries, &self.defines)?;
+
+        // load input data from file
+        let input_data = InputFile::read(&self.input_file, &self.assembly_file)?;
+
+        // fetch the stack and program inputs from the arguments
+        let stack_inputs = input_data.parse_stack_inputs()?;
+        let host = DefaultHost::new(input_data.parse_advice_provider()?)
+            .with_error_messages(errors.messages());
+
+        let profile = profile(&program, stack_inputs, host)
+            .map_err(|err| format!("Failed to profile program = {:?}", err))?;
+
+        println!("============================================================");
+        println!("Profiled {} program", self.assembly_file.display());
+        println!("{}", profile);
+
+        if let Some(output_path) = &self.output_file {
+            fs::write(output_path, profile.to_folded_stacks(metric))
+                .map_err(|err| format!("Failed to write folded stacks to file - {err}"))?;
+            println!("Folded stacks ({}) written to {}", self.metric, output_path.display());
+        }
+
+        Ok(())
+    }
+}
+
+// PROFILE METRIC
+// ================================================================================================
+
+/// Specifies which cost is used as the sample count of a stack when exporting folded stacks.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ProfileMetric {
+    Cycles,
+    HasherRows,
+    BitwiseRows,
+    MemoryRows,
+}
+
+impl ProfileMetric {
+    /// Parses the metric from its CLI name.
+    pub fn parse(metric: &str) -> Result<Self, String> {
+        match metric {
+            "cycles" => Ok(Self::Cycles),
+            "hasher" => Ok(Self::HasherRows),
+            "bitwise" => Ok(Self::BitwiseRows),
+            "memory" => Ok(Self::MemoryRows),
+            _ => Err(format!(
+                "Invalid metric '{metric}'; expected one of: cycles, hasher, bitwise, memory"
+            )),
+        }
+    }
+}
+
+// EXECUTION COST
+// ================================================================================================
+
+/// Number of VM cycles and chiplet trace rows attributed to a part of a program.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct ExecutionCost {
+    /// Number of VM cycles.
+    pub cycles: usize,
+    /// Number of rows in the hash chiplet trace.
+    pub hasher_rows: usize,
+    /// Number of rows in the bitwise chiplet trace.
+    pub bitwise_rows: usize,
+    /// Number of rows in the memory chiplet trace.
+    pub memory_rows: usize,
+}
+
+impl ExecutionCost {
+    /// Returns the cost of executing a single operation.
+    ///
+    /// `depth` is the value at position 4 of the stack before the operation was executed; for
+    /// Merkle path operations this is the depth of the path.
+    pub fn of_operation(op: Operation, depth: u64) -> Self {
+        // number of rows required by the hasher to compute a single permutation
+        const HASH_CYCLE_LEN: usize = 8;
+        // number of rows required by the bitwise chiplet to process a single operation
+        const BITWISE_CYCLE_LEN: usize = 8;
+
+        let mut cost = Self {
+            cycles: 1,
+            ..Self::default()
+        };
+
+        match op {
+            Operation::Join
+            | Operation::Split
+            | Operation::Loop
+            | Operation::Call
+            | Operation::SysCall
+            | Operation::Dyn
+            | Operation::Span
+            | Operation::Respan
+            | Operation::HPerm => cost.hasher_rows = HASH_CYCLE_LEN,
+            Operation::MpVerify => cost.hasher_rows = HASH_CYCLE_LEN * depth as usize,
+            Operation::MrUpdate => cost.hasher_rows = 2 * HASH_CYCLE_LEN * depth as usize,
+            Operation::U32and | Operation::U32xor => cost.bitwise_rows = BITWISE_CYCLE_LEN,
+            Operation::MLoad | Operation::MLoadW | Operation::MStore | Operation::MStoreW => {
+                cost.memory_rows = 1
+            }
+            Operation::MStream | Operation::Pipe | Operation::RCombBase => cost.memory_rows = 2,
+            _ => (),
+        }
+
+        cost
+    }
+
+    /// Returns the value of the specified metric.
+    pub fn get(&self, metric: ProfileMetric) -> usize {
+        match metric {
+            ProfileMetric::Cycles => self.cycles,
+            ProfileMetric::HasherRows => self.hasher_rows,
+            ProfileMetric::BitwiseRows => self.bitwise_rows,
+            ProfileMetric::MemoryRows => self.memory_rows,
+        }
+    }
+
+    /// Adds the specified cost to this cost.
+    pub fn add(&mut self, other: &Self) {
+        self.cycles += other.cycles;
+        self.hasher_rows += other.hasher_rows;
+        self.bitwise_rows += other.bitwise_rows;
+        self.memory_rows += other.memory_rows;
+    }
+}
+
+// PROCEDURE STATS
+// ================================================================================================
+
+/// Execution statistics of a single procedure.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct ProcedureStats {
+    /// Number of times the procedure was entered.
+    pub calls: usize,
+    /// Cost of executing the body of the procedure, excluding the procedures it invokes.
+    pub self_cost: ExecutionCost,
+    /// Cost of executing the procedure, including the procedures it invokes.
+    pub total_cost: ExecutionCost,
+}
+
+// EXECUTION PROFILE
+// ================================================================================================
+
+/// Contains the costs of executing a program attributed to procedure call stacks.
+///
+/// Procedure call stacks are reconstructed from the contexts of `AsmOp` decorators (see
+/// [CallStack]), and thus the program must be compiled in debug mode.
+///
+/// Cycles which cannot be attributed to an assembly instruction (e.g., control flow operations)
+/// are attributed to the innermost procedure on the stack at the time they are executed.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct ExecutionProfile {
+    /// Costs attributed to call stacks, keyed by `;`-separated stack frames.
+    stacks: BTreeMap<String, ExecutionCost>,
+    /// Per-procedure statistics, keyed by procedure name.
+    procedures: BTreeMap<String, ProcedureStats>,
+    /// Information about VM components trace lengths.
+    trace_len_summary: TraceLenSummary,
+}
+
+impl ExecutionProfile {
+    /// Returns costs attributed to each call stack, keyed by `;`-separated stack frames.
+    pub fn stacks(&self) -> &BTreeMap<String, ExecutionCost> {
+        &self.stacks
+    }
+
+    /// Returns execution statistics for each procedure, keyed by procedure name.
+    pub fn procedures(&self) -> &BTreeMap<String, ProcedureStats> {
+        &self.procedures
+    }
+
+    /// Returns [TraceLenSummary] that contains the data about lengths of the trace parts.
+    pub fn trace_len_summary(&self) -> TraceLenSummary {
+        self.trace_len_summary
+    }
+
+    /// Returns the profile in the folded stacks format (one `frame;frame;... count` line per
+    /// call stack) using the specified metric as the sample count.
+    ///
+    /// Call stacks with a zero count are omitted.
+    pub fn to_folded_stacks(&self, metric: ProfileMetric) -> String {
+        self.stacks()
+            .iter()
+            .filter(|(_, cost)| cost.get(metric) > 0)
+            .map(|(stack, cost)| format!("{stack} {}\n", cost.get(metric)))
+            .collect()
+    }
+}
+
+impl fmt::Display for ExecutionProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let summary = self.trace_len_summary();
+        writeln!(
+            f,
+            "\nVM cycles: {}
+├── Hash chiplet rows: {}
+├── Bitwise chiplet rows: {}
+└── Memory chiplet rows: {}\n",
+            summary.main_trace_len(),
+            summary.chiplets_trace_len().hash_chiplet_len(),
+            summary.chiplets_trace_len().bitwise_chiplet_len(),
+            summary.chiplets_trace_len().memory_chiplet_len(),
+        )?;
+
+        // sort procedures by total number of cycles, most expensive first
+        let mut procedures: Vec<_> = self.procedures().iter().collect();
+        procedures.sort_by_key(|(_, stats)| Reverse(stats.total_cost.cycles));
+
+        let padding = procedures.iter().fold(20, |max, (name, _)| name.len().max(max));
+
+        writeln!(
+            f,
+            "{0: <width$} | {1: <10} | {2: <12} | {3: <12} | {4: <12} | {5: <12} | Memory rows",
+            "Procedure",
+            "Calls",
+            "Self cycles",
+            "Total cycles",
+            "Hasher rows",
+            "Bitwise rows",
+            width = padding,
+        )?;
+
+        let delimiter = "-".repeat(padding + 87);
+        writeln!(f, "{delimiter}")?;
+
+        for (name, stats) in procedures {
+            writeln!(
+                f,
+                "{0: <width$} | {1: <10} | {2: <12} | {3: <12} | {4: <12} | {5: <12} | {6:}",
+                name,
+                stats.calls,
+                stats.self_cost.cycles,
+                stats.total_cost.cycles,
+                stats.total_cost.hasher_rows,
+                stats.total_cost.bitwise_rows,
+                stats.total_cost.memory_rows,
+                width = padding,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+// PROFILER
+// ================================================================================================
+
+/// Builds an [ExecutionProfile] from a sequence of VM states.
+struct Profiler {
+    call_stack: CallStack,
+    prev_depth: u64,
+    profile: ExecutionProfile,
+}
+
+impl Profiler {
+    fn new(trace_len_summary: TraceLenSummary) -> Self {
+        let call_stack = CallStack::default();
+        let mut profile = ExecutionProfile {
+            trace_len_summary,
+            ..Default::default()
+        };
+        let main = call_stack.current_frame().name().to_string();
+        profile.procedures.entry(main).or_default().calls = 1;
+
+        Self {
+            call_stack,
+            prev_depth: 0,
+            profile,
+        }
+    }
+
+    /// Attributes the operation executed in the specified state to the current call stack.
+    ///
+    /// The procedure invocations which took place right before the operation are recorded first.
+    fn record_state<'a>(
+        &mut self,
+        state: &'a VmState,
+        call_sites: impl Iterator<Item = &'a AssemblyOp>,
+    ) {
+        let asmop = state.asmop.as_ref().filter(|asmop| asmop.cycle_idx() == 1);
+        for asmop in call_sites.chain(asmop.map(|asmop| asmop.asmop())) {
+            if self.call_stack.record_asmop(asmop) {
+                let name = asmop.context_name().to_string();
+                self.profile.procedures.entry(name).or_default().calls += 1;
+            }
+        }
+
+        if let Some(op) = state.op {
+            let cost = ExecutionCost::of_operation(op, self.prev_depth);
+            let frames: Vec<&str> =
+                self.call_stack.frames().iter().map(|frame| frame.name()).collect();
+            self.profile.stacks.entry(frames.join(";")).or_default().add(&cost);
+        }
+
+        self.prev_depth = state.stack.get(4).unwrap_or(&ZERO).as_int();
+    }
+
+    /// Computes per-procedure costs and returns the resulting profile.
+    fn into_profile(mut self) -> ExecutionProfile {
+        for (stack, cost) in self.profile.stacks.iter() {
+            let frames: Vec<&str> = stack.split(';').collect();
+            for (i, frame) in frames.iter().enumerate() {
+                // procedure names may be repeated on a stack only if procedures in different
+                // modules share a name; make sure the cost is counted only once in such cases
+                if frames[..i].contains(frame) {
+                    continue;
+                }
+                let stats = self.profile.procedures.entry(frame.to_string()).or_default();
+                stats.total_cost.add(cost);
+                if i == frames.len() - 1 {
+                    stats.self_cost.add(cost);
+                }
+            }
+        }
+
+        self.profile
+    }
+}
+
+/// Returns the execution profile of the given program.
+///
+/// The program must be compiled in debug mode for the cycles to be attributed to procedures;
+/// otherwise, all cycles are attributed to the program entrypoint.
+pub fn profile<H>(
+    program: &Program,
+    stack_inputs: StackInputs,
+    host: H,
+) -> Result<ExecutionProfile, ExecutionError>
+where
+    H: Host,
+{
+    let mut vm_state_iterator = processor::execute_iter(program, stack_inputs, host);
+    let mut profiler = Profiler::new(*vm_state_iterator.trace_len_summary());
+
+    while let Some(state) = vm_state_iterator.next() {
+        let state = state?;
+        profiler.record_state(&state, vm_state_iterator.call_sites_at(state.clk));
+    }
+
+    Ok(profiler.into_profile())
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{ExecutionCost, ProfileMetric, StackInputs};
+    use miden_vm::{Assembler, DefaultHost};
+
+    #[test]
+    fn profile_test() {
+        let source = "
+            proc.hash
+                hperm
+            end
+            proc.bitwise
+                u32and
+            end
+            proc.store
+                mem_store.0
+                exec.bitwise
+            end
+            begin
+                push.1.2.3.4
+                exec.hash
+                dropw dropw dropw
+                push.5.6
+                call.store
+                drop
+            end";
+        let program = Assembler::default().with_debug_mode(true).compile(source).unwrap();
+        let profile =
+            super::profile(&program, StackInputs::default(), DefaultHost::default()).unwrap();
+
+        // costs attributed to all stacks must add up to the lengths of the trace segments
+        let mut total = ExecutionCost::default();
+        profile.stacks().values().for_each(|cost| total.add(cost));
+        let summary = profile.trace_len_summary();
+        assert_eq!(total.cycles, summary.main_trace_len());
+        assert_eq!(total.hasher_rows, summary.chiplets_trace_len().hash_chiplet_len());
+        assert_eq!(total.bitwise_rows, summary.chiplets_trace_len().bitwise_chiplet_len());
+        assert_eq!(total.memory_rows, summary.chiplets_trace_len().memory_chiplet_len());
+
+        let stacks: Vec<&str> = profile.stacks().keys().map(String::as_str).collect();
+        assert_eq!(stacks, ["#main", "#main;hash", "#main;store", "#main;store;bitwise"]);
+
+        let stats = |name: &str| profile.procedures()[name];
+        assert_eq!(stats("#main").calls, 1);
+        assert_eq!(stats("#main").total_cost, total);
+        assert_eq!(stats("hash").calls, 1);
+        assert_eq!(stats("hash").self_cost.hasher_rows, 8);
+        assert_eq!(stats("store").calls, 1);
+        assert_eq!(stats("store").self_cost.memory_rows, 1);
+        assert_eq!(stats("store").self_cost.bitwise_rows, 0);
+        assert_eq!(stats("store").total_cost.bitwise_rows, 8);
+        assert_eq!(stats("bitwise").self_cost.bitwise_rows, 8);
+
+        let folded = profile.to_folded_stacks(ProfileMetric::BitwiseRows);
+        assert_eq!(folded, "#main;store;bitwise 8\n");
+    }
+
+    #[test]
+    fn profile_metric_parse() {
+        assert_eq!(ProfileMetric::parse("hasher"), Ok(ProfileMetric::HasherRows));
+        assert!(ProfileMetric::parse("stack").is_err());
+    }
+}
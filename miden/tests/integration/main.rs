@@ -1,10 +1,20 @@
-use test_utils::build_test;
+use processor::{
+    AdviceInputs, DefaultHost, ExecutionOptions, FileAdviceProvider, MemAdviceProvider, StackInputs,
+};
+use std::{env, fs, process};
+use test_utils::{
+    build_debug_test, build_test,
+    crypto::{init_merkle_leaf, init_merkle_store, MerkleTree},
+    serde::{Deserializable, Serializable},
+    Program, Word,
+};
 
 mod air;
 mod cli;
 mod exec_iters;
 mod flow_control;
 mod operations;
+mod optimizer;
 
 // TESTS
 // ================================================================================================
@@ -19,3 +29,141 @@ fn multi_output_program() {
     let test = build_test!("begin mul movup.2 drop end", &[1, 2, 3]);
     test.prove_and_verify(vec![1, 2, 3], false);
 }
+
+#[test]
+fn mast_serialization_roundtrip() {
+    let kernel = "export.foo add end";
+    let source = "
+        proc.bar.2 loc_store.1 loc_load.1 end
+
+        begin
+            push.3 push.4 syscall.foo
+            exec.bar
+            push.1 if.true push.1 else push.2 end
+            dup while.true push.0 end
+            debug.stack emit.7 drop
+        end";
+    let mut test = build_debug_test!(source);
+    test.kernel = Some(kernel.to_string());
+    let program = test.compile().unwrap();
+
+    let bytes = program.to_bytes();
+    let deser = Program::read_from_bytes(&bytes).unwrap();
+    assert_eq!(program.hash(), deser.hash());
+    assert_eq!(bytes, deser.to_bytes());
+
+    // both programs must execute identically
+    let execute = |program: &Program| {
+        let host = DefaultHost::new(MemAdviceProvider::default());
+        processor::execute(program, StackInputs::default(), host, ExecutionOptions::default())
+            .unwrap()
+    };
+    let trace = execute(&program);
+    let deser_trace = execute(&deser);
+    assert_eq!(trace.stack_outputs(), deser_trace.stack_outputs());
+    assert_eq!(trace.trace_len_summary(), deser_trace.trace_len_summary());
+}
+
+#[test]
+fn trace_len_estimation() {
+    let kernel = "export.foo add end";
+    let source = "
+        begin
+            push.3 push.4 syscall.foo
+            push.1 push.2 u32and
+            mem_store.0 mem_load.0
+            hperm
+            dropw dropw dropw dropw dropw
+        end";
+    let mut test = build_test!(source);
+    test.kernel = Some(kernel.to_string());
+    assert_trace_len_estimation(&test.compile().unwrap());
+
+    // a long execution makes the VM components discard the rows which are no longer needed, and
+    // exercises stack overflow, repeated memory accesses in several contexts and memoized hashes
+    let source = "
+        proc.foo
+            push.7 mem_store.5 mem_load.5 drop
+        end
+        begin
+            push.100 push.1
+            while.true
+                padw padw padw padw padw dropw dropw dropw dropw dropw
+                dup mem_store.0 mem_load.1 drop
+                dup push.3 u32and drop
+                call.foo
+                sub.1 dup neq.0
+            end
+            drop
+        end";
+    assert_trace_len_estimation(&build_test!(source).compile().unwrap());
+}
+
+/// Asserts that the estimated trace lengths of the program match the lengths of its actual
+/// execution trace.
+fn assert_trace_len_estimation(program: &Program) {
+    let host = DefaultHost::new(MemAdviceProvider::default());
+    let trace =
+        processor::execute(program, StackInputs::default(), host, ExecutionOptions::default())
+            .unwrap();
+    let host = DefaultHost::new(MemAdviceProvider::default());
+    let summary = processor::estimate_trace_len(
+        program,
+        StackInputs::default(),
+        host,
+        ExecutionOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(trace.trace_len_summary(), &summary);
+}
+
+#[test]
+fn file_advice_provider() {
+    let dir = env::temp_dir().join(format!("miden-file-advice-provider-{}", process::id()));
+    let _ = fs::remove_dir_all(&dir);
+
+    let (leaves, store) = init_merkle_store(&[1, 2, 3, 4, 5, 6, 7, 8]);
+    let tree = MerkleTree::new(leaves.clone()).unwrap();
+    let new_leaf = init_merkle_leaf(9);
+
+    let execute = |source: String, inputs: AdviceInputs| {
+        let program = build_test!(&source).compile().unwrap();
+        let provider = FileAdviceProvider::open(&dir, inputs, 16).unwrap();
+        let host = DefaultHost::new(provider);
+        let options = ExecutionOptions::default();
+        processor::execute(&program, StackInputs::default(), host, options).unwrap()
+    };
+
+    // update a leaf of a tree provided via the advice inputs
+    let source = format!(
+        "begin push.{} push.{} push.5.3 mtree_set dropw end",
+        word_imm(new_leaf),
+        word_imm(tree.root().into())
+    );
+    let trace = execute(source, AdviceInputs::default().with_merkle_store(store));
+    let new_root = trace.stack_outputs().get_stack_word(0).unwrap();
+
+    let mut expected_tree = tree.clone();
+    expected_tree.update_leaf(5, new_leaf).unwrap();
+    assert_eq!(new_root, Word::from(expected_tree.root()));
+
+    // both trees are available to a subsequent execution using the same store
+    let source = format!(
+        "begin push.{} push.5.3 mtree_get swapw dropw push.{} push.5.3 mtree_get swapw dropw end",
+        word_imm(tree.root().into()),
+        word_imm(new_root)
+    );
+    let trace = execute(source, AdviceInputs::default());
+    let outputs = trace.stack_outputs();
+    assert_eq!(outputs.get_stack_word(0).unwrap(), new_leaf);
+    assert_eq!(outputs.get_stack_word(4).unwrap(), leaves[5]);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn word_imm(word: Word) -> String {
+    format!("{}.{}.{}.{}", word[0], word[1], word[2], word[3])
+}
@@ -122,6 +122,46 @@ fn advice_push_u64div_conditional_execution() {
     test.expect_stack(&[0, 0, 0, 0, 0, 4, 0, 8]);
 }
 
+#[test]
+fn advice_push_u256div() {
+    // push a/b onto the advice stack and then move these values onto the operand stack.
+    let source = "begin adv.push_u256div adv_push.16 end";
+
+    // get a random 128-bit dividend and a random 96-bit divisor and split them into 32-bit limbs;
+    // the upper limbs of both 256-bit values are set to zeros
+    let a = ((rand_value::<u64>() as u128) << 64) | rand_value::<u64>() as u128;
+    let b = ((rand_value::<u32>() as u128) << 64) | rand_value::<u64>() as u128;
+    let q = a / b;
+    let r = a % b;
+
+    let mut stack_inputs = to_u256_limbs(a);
+    stack_inputs.extend_from_slice(&to_u256_limbs(b));
+
+    let mut expected = to_u256_limbs(q);
+    expected.extend_from_slice(&to_u256_limbs(r));
+    expected.reverse();
+
+    let test = build_test!(source, &stack_inputs);
+    test.expect_stack(&expected);
+}
+
+#[test]
+fn advice_push_lower_bound() {
+    // store the array [3, 5, 5, 9] at addresses 100..104, and push the lower bounds of several
+    // values in the array onto the operand stack
+    let source = "begin
+        push.3 mem_store.100 push.5 mem_store.101 push.5 mem_store.102 push.9 mem_store.103
+        push.104.100.0 adv.push_lower_bound drop
+        push.5 adv.push_lower_bound drop
+        push.6 adv.push_lower_bound drop
+        push.10 adv.push_lower_bound
+        adv_push.4 movup.4 drop movup.4 drop movup.4 drop
+    end";
+
+    let test = build_test!(source, &[]);
+    test.expect_stack(&[100, 101, 103, 104]);
+}
+
 #[test]
 fn advice_insert_mem() {
     let source = "begin
@@ -329,3 +369,13 @@ fn advice_insert_hdword() {
     let test = build_test!(source, &stack_inputs);
     test.expect_stack(&[1, 2, 3, 4, 5, 6, 7, 8]);
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Splits the provided value into eight 32-bit limbs, starting with the least significant one.
+fn to_u256_limbs(value: u128) -> Vec<u64> {
+    (0..8)
+        .map(|i| if i < 4 { (value >> (32 * i)) as u32 as u64 } else { 0 })
+        .collect()
+}
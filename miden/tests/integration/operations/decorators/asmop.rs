@@ -1,6 +1,6 @@
 use processor::{AsmOpInfo, VmStateIterator};
 use test_utils::build_debug_test;
-use vm_core::{AssemblyOp, Felt, Operation};
+use vm_core::{AssemblyOp, Felt, Location, Operation};
 
 #[test]
 fn asmop_one_span_block_test() {
@@ -21,7 +21,8 @@ fn asmop_one_span_block_test() {
         VmStatePartial {
             clk: 2,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 1, 7)),
                 1,
             )),
             op: Some(Operation::Pad),
@@ -29,7 +30,8 @@ fn asmop_one_span_block_test() {
         VmStatePartial {
             clk: 3,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 1, 7)),
                 2,
             )),
             op: Some(Operation::Incr),
@@ -37,7 +39,8 @@ fn asmop_one_span_block_test() {
         VmStatePartial {
             clk: 4,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "push.2".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "push.2".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 1, 14)),
                 1,
             )),
             op: Some(Operation::Push(Felt::new(2))),
@@ -45,7 +48,8 @@ fn asmop_one_span_block_test() {
         VmStatePartial {
             clk: 5,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "add".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "add".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 1, 21)),
                 1,
             )),
             op: Some(Operation::Add),
@@ -76,40 +80,54 @@ fn asmop_with_one_procedure() {
             asmop: None,
             op: Some(Operation::Span),
         },
+        // in debug mode, a NOOP is executed on behalf of `exec.foo` to record its call site
         VmStatePartial {
             clk: 2,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("foo".to_string(), 2, "push.1".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "exec.foo".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 1, 38)),
                 1,
             )),
-            op: Some(Operation::Pad),
+            op: Some(Operation::Noop),
         },
         VmStatePartial {
             clk: 3,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("foo".to_string(), 2, "push.1".to_string(), false),
+                AssemblyOp::new("foo".to_string(), 2, "push.1".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 1, 10)),
+                1,
+            )),
+            op: Some(Operation::Pad),
+        },
+        VmStatePartial {
+            clk: 4,
+            asmop: Some(AsmOpInfo::new(
+                AssemblyOp::new("foo".to_string(), 2, "push.1".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 1, 10)),
                 2,
             )),
             op: Some(Operation::Incr),
         },
         VmStatePartial {
-            clk: 4,
+            clk: 5,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("foo".to_string(), 1, "push.2".to_string(), false),
+                AssemblyOp::new("foo".to_string(), 1, "push.2".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 1, 17)),
                 1,
             )),
             op: Some(Operation::Push(Felt::new(2))),
         },
         VmStatePartial {
-            clk: 5,
+            clk: 6,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("foo".to_string(), 1, "add".to_string(), false),
+                AssemblyOp::new("foo".to_string(), 1, "add".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 1, 24)),
                 1,
             )),
             op: Some(Operation::Add),
         },
         VmStatePartial {
-            clk: 6,
+            clk: 7,
             asmop: None,
             op: Some(Operation::End),
         },
@@ -141,7 +159,8 @@ fn asmop_repeat_test() {
         VmStatePartial {
             clk: 2,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 3, 17)),
                 1,
             )),
             op: Some(Operation::Pad),
@@ -149,7 +168,8 @@ fn asmop_repeat_test() {
         VmStatePartial {
             clk: 3,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 3, 17)),
                 2,
             )),
             op: Some(Operation::Incr),
@@ -157,7 +177,8 @@ fn asmop_repeat_test() {
         VmStatePartial {
             clk: 4,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "push.2".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "push.2".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 3, 24)),
                 1,
             )),
             op: Some(Operation::Push(Felt::new(2))),
@@ -165,7 +186,8 @@ fn asmop_repeat_test() {
         VmStatePartial {
             clk: 5,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "add".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "add".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 3, 31)),
                 1,
             )),
             op: Some(Operation::Add),
@@ -173,7 +195,8 @@ fn asmop_repeat_test() {
         VmStatePartial {
             clk: 6,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 3, 17)),
                 1,
             )),
             op: Some(Operation::Pad),
@@ -181,7 +204,8 @@ fn asmop_repeat_test() {
         VmStatePartial {
             clk: 7,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 3, 17)),
                 2,
             )),
             op: Some(Operation::Incr),
@@ -189,7 +213,8 @@ fn asmop_repeat_test() {
         VmStatePartial {
             clk: 8,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "push.2".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "push.2".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 3, 24)),
                 1,
             )),
             op: Some(Operation::Push(Felt::new(2))),
@@ -197,7 +222,8 @@ fn asmop_repeat_test() {
         VmStatePartial {
             clk: 9,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "add".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "add".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 3, 31)),
                 1,
             )),
             op: Some(Operation::Add),
@@ -205,7 +231,8 @@ fn asmop_repeat_test() {
         VmStatePartial {
             clk: 10,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 3, 17)),
                 1,
             )),
             op: Some(Operation::Pad),
@@ -213,7 +240,8 @@ fn asmop_repeat_test() {
         VmStatePartial {
             clk: 11,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 3, 17)),
                 2,
             )),
             op: Some(Operation::Incr),
@@ -221,7 +249,8 @@ fn asmop_repeat_test() {
         VmStatePartial {
             clk: 12,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "push.2".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "push.2".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 3, 24)),
                 1,
             )),
             op: Some(Operation::Push(Felt::new(2))),
@@ -229,7 +258,8 @@ fn asmop_repeat_test() {
         VmStatePartial {
             clk: 13,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "add".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "add".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 3, 31)),
                 1,
             )),
             op: Some(Operation::Add),
@@ -292,7 +322,8 @@ fn asmop_conditional_execution_test() {
         VmStatePartial {
             clk: 3,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "eq".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "eq".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 2, 13)),
                 1,
             )),
             op: Some(Operation::Eq),
@@ -315,7 +346,8 @@ fn asmop_conditional_execution_test() {
         VmStatePartial {
             clk: 7,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 4, 17)),
                 1,
             )),
             op: Some(Operation::Pad),
@@ -323,7 +355,8 @@ fn asmop_conditional_execution_test() {
         VmStatePartial {
             clk: 8,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 4, 17)),
                 2,
             )),
             op: Some(Operation::Incr),
@@ -331,7 +364,8 @@ fn asmop_conditional_execution_test() {
         VmStatePartial {
             clk: 9,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "push.2".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "push.2".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 4, 24)),
                 1,
             )),
             op: Some(Operation::Push(Felt::new(2))),
@@ -339,7 +373,8 @@ fn asmop_conditional_execution_test() {
         VmStatePartial {
             clk: 10,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "add".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "add".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 4, 31)),
                 1,
             )),
             op: Some(Operation::Add),
@@ -385,7 +420,8 @@ fn asmop_conditional_execution_test() {
         VmStatePartial {
             clk: 3,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "eq".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "eq".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 2, 13)),
                 1,
             )),
             op: Some(Operation::Eq),
@@ -408,7 +444,8 @@ fn asmop_conditional_execution_test() {
         VmStatePartial {
             clk: 7,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "push.3".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "push.3".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 6, 17)),
                 1,
             )),
             op: Some(Operation::Push(Felt::new(3))),
@@ -416,7 +453,8 @@ fn asmop_conditional_execution_test() {
         VmStatePartial {
             clk: 8,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "push.4".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "push.4".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 6, 24)),
                 1,
             )),
             op: Some(Operation::Push(Felt::new(4))),
@@ -424,7 +462,8 @@ fn asmop_conditional_execution_test() {
         VmStatePartial {
             clk: 9,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "add".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "add".to_string(), false)
+                    .with_location(Location::new("#exec".to_string(), 6, 31)),
                 1,
             )),
             op: Some(Operation::Add),
@@ -1,6 +1,10 @@
 use super::TestHost;
 use assembly::Assembler;
-use processor::ExecutionOptions;
+use processor::{
+    AdviceProvider, AdviceSource, DefaultHost, EventHandlerRegistry, ExecutionError,
+    ExecutionOptions, HostResponse, MemAdviceProvider, ProcessState, UnhandledEventPolicy,
+};
+use vm_core::{Felt, ONE, ZERO};
 
 #[test]
 fn test_event_handling() {
@@ -22,6 +26,92 @@ fn test_event_handling() {
     assert_eq!(host.event_handler, expected);
 }
 
+#[test]
+fn test_event_handler_registry() {
+    let source = "\
+    begin
+        push.7
+        emit.1
+        adv_push.1
+        emit.10
+        adv_push.1
+        emit.15
+        adv_push.1
+    end";
+
+    // the handler for event 1 pushes the square of the top stack element onto the advice stack;
+    // the handler for events 10..=20 pushes the event ID itself
+    let handlers = EventHandlerRegistry::new(UnhandledEventPolicy::Error)
+        .with_handler(
+            1,
+            |process: &dyn ProcessState, advice: &mut MemAdviceProvider, _event_id: u32| {
+                let value = process.get_stack_item(0);
+                advice.push_stack(AdviceSource::Value(value * value))?;
+                Ok(HostResponse::None)
+            },
+        )
+        .with_range_handler(
+            10..=20,
+            |_process: &dyn ProcessState, advice: &mut MemAdviceProvider, event_id: u32| {
+                advice.push_stack(AdviceSource::Value(Felt::from(event_id)))?;
+                Ok(HostResponse::None)
+            },
+        );
+
+    let program = Assembler::default().compile(source).unwrap();
+    let host = DefaultHost::default().with_event_handlers(handlers);
+    let trace = processor::execute(&program, Default::default(), host, Default::default()).unwrap();
+
+    let expected = [15_u32, 10, 49, 7].map(Felt::from);
+    assert_eq!(&trace.stack_outputs().stack()[..4], &expected);
+}
+
+#[test]
+fn test_event_handler_registry_unhandled_events() {
+    let source = "\
+    begin
+        push.1
+        emit.3
+    end";
+    let program = Assembler::default().compile(source).unwrap();
+
+    // unhandled events are ignored
+    let handlers = EventHandlerRegistry::new(UnhandledEventPolicy::Ignore);
+    let host = DefaultHost::default().with_event_handlers(handlers);
+    assert!(processor::execute(&program, Default::default(), host, Default::default()).is_ok());
+
+    // unhandled events result in an error
+    let handlers = EventHandlerRegistry::new(UnhandledEventPolicy::Error)
+        .with_handler(2, |_: &dyn ProcessState, _: &mut MemAdviceProvider, _: u32| {
+            Ok(HostResponse::None)
+        });
+    let host = DefaultHost::default().with_event_handlers(handlers);
+    let err = processor::execute(&program, Default::default(), host, Default::default());
+    assert_eq!(
+        err.err(),
+        Some(ExecutionError::UnhandledEvent {
+            clk: 4,
+            event_id: 3
+        })
+    );
+
+    // errors returned by handlers are propagated
+    let handlers = EventHandlerRegistry::new(UnhandledEventPolicy::Ignore).with_range_handler(
+        0..=5,
+        |process: &dyn ProcessState, _: &mut MemAdviceProvider, _: u32| {
+            if process.get_stack_item(0) == ONE {
+                Err(ExecutionError::EventError("unexpected value".into()))
+            } else {
+                assert_eq!(process.get_stack_item(0), ZERO);
+                Ok(HostResponse::None)
+            }
+        },
+    );
+    let host = DefaultHost::default().with_event_handlers(handlers);
+    let err = processor::execute(&program, Default::default(), host, Default::default());
+    assert_eq!(err.err(), Some(ExecutionError::EventError("unexpected value".into())));
+}
+
 #[test]
 fn test_trace_handling() {
     let source = "\
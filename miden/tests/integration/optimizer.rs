@@ -0,0 +1,105 @@
+use processor::{DefaultHost, ExecutionOptions, ExecutionTrace, MemAdviceProvider, StackInputs};
+use stdlib::StdLibrary;
+use test_utils::{Felt, Program, StarkField};
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn compile(source: &str, optimize: bool) -> Program {
+    assembly::Assembler::default()
+        .with_library(&StdLibrary::default())
+        .unwrap()
+        .with_optimizations(optimize)
+        .compile(source)
+        .unwrap()
+}
+
+fn execute(program: &Program, stack_inputs: &[u64]) -> ExecutionTrace {
+    let stack_inputs = StackInputs::try_from_ints(stack_inputs.iter().copied()).unwrap();
+    let host = DefaultHost::new(MemAdviceProvider::default());
+    processor::execute(program, stack_inputs, host, ExecutionOptions::default()).unwrap()
+}
+
+/// Executes the program compiled with and without optimizations, and checks that both programs
+/// produce the same output stack while the optimized program takes fewer cycles to execute.
+///
+/// Addresses of the stack overflow table are not compared, as they are the clock cycles at which
+/// elements were pushed onto the overflow table.
+fn check_optimized_execution(source: &str, stack_inputs: &[u64]) {
+    let trace = execute(&compile(source, false), stack_inputs);
+    let optimized_trace = execute(&compile(source, true), stack_inputs);
+
+    assert_eq!(trace.stack_outputs().stack(), optimized_trace.stack_outputs().stack());
+    assert!(
+        optimized_trace.trace_len_summary().main_trace_len()
+            < trace.trace_len_summary().main_trace_len()
+    );
+}
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn optimized_stack_manipulation() {
+    let source = "
+        begin
+            swap swap
+            dup.3 drop
+            swapw swapw
+            movup.2 movdn.2
+            padw dropw
+            add.0 mul.1
+        end";
+    check_optimized_execution(source, &[1, 2, 3, 4, 5, 6, 7, 8]);
+}
+
+#[test]
+fn optimized_constant_folding() {
+    let source = "
+        proc.compute.2
+            push.3 push.4 add
+            push.5 mul
+            loc_store.0
+            push.10 neg add
+            loc_load.0 add
+        end
+
+        begin
+            exec.compute
+            add.1
+            repeat.4
+                push.2 push.3 mul add
+            end
+        end";
+    check_optimized_execution(source, &[7, 11]);
+
+    // folded constants must wrap around the field modulus in the same way as the original ops
+    let source = format!(
+        "begin push.{} push.2 add push.{} push.3 mul end",
+        Felt::MODULUS - 1,
+        Felt::MODULUS / 2
+    );
+    check_optimized_execution(&source, &[]);
+}
+
+#[test]
+fn optimized_control_flow_and_stdlib() {
+    let source = "
+        use.std::math::u64
+
+        begin
+            if.true
+                push.1 push.2 add swap swap
+            else
+                push.5 push.0 add
+            end
+            dup neq.0
+            while.true
+                sub.1 swap swap dup neq.0
+            end
+            push.3.4 push.5.6 exec.u64::wrapping_add
+            push.7 drop
+        end";
+    check_optimized_execution(source, &[1]);
+    check_optimized_execution(source, &[0]);
+}
@@ -1,8 +1,8 @@
 use super::{utils::get_trace_len, ExecutionError, Felt, TraceFragment, ZERO};
 use alloc::vec::Vec;
 use miden_air::trace::chiplets::bitwise::{
-    A_COL_IDX, A_COL_RANGE, BITWISE_AND, BITWISE_XOR, B_COL_IDX, B_COL_RANGE, OUTPUT_COL_IDX,
-    PREV_OUTPUT_COL_IDX, TRACE_WIDTH,
+    A_COL_IDX, A_COL_RANGE, BITWISE_AND, BITWISE_XOR, B_COL_IDX, B_COL_RANGE, OP_CYCLE_LEN,
+    OUTPUT_COL_IDX, PREV_OUTPUT_COL_IDX, TRACE_WIDTH,
 };
 
 #[cfg(test)]
@@ -48,6 +48,8 @@ const INIT_TRACE_CAPACITY: usize = 128;
 ///   contains the full result of the bitwise operation.
 pub struct Bitwise {
     trace: [Vec<Felt>; TRACE_WIDTH],
+    record_trace: bool,
+    num_counted_rows: usize,
 }
 
 impl Bitwise {
@@ -60,7 +62,21 @@ impl Bitwise {
             .collect::<Vec<_>>()
             .try_into()
             .expect("failed to convert vector to array");
-        Self { trace }
+        Self {
+            trace,
+            record_trace: true,
+            num_counted_rows: 0,
+        }
+    }
+
+    /// Returns a new [Bitwise] which computes bitwise operations but only counts the rows of its
+    /// execution trace.
+    pub fn new_counting() -> Self {
+        Self {
+            trace: Default::default(),
+            record_trace: false,
+            num_counted_rows: 0,
+        }
     }
 
     // PUBLIC ACCESSORS
@@ -69,7 +85,11 @@ impl Bitwise {
     /// Returns length of execution trace required to describe bitwise operations executed on the
     /// VM.
     pub fn trace_len(&self) -> usize {
-        get_trace_len(&self.trace)
+        if self.record_trace {
+            get_trace_len(&self.trace)
+        } else {
+            self.num_counted_rows
+        }
     }
 
     // TRACE MUTATORS
@@ -83,6 +103,12 @@ impl Bitwise {
     pub fn u32and(&mut self, a: Felt, b: Felt) -> Result<Felt, ExecutionError> {
         let a = assert_u32(a)?.as_int();
         let b = assert_u32(b)?.as_int();
+
+        if !self.record_trace {
+            self.num_counted_rows += OP_CYCLE_LEN;
+            return Ok(Felt::new(a & b));
+        }
+
         let mut result = 0u64;
 
         // append 8 rows to the trace, each row computing bitwise AND in 4 bit limbs starting with
@@ -118,6 +144,12 @@ impl Bitwise {
     pub fn u32xor(&mut self, a: Felt, b: Felt) -> Result<Felt, ExecutionError> {
         let a = assert_u32(a)?.as_int();
         let b = assert_u32(b)?.as_int();
+
+        if !self.record_trace {
+            self.num_counted_rows += OP_CYCLE_LEN;
+            return Ok(Felt::new(a ^ b));
+        }
+
         let mut result = 0u64;
 
         // append 8 rows to the trace, each row computing bitwise XOR in 4 bit limbs starting with
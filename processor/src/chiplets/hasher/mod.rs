@@ -2,6 +2,7 @@ use super::{
     Felt, HasherState, MerklePath, MerkleRootUpdate, OpBatch, TraceFragment, Word, ONE, ZERO,
 };
 use alloc::collections::BTreeMap;
+use core::ops::Range;
 use miden_air::trace::chiplets::hasher::{
     Digest, Selectors, DIGEST_LEN, DIGEST_RANGE, LINEAR_HASH, MP_VERIFY, MR_UPDATE_NEW,
     MR_UPDATE_OLD, RATE_LEN, RETURN_HASH, RETURN_STATE, STATE_WIDTH, TRACE_WIDTH,
@@ -59,6 +60,18 @@ pub struct Hasher {
 }
 
 impl Hasher {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new [Hasher] which performs hash computations but only counts the rows of its
+    /// execution trace.
+    pub fn new_counting() -> Self {
+        Self {
+            trace: HasherTrace::new_counting(),
+            memoized_trace_map: BTreeMap::new(),
+        }
+    }
+
     // STATE ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -101,7 +114,7 @@ impl Hasher {
 
         if let Some((start_row, end_row)) = self.get_memoized_trace(expected_hash) {
             // copy the trace of a block with same hash instead of building it again.
-            self.trace.copy_trace(&mut state, *start_row..*end_row);
+            self.copy_memoized_trace(&mut state, *start_row..*end_row, expected_hash);
         } else {
             // perform the hash.
             self.trace.append_permutation(&mut state, LINEAR_HASH, RETURN_HASH);
@@ -180,7 +193,7 @@ impl Hasher {
             }
             self.insert_to_memoized_trace_map(addr, expected_hash);
         } else {
-            self.trace.copy_trace(&mut state, start_row..end_row);
+            self.copy_memoized_trace(&mut state, start_row..end_row, expected_hash);
         }
 
         let result = get_digest(&state);
@@ -352,6 +365,18 @@ impl Hasher {
 
     /// Checks if a trace for a program block already exists and returns the start and end rows
     /// of the memoized trace. Returns None otherwise.
+    /// Copies the memoized section of the trace of a block with the specified hash and updates the
+    /// provided state to the state at the last copied row.
+    ///
+    /// If the trace is not recorded, the copied rows are only counted. In this case, only the
+    /// digest part of the state is updated, which is known to be equal to the hash of the block.
+    fn copy_memoized_trace(&mut self, state: &mut HasherState, rows: Range<usize>, hash: Digest) {
+        self.trace.copy_trace(state, rows);
+        if !self.trace.is_recorded() {
+            state[DIGEST_RANGE].copy_from_slice(hash.as_elements());
+        }
+    }
+
     fn get_memoized_trace(&self, hash: Digest) -> Option<&(usize, usize)> {
         let key: [u8; 32] = hash.into();
         self.memoized_trace_map.get(&key)
@@ -13,20 +13,39 @@ use vm_core::chiplets::hasher::apply_round;
 /// - 3 selector columns.
 /// - 12 columns describing hasher state.
 /// - 1 node index column used for Merkle path related computations.
-#[derive(Default)]
+///
+/// When the trace is not recorded, only the number of its rows is tracked.
 pub struct HasherTrace {
     selectors: [Vec<Felt>; 3],
     hasher_state: [Vec<Felt>; STATE_WIDTH],
     node_index: Vec<Felt>,
+    record_trace: bool,
+    num_rows: usize,
 }
 
 impl HasherTrace {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an empty [HasherTrace] which only counts its rows.
+    pub fn new_counting() -> Self {
+        Self {
+            record_trace: false,
+            ..Self::default()
+        }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
     /// Returns current length of this execution trace.
     pub fn trace_len(&self) -> usize {
-        self.selectors[0].len()
+        self.num_rows
+    }
+
+    /// Returns true if the rows of this execution trace are recorded.
+    pub fn is_recorded(&self) -> bool {
+        self.record_trace
     }
 
     /// Returns the next row address. The address is equal to the current trace length + 1.
@@ -94,6 +113,11 @@ impl HasherTrace {
 
     /// Appends a new row to the execution trace based on the supplied parameters.
     fn append_row(&mut self, selectors: Selectors, state: &HasherState, index: Felt) {
+        self.num_rows += 1;
+        if !self.record_trace {
+            return;
+        }
+
         for (trace_col, selector_val) in self.selectors.iter_mut().zip(selectors) {
             trace_col.push(selector_val);
         }
@@ -105,7 +129,15 @@ impl HasherTrace {
 
     /// Copies section of trace from the given range of start and end rows at the end of the trace.
     /// The hasher state of the last row is copied to the provided state input.
+    ///
+    /// If the trace is not recorded, only the number of copied rows is counted, and the provided
+    /// state is left unchanged.
     pub fn copy_trace(&mut self, state: &mut [Felt; STATE_WIDTH], range: Range<usize>) {
+        self.num_rows += range.len();
+        if !self.record_trace {
+            return;
+        }
+
         for selector in self.selectors.iter_mut() {
             selector.extend_from_within(range.clone());
         }
@@ -145,3 +177,15 @@ impl HasherTrace {
         }
     }
 }
+
+impl Default for HasherTrace {
+    fn default() -> Self {
+        Self {
+            selectors: Default::default(),
+            hasher_state: Default::default(),
+            node_index: Vec::new(),
+            record_trace: true,
+            num_rows: 0,
+        }
+    }
+}
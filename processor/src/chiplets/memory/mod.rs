@@ -71,7 +71,6 @@ const INIT_MEM_VALUE: Word = EMPTY_WORD;
 ///   clock cycles computed as described above.
 ///
 /// For the first row of the trace, values in `d0`, `d1`, and `d_inv` are set to zeros.
-#[derive(Default)]
 pub struct Memory {
     /// Memory segment traces sorted by their execution context ID.
     trace: BTreeMap<ContextId, MemorySegmentTrace>,
@@ -79,9 +78,32 @@ pub struct Memory {
     /// Total number of entries in the trace (across all contexts); tracked separately so that we
     /// don't have to sum up lengths of all address trace vectors for all contexts all the time.
     num_trace_rows: usize,
+
+    /// A flag which specifies whether all memory accesses are recorded. When set to false, only
+    /// the last access to each address is kept in the trace.
+    record_trace: bool,
+
+    /// Lookup count for each value range-checked by the memory accesses which were dropped from
+    /// the trace; populated only when `record_trace` is set to false.
+    dropped_range_checks: BTreeMap<u16, usize>,
 }
 
 impl Memory {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new [Memory] which keeps only the last access to each address in its trace.
+    ///
+    /// The range checks of the clock cycle deltas between consecutive accesses to the same address
+    /// are counted as the accesses happen. Thus, the number of rows of the memory trace and of
+    /// the range checks it requires can be determined, but the trace cannot be built.
+    pub fn new_counting() -> Self {
+        Self {
+            record_trace: false,
+            ..Self::default()
+        }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -124,6 +146,12 @@ impl Memory {
         }
     }
 
+    /// Returns the type of the access to the specified context/address which happened at the
+    /// specified cycle, or None if the address wasn't accessed at this cycle.
+    pub fn get_access_at(&self, ctx: ContextId, addr: u32, clk: u32) -> Option<MemoryAccessType> {
+        self.trace.get(&ctx).and_then(|segment| segment.get_access_at(addr, clk))
+    }
+
     // STATE ACCESSORS AND MUTATORS
     // --------------------------------------------------------------------------------------------
 
@@ -133,13 +161,16 @@ impl Memory {
     /// returned. This effectively implies that memory is initialized to ZERO.
     pub fn read(&mut self, ctx: ContextId, addr: u32, clk: u32) -> Word {
         self.num_trace_rows += 1;
-        self.trace.entry(ctx).or_default().read(addr, Felt::from(clk))
+        let value = self.trace.entry(ctx).or_default().read(addr, Felt::from(clk));
+        self.drop_previous_access(ctx, addr, clk);
+        value
     }
 
     /// Writes the provided word at the specified context/address.
     pub fn write(&mut self, ctx: ContextId, addr: u32, clk: u32, value: Word) {
         self.num_trace_rows += 1;
         self.trace.entry(ctx).or_default().write(addr, Felt::from(clk), value);
+        self.drop_previous_access(ctx, addr, clk);
     }
 
     // EXECUTION TRACE GENERATION
@@ -186,6 +217,12 @@ impl Memory {
                 }
             }
         }
+
+        for (&value, &num_lookups) in self.dropped_range_checks.iter() {
+            for _ in 0..num_lookups {
+                range.add_value(value);
+            }
+        }
     }
 
     /// Fills the provided trace fragment with trace data from this memory instance.
@@ -252,6 +289,24 @@ impl Memory {
     // HELPER METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Drops the access preceding the access at the specified clock cycle from the trace of the
+    /// specified address if the trace is not recorded, and counts the range checks required for
+    /// the clock cycle delta between the two accesses.
+    fn drop_previous_access(&mut self, ctx: ContextId, addr: u32, clk: u32) {
+        if self.record_trace {
+            return;
+        }
+
+        let segment = self.trace.get_mut(&ctx).expect("no memory segment for the context");
+        if let Some(prev_clk) = segment.drop_previous_access(addr) {
+            let delta = clk as u64 - prev_clk.as_int() - 1;
+            let (delta_hi, delta_lo) = split_u32_into_u16(delta);
+            for value in [delta_lo, delta_hi] {
+                *self.dropped_range_checks.entry(value).or_default() += 1;
+            }
+        }
+    }
+
     /// Returns the context, address, and clock cycle of the first trace row, or None if the trace
     /// is empty.
     fn get_first_row_info(&self) -> Option<(ContextId, u32, Felt)> {
@@ -274,3 +329,24 @@ impl Memory {
         self.trace.iter().fold(0, |acc, (_, s)| acc + s.size())
     }
 }
+
+impl Default for Memory {
+    fn default() -> Self {
+        Self {
+            trace: BTreeMap::new(),
+            num_trace_rows: 0,
+            record_trace: true,
+            dropped_range_checks: BTreeMap::new(),
+        }
+    }
+}
+
+// MEMORY ACCESS TYPE
+// ================================================================================================
+
+/// Type of an access to a memory address.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum MemoryAccessType {
+    Read,
+    Write,
+}
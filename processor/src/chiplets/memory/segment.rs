@@ -3,7 +3,7 @@ use miden_air::trace::chiplets::memory::{
     Selectors, MEMORY_COPY_READ, MEMORY_INIT_READ, MEMORY_WRITE,
 };
 
-use super::{Felt, Word, INIT_MEM_VALUE};
+use super::{Felt, MemoryAccessType, Word, INIT_MEM_VALUE};
 
 // MEMORY SEGMENT TRACE
 // ================================================================================================
@@ -62,6 +62,16 @@ impl MemorySegmentTrace {
         result
     }
 
+    /// Returns the type of the access to the specified address which happened at the specified
+    /// cycle, or None if the address wasn't accessed at this cycle.
+    pub fn get_access_at(&self, addr: u32, clk: u32) -> Option<MemoryAccessType> {
+        let addr_trace = self.0.get(&addr)?;
+        match addr_trace.binary_search_by(|access| access.clk().as_int().cmp(&(clk as u64))) {
+            Ok(i) => Some(addr_trace[i].access_type()),
+            Err(_) => None,
+        }
+    }
+
     // STATE MUTATORS
     // --------------------------------------------------------------------------------------------
 
@@ -103,6 +113,19 @@ impl MemorySegmentTrace {
             .or_insert_with(|| vec![access]);
     }
 
+    /// Removes the access preceding the last access from the trace of the specified address and
+    /// returns the clock cycle at which it happened, or None if the address was accessed only
+    /// once.
+    pub fn drop_previous_access(&mut self, addr: u32) -> Option<Felt> {
+        let addr_trace = self.0.get_mut(&addr)?;
+        if addr_trace.len() < 2 {
+            return None;
+        }
+
+        debug_assert_eq!(addr_trace.len(), 2, "previous accesses were not dropped");
+        Some(addr_trace.remove(0).clk())
+    }
+
     // INNER VALUE ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -164,6 +187,14 @@ impl MemorySegmentAccess {
         }
     }
 
+    /// Returns the type of this memory access.
+    pub(super) fn access_type(&self) -> MemoryAccessType {
+        match self.op {
+            MemoryOperation::InitRead | MemoryOperation::CopyRead => MemoryAccessType::Read,
+            MemoryOperation::Write => MemoryAccessType::Write,
+        }
+    }
+
     /// Returns the word value for this memory access.
     pub(super) fn value(&self) -> Word {
         self.value
@@ -3,6 +3,8 @@ use super::{
     D0_COL_IDX, D1_COL_IDX, D_INV_COL_IDX, EMPTY_WORD, ONE, V_COL_RANGE,
 };
 use crate::ContextId;
+use crate::MemoryAccessType;
+use crate::RangeChecker;
 use alloc::vec::Vec;
 use miden_air::trace::chiplets::memory::{
     Selectors, MEMORY_COPY_READ, MEMORY_INIT_READ, MEMORY_WRITE, TRACE_WIDTH as MEMORY_TRACE_WIDTH,
@@ -293,6 +295,58 @@ fn mem_get_state_at() {
     assert_eq!(mem.get_state_at(3.into(), 5), vec![(3, value7)]);
 }
 
+#[test]
+fn mem_get_access_at() {
+    let mut mem = Memory::default();
+
+    // write into (ctx = 0, addr = 5) at clk = 1 and read it back at clk = 3
+    mem.write(ContextId::root(), 5, 1, [ONE, ZERO, ZERO, ZERO]);
+    mem.read(ContextId::root(), 5, 3);
+
+    // read from (ctx = 3, addr = 5) at clk = 2
+    mem.read(3.into(), 5, 2);
+
+    assert_eq!(mem.get_access_at(ContextId::root(), 5, 1), Some(MemoryAccessType::Write));
+    assert_eq!(mem.get_access_at(ContextId::root(), 5, 2), None);
+    assert_eq!(mem.get_access_at(ContextId::root(), 5, 3), Some(MemoryAccessType::Read));
+    assert_eq!(mem.get_access_at(ContextId::root(), 4, 1), None);
+
+    assert_eq!(mem.get_access_at(3.into(), 5, 1), None);
+    assert_eq!(mem.get_access_at(3.into(), 5, 2), Some(MemoryAccessType::Read));
+    assert_eq!(mem.get_access_at(2.into(), 5, 2), None);
+}
+
+#[test]
+fn mem_counting() {
+    let mut mem = Memory::default();
+    let mut counting_mem = Memory::new_counting();
+
+    // access the same addresses in two contexts several times, with different clock cycle deltas
+    for mem in [&mut mem, &mut counting_mem] {
+        mem.write(ContextId::root(), 5, 1, [ONE, ZERO, ZERO, ZERO]);
+        mem.read(ContextId::root(), 7, 2);
+        mem.read(3.into(), 5, 4);
+        mem.read(ContextId::root(), 5, 9);
+        mem.write(ContextId::root(), 5, 100_000, [ZERO, ONE, ZERO, ZERO]);
+        mem.read(3.into(), 5, 100_010);
+    }
+
+    // only the last access to each address is kept, but all accesses are counted
+    assert_eq!(mem.trace_len(), counting_mem.trace_len());
+    assert_eq!(mem.size(), counting_mem.size());
+    assert_eq!(counting_mem.get_value(ContextId::root(), 5), Some([ZERO, ONE, ZERO, ZERO]));
+
+    // the range checks of dropped accesses are still required
+    let mut range = RangeChecker::new();
+    mem.append_range_checks(0, &mut range);
+    let mut counting_range = RangeChecker::new_counting();
+    counting_mem.append_range_checks(0, &mut counting_range);
+    assert_eq!(
+        range.get_number_range_checker_rows(),
+        counting_range.get_number_range_checker_rows()
+    );
+}
+
 // HELPER STRUCT & FUNCTIONS
 // ================================================================================================
 
@@ -18,6 +18,7 @@ use hasher::Hasher;
 
 mod memory;
 use memory::Memory;
+pub use memory::MemoryAccessType;
 
 mod kernel_rom;
 use kernel_rom::KernelRom;
@@ -135,6 +136,20 @@ impl Chiplets {
         }
     }
 
+    /// Returns a new [Chiplets] component instantiated with the provided Kernel which performs
+    /// all chiplet computations but only counts the rows of the chiplet traces.
+    ///
+    /// The execution trace cannot be built from such a component.
+    pub fn new_counting(kernel: Kernel) -> Self {
+        Self {
+            clk: 0,
+            hasher: Hasher::new_counting(),
+            bitwise: Bitwise::new_counting(),
+            memory: Memory::new_counting(),
+            kernel_rom: KernelRom::new(kernel),
+        }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -346,6 +361,17 @@ impl Chiplets {
         self.memory.get_state_at(ctx, clk)
     }
 
+    /// Returns the type of the access to the specified context/address which happened at the
+    /// specified cycle, or None if the address wasn't accessed at this cycle.
+    pub fn get_mem_access_at(
+        &self,
+        ctx: ContextId,
+        addr: u32,
+        clk: u32,
+    ) -> Option<MemoryAccessType> {
+        self.memory.get_access_at(ctx, addr, clk)
+    }
+
     /// Returns current size of the memory (in words) across all execution contexts.
     #[cfg(test)]
     pub fn get_mem_size(&self) -> usize {
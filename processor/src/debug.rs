@@ -1,11 +1,11 @@
 use crate::{
-    range::RangeChecker, system::ContextId, Chiplets, ChipletsLengths, Decoder, ExecutionError,
-    Felt, Host, Process, Stack, System, TraceLenSummary,
+    decoder::DebugInfo, range::RangeChecker, system::ContextId, Chiplets, ChipletsLengths, Decoder,
+    ExecutionError, Felt, Host, MemoryAccessType, Process, Stack, System, TraceLenSummary,
 };
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use core::fmt;
-use vm_core::{AssemblyOp, Operation, StackOutputs, Word};
+use vm_core::{AssemblyOp, Location, Operation, StackOutputs, Word};
 
 /// VmState holds a current process state information at a specific clock cycle.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -56,6 +56,7 @@ pub struct VmStateIterator {
     asmop_idx: usize,
     forward: bool,
     trace_len_summary: TraceLenSummary,
+    error_diagnostic: Option<ErrorDiagnostic>,
 }
 
 impl VmStateIterator {
@@ -65,6 +66,10 @@ impl VmStateIterator {
     {
         let (system, decoder, stack, mut range, chiplets, _) = process.into_parts();
         let trace_len_summary = Self::build_trace_len_summary(&system, &mut range, &chiplets);
+        let error_diagnostic = result
+            .as_ref()
+            .err()
+            .map(|error| ErrorDiagnostic::new(error.clone(), system.clk(), &decoder));
 
         Self {
             chiplets,
@@ -76,6 +81,7 @@ impl VmStateIterator {
             asmop_idx: 0,
             forward: true,
             trace_len_summary,
+            error_diagnostic,
         }
     }
 
@@ -180,6 +186,50 @@ impl VmStateIterator {
         &self.trace_len_summary
     }
 
+    /// Returns the procedure call stack at the specified clock cycle.
+    ///
+    /// The call stack reflects all assembly instructions which started executing before the
+    /// specified clock cycle, and thus includes the instruction of the [VmState] at this cycle.
+    pub fn call_stack_at(&self, clk: u32) -> CallStack {
+        CallStack::build(self.decoder.debug_info(), clk)
+    }
+
+    /// Returns the assembly instructions which invoked procedures right before the operation of
+    /// the [VmState] at the specified clock cycle was executed.
+    ///
+    /// Such instructions (e.g., `exec` and `call`) do not execute any operations of their own, and
+    /// thus do not appear in any [VmState]; they need to be recorded in a [CallStack] before the
+    /// assembly instruction of the [VmState] for the call stack to include their locations.
+    pub fn call_sites_at(&self, clk: u32) -> impl Iterator<Item = &AssemblyOp> {
+        // the operation of the state at a given cycle is executed during the previous cycle
+        let call_sites = self.decoder.debug_info().call_sites();
+        let start = call_sites.partition_point(|(call_clk, _)| (*call_clk as u32) + 1 < clk);
+        call_sites[start..]
+            .iter()
+            .take_while(move |(call_clk, _)| (*call_clk as u32) + 1 == clk)
+            .map(|(_, asmop)| asmop)
+    }
+
+    /// Returns the type of the access to the specified context/address made by the operation
+    /// executed in the [VmState] at the specified clock cycle, or None if the address wasn't
+    /// accessed by this operation.
+    pub fn get_mem_access_at(
+        &self,
+        ctx: ContextId,
+        addr: u32,
+        clk: u32,
+    ) -> Option<MemoryAccessType> {
+        // the operation of the state at a given cycle is executed during the previous cycle
+        clk.checked_sub(1)
+            .and_then(|clk| self.chiplets.get_mem_access_at(ctx, addr, clk))
+    }
+
+    /// Returns the diagnostic for the error with which the execution failed, or None if the
+    /// execution completed successfully.
+    pub fn error_diagnostic(&self) -> Option<&ErrorDiagnostic> {
+        self.error_diagnostic.as_ref()
+    }
+
     /// Returns an instance of [TraceLenSummary] based on provided data.
     fn build_trace_len_summary(
         system: &System,
@@ -243,6 +293,196 @@ impl Iterator for VmStateIterator {
     }
 }
 
+// CALL STACK
+// ================================================================================================
+
+/// Name of the context of the program entrypoint, as set by the assembler.
+const MAIN_CONTEXT_NAME: &str = "#main";
+
+/// A frame of a procedure [CallStack].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CallFrame {
+    name: String,
+    asmop: Option<AssemblyOp>,
+}
+
+impl CallFrame {
+    /// Returns the name of the procedure executed in this frame.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the last assembly instruction executed in this frame, if any.
+    pub fn asmop(&self) -> Option<&AssemblyOp> {
+        self.asmop.as_ref()
+    }
+
+    /// Returns the source location of the last assembly instruction executed in this frame, if
+    /// known.
+    pub fn location(&self) -> Option<&Location> {
+        self.asmop.as_ref().and_then(|asmop| asmop.location())
+    }
+}
+
+/// A procedure call stack reconstructed from the contexts of executed assembly instructions.
+///
+/// Since procedures invoked via `exec` are inlined, procedure boundaries are not visible at
+/// runtime. Instead, a procedure is assumed to return when an instruction of one of its callers is
+/// executed, and to be invoked by the procedure on top of the stack when its first instruction is
+/// executed. The instructions invoking procedures (e.g., `exec` and `call`) are recorded as well,
+/// and thus the frame of a caller points to the call site of the procedure invoked by it.
+///
+/// The stack always contains the frame of the program entrypoint at the bottom.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CallStack {
+    frames: Vec<CallFrame>,
+}
+
+impl Default for CallStack {
+    fn default() -> Self {
+        Self {
+            frames: vec![CallFrame {
+                name: MAIN_CONTEXT_NAME.to_string(),
+                asmop: None,
+            }],
+        }
+    }
+}
+
+impl CallStack {
+    /// Returns the frames of this call stack, starting with the outermost frame.
+    pub fn frames(&self) -> &[CallFrame] {
+        &self.frames
+    }
+
+    /// Returns the innermost frame of this call stack.
+    pub fn current_frame(&self) -> &CallFrame {
+        self.frames.last().expect("call stack is empty")
+    }
+
+    /// Updates the call stack with the execution of the specified assembly instruction.
+    ///
+    /// Returns true if a new frame was pushed onto the stack as a result.
+    pub fn record_asmop(&mut self, asmop: &AssemblyOp) -> bool {
+        let name = asmop.context_name();
+        let is_new_frame = match self.frames.iter().rposition(|frame| frame.name == name) {
+            Some(pos) => {
+                self.frames.truncate(pos + 1);
+                false
+            }
+            None => {
+                self.frames.push(CallFrame {
+                    name: name.to_string(),
+                    asmop: None,
+                });
+                true
+            }
+        };
+
+        self.frames.last_mut().expect("call stack is empty").asmop = Some(asmop.clone());
+        is_new_frame
+    }
+
+    /// Returns the call stack resulting from the execution of the assembly instructions and the
+    /// procedure invocations which started before the specified clock cycle.
+    fn build(debug_info: &DebugInfo, clk: u32) -> Self {
+        let mut call_stack = Self::default();
+        let mut call_sites = debug_info.call_sites().iter().peekable();
+        for (asmop_clk, asmop) in debug_info.assembly_ops() {
+            if *asmop_clk as u32 >= clk {
+                break;
+            }
+            // a procedure is invoked before its first instruction is executed
+            while let Some((_, call_site)) =
+                call_sites.next_if(|(call_clk, _)| call_clk <= asmop_clk)
+            {
+                call_stack.record_asmop(call_site);
+            }
+            call_stack.record_asmop(asmop);
+        }
+        for (_, call_site) in call_sites.take_while(|(call_clk, _)| (*call_clk as u32) < clk) {
+            call_stack.record_asmop(call_site);
+        }
+        call_stack
+    }
+}
+
+// ERROR DIAGNOSTIC
+// ================================================================================================
+
+/// Describes an execution failure in terms of the program source: the assembly instruction which
+/// failed, its location in the source code, and the procedure call stack at the time of the
+/// failure.
+///
+/// Instructions and their locations are available only if the program was compiled in debug mode
+/// from sources with location information.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ErrorDiagnostic {
+    error: ExecutionError,
+    clk: u32,
+    call_stack: CallStack,
+}
+
+impl ErrorDiagnostic {
+    /// Returns a diagnostic for the specified error which occurred at the specified clock cycle.
+    fn new(error: ExecutionError, clk: u32, decoder: &Decoder) -> Self {
+        // the failing instruction starts at the cycle at which the execution failed
+        let call_stack = CallStack::build(decoder.debug_info(), clk + 1);
+
+        Self {
+            error,
+            clk,
+            call_stack,
+        }
+    }
+
+    /// Returns the error with which the execution failed.
+    pub fn error(&self) -> &ExecutionError {
+        &self.error
+    }
+
+    /// Returns the clock cycle at which the execution failed.
+    pub fn clk(&self) -> u32 {
+        self.clk
+    }
+
+    /// Returns the last assembly instruction executed before the failure, if known.
+    pub fn asmop(&self) -> Option<&AssemblyOp> {
+        self.call_stack.current_frame().asmop()
+    }
+
+    /// Returns the source location of the last assembly instruction executed before the failure,
+    /// if known.
+    pub fn location(&self) -> Option<&Location> {
+        self.call_stack.current_frame().location()
+    }
+
+    /// Returns the procedure call stack at the time of the failure.
+    pub fn call_stack(&self) -> &CallStack {
+        &self.call_stack
+    }
+}
+
+impl fmt::Display for ErrorDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error)?;
+        if let Some(asmop) = self.asmop() {
+            write!(f, "\n  in `{}`", asmop.op())?;
+            if let Some(location) = asmop.location() {
+                write!(f, " at {location}")?;
+            }
+        }
+        write!(f, "\ncall stack:")?;
+        for (depth, frame) in self.call_stack.frames().iter().rev().enumerate() {
+            write!(f, "\n  {depth}: {}", frame.name())?;
+            if let Some(location) = frame.location() {
+                write!(f, " at {location}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 fn word_to_ints(word: &Word) -> [u64; 4] {
@@ -268,6 +508,11 @@ impl AsmOpInfo {
         Self { asmop, cycle_idx }
     }
 
+    /// Returns the assembly instruction information of this operation.
+    pub fn asmop(&self) -> &AssemblyOp {
+        &self.asmop
+    }
+
     /// Returns the context name for this operation.
     pub fn context_name(&self) -> &str {
         self.asmop.context_name()
@@ -355,6 +355,16 @@ impl Decoder {
         }
     }
 
+    /// Returns an empty instance of [Decoder] which only counts the rows of its execution trace.
+    pub fn new_counting() -> Self {
+        Self {
+            block_stack: BlockStack::default(),
+            span_context: None,
+            trace: DecoderTrace::new_counting(),
+            debug_info: DebugInfo::new(false),
+        }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -627,6 +637,12 @@ impl Decoder {
         self.debug_info.append_asmop(clk, asmop);
     }
 
+    /// Appends an asmop decorator of an instruction invoking a procedure at the specified clock
+    /// cycle to the call site list in debug mode.
+    pub fn append_call_site(&mut self, clk: u32, asmop: AssemblyOp) {
+        self.debug_info.append_call_site(clk, asmop);
+    }
+
     // TEST METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -707,6 +723,7 @@ pub struct DebugInfo {
     in_debug_mode: bool,
     operations: Vec<Operation>,
     assembly_ops: Vec<(usize, AssemblyOp)>,
+    call_sites: Vec<(usize, AssemblyOp)>,
 }
 
 impl DebugInfo {
@@ -715,6 +732,7 @@ impl DebugInfo {
             in_debug_mode,
             operations: Vec::<Operation>::new(),
             assembly_ops: Vec::<(usize, AssemblyOp)>::new(),
+            call_sites: Vec::<(usize, AssemblyOp)>::new(),
         }
     }
 
@@ -734,6 +752,15 @@ impl DebugInfo {
         &self.assembly_ops
     }
 
+    /// Returns list of assembly instructions which invoked procedures without executing any
+    /// operations of their own (e.g., `exec` and `call`) in debug mode.
+    ///
+    /// Each instruction is recorded at the clock cycle at which the execution of the invoked
+    /// procedure started.
+    pub fn call_sites(&self) -> &[(usize, AssemblyOp)] {
+        &self.call_sites
+    }
+
     /// Adds an operation to the operations vector in debug mode.
     #[inline(always)]
     pub fn append_operation(&mut self, op: Operation) {
@@ -746,4 +773,10 @@ impl DebugInfo {
     pub fn append_asmop(&mut self, clk: u32, asmop: AssemblyOp) {
         self.assembly_ops.push((clk as usize, asmop));
     }
+
+    /// Appends an asmop decorator of an instruction invoking a procedure at the specified clock
+    /// cycle to the call site list in debug mode.
+    pub fn append_call_site(&mut self, clk: u32, asmop: AssemblyOp) {
+        self.call_sites.push((clk as usize, asmop));
+    }
 }
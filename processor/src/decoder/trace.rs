@@ -47,6 +47,8 @@ pub struct DecoderTrace {
     op_idx_trace: Vec<Felt>,
     op_batch_flag_trace: [Vec<Felt>; NUM_OP_BATCH_FLAGS],
     op_bit_extra_trace: [Vec<Felt>; NUM_OP_BITS_EXTRA_COLS],
+    record_trace: bool,
+    num_dropped_rows: usize,
 }
 
 impl DecoderTrace {
@@ -63,6 +65,19 @@ impl DecoderTrace {
             op_idx_trace: Vec::with_capacity(MIN_TRACE_LEN),
             op_batch_flag_trace: new_array_vec(MIN_TRACE_LEN),
             op_bit_extra_trace: new_array_vec(MIN_TRACE_LEN),
+            record_trace: true,
+            num_dropped_rows: 0,
+        }
+    }
+
+    /// Initializes a blank [DecoderTrace] which only counts its rows.
+    ///
+    /// Only the most recent rows of such a trace are kept in memory: as soon as
+    /// [MIN_TRACE_LEN] rows accumulate, all rows but the last one are dropped.
+    pub fn new_counting() -> Self {
+        Self {
+            record_trace: false,
+            ..Self::new()
         }
     }
 
@@ -70,8 +85,10 @@ impl DecoderTrace {
     // --------------------------------------------------------------------------------------------
 
     /// Returns the current length of columns in this trace.
+    ///
+    /// This includes the rows which were dropped from a trace which is not recorded.
     pub fn trace_len(&self) -> usize {
-        self.addr_trace.len()
+        self.num_dropped_rows + self.addr_trace.len()
     }
 
     /// Returns the contents of the first 4 registers of the hasher state at the last row.
@@ -105,6 +122,7 @@ impl DecoderTrace {
     /// - Set operation index register to ZERO.
     /// - Set op_batch_flags to ZEROs.
     pub fn append_block_start(&mut self, parent_addr: Felt, op: Operation, h1: Word, h2: Word) {
+        self.drop_old_rows();
         self.addr_trace.push(parent_addr);
         self.append_opcode(op);
 
@@ -149,6 +167,7 @@ impl DecoderTrace {
         is_call: Felt,
         is_syscall: Felt,
     ) {
+        self.drop_old_rows();
         debug_assert!(is_loop_body.as_int() <= 1, "invalid is_loop_body");
         debug_assert!(is_loop.as_int() <= 1, "invalid is_loop");
         debug_assert!(is_call.as_int() <= 1, "invalid is_call");
@@ -193,6 +212,7 @@ impl DecoderTrace {
     /// - Set operation index register to ZERO.
     /// - Set op_batch_flags to ZEROs.
     pub fn append_loop_repeat(&mut self, loop_addr: Felt) {
+        self.drop_old_rows();
         self.addr_trace.push(loop_addr);
         self.append_opcode(Operation::Repeat);
 
@@ -228,6 +248,7 @@ impl DecoderTrace {
         first_op_batch: &[Felt; OP_BATCH_SIZE],
         num_op_groups: Felt,
     ) {
+        self.drop_old_rows();
         self.addr_trace.push(parent_addr);
         self.append_opcode(Operation::Span);
         for (i, &op_group) in first_op_batch.iter().enumerate() {
@@ -256,6 +277,7 @@ impl DecoderTrace {
     /// - Set operation index register to ZERO.
     /// - Set the op_batch_flags based on the current operation group count.
     pub fn append_respan(&mut self, op_batch: &[Felt; OP_BATCH_SIZE]) {
+        self.drop_old_rows();
         self.addr_trace.push(self.last_addr());
         self.append_opcode(Operation::Respan);
         for (i, &op_group) in op_batch.iter().enumerate() {
@@ -297,6 +319,7 @@ impl DecoderTrace {
         group_ops_left: Felt,
         op_idx: Felt,
     ) {
+        self.drop_old_rows();
         self.addr_trace.push(span_addr);
         self.append_opcode(op);
 
@@ -330,6 +353,7 @@ impl DecoderTrace {
     /// - Set op_batch_flags to ZEROs.
     pub fn append_span_end(&mut self, span_hash: Word, is_loop_body: Felt) {
         debug_assert!(is_loop_body.as_int() <= 1, "invalid loop body");
+        self.drop_old_rows();
 
         self.addr_trace.push(self.last_addr());
         self.append_opcode(Operation::End);
@@ -450,6 +474,28 @@ impl DecoderTrace {
     // HELPER FUNCTIONS
     // --------------------------------------------------------------------------------------------
 
+    /// Drops all rows but the last one if this trace is not recorded and [MIN_TRACE_LEN] rows
+    /// have accumulated.
+    ///
+    /// The last row is kept because the values of the next row may be derived from it.
+    fn drop_old_rows(&mut self) {
+        let num_rows = self.addr_trace.len();
+        if self.record_trace || num_rows < MIN_TRACE_LEN {
+            return;
+        }
+
+        let columns = core::iter::once(&mut self.addr_trace)
+            .chain(self.op_bits_trace.iter_mut())
+            .chain(self.hasher_trace.iter_mut())
+            .chain([&mut self.in_span_trace, &mut self.group_count_trace, &mut self.op_idx_trace])
+            .chain(self.op_batch_flag_trace.iter_mut())
+            .chain(self.op_bit_extra_trace.iter_mut());
+        for column in columns {
+            column.drain(..num_rows - 1);
+        }
+        self.num_dropped_rows += num_rows - 1;
+    }
+
     /// Returns the last block address.
     fn last_addr(&self) -> Felt {
         *self.addr_trace.last().expect("no last addr")
@@ -57,6 +57,7 @@ pub enum ExecutionError {
     },
     MerkleStoreLookupFailed(MerkleError),
     MerkleStoreMergeFailed(MerkleError),
+    MerkleStoreStorageFailed(String),
     MerkleStoreUpdateFailed(MerkleError),
     NotBinaryValue(Felt),
     NotU32Value(Felt, Felt),
@@ -65,6 +66,10 @@ pub enum ExecutionError {
     SmtNodePreImageNotValid(Word, usize),
     SyscallTargetNotInKernel(Digest),
     UnexecutableCodeBlock(CodeBlock),
+    UnhandledEvent {
+        clk: u32,
+        event_id: u32,
+    },
 }
 
 impl Display for ExecutionError {
@@ -165,6 +170,9 @@ impl Display for ExecutionError {
             MerkleStoreMergeFailed(reason) => {
                 write!(f, "Advice provider Merkle store backend merge failed: {reason}")
             }
+            MerkleStoreStorageFailed(reason) => {
+                write!(f, "Advice provider Merkle store backend storage failed: {reason}")
+            }
             MerkleStoreUpdateFailed(reason) => {
                 write!(f, "Advice provider Merkle store backend update failed: {reason}")
             }
@@ -193,6 +201,9 @@ impl Display for ExecutionError {
             UnexecutableCodeBlock(block) => {
                 write!(f, "Execution reached unexecutable code block {block:?}")
             }
+            UnhandledEvent { clk, event_id } => {
+                write!(f, "No handler registered for event with id {event_id} emitted at clock cycle {clk}")
+            }
         }
     }
 }
@@ -0,0 +1,798 @@
+use super::{InnerNodeInfo, MerklePath, MerkleStore, NodeIndex, RpoDigest};
+use alloc::{collections::BTreeMap, string::ToString, vec::Vec};
+use core::{borrow::Borrow, cell::RefCell, fmt};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufReader, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+use vm_core::{
+    crypto::{
+        hash::Rpo256,
+        merkle::{EmptySubtreeRoots, MerkleError},
+    },
+    utils::{Deserializable, Serializable},
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Name of the file in which the nodes of the store are kept.
+const NODES_FILE_NAME: &str = "nodes.bin";
+
+/// Name of the file in which the offsets of the nodes in the nodes file are kept.
+const INDEX_FILE_NAME: &str = "index.bin";
+
+/// Size of a serialized digest in bytes.
+const DIGEST_SIZE: usize = 32;
+
+/// Size of a node record in bytes; a record consists of the node hash followed by the hashes of
+/// its left and right children.
+const NODE_RECORD_SIZE: usize = 3 * DIGEST_SIZE;
+
+/// Maximum depth of the empty subtrees which are implicitly contained in the store.
+const MAX_EMPTY_SUBTREE_DEPTH: u8 = 255;
+
+/// Size of the header of the index file in bytes; the header consists of the capacity of the
+/// index, the number of its entries, and the length of the indexed part of the nodes file.
+const INDEX_HEADER_SIZE: u64 = 24;
+
+/// Size of an index slot in bytes; a slot consists of a node hash followed by the offset of the
+/// node record in the nodes file.
+const INDEX_SLOT_SIZE: u64 = DIGEST_SIZE as u64 + 8;
+
+/// Minimum number of slots in the index.
+const MIN_INDEX_CAPACITY: u64 = 1 << 10;
+
+// FILE MERKLE STORE
+// ================================================================================================
+
+/// A Merkle store which keeps its nodes in an append-only file.
+///
+/// Similarly to [MerkleStore], the store is a collection of inner nodes keyed by their hashes, and
+/// thus can contain many Merkle trees sharing common subtrees. The offsets of the nodes in the
+/// nodes file are kept in an on-disk hash table, and the nodes themselves are loaded lazily and
+/// kept in a cache of bounded size. Thus, the memory used by the store does not depend on the
+/// number of nodes in it.
+///
+/// The nodes are never removed from the file; updating a tree appends the nodes of the new path
+/// to the file, and the nodes of the old tree remain accessible via the old root. Roots of empty
+/// subtrees up to depth 255 are implicitly contained in the store.
+#[derive(Debug)]
+pub struct FileMerkleStore {
+    file: File,
+    index: OffsetTable,
+    empty_nodes: BTreeMap<RpoDigest, StoreEntry>,
+    cache: RefCell<NodeCache>,
+}
+
+impl FileMerkleStore {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Opens the store located in the specified directory, creating it if it doesn't exist.
+    ///
+    /// At most `cache_capacity` nodes are kept in memory at any given time.
+    ///
+    /// If the last node record in the nodes file is incomplete (e.g., because the process writing
+    /// it was interrupted), the record is discarded. If the index file is missing or does not
+    /// match the nodes file, the index is rebuilt from the nodes file.
+    ///
+    /// # Errors
+    /// Returns an error if the directory or the store files cannot be created, read or written,
+    /// or if the nodes file is malformed.
+    pub fn open(dir: &Path, cache_capacity: usize) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        let file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(dir.join(NODES_FILE_NAME))?;
+
+        // discard a partially written record at the end of the file
+        let file_len = file.metadata()?.len();
+        let nodes_len = file_len - file_len % NODE_RECORD_SIZE as u64;
+        if nodes_len != file_len {
+            file.set_len(nodes_len)?;
+        }
+
+        let index = OffsetTable::open(dir.join(INDEX_FILE_NAME), &file, nodes_len)?;
+
+        Ok(Self {
+            file,
+            index,
+            empty_nodes: empty_subtree_nodes(),
+            cache: RefCell::new(NodeCache::new(cache_capacity)),
+        })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of nodes stored in the file.
+    pub fn num_nodes(&self) -> usize {
+        self.index.len as usize
+    }
+
+    /// Returns the node at the specified index of the tree with the specified root.
+    pub fn get_node(
+        &self,
+        root: RpoDigest,
+        index: NodeIndex,
+    ) -> Result<RpoDigest, FileMerkleStoreError> {
+        self.traverse(root, index, |_| ())
+    }
+
+    /// Returns the path from the node at the specified index of the tree with the specified root
+    /// to the root of the tree.
+    ///
+    /// The path starts with the sibling of the node and ends with a child of the root.
+    pub fn get_path(
+        &self,
+        root: RpoDigest,
+        index: NodeIndex,
+    ) -> Result<MerklePath, FileMerkleStoreError> {
+        let mut path = Vec::with_capacity(index.depth() as usize);
+        self.traverse(root, index, |sibling| path.push(sibling))?;
+        path.reverse();
+        Ok(MerklePath::new(path))
+    }
+
+    /// Returns the depth of the first leaf or empty node on the path from the root of the tree
+    /// with the specified root and depth to the leaf at the specified index.
+    pub fn get_leaf_depth(
+        &self,
+        root: RpoDigest,
+        tree_depth: u8,
+        index: u64,
+    ) -> Result<u8, FileMerkleStoreError> {
+        if tree_depth == 0 {
+            return Err(MerkleError::DepthTooSmall(tree_depth).into());
+        } else if tree_depth > 64 {
+            return Err(MerkleError::DepthTooBig(tree_depth as u64).into());
+        }
+        NodeIndex::new(tree_depth, index)?;
+
+        if self.get_entry(&root)?.is_none() {
+            return Err(MerkleError::RootNotInStore(root).into());
+        }
+
+        let mut hash = root;
+        for depth in 0..=tree_depth {
+            if hash == *EmptySubtreeRoots::entry(tree_depth, depth) {
+                return Ok(depth);
+            }
+            let entry = match self.get_entry(&hash)? {
+                Some(entry) => entry,
+                None => return Ok(depth),
+            };
+            let is_right = depth < tree_depth && (index >> (tree_depth - depth - 1)) & 1 == 1;
+            hash = if is_right { entry.right } else { entry.left };
+        }
+
+        Err(MerkleError::DepthTooBig(tree_depth as u64 + 1).into())
+    }
+
+    /// Returns a [MerkleStore] containing all nodes of the trees with the specified roots.
+    pub fn subset<I, R>(&self, roots: I) -> Result<MerkleStore, FileMerkleStoreError>
+    where
+        I: Iterator<Item = R>,
+        R: Borrow<RpoDigest>,
+    {
+        let mut nodes = BTreeMap::new();
+        let mut pending: Vec<RpoDigest> = roots.map(|root| *root.borrow()).collect();
+        while let Some(hash) = pending.pop() {
+            // empty subtrees are contained in every Merkle store
+            if nodes.contains_key(&hash) || self.empty_nodes.contains_key(&hash) {
+                continue;
+            }
+            if let Some(entry) = self.get_entry(&hash)? {
+                pending.push(entry.left);
+                pending.push(entry.right);
+                nodes.insert(hash, entry);
+            }
+        }
+
+        Ok(nodes
+            .into_iter()
+            .map(|(value, entry)| InnerNodeInfo {
+                value,
+                left: entry.left,
+                right: entry.right,
+            })
+            .collect())
+    }
+
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Inserts the specified inner nodes into the store.
+    pub fn extend<I>(&mut self, nodes: I) -> Result<(), FileMerkleStoreError>
+    where
+        I: IntoIterator<Item = InnerNodeInfo>,
+    {
+        nodes
+            .into_iter()
+            .try_for_each(|node| self.insert(node.value, StoreEntry::new(node.left, node.right)))
+    }
+
+    /// Sets the node at the specified index of the tree with the specified root to the specified
+    /// value, and returns the path to the updated node together with the root of the new tree.
+    ///
+    /// The tree with the specified root remains in the store.
+    pub fn set_node(
+        &mut self,
+        root: RpoDigest,
+        index: NodeIndex,
+        value: RpoDigest,
+    ) -> Result<(MerklePath, RpoDigest), FileMerkleStoreError> {
+        let path = self.get_path(root, index)?;
+
+        let mut hash = value;
+        let mut node_pos = index.value();
+        for &sibling in path.iter() {
+            let entry = if node_pos & 1 == 0 {
+                StoreEntry::new(hash, sibling)
+            } else {
+                StoreEntry::new(sibling, hash)
+            };
+            hash = entry.hash();
+            self.insert(hash, entry)?;
+            node_pos >>= 1;
+        }
+
+        Ok((path, hash))
+    }
+
+    /// Adds a node with the specified children to the store and returns its hash.
+    pub fn merge_roots(
+        &mut self,
+        left: RpoDigest,
+        right: RpoDigest,
+    ) -> Result<RpoDigest, FileMerkleStoreError> {
+        let entry = StoreEntry::new(left, right);
+        let hash = entry.hash();
+        self.insert(hash, entry)?;
+        Ok(hash)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Walks the tree with the specified root down to the node at the specified index, passing
+    /// the siblings of the visited nodes to the provided closure, and returns the hash of the node.
+    fn traverse<F>(
+        &self,
+        root: RpoDigest,
+        index: NodeIndex,
+        mut on_sibling: F,
+    ) -> Result<RpoDigest, FileMerkleStoreError>
+    where
+        F: FnMut(RpoDigest),
+    {
+        let mut hash = root;
+        for depth in (0..index.depth()).rev() {
+            let entry = match self.get_entry(&hash)? {
+                Some(entry) => entry,
+                None if hash == root => return Err(MerkleError::RootNotInStore(root).into()),
+                None => return Err(MerkleError::NodeNotInStore(hash, index).into()),
+            };
+            if (index.value() >> depth) & 1 == 0 {
+                on_sibling(entry.right);
+                hash = entry.left;
+            } else {
+                on_sibling(entry.left);
+                hash = entry.right;
+            }
+        }
+
+        Ok(hash)
+    }
+
+    /// Returns the children of the node with the specified hash, or None if the node is not in
+    /// the store.
+    fn get_entry(&self, hash: &RpoDigest) -> Result<Option<StoreEntry>, FileMerkleStoreError> {
+        if let Some(entry) = self.empty_nodes.get(hash) {
+            return Ok(Some(*entry));
+        }
+        if let Some(entry) = self.cache.borrow_mut().get(hash) {
+            return Ok(Some(entry));
+        }
+        let offset = match self.index.get(hash)? {
+            Some(offset) => offset,
+            None => return Ok(None),
+        };
+
+        let mut record = [0u8; NODE_RECORD_SIZE];
+        let mut file = &self.file;
+        file.seek(SeekFrom::Start(offset))?;
+        file.read_exact(&mut record)?;
+        let entry = StoreEntry::new(
+            read_digest(&record[DIGEST_SIZE..2 * DIGEST_SIZE])?,
+            read_digest(&record[2 * DIGEST_SIZE..])?,
+        );
+
+        self.cache.borrow_mut().insert(*hash, entry);
+        Ok(Some(entry))
+    }
+
+    /// Appends the node with the specified hash to the nodes file, unless it is already in the
+    /// store.
+    fn insert(&mut self, hash: RpoDigest, entry: StoreEntry) -> Result<(), FileMerkleStoreError> {
+        if self.empty_nodes.contains_key(&hash) || self.index.get(&hash)?.is_some() {
+            return Ok(());
+        }
+
+        let mut record = Vec::with_capacity(NODE_RECORD_SIZE);
+        hash.write_into(&mut record);
+        entry.left.write_into(&mut record);
+        entry.right.write_into(&mut record);
+
+        let offset = self.file.seek(SeekFrom::End(0))?;
+        self.file.write_all(&record)?;
+        self.index.insert(hash, offset, &self.file)?;
+        self.cache.borrow_mut().insert(hash, entry);
+        Ok(())
+    }
+}
+
+// FILE MERKLE STORE ERROR
+// ================================================================================================
+
+/// Errors which can occur when accessing a [FileMerkleStore].
+#[derive(Debug)]
+pub enum FileMerkleStoreError {
+    Merkle(MerkleError),
+    Io(io::Error),
+}
+
+impl fmt::Display for FileMerkleStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use FileMerkleStoreError::*;
+        match self {
+            Merkle(err) => write!(f, "Merkle store operation failed: {err}"),
+            Io(err) => write!(f, "failed to access Merkle store files: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for FileMerkleStoreError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Merkle(_) => None,
+            Self::Io(err) => Some(err),
+        }
+    }
+}
+
+impl From<MerkleError> for FileMerkleStoreError {
+    fn from(err: MerkleError) -> Self {
+        Self::Merkle(err)
+    }
+}
+
+impl From<io::Error> for FileMerkleStoreError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+// STORE ENTRY
+// ================================================================================================
+
+/// Children of an inner node of a Merkle tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct StoreEntry {
+    left: RpoDigest,
+    right: RpoDigest,
+}
+
+impl StoreEntry {
+    fn new(left: RpoDigest, right: RpoDigest) -> Self {
+        Self { left, right }
+    }
+
+    /// Returns the hash of the node with these children.
+    fn hash(&self) -> RpoDigest {
+        Rpo256::merge(&[self.left, self.right])
+    }
+}
+
+// OFFSET TABLE
+// ================================================================================================
+
+/// An on-disk hash table which maps node hashes to the offsets of their records in the nodes file.
+///
+/// The table is an array of slots addressed via linear probing, each holding a node hash and the
+/// offset of its record incremented by one (so that a zeroed slot is empty). The number of entries
+/// is kept below half of the capacity, and the table is rebuilt with twice the capacity once this
+/// limit is reached.
+///
+/// The header of the table records the length of the part of the nodes file indexed by it. When
+/// this length does not match the nodes file (e.g., because the process was interrupted after a
+/// record was appended to the nodes file but before it was indexed), the table is rebuilt from
+/// the nodes file.
+#[derive(Debug)]
+struct OffsetTable {
+    path: PathBuf,
+    file: File,
+    capacity: u64,
+    len: u64,
+    indexed_len: u64,
+}
+
+impl OffsetTable {
+    /// Opens the table located at the specified path, rebuilding it if it doesn't match the first
+    /// `nodes_len` bytes of the specified nodes file.
+    fn open(path: PathBuf, nodes: &File, nodes_len: u64) -> io::Result<Self> {
+        if let Ok(file) = OpenOptions::new().read(true).write(true).open(&path) {
+            let mut header = [0u8; INDEX_HEADER_SIZE as usize];
+            if (&file).read_exact(&mut header).is_ok() {
+                let [capacity, len, indexed_len] = read_header(&header);
+                let is_valid = capacity.is_power_of_two()
+                    && len <= capacity / 2
+                    && indexed_len == nodes_len
+                    && file.metadata()?.len() == INDEX_HEADER_SIZE + capacity * INDEX_SLOT_SIZE;
+                if is_valid {
+                    return Ok(Self {
+                        path,
+                        file,
+                        capacity,
+                        len,
+                        indexed_len,
+                    });
+                }
+            }
+        }
+
+        let num_records = nodes_len / NODE_RECORD_SIZE as u64;
+        let capacity = (2 * num_records + 1).next_power_of_two().max(MIN_INDEX_CAPACITY);
+        Self::build(path, nodes, nodes_len, capacity)
+    }
+
+    /// Creates a table with the specified capacity at the specified path, indexing the first
+    /// `nodes_len` bytes of the specified nodes file.
+    fn build(path: PathBuf, nodes: &File, nodes_len: u64, capacity: u64) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)?;
+        file.set_len(INDEX_HEADER_SIZE + capacity * INDEX_SLOT_SIZE)?;
+
+        // the table is marked as indexing an empty nodes file until all records are inserted
+        let mut table = Self {
+            path,
+            file,
+            capacity,
+            len: 0,
+            indexed_len: 0,
+        };
+        table.write_header()?;
+
+        let mut reader = BufReader::new(nodes);
+        reader.seek(SeekFrom::Start(0))?;
+        let mut record = [0u8; NODE_RECORD_SIZE];
+        let mut offset = 0;
+        while offset < nodes_len {
+            reader.read_exact(&mut record)?;
+            let hash = read_digest(&record[..DIGEST_SIZE])?;
+            let (slot, existing) = table.find_slot(&hash)?;
+            if existing.is_none() {
+                table.write_slot(slot, &hash, offset)?;
+                table.len += 1;
+            }
+            offset += NODE_RECORD_SIZE as u64;
+        }
+
+        table.indexed_len = nodes_len;
+        table.write_header()?;
+        Ok(table)
+    }
+
+    /// Returns the offset of the record of the node with the specified hash, or None if the node
+    /// is not in the table.
+    fn get(&self, hash: &RpoDigest) -> io::Result<Option<u64>> {
+        self.find_slot(hash).map(|(_, offset)| offset)
+    }
+
+    /// Adds the offset of the record of the node with the specified hash to the table; the record
+    /// must be the last record of the specified nodes file.
+    ///
+    /// The node must not be in the table already.
+    fn insert(&mut self, hash: RpoDigest, offset: u64, nodes: &File) -> io::Result<()> {
+        let nodes_len = offset + NODE_RECORD_SIZE as u64;
+        if 2 * (self.len + 1) > self.capacity {
+            *self = Self::build(self.path.clone(), nodes, nodes_len, 2 * self.capacity)?;
+            return Ok(());
+        }
+
+        let (slot, _) = self.find_slot(&hash)?;
+        self.write_slot(slot, &hash, offset)?;
+        self.len += 1;
+        self.indexed_len = nodes_len;
+        self.write_header()
+    }
+
+    /// Returns the index of the slot holding the specified hash together with the offset stored
+    /// in it, or the index of the first empty slot probed for the hash if the hash is not in the
+    /// table.
+    fn find_slot(&self, hash: &RpoDigest) -> io::Result<(u64, Option<u64>)> {
+        let hash_bytes = hash.as_bytes();
+        let mask = self.capacity - 1;
+        let mut slot = u64::from_le_bytes(hash_bytes[..8].try_into().unwrap()) & mask;
+        let mut file = &self.file;
+        let mut slot_bytes = [0u8; INDEX_SLOT_SIZE as usize];
+        loop {
+            file.seek(SeekFrom::Start(INDEX_HEADER_SIZE + slot * INDEX_SLOT_SIZE))?;
+            file.read_exact(&mut slot_bytes)?;
+            let offset = u64::from_le_bytes(slot_bytes[DIGEST_SIZE..].try_into().unwrap());
+            if offset == 0 {
+                return Ok((slot, None));
+            } else if slot_bytes[..DIGEST_SIZE] == hash_bytes {
+                return Ok((slot, Some(offset - 1)));
+            }
+            slot = (slot + 1) & mask;
+        }
+    }
+
+    fn write_slot(&mut self, slot: u64, hash: &RpoDigest, offset: u64) -> io::Result<()> {
+        let mut slot_bytes = [0u8; INDEX_SLOT_SIZE as usize];
+        slot_bytes[..DIGEST_SIZE].copy_from_slice(&hash.as_bytes());
+        slot_bytes[DIGEST_SIZE..].copy_from_slice(&(offset + 1).to_le_bytes());
+        self.file.seek(SeekFrom::Start(INDEX_HEADER_SIZE + slot * INDEX_SLOT_SIZE))?;
+        self.file.write_all(&slot_bytes)
+    }
+
+    fn write_header(&mut self) -> io::Result<()> {
+        let mut header = [0u8; INDEX_HEADER_SIZE as usize];
+        let values = [self.capacity, self.len, self.indexed_len];
+        for (chunk, value) in header.chunks_mut(8).zip(values) {
+            chunk.copy_from_slice(&value.to_le_bytes());
+        }
+        self.file.seek(SeekFrom::Start(0))?;
+        self.file.write_all(&header)
+    }
+}
+
+// NODE CACHE
+// ================================================================================================
+
+/// A least-recently-used cache of store entries.
+#[derive(Debug)]
+struct NodeCache {
+    capacity: usize,
+    tick: u64,
+    entries: BTreeMap<RpoDigest, (StoreEntry, u64)>,
+    usage: BTreeMap<u64, RpoDigest>,
+}
+
+impl NodeCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            tick: 0,
+            entries: BTreeMap::new(),
+            usage: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the cached entry for the specified hash, marking it as most recently used.
+    fn get(&mut self, hash: &RpoDigest) -> Option<StoreEntry> {
+        let tick = self.next_tick();
+        let (entry, last_used) = self.entries.get_mut(hash)?;
+        self.usage.remove(last_used);
+        self.usage.insert(tick, *hash);
+        *last_used = tick;
+        Some(*entry)
+    }
+
+    /// Caches the specified entry, evicting the least recently used entry if the cache is full.
+    fn insert(&mut self, hash: RpoDigest, entry: StoreEntry) {
+        if self.capacity == 0 || self.get(&hash).is_some() {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            if let Some((_, evicted)) = self.usage.pop_first() {
+                self.entries.remove(&evicted);
+            }
+        }
+        let tick = self.next_tick();
+        self.entries.insert(hash, (entry, tick));
+        self.usage.insert(tick, hash);
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the inner nodes of empty subtrees of all depths up to the maximum depth.
+fn empty_subtree_nodes() -> BTreeMap<RpoDigest, StoreEntry> {
+    let roots = EmptySubtreeRoots::empty_hashes(MAX_EMPTY_SUBTREE_DEPTH);
+    roots
+        .iter()
+        .rev()
+        .zip(roots.iter().rev().skip(1))
+        .map(|(child, parent)| (*parent, StoreEntry::new(*child, *child)))
+        .collect()
+}
+
+/// Reads the capacity, the number of entries and the indexed length from an index file header.
+fn read_header(header: &[u8; INDEX_HEADER_SIZE as usize]) -> [u64; 3] {
+    let mut values = [0; 3];
+    for (value, chunk) in values.iter_mut().zip(header.chunks(8)) {
+        *value = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+    values
+}
+
+/// Reads a digest from the specified bytes.
+fn read_digest(bytes: &[u8]) -> io::Result<RpoDigest> {
+    RpoDigest::read_from_bytes(bytes)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        FileMerkleStore, FileMerkleStoreError, MerkleError, MerkleStore, NodeIndex,
+        INDEX_FILE_NAME, MIN_INDEX_CAPACITY, NODES_FILE_NAME, NODE_RECORD_SIZE,
+    };
+    use crate::crypto::{MerkleTree, RpoDigest};
+    use crate::Word;
+    use alloc::vec::Vec;
+    use std::{env, fs, path::PathBuf};
+    use vm_core::{Felt, ZERO};
+
+    #[test]
+    fn file_store_matches_merkle_store() {
+        let dir = temp_store_dir("matches_merkle_store");
+        let tree = MerkleTree::new(leaves(8)).unwrap();
+        let mut expected: MerkleStore = MerkleStore::from(&tree);
+
+        // a small cache forces nodes to be reloaded from the file
+        let mut store = FileMerkleStore::open(&dir, 2).unwrap();
+        store.extend(tree.inner_nodes()).unwrap();
+        assert_eq!(store.num_nodes(), 7);
+
+        for value in 0..8 {
+            let index = NodeIndex::new(3, value).unwrap();
+            assert_eq!(
+                store.get_node(tree.root(), index).unwrap(),
+                expected.get_node(tree.root(), index).unwrap()
+            );
+            assert_eq!(
+                store.get_path(tree.root(), index).unwrap(),
+                expected.get_path(tree.root(), index).unwrap().path
+            );
+        }
+
+        // update a leaf; the old tree remains in the store
+        let index = NodeIndex::new(3, 5).unwrap();
+        let value = RpoDigest::from([Felt::new(42), ZERO, ZERO, ZERO]);
+        let (path, root) = store.set_node(tree.root(), index, value).unwrap();
+        let expected_root = expected.set_node(tree.root(), index, value).unwrap();
+        assert_eq!(path, expected_root.path);
+        assert_eq!(root, expected_root.root);
+        assert_eq!(store.get_node(root, index).unwrap(), value);
+        assert_eq!(store.get_node(tree.root(), index).unwrap(), leaves(8)[5].into());
+
+        // merge the old and the new tree
+        let merged = store.merge_roots(tree.root(), root).unwrap();
+        assert_eq!(merged, expected.merge_roots(tree.root(), root).unwrap());
+        let index = NodeIndex::new(4, 13).unwrap();
+        assert_eq!(store.get_node(merged, index).unwrap(), value);
+
+        // the subset of the new tree contains the same nodes as in the in-memory store
+        let subset = store.subset([root].iter()).unwrap();
+        let index = NodeIndex::new(3, 5).unwrap();
+        assert_eq!(subset.get_path(root, index).unwrap().path, path);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn file_store_persists_nodes() {
+        let dir = temp_store_dir("persists_nodes");
+        let tree = MerkleTree::new(leaves(4)).unwrap();
+        let index = NodeIndex::new(2, 1).unwrap();
+
+        {
+            let mut store = FileMerkleStore::open(&dir, 16).unwrap();
+            store.extend(tree.inner_nodes()).unwrap();
+        }
+
+        let store = FileMerkleStore::open(&dir, 16).unwrap();
+        assert_eq!(store.num_nodes(), 3);
+        assert_eq!(store.get_node(tree.root(), index).unwrap(), leaves(4)[1].into());
+
+        // unknown roots are reported as missing
+        let root = RpoDigest::from([Felt::new(7), ZERO, ZERO, ZERO]);
+        let err = store.get_node(root, index).unwrap_err();
+        assert!(
+            matches!(err, FileMerkleStoreError::Merkle(MerkleError::RootNotInStore(r)) if r == root)
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn file_store_recovers_from_interrupted_writes() {
+        let dir = temp_store_dir("interrupted_writes");
+        let tree = MerkleTree::new(leaves(4)).unwrap();
+        let index = NodeIndex::new(2, 3).unwrap();
+
+        {
+            let mut store = FileMerkleStore::open(&dir, 0).unwrap();
+            store.extend(tree.inner_nodes()).unwrap();
+        }
+
+        // a partially written record at the end of the nodes file is discarded
+        let nodes_path = dir.join(NODES_FILE_NAME);
+        let mut nodes = fs::read(&nodes_path).unwrap();
+        nodes.extend_from_slice(&[1; 40]);
+        fs::write(&nodes_path, &nodes).unwrap();
+
+        let mut store = FileMerkleStore::open(&dir, 0).unwrap();
+        assert_eq!(fs::metadata(&nodes_path).unwrap().len(), 3 * NODE_RECORD_SIZE as u64);
+        assert_eq!(store.get_node(tree.root(), index).unwrap(), leaves(4)[3].into());
+        let root = store.merge_roots(tree.root(), tree.root()).unwrap();
+        drop(store);
+
+        // a missing index is rebuilt from the nodes file
+        fs::remove_file(dir.join(INDEX_FILE_NAME)).unwrap();
+        let store = FileMerkleStore::open(&dir, 0).unwrap();
+        assert_eq!(store.num_nodes(), 4);
+        let index = NodeIndex::new(3, 7).unwrap();
+        assert_eq!(store.get_node(root, index).unwrap(), leaves(4)[3].into());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn file_store_grows_index() {
+        let dir = temp_store_dir("grows_index");
+        let num_leaves = MIN_INDEX_CAPACITY * 2;
+        let tree = MerkleTree::new(leaves(num_leaves)).unwrap();
+
+        {
+            let mut store = FileMerkleStore::open(&dir, 16).unwrap();
+            store.extend(tree.inner_nodes()).unwrap();
+            assert_eq!(store.num_nodes() as u64, num_leaves - 1);
+        }
+
+        let store = FileMerkleStore::open(&dir, 16).unwrap();
+        assert_eq!(store.num_nodes() as u64, num_leaves - 1);
+        for value in [0, 17, num_leaves - 1] {
+            let index = NodeIndex::new(11, value).unwrap();
+            assert_eq!(
+                store.get_node(tree.root(), index).unwrap(),
+                leaves(num_leaves)[value as usize].into()
+            );
+        }
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    fn leaves(num_leaves: u64) -> Vec<Word> {
+        (0..num_leaves).map(|i| [Felt::new(i), ZERO, ZERO, ZERO]).collect()
+    }
+
+    fn temp_store_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("miden-file-store-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+}
@@ -162,7 +162,7 @@ pub(crate) fn merge_merkle_nodes<S: ProcessState, A: AdviceProvider>(
 
 /// Reads (start_addr, end_addr) tuple from the specified elements of the operand stack (
 /// without modifying the state of the stack), and verifies that memory range is valid.
-fn get_mem_addr_range<S: ProcessState>(
+pub(super) fn get_mem_addr_range<S: ProcessState>(
     process: &S,
     start_idx: usize,
     end_idx: usize,
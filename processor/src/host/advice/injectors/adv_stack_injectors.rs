@@ -1,4 +1,7 @@
-use super::super::{AdviceSource, ExecutionError, Felt, HostResponse};
+use super::{
+    super::{AdviceSource, ExecutionError, Felt, HostResponse},
+    adv_map_injectors::get_mem_addr_range,
+};
 use crate::{AdviceProvider, Ext2InttError, FieldElement, ProcessState, ZERO};
 use alloc::vec::Vec;
 use vm_core::{QuadExtension, SignatureKind};
@@ -150,6 +153,45 @@ pub(crate) fn push_u64_div_result<S: ProcessState, A: AdviceProvider>(
     Ok(HostResponse::None)
 }
 
+/// Pushes the result of 256-bit unsigned integer division (both the quotient and the remainder)
+/// onto the advice stack.
+///
+/// Inputs:
+///   Operand stack: [b7, ..., b0, a7, ..., a0, ...]
+///   Advice stack: [...]
+///
+/// Outputs:
+///   Operand stack: [b7, ..., b0, a7, ..., a0, ...]
+///   Advice stack: [q0, ..., q7, r0, ..., r7, ...]
+///
+/// Where (a0, ..., a7) and (b0, ..., b7) are the 32-bit limbs of the dividend and the divisor
+/// respectively (with a0 representing the 32 least significant bits and a7 representing the
+/// 32 most significant bits). Similarly, (q0, ..., q7) and (r0, ..., r7) represent the quotient
+/// and the remainder respectively.
+///
+/// # Errors
+/// Returns an error if:
+/// - Any of the limbs of the dividend or the divisor is not a valid u32 value.
+/// - The divisor is ZERO.
+pub(crate) fn push_u256_div_result<S: ProcessState, A: AdviceProvider>(
+    advice_provider: &mut A,
+    process: &S,
+) -> Result<HostResponse, ExecutionError> {
+    let divisor = read_u256_limbs(process, 0)?;
+    if divisor.iter().all(|&limb| limb == 0) {
+        return Err(ExecutionError::DivideByZero(process.clk()));
+    }
+    let dividend = read_u256_limbs(process, 8)?;
+
+    let (quotient, remainder) = u256_divmod(dividend, divisor);
+
+    for &limb in remainder.iter().rev().chain(quotient.iter().rev()) {
+        advice_provider.push_stack(AdviceSource::Value(Felt::from(limb)))?;
+    }
+
+    Ok(HostResponse::None)
+}
+
 /// Given an element in a quadratic extension field on the top of the stack (i.e., a0, b1),
 /// computes its multiplicative inverse and push the result onto the advice stack.
 ///
@@ -393,6 +435,52 @@ pub(crate) fn push_ilog2<S: ProcessState, A: AdviceProvider>(
     Ok(HostResponse::None)
 }
 
+/// Pushes the address of the first element not less than `value` in a sorted array of field
+/// elements located in memory[start_addr..end_addr] onto the advice stack.
+///
+/// Inputs:
+///   Operand stack: [value, start_addr, end_addr, ...]
+///   Advice stack: [...]
+///
+/// Outputs:
+///   Operand stack: [value, start_addr, end_addr, ...]
+///   Advice stack: [addr, ...]
+///
+/// Where `addr` is equal to `end_addr` if all elements of the array are less than `value`. The
+/// elements of the array are read from the first elements of the memory words, and uninitialized
+/// memory is treated as ZERO.
+///
+/// The address is found via binary search, and thus if the array is not sorted, the returned
+/// address is not guaranteed to be the first one satisfying the condition. However, it is
+/// guaranteed that the element preceding `addr` (if any) is less than `value`, and the element at
+/// `addr` (if any) is not less than `value`.
+///
+/// # Errors
+/// Returns an error if `start_addr` or `end_addr` is not a valid memory address, or if
+/// `start_addr` is greater than `end_addr`.
+pub(crate) fn push_lower_bound<S: ProcessState, A: AdviceProvider>(
+    advice_provider: &mut A,
+    process: &S,
+) -> Result<HostResponse, ExecutionError> {
+    let value = process.get_stack_item(0).as_int();
+    let (start_addr, end_addr) = get_mem_addr_range(process, 1, 2)?;
+    let ctx = process.ctx();
+
+    let (mut lo, mut hi) = (start_addr, end_addr);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        let element = process.get_mem_value(ctx, mid).map_or(0, |word| word[0].as_int());
+        if element < value {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+
+    advice_provider.push_stack(AdviceSource::Value(Felt::from(lo)))?;
+    Ok(HostResponse::None)
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -402,6 +490,53 @@ fn u64_to_u32_elements(value: u64) -> (Felt, Felt) {
     (hi, lo)
 }
 
+/// Reads a 256-bit unsigned integer represented by eight 32-bit limbs from the operand stack, and
+/// returns its limbs with the least significant limb first.
+///
+/// The most significant limb is expected to be located at the specified stack position.
+fn read_u256_limbs<S: ProcessState>(process: &S, start: usize) -> Result<[u32; 8], ExecutionError> {
+    let mut limbs = [0_u32; 8];
+    for (i, limb) in limbs.iter_mut().rev().enumerate() {
+        let value = process.get_stack_item(start + i);
+        *limb = value
+            .as_int()
+            .try_into()
+            .map_err(|_| ExecutionError::NotU32Value(value, ZERO))?;
+    }
+    Ok(limbs)
+}
+
+/// Divides two 256-bit unsigned integers represented by their 32-bit limbs (with the least
+/// significant limb first), and returns the quotient and the remainder.
+///
+/// The divisor is expected to be non-zero.
+fn u256_divmod(dividend: [u32; 8], divisor: [u32; 8]) -> ([u32; 8], [u32; 8]) {
+    let mut quotient = [0_u32; 8];
+    let mut remainder = [0_u32; 8];
+    for bit in (0..256).rev() {
+        // shift the next bit of the dividend into the remainder; the bit shifted out of the most
+        // significant limb is kept since the shifted remainder may not fit into 256 bits
+        let mut carry = (dividend[bit / 32] >> (bit % 32)) & 1;
+        for limb in remainder.iter_mut() {
+            let shifted_out = *limb >> 31;
+            *limb = (*limb << 1) | carry;
+            carry = shifted_out;
+        }
+
+        if carry == 1 || remainder.iter().rev().ge(divisor.iter().rev()) {
+            let mut borrow = false;
+            for (limb, &divisor_limb) in remainder.iter_mut().zip(divisor.iter()) {
+                let (diff, borrow1) = limb.overflowing_sub(divisor_limb);
+                let (diff, borrow2) = diff.overflowing_sub(borrow as u32);
+                *limb = diff;
+                borrow = borrow1 || borrow2;
+            }
+            quotient[bit / 32] |= 1 << (bit % 32);
+        }
+    }
+    (quotient, remainder)
+}
+
 /// Gets the top stack element, applies a provided function to it and pushes it to the advice
 /// provider.
 fn push_transformed_stack_top<S: ProcessState, A: AdviceProvider>(
@@ -19,8 +19,15 @@ pub use inputs::AdviceInputs;
 mod injectors;
 
 mod providers;
+#[cfg(feature = "std")]
+pub use providers::FileAdviceProvider;
 pub use providers::{MemAdviceProvider, RecAdviceProvider};
 
+#[cfg(feature = "std")]
+mod file_store;
+#[cfg(feature = "std")]
+pub use file_store::{FileMerkleStore, FileMerkleStoreError};
+
 mod source;
 pub use source::AdviceSource;
 
@@ -64,6 +71,7 @@ pub trait AdviceProvider: Sized {
             } => self.copy_map_value_to_adv_stack(process, *include_len, *key_offset),
             AdviceInjector::UpdateMerkleNode => self.update_operand_stack_merkle_node(process),
             AdviceInjector::U64Div => self.push_u64_div_result(process),
+            AdviceInjector::U256Div => self.push_u256_div_result(process),
             AdviceInjector::Ext2Inv => self.push_ext2_inv_result(process),
             AdviceInjector::Ext2Intt => self.push_ext2_intt_result(process),
             AdviceInjector::SmtGet => self.push_smtget_inputs(process),
@@ -74,6 +82,7 @@ pub trait AdviceProvider: Sized {
             AdviceInjector::U32Clo => self.push_leading_ones(process),
             AdviceInjector::U32Cto => self.push_trailing_ones(process),
             AdviceInjector::ILog2 => self.push_ilog2(process),
+            AdviceInjector::LowerBound => self.push_lower_bound(process),
 
             AdviceInjector::MemToMap => self.insert_mem_values_into_adv_map(process),
             AdviceInjector::HdwordToMap { domain } => {
@@ -283,6 +292,33 @@ pub trait AdviceProvider: Sized {
         injectors::adv_stack_injectors::push_u64_div_result(self, process)
     }
 
+    /// Pushes the result of 256-bit unsigned integer division (both the quotient and the
+    /// remainder) onto the advice stack.
+    ///
+    /// Inputs:
+    ///   Operand stack: [b7, ..., b0, a7, ..., a0, ...]
+    ///   Advice stack: [...]
+    ///
+    /// Outputs:
+    ///   Operand stack: [b7, ..., b0, a7, ..., a0, ...]
+    ///   Advice stack: [q0, ..., q7, r0, ..., r7, ...]
+    ///
+    /// Where (a0, ..., a7) and (b0, ..., b7) are the 32-bit limbs of the dividend and the divisor
+    /// respectively (with a0 representing the 32 least significant bits and a7 representing the
+    /// 32 most significant bits). Similarly, (q0, ..., q7) and (r0, ..., r7) represent the
+    /// quotient and the remainder respectively.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Any of the limbs of the dividend or the divisor is not a valid u32 value.
+    /// - The divisor is ZERO.
+    fn push_u256_div_result<S: ProcessState>(
+        &mut self,
+        process: &S,
+    ) -> Result<HostResponse, ExecutionError> {
+        injectors::adv_stack_injectors::push_u256_div_result(self, process)
+    }
+
     /// Given an element in a quadratic extension field on the top of the stack (i.e., a0, b1),
     /// computes its multiplicative inverse and push the result onto the advice stack.
     ///
@@ -445,6 +481,29 @@ pub trait AdviceProvider: Sized {
         injectors::adv_stack_injectors::push_ilog2(self, process)
     }
 
+    /// Pushes the address of the first element not less than `value` in a sorted array of field
+    /// elements located in memory[start_addr..end_addr] onto the advice stack.
+    ///
+    /// Inputs:
+    ///   Operand stack: [value, start_addr, end_addr, ...]
+    ///   Advice stack: [...]
+    ///
+    /// Outputs:
+    ///   Operand stack: [value, start_addr, end_addr, ...]
+    ///   Advice stack: [addr, ...]
+    ///
+    /// Where `addr` is equal to `end_addr` if all elements of the array are less than `value`.
+    ///
+    /// # Errors
+    /// Returns an error if `start_addr` or `end_addr` is not a valid memory address, or if
+    /// `start_addr` is greater than `end_addr`.
+    fn push_lower_bound<S: ProcessState>(
+        &mut self,
+        process: &S,
+    ) -> Result<HostResponse, ExecutionError> {
+        injectors::adv_stack_injectors::push_lower_bound(self, process)
+    }
+
     // DEFAULT MERKLE STORE INJECTORS
     // --------------------------------------------------------------------------------------------
 
@@ -710,7 +769,10 @@ pub trait AdviceProvider: Sized {
     /// nodes which are descendants of the specified roots.
     ///
     /// The roots for which no descendants exist in this Merkle store are ignored.
-    fn get_store_subset<I, R>(&self, roots: I) -> MerkleStore
+    ///
+    /// # Errors
+    /// Returns an error if the nodes of the Merkle store cannot be read from its backend storage.
+    fn get_store_subset<I, R>(&self, roots: I) -> Result<MerkleStore, ExecutionError>
     where
         I: Iterator<Item = R>,
         R: Borrow<RpoDigest>;
@@ -797,7 +859,7 @@ where
         T::merge_roots(self, lhs, rhs)
     }
 
-    fn get_store_subset<I, R>(&self, roots: I) -> MerkleStore
+    fn get_store_subset<I, R>(&self, roots: I) -> Result<MerkleStore, ExecutionError>
     where
         I: Iterator<Item = R>,
         R: Borrow<RpoDigest>,
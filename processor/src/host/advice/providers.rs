@@ -10,6 +10,15 @@ use vm_core::utils::collections::KvMap;
 use vm_core::utils::collections::RecordingMap;
 use vm_core::SignatureKind;
 
+#[cfg(feature = "std")]
+use super::{FileMerkleStore, FileMerkleStoreError};
+#[cfg(feature = "std")]
+use alloc::string::ToString;
+#[cfg(feature = "std")]
+use std::path::Path;
+#[cfg(feature = "std")]
+use vm_core::crypto::merkle::MerkleError;
+
 // TYPE ALIASES
 // ================================================================================================
 
@@ -216,12 +225,12 @@ where
             .map_err(ExecutionError::MerkleStoreMergeFailed)
     }
 
-    fn get_store_subset<I, R>(&self, roots: I) -> MerkleStore
+    fn get_store_subset<I, R>(&self, roots: I) -> Result<MerkleStore, ExecutionError>
     where
         I: Iterator<Item = R>,
         R: core::borrow::Borrow<RpoDigest>,
     {
-        self.store.subset(roots).into_inner().into_iter().collect()
+        Ok(self.store.subset(roots).into_inner().into_iter().collect())
     }
 }
 
@@ -318,7 +327,7 @@ impl AdviceProvider for MemAdviceProvider {
         self.provider.merge_roots(lhs, rhs)
     }
 
-    fn get_store_subset<I, R>(&self, roots: I) -> MerkleStore
+    fn get_store_subset<I, R>(&self, roots: I) -> Result<MerkleStore, ExecutionError>
         where
             I: Iterator<Item = R>,
             R: core::borrow::Borrow<RpoDigest> {
@@ -439,7 +448,7 @@ impl AdviceProvider for RecAdviceProvider {
         self.provider.merge_roots(lhs, rhs)
     }
 
-    fn get_store_subset<I, R>(&self, roots: I) -> MerkleStore
+    fn get_store_subset<I, R>(&self, roots: I) -> Result<MerkleStore, ExecutionError>
         where
             I: Iterator<Item = R>,
             R: core::borrow::Borrow<RpoDigest> {
@@ -478,3 +487,214 @@ impl RecAdviceProvider {
         (proof, stack, map, store.into())
     }
 }
+
+// FILE ADVICE PROVIDER
+// ================================================================================================
+
+/// An [AdviceProvider] implementation which keeps its Merkle store in a file on disk.
+///
+/// The Merkle store is backed by a [FileMerkleStore], and thus only a bounded number of Merkle
+/// tree nodes is kept in memory at any given time. The advice stack and the advice map are kept in
+/// memory.
+///
+/// Since the store is persistent, Merkle trees created or updated during program execution remain
+/// available to subsequent executions which use the same store directory.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct FileAdviceProvider {
+    provider: BaseAdviceProvider<SimpleAdviceMap, SimpleMerkleMap>,
+    store: FileMerkleStore,
+}
+
+#[cfg(feature = "std")]
+impl FileAdviceProvider {
+    /// Default number of Merkle tree nodes kept in memory.
+    pub const DEFAULT_CACHE_CAPACITY: usize = 1 << 16;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new [FileAdviceProvider] instantiated from the specified inputs, with the Merkle
+    /// store located in the specified directory.
+    ///
+    /// The nodes of the Merkle store in the inputs are added to the store on disk, and at most
+    /// `cache_capacity` nodes of the store are kept in memory.
+    ///
+    /// # Errors
+    /// Returns an error if the store cannot be opened or the input nodes cannot be written to it.
+    pub fn open(
+        dir: &Path,
+        inputs: AdviceInputs,
+        cache_capacity: usize,
+    ) -> Result<Self, FileMerkleStoreError> {
+        let (mut stack, map, store) = inputs.into_parts();
+        stack.reverse();
+
+        let mut file_store = FileMerkleStore::open(dir, cache_capacity)?;
+        file_store.extend(store.inner_nodes())?;
+
+        let provider = BaseAdviceProvider {
+            stack,
+            map: map.into_iter().collect(),
+            store: MerkleStore::default(),
+        };
+
+        Ok(Self {
+            provider,
+            store: file_store,
+        })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the Merkle store of this advice provider.
+    pub fn store(&self) -> &FileMerkleStore {
+        &self.store
+    }
+}
+
+#[cfg(feature = "std")]
+impl AdviceProvider for FileAdviceProvider {
+    // ADVICE STACK AND MAP
+    // --------------------------------------------------------------------------------------------
+
+    fn pop_stack<S: ProcessState>(&mut self, process: &S) -> Result<Felt, ExecutionError> {
+        self.provider.pop_stack(process)
+    }
+
+    fn pop_stack_word<S: ProcessState>(&mut self, process: &S) -> Result<Word, ExecutionError> {
+        self.provider.pop_stack_word(process)
+    }
+
+    fn pop_stack_dword<S: ProcessState>(
+        &mut self,
+        process: &S,
+    ) -> Result<[Word; 2], ExecutionError> {
+        self.provider.pop_stack_dword(process)
+    }
+
+    fn push_stack(&mut self, source: AdviceSource) -> Result<(), ExecutionError> {
+        self.provider.push_stack(source)
+    }
+
+    fn insert_into_map(&mut self, key: Word, values: Vec<Felt>) -> Result<(), ExecutionError> {
+        self.provider.insert_into_map(key, values)
+    }
+
+    fn get_signature(
+        &self,
+        kind: SignatureKind,
+        pub_key: Word,
+        msg: Word,
+    ) -> Result<Vec<Felt>, ExecutionError> {
+        self.provider.get_signature(kind, pub_key, msg)
+    }
+
+    fn get_mapped_values(&self, key: &RpoDigest) -> Option<&[Felt]> {
+        self.provider.get_mapped_values(key)
+    }
+
+    // MERKLE STORE
+    // --------------------------------------------------------------------------------------------
+
+    fn get_tree_node(
+        &self,
+        root: Word,
+        depth: &Felt,
+        index: &Felt,
+    ) -> Result<Word, ExecutionError> {
+        let index = NodeIndex::from_elements(depth, index).map_err(|_| {
+            ExecutionError::InvalidTreeNodeIndex {
+                depth: *depth,
+                value: *index,
+            }
+        })?;
+        self.store
+            .get_node(root.into(), index)
+            .map(|v| v.into())
+            .map_err(|err| store_error(err, ExecutionError::MerkleStoreLookupFailed))
+    }
+
+    fn get_merkle_path(
+        &self,
+        root: Word,
+        depth: &Felt,
+        index: &Felt,
+    ) -> Result<MerklePath, ExecutionError> {
+        let index = NodeIndex::from_elements(depth, index).map_err(|_| {
+            ExecutionError::InvalidTreeNodeIndex {
+                depth: *depth,
+                value: *index,
+            }
+        })?;
+        self.store
+            .get_path(root.into(), index)
+            .map_err(|err| store_error(err, ExecutionError::MerkleStoreLookupFailed))
+    }
+
+    fn get_leaf_depth(
+        &self,
+        root: Word,
+        tree_depth: &Felt,
+        index: &Felt,
+    ) -> Result<u8, ExecutionError> {
+        let tree_depth = u8::try_from(tree_depth.as_int())
+            .map_err(|_| ExecutionError::InvalidTreeDepth { depth: *tree_depth })?;
+        self.store
+            .get_leaf_depth(root.into(), tree_depth, index.as_int())
+            .map_err(|err| store_error(err, ExecutionError::MerkleStoreLookupFailed))
+    }
+
+    fn update_merkle_node(
+        &mut self,
+        root: Word,
+        depth: &Felt,
+        index: &Felt,
+        value: Word,
+    ) -> Result<(MerklePath, Word), ExecutionError> {
+        let node_index = NodeIndex::from_elements(depth, index).map_err(|_| {
+            ExecutionError::InvalidTreeNodeIndex {
+                depth: *depth,
+                value: *index,
+            }
+        })?;
+        self.store
+            .set_node(root.into(), node_index, value.into())
+            .map(|(path, root)| (path, root.into()))
+            .map_err(|err| store_error(err, ExecutionError::MerkleStoreUpdateFailed))
+    }
+
+    fn merge_roots(&mut self, lhs: Word, rhs: Word) -> Result<Word, ExecutionError> {
+        self.store
+            .merge_roots(lhs.into(), rhs.into())
+            .map(|v| v.into())
+            .map_err(|err| store_error(err, ExecutionError::MerkleStoreMergeFailed))
+    }
+
+    fn get_store_subset<I, R>(&self, roots: I) -> Result<MerkleStore, ExecutionError>
+    where
+        I: Iterator<Item = R>,
+        R: core::borrow::Borrow<RpoDigest>,
+    {
+        self.store
+            .subset(roots)
+            .map_err(|err| store_error(err, ExecutionError::MerkleStoreLookupFailed))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Converts an error of a [FileMerkleStore] into an [ExecutionError], using the specified
+/// constructor for Merkle store errors.
+#[cfg(feature = "std")]
+fn store_error(
+    err: FileMerkleStoreError,
+    merkle_error: fn(MerkleError) -> ExecutionError,
+) -> ExecutionError {
+    match err {
+        FileMerkleStoreError::Merkle(err) => merkle_error(err),
+        FileMerkleStoreError::Io(err) => ExecutionError::MerkleStoreStorageFailed(err.to_string()),
+    }
+}
@@ -0,0 +1,201 @@
+use super::{AdviceProvider, ExecutionError, HostResponse, ProcessState};
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
+use core::ops::RangeInclusive;
+
+// EVENT HANDLER
+// ================================================================================================
+
+/// Defines an interface for handling events emitted by the VM via the `emit.<event_id>`
+/// instruction.
+///
+/// A handler is provided with a read-only view of the current state of the VM ([ProcessState])
+/// and with mutable access to the advice provider of the host. The latter can be used to respond
+/// to the event by pushing values onto the advice stack, or by inserting values into the advice
+/// map.
+///
+/// This trait is implemented for all closures with a matching signature, and thus handlers can
+/// be specified either as closures or as trait objects.
+pub trait EventHandler<A> {
+    /// Handles the event with the specified ID.
+    fn on_event(
+        &mut self,
+        process: &dyn ProcessState,
+        advice_provider: &mut A,
+        event_id: u32,
+    ) -> Result<HostResponse, ExecutionError>;
+}
+
+impl<A, F> EventHandler<A> for F
+where
+    F: FnMut(&dyn ProcessState, &mut A, u32) -> Result<HostResponse, ExecutionError>,
+{
+    fn on_event(
+        &mut self,
+        process: &dyn ProcessState,
+        advice_provider: &mut A,
+        event_id: u32,
+    ) -> Result<HostResponse, ExecutionError> {
+        self(process, advice_provider, event_id)
+    }
+}
+
+// UNHANDLED EVENT POLICY
+// ================================================================================================
+
+/// Defines how an [EventHandlerRegistry] treats events for which no handler was registered.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum UnhandledEventPolicy {
+    /// Execution fails with [ExecutionError::UnhandledEvent].
+    Error,
+    /// A message describing the event is printed to the standard output (only when the `std`
+    /// feature is enabled) and execution continues.
+    #[default]
+    Warn,
+    /// The event is silently ignored.
+    Ignore,
+}
+
+// EVENT HANDLER REGISTRY
+// ================================================================================================
+
+/// A collection of [EventHandler]s keyed by event ID.
+///
+/// Handlers can be registered either for a single event ID or for an inclusive range of event IDs.
+/// When an event is dispatched, a handler registered for the exact event ID takes precedence;
+/// otherwise, ranges are searched in the order in which they were registered and the first range
+/// containing the event ID is used. If no handler is found, the event is processed according to
+/// the [UnhandledEventPolicy] of the registry.
+pub struct EventHandlerRegistry<A> {
+    handlers: BTreeMap<u32, Box<dyn EventHandler<A>>>,
+    range_handlers: Vec<(RangeInclusive<u32>, Box<dyn EventHandler<A>>)>,
+    unhandled_policy: UnhandledEventPolicy,
+}
+
+impl<A> Default for EventHandlerRegistry<A> {
+    fn default() -> Self {
+        Self {
+            handlers: BTreeMap::new(),
+            range_handlers: Vec::new(),
+            unhandled_policy: UnhandledEventPolicy::default(),
+        }
+    }
+}
+
+impl<A: AdviceProvider> EventHandlerRegistry<A> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new empty registry which processes unhandled events according to the specified
+    /// policy.
+    pub fn new(unhandled_policy: UnhandledEventPolicy) -> Self {
+        Self {
+            unhandled_policy,
+            ..Default::default()
+        }
+    }
+
+    /// Registers the handler for the specified event ID and returns the updated registry.
+    pub fn with_handler<H>(mut self, event_id: u32, handler: H) -> Self
+    where
+        H: EventHandler<A> + 'static,
+    {
+        self.register(event_id, handler);
+        self
+    }
+
+    /// Registers the handler for the specified range of event IDs and returns the updated
+    /// registry.
+    pub fn with_range_handler<H>(mut self, event_ids: RangeInclusive<u32>, handler: H) -> Self
+    where
+        H: EventHandler<A> + 'static,
+    {
+        self.register_range(event_ids, handler);
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the policy used for events without a registered handler.
+    pub fn unhandled_policy(&self) -> UnhandledEventPolicy {
+        self.unhandled_policy
+    }
+
+    /// Returns true if a handler is registered for the specified event ID.
+    pub fn contains(&self, event_id: u32) -> bool {
+        self.handlers.contains_key(&event_id)
+            || self.range_handlers.iter().any(|(range, _)| range.contains(&event_id))
+    }
+
+    // PUBLIC MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Registers the handler for the specified event ID, replacing the handler previously
+    /// registered for this ID (if any).
+    pub fn register<H>(&mut self, event_id: u32, handler: H)
+    where
+        H: EventHandler<A> + 'static,
+    {
+        self.handlers.insert(event_id, Box::new(handler));
+    }
+
+    /// Registers the handler for the specified inclusive range of event IDs.
+    ///
+    /// Handlers registered for individual event IDs take precedence over range handlers, and
+    /// earlier registered ranges take precedence over later ones.
+    pub fn register_range<H>(&mut self, event_ids: RangeInclusive<u32>, handler: H)
+    where
+        H: EventHandler<A> + 'static,
+    {
+        self.range_handlers.push((event_ids, Box::new(handler)));
+    }
+
+    /// Sets the policy used for events without a registered handler.
+    pub fn set_unhandled_policy(&mut self, policy: UnhandledEventPolicy) {
+        self.unhandled_policy = policy;
+    }
+
+    /// Dispatches the event with the specified ID to the handler registered for it.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The handler for the event returns an error.
+    /// - No handler is registered for the event and the policy for unhandled events is
+    ///   [UnhandledEventPolicy::Error].
+    pub fn handle_event<S: ProcessState>(
+        &mut self,
+        process: &S,
+        advice_provider: &mut A,
+        event_id: u32,
+    ) -> Result<HostResponse, ExecutionError> {
+        let handler = match self.handlers.get_mut(&event_id) {
+            Some(handler) => Some(handler),
+            None => self
+                .range_handlers
+                .iter_mut()
+                .find(|(range, _)| range.contains(&event_id))
+                .map(|(_, handler)| handler),
+        };
+
+        match handler {
+            Some(handler) => handler.on_event(process, advice_provider, event_id),
+            None => match self.unhandled_policy {
+                UnhandledEventPolicy::Error => Err(ExecutionError::UnhandledEvent {
+                    clk: process.clk(),
+                    event_id,
+                }),
+                UnhandledEventPolicy::Warn => {
+                    #[cfg(feature = "std")]
+                    std::println!(
+                        "Event with id {} emitted at step {} in context {}",
+                        event_id,
+                        process.clk(),
+                        process.ctx()
+                    );
+                    Ok(HostResponse::None)
+                }
+                UnhandledEventPolicy::Ignore => Ok(HostResponse::None),
+            },
+        }
+    }
+}
@@ -1,10 +1,14 @@
 use super::{ExecutionError, Felt, ProcessState};
 use crate::MemAdviceProvider;
+use alloc::{collections::BTreeMap, string::String};
 use vm_core::{crypto::merkle::MerklePath, AdviceInjector, DebugOptions, Word};
 
 pub(super) mod advice;
 use advice::{AdviceExtractor, AdviceProvider};
 
+mod events;
+pub use events::{EventHandler, EventHandlerRegistry, UnhandledEventPolicy};
+
 #[cfg(feature = "std")]
 mod debug;
 
@@ -264,21 +268,64 @@ impl From<HostResponse> for Felt {
 // ================================================================================================
 
 /// A default [Host] implementation that provides the essential functionality required by the VM.
+///
+/// Events emitted by the VM are dispatched to the handlers registered in the host's
+/// [EventHandlerRegistry]. By default, the registry is empty and all events are handled according
+/// to [UnhandledEventPolicy::Warn].
+///
+/// When an assertion fails, the message associated with the error code of the assertion (if any)
+/// is attached to the returned [ExecutionError::FailedAssertion] error.
 pub struct DefaultHost<A> {
     adv_provider: A,
+    event_handlers: EventHandlerRegistry<A>,
+    error_messages: BTreeMap<u32, String>,
 }
 
 impl Default for DefaultHost<MemAdviceProvider> {
     fn default() -> Self {
-        Self {
-            adv_provider: MemAdviceProvider::default(),
-        }
+        Self::new(MemAdviceProvider::default())
     }
 }
 
 impl<A: AdviceProvider> DefaultHost<A> {
     pub fn new(adv_provider: A) -> Self {
-        Self { adv_provider }
+        Self {
+            adv_provider,
+            event_handlers: EventHandlerRegistry::default(),
+            error_messages: BTreeMap::new(),
+        }
+    }
+
+    /// Replaces the event handlers of this host with the specified registry and returns the
+    /// updated host.
+    pub fn with_event_handlers(mut self, event_handlers: EventHandlerRegistry<A>) -> Self {
+        self.event_handlers = event_handlers;
+        self
+    }
+
+    /// Adds the provided error messages keyed by error codes to this host and returns the updated
+    /// host.
+    ///
+    /// If a message for an error code has already been added, the message is replaced.
+    pub fn with_error_messages<I>(mut self, error_messages: I) -> Self
+    where
+        I: IntoIterator<Item = (u32, String)>,
+    {
+        self.error_messages.extend(error_messages);
+        self
+    }
+
+    /// Returns the message associated with the specified error code, if any.
+    pub fn error_message(&self, err_code: u32) -> Option<&str> {
+        self.error_messages.get(&err_code).map(String::as_str)
+    }
+
+    pub fn event_handlers(&self) -> &EventHandlerRegistry<A> {
+        &self.event_handlers
+    }
+
+    pub fn event_handlers_mut(&mut self) -> &mut EventHandlerRegistry<A> {
+        &mut self.event_handlers
     }
 
     #[cfg(any(test, feature = "internals"))]
@@ -312,4 +359,20 @@ impl<A: AdviceProvider> Host for DefaultHost<A> {
     ) -> Result<HostResponse, ExecutionError> {
         self.adv_provider.set_advice(process, &injector)
     }
+
+    fn on_event<S: ProcessState>(
+        &mut self,
+        process: &S,
+        event_id: u32,
+    ) -> Result<HostResponse, ExecutionError> {
+        self.event_handlers.handle_event(process, &mut self.adv_provider, event_id)
+    }
+
+    fn on_assert_failed<S: ProcessState>(&mut self, process: &S, err_code: u32) -> ExecutionError {
+        ExecutionError::FailedAssertion {
+            clk: process.clk(),
+            err_code,
+            err_msg: self.error_messages.get(&err_code).cloned(),
+        }
+    }
 }
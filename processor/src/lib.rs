@@ -16,8 +16,8 @@ use miden_air::trace::{
 pub use miden_air::{ExecutionOptions, ExecutionOptionsError};
 pub use vm_core::{
     chiplets::hasher::Digest, crypto::merkle::SMT_DEPTH, errors::InputError,
-    utils::DeserializationError, AdviceInjector, AssemblyOp, Felt, Kernel, Operation, Program,
-    ProgramInfo, QuadExtension, StackInputs, StackOutputs, Word, EMPTY_WORD, ONE, ZERO,
+    utils::DeserializationError, AdviceInjector, AssemblyOp, Felt, Kernel, Location, Operation,
+    Program, ProgramInfo, QuadExtension, StackInputs, StackOutputs, Word, EMPTY_WORD, ONE, ZERO,
 };
 use vm_core::{
     code_blocks::{
@@ -44,16 +44,19 @@ mod range;
 use range::RangeChecker;
 
 mod host;
+#[cfg(feature = "std")]
+pub use host::advice::{FileAdviceProvider, FileMerkleStore, FileMerkleStoreError};
 pub use host::{
     advice::{
         AdviceExtractor, AdviceInputs, AdviceMap, AdviceProvider, AdviceSource, MemAdviceProvider,
         RecAdviceProvider,
     },
-    DefaultHost, Host, HostResponse,
+    DefaultHost, EventHandler, EventHandlerRegistry, Host, HostResponse, UnhandledEventPolicy,
 };
 
 mod chiplets;
 use chiplets::Chiplets;
+pub use chiplets::MemoryAccessType;
 
 mod trace;
 use trace::TraceFragment;
@@ -65,7 +68,7 @@ pub use errors::{ExecutionError, Ext2InttError};
 pub mod utils;
 
 mod debug;
-pub use debug::{AsmOpInfo, VmState, VmStateIterator};
+pub use debug::{AsmOpInfo, CallFrame, CallStack, ErrorDiagnostic, VmState, VmStateIterator};
 
 // RE-EXPORTS
 // ================================================================================================
@@ -135,6 +138,40 @@ where
     Ok(trace)
 }
 
+/// Returns a summary of the trace lengths resulting from executing the provided program against
+/// the provided inputs.
+///
+/// This is a dry-run version of [execute()]: the program is executed, but the components of the
+/// VM only count the number of rows required by each segment of the trace (including each of the
+/// chiplets) instead of recording the rows. Specifically, only the most recent rows of the system,
+/// stack, and decoder segments are kept in memory, chiplets perform their computations without
+/// recording them, and range checks are counted per checked value. Thus, the memory required by
+/// this function does not grow with the number of executed cycles (apart from the memory used by
+/// the program itself), and it runs faster than [execute()], which also needs to build the trace.
+#[tracing::instrument("estimate_trace_len", skip_all)]
+pub fn estimate_trace_len<H>(
+    program: &Program,
+    stack_inputs: StackInputs,
+    host: H,
+    options: ExecutionOptions,
+) -> Result<TraceLenSummary, ExecutionError>
+where
+    H: Host,
+{
+    let mut process = Process::new_counting(program.kernel().clone(), stack_inputs, host, options);
+    process.execute(program)?;
+
+    let (system, _, _, mut range, chiplets, _) = process.into_parts();
+    chiplets.append_range_checks(&mut range);
+    let range_table_len = range.get_number_range_checker_rows();
+
+    Ok(TraceLenSummary::new(
+        system.clk() as usize,
+        range_table_len,
+        ChipletsLengths::new(&chiplets),
+    ))
+}
+
 /// Returns an iterator which allows callers to step through the execution and inspect VM state at
 /// each execution step.
 pub fn execute_iter<H>(program: &Program, stack_inputs: StackInputs, host: H) -> VmStateIterator
@@ -197,6 +234,30 @@ where
         )
     }
 
+    /// Creates a new process with the provided inputs which only counts the rows of the execution
+    /// trace.
+    ///
+    /// Such a process executes programs in the same way as a regular process, but its components
+    /// do not record the rows of their trace segments, and thus the execution trace cannot be
+    /// built from it.
+    pub fn new_counting(
+        kernel: Kernel,
+        stack_inputs: StackInputs,
+        host: H,
+        execution_options: ExecutionOptions,
+    ) -> Self {
+        Self {
+            system: System::new_counting(),
+            decoder: Decoder::new_counting(),
+            stack: Stack::new_counting(&stack_inputs),
+            range: RangeChecker::new_counting(),
+            chiplets: Chiplets::new_counting(kernel),
+            host: RefCell::new(host),
+            max_cycles: execution_options.max_cycles(),
+            enable_tracing: execution_options.enable_tracing(),
+        }
+    }
+
     fn initialize(
         kernel: Kernel,
         stack: StackInputs,
@@ -505,7 +566,13 @@ where
             }
             Decorator::AsmOp(assembly_op) => {
                 if self.decoder.in_debug_mode() {
-                    self.decoder.append_asmop(self.system.clk(), assembly_op.clone());
+                    // instructions which take no cycles invoke procedures, and thus only record the
+                    // call sites of these procedures
+                    if assembly_op.num_cycles() == 0 {
+                        self.decoder.append_call_site(self.system.clk(), assembly_op.clone());
+                    } else {
+                        self.decoder.append_asmop(self.system.clk(), assembly_op.clone());
+                    }
                 }
             }
             Decorator::Event(id) => {
@@ -116,7 +116,8 @@ where
 #[cfg(test)]
 mod tests {
     use super::{super::Operation, super::STACK_TOP_SIZE, Felt, Process, FMP_MAX, FMP_MIN};
-    use crate::{StackInputs, ONE, ZERO};
+    use crate::{DefaultHost, ExecutionError, ExecutionOptions, Kernel, StackInputs, ONE, ZERO};
+    use alloc::string::ToString;
 
     const MAX_PROC_LOCALS: u64 = 2_u64.pow(31) - 1;
 
@@ -131,6 +132,31 @@ mod tests {
         assert!(process.execute_op(Operation::Assert(0)).is_ok());
     }
 
+    #[test]
+    fn op_assert_with_error_message() {
+        // messages of failed assertions are provided by the host
+        let host = DefaultHost::default().with_error_messages([(7, "balance too low".to_string())]);
+        let options = ExecutionOptions::default();
+        let mut process = Process::new(Kernel::default(), StackInputs::default(), host, options);
+        process.execute_op(Operation::Noop).unwrap();
+
+        let Err(ExecutionError::FailedAssertion {
+            err_code, err_msg, ..
+        }) = process.execute_op(Operation::Assert(7))
+        else {
+            panic!("assertion did not fail");
+        };
+        assert_eq!(err_code, 7);
+        assert_eq!(err_msg.as_deref(), Some("balance too low"));
+
+        let Err(ExecutionError::FailedAssertion { err_msg, .. }) =
+            process.execute_op(Operation::Assert(8))
+        else {
+            panic!("assertion did not fail");
+        };
+        assert_eq!(err_msg, None);
+    }
+
     #[test]
     fn op_fmpupdate() {
         let mut process = Process::new_dummy_with_empty_stack();
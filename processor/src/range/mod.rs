@@ -44,6 +44,10 @@ pub struct RangeChecker {
     /// Each cycle is mapped to a vector of the range checks requested at that cycle, which can come
     /// from the stack, memory, or both.
     cycle_lookups: BTreeMap<u32, Vec<u16>>,
+    /// A flag which specifies whether range check lookups are tracked per clock cycle. This is
+    /// set to false when the number of rows of the range checker is counted without building its
+    /// trace.
+    record_trace: bool,
 }
 
 impl RangeChecker {
@@ -59,6 +63,18 @@ impl RangeChecker {
         Self {
             lookups,
             cycle_lookups: BTreeMap::new(),
+            record_trace: true,
+        }
+    }
+
+    /// Returns a new [RangeChecker] which tracks only the lookup count for each checked value.
+    ///
+    /// This is sufficient to determine the number of rows of the range checker trace, but not to
+    /// build the trace.
+    pub fn new_counting() -> Self {
+        Self {
+            record_trace: false,
+            ..Self::new()
         }
     }
 
@@ -81,6 +97,10 @@ impl RangeChecker {
             self.add_value(*value);
         }
 
+        if !self.record_trace {
+            return;
+        }
+
         // track the range check requests at each cycle
         // TODO: optimize this to use a struct instead of vectors, e.g.:
         // struct MemoryLookupValues {
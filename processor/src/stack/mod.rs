@@ -1,6 +1,7 @@
 use super::{Felt, FieldElement, StackInputs, StackOutputs, ONE, STACK_TRACE_WIDTH, ZERO};
 use alloc::vec::Vec;
 use core::cmp;
+use miden_air::trace::MIN_TRACE_LEN;
 use vm_core::{stack::STACK_TOP_SIZE, Word, WORD_SIZE};
 
 mod trace;
@@ -94,6 +95,20 @@ impl Stack {
         }
     }
 
+    /// Returns a [Stack] initialized with the specified program inputs which keeps track of the
+    /// stack state without recording its execution trace.
+    ///
+    /// Only the most recent rows of the trace are kept in memory, and rows removed from the
+    /// overflow table are discarded.
+    pub fn new_counting(inputs: &StackInputs) -> Self {
+        let stack = Self::new(inputs, MIN_TRACE_LEN, false);
+        Self {
+            trace: stack.trace.without_past_rows(),
+            overflow: stack.overflow.without_removed_rows(),
+            ..stack
+        }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
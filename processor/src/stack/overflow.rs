@@ -25,6 +25,9 @@ pub struct OverflowTable {
     /// whenever an update happens. This is set to true only when executing programs for debug
     /// purposes.
     trace_enabled: bool,
+    /// A flag which specifies whether rows removed from the overflow table should be kept in
+    /// `all_rows`. These rows are needed only to build the auxiliary trace column.
+    keep_removed_rows: bool,
     /// The number of rows in the overflow table when execution begins.
     num_init_rows: usize,
     /// Holds the address (the clock cycle) of the row at to top of the overflow table. When
@@ -43,6 +46,7 @@ impl OverflowTable {
             active_rows: Vec::new(),
             trace: BTreeMap::new(),
             trace_enabled: enable_trace,
+            keep_removed_rows: true,
             num_init_rows: 0,
             last_row_addr: ZERO,
         }
@@ -67,6 +71,14 @@ impl OverflowTable {
         overflow_table
     }
 
+    /// Returns this [OverflowTable] modified to discard the rows which are removed from it.
+    ///
+    /// Such a table cannot be converted into an auxiliary trace builder.
+    pub fn without_removed_rows(mut self) -> Self {
+        self.keep_removed_rows = false;
+        self
+    }
+
     // STATE MUTATORS
     // --------------------------------------------------------------------------------------------
 
@@ -114,6 +126,12 @@ impl OverflowTable {
         let removed_value = last_row.val;
         self.last_row_addr = last_row.prev;
 
+        // the removed row is always the last one in `all_rows` when removed rows are discarded,
+        // since no row can be removed before all rows added after it have been removed.
+        if !self.keep_removed_rows {
+            self.all_rows.truncate(last_row_idx);
+        }
+
         if self.trace_enabled {
             // insert a copy of the current table state into the trace
             self.save_current_state(clk);
@@ -197,6 +215,7 @@ impl OverflowTable {
     /// Converts this [OverflowTable] into an auxiliary trace builder which can be used to construct
     /// the auxiliary trace column describing the state of the overflow table at every cycle.
     pub fn into_aux_builder(self) -> AuxTraceBuilder {
+        debug_assert!(self.keep_removed_rows, "removed overflow table rows were discarded");
         AuxTraceBuilder {
             num_init_rows: self.num_init_rows,
             overflow_table_rows: self.all_rows,
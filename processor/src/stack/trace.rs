@@ -16,6 +16,8 @@ use miden_air::trace::stack::{H0_COL_IDX, NUM_STACK_HELPER_COLS, STACK_TOP_SIZE}
 pub struct StackTrace {
     stack: [Vec<Felt>; STACK_TOP_SIZE],
     helpers: [Vec<Felt>; NUM_STACK_HELPER_COLS],
+    record_trace: bool,
+    first_row_clk: u32,
 }
 
 impl StackTrace {
@@ -36,29 +38,42 @@ impl StackTrace {
         StackTrace {
             stack: init_stack_columns(init_trace_capacity, init_values),
             helpers: init_helper_columns(init_trace_capacity, init_depth, init_overflow_addr),
+            record_trace: true,
+            first_row_clk: 0,
         }
     }
 
+    /// Returns this [StackTrace] modified to keep only the most recent rows of the trace in
+    /// memory.
+    ///
+    /// Once the allocated rows are filled up, the row of the current clock cycle is moved to the
+    /// start of the columns and all prior rows are discarded, rather than growing the columns.
+    pub fn without_past_rows(mut self) -> Self {
+        self.record_trace = false;
+        self
+    }
+
     // STACK ACCESSORS AND MUTATORS
     // --------------------------------------------------------------------------------------------
 
     /// Returns a copy of the item at the top of the stack at the specified clock cycle.
     #[inline(always)]
     pub fn peek_at(&self, clk: u32) -> Felt {
-        self.stack[0][clk as usize]
+        self.stack[0][self.row(clk)]
     }
 
     /// Returns the value located at the specified position on the stack at the specified clock
     /// cycle.
     #[inline(always)]
     pub fn get_stack_value_at(&self, clk: u32, pos: usize) -> Felt {
-        self.stack[pos][clk as usize]
+        self.stack[pos][self.row(clk)]
     }
 
     /// Sets the value at the specified position on the stack at the specified cycle.
     #[inline(always)]
     pub fn set_stack_value_at(&mut self, clk: u32, pos: usize, value: Felt) {
-        self.stack[pos][clk as usize] = value;
+        let row = self.row(clk);
+        self.stack[pos][row] = value;
     }
 
     /// Copies the stack values starting at the specified position at the specified clock cycle to
@@ -73,15 +88,15 @@ impl StackTrace {
         stack_depth: Felt,
         next_overflow_addr: Felt,
     ) {
-        let clk = clk as usize;
+        let row = self.row(clk);
 
         // copy over stack top columns
         for i in start_pos..STACK_TOP_SIZE {
-            self.stack[i][clk + 1] = self.stack[i][clk];
+            self.stack[i][row + 1] = self.stack[i][row];
         }
 
         // update stack helper columns
-        self.set_helpers_at(clk, stack_depth, next_overflow_addr);
+        self.set_helpers_at(row, stack_depth, next_overflow_addr);
     }
 
     /// Copies the stack values starting at the specified position at the specified clock cycle to
@@ -104,23 +119,23 @@ impl StackTrace {
         last_value: Felt,
         next_overflow_addr: Option<Felt>,
     ) {
-        let clk = clk as usize;
+        let row = self.row(clk);
 
         // update stack top columns
         for i in start_pos..=MAX_TOP_IDX {
-            self.stack[i - 1][clk + 1] = self.stack[i][clk];
+            self.stack[i - 1][row + 1] = self.stack[i][row];
         }
-        self.stack[MAX_TOP_IDX][clk + 1] = last_value;
+        self.stack[MAX_TOP_IDX][row + 1] = last_value;
 
         // update stack helper columns
         if let Some(next_overflow_addr) = next_overflow_addr {
-            let next_depth = self.helpers[0][clk] - ONE;
-            self.set_helpers_at(clk, next_depth, next_overflow_addr);
+            let next_depth = self.helpers[0][row] - ONE;
+            self.set_helpers_at(row, next_depth, next_overflow_addr);
         } else {
             // if next_overflow_addr was not provide, just copy over the values from the last row
-            let next_depth = self.helpers[0][clk];
-            let next_overflow_addr = self.helpers[1][clk];
-            self.set_helpers_at(clk, next_depth, next_overflow_addr);
+            let next_depth = self.helpers[0][row];
+            let next_overflow_addr = self.helpers[1][row];
+            self.set_helpers_at(row, next_depth, next_overflow_addr);
         }
     }
 
@@ -135,16 +150,16 @@ impl StackTrace {
     /// - Set h0 to (depth - 16). Inverses of these values will be computed in into_array() method
     ///   after the entire trace is constructed.
     pub fn stack_shift_right_at(&mut self, clk: u32, start_pos: usize) {
-        let clk = clk as usize;
+        let row = self.row(clk);
 
         // update stack top columns
         for i in start_pos..MAX_TOP_IDX {
-            self.stack[i + 1][clk + 1] = self.stack[i][clk];
+            self.stack[i + 1][row + 1] = self.stack[i][row];
         }
 
         // update stack helper columns
-        let next_depth = self.helpers[0][clk] + ONE;
-        self.set_helpers_at(clk, next_depth, Felt::from(clk as u32));
+        let next_depth = self.helpers[0][row] + ONE;
+        self.set_helpers_at(row, next_depth, Felt::from(clk));
     }
 
     // UTILITY METHODS
@@ -152,9 +167,21 @@ impl StackTrace {
 
     /// Makes sure there is enough memory allocated for the trace to accommodate a new row.
     ///
-    /// Trace length is doubled every time it needs to be increased.
+    /// Trace length is doubled every time it needs to be increased. If the trace is not recorded,
+    /// the row at the specified clock cycle is moved to the start of the trace instead.
     pub fn ensure_trace_capacity(&mut self, clk: u32) {
         let current_capacity = get_trace_len(&self.stack);
+        let row = self.row(clk);
+        if !self.record_trace {
+            if row + 1 >= current_capacity {
+                for column in self.stack.iter_mut().chain(self.helpers.iter_mut()) {
+                    column[0] = column[row];
+                }
+                self.first_row_clk = clk;
+            }
+            return;
+        }
+
         // current_capacity as trace_length can not be bigger than clk, so it is safe to cast to u32
         if clk + 1 >= current_capacity as u32 {
             let new_length = current_capacity * 2;
@@ -167,7 +194,7 @@ impl StackTrace {
     /// Appends stack top state (16 items) at the specified clock cycle into the provided vector.
     pub fn append_state_into(&self, result: &mut Vec<Felt>, clk: u32) {
         for column in self.stack.iter() {
-            result.push(column[clk as usize]);
+            result.push(column[self.row(clk)]);
         }
     }
 
@@ -187,14 +214,20 @@ impl StackTrace {
     // HELPER METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns the index of the row holding the stack state at the specified clock cycle.
+    #[inline(always)]
+    fn row(&self, clk: u32) -> usize {
+        (clk - self.first_row_clk) as usize
+    }
+
     /// Sets values of stack helper columns for the next clock cycle. Note that h0 column value is
     /// set to (stack_depth - 16) rather than to 1 / (stack_depth - 16). Inverses of these values
     /// will be computed in into_array() method (using batch inversion) after the entire trace is
     /// constructed.
-    fn set_helpers_at(&mut self, clk: usize, stack_depth: Felt, next_overflow_addr: Felt) {
-        self.helpers[0][clk + 1] = stack_depth;
-        self.helpers[1][clk + 1] = next_overflow_addr;
-        self.helpers[2][clk + 1] = stack_depth - Felt::from(STACK_TOP_SIZE as u32);
+    fn set_helpers_at(&mut self, row: usize, stack_depth: Felt, next_overflow_addr: Felt) {
+        self.helpers[0][row + 1] = stack_depth;
+        self.helpers[1][row + 1] = next_overflow_addr;
+        self.helpers[2][row + 1] = stack_depth - Felt::from(STACK_TOP_SIZE as u32);
     }
 
     // TEST HELPERS
@@ -205,7 +238,7 @@ impl StackTrace {
     pub fn get_stack_state_at(&self, clk: u32) -> [Felt; STACK_TOP_SIZE] {
         let mut result = [ZERO; STACK_TOP_SIZE];
         for (result, column) in result.iter_mut().zip(self.stack.iter()) {
-            *result = column[clk as usize];
+            *result = column[self.row(clk)];
         }
         result
     }
@@ -215,7 +248,7 @@ impl StackTrace {
     pub fn get_helpers_state_at(&self, clk: u32) -> [Felt; NUM_STACK_HELPER_COLS] {
         let mut result = [ZERO; NUM_STACK_HELPER_COLS];
         for (result, column) in result.iter_mut().zip(self.helpers.iter()) {
-            *result = column[clk as usize];
+            *result = column[self.row(clk)];
         }
         result
     }
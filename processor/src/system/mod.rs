@@ -43,6 +43,7 @@ pub struct System {
     fmp: Felt,
     in_syscall: bool,
     fn_hash: Word,
+    record_trace: bool,
     ctx_trace: Vec<Felt>,
     clk_trace: Vec<Felt>,
     fmp_trace: Vec<Felt>,
@@ -68,6 +69,7 @@ impl System {
             fmp,
             in_syscall: false,
             fn_hash: EMPTY_WORD,
+            record_trace: true,
             clk_trace: Felt::zeroed_vector(init_trace_capacity),
             ctx_trace: Felt::zeroed_vector(init_trace_capacity),
             fmp_trace,
@@ -81,6 +83,26 @@ impl System {
         }
     }
 
+    /// Returns a new [System] struct which keeps track of the system registers without recording
+    /// their execution trace.
+    ///
+    /// This is used to count the number of cycles executed by the VM without building the trace.
+    pub fn new_counting() -> Self {
+        Self {
+            clk: 0,
+            ctx: ContextId::root(),
+            fmp: Felt::new(FMP_MIN),
+            in_syscall: false,
+            fn_hash: EMPTY_WORD,
+            record_trace: false,
+            clk_trace: Vec::new(),
+            ctx_trace: Vec::new(),
+            fmp_trace: Vec::new(),
+            in_syscall_trace: Vec::new(),
+            fn_hash_trace: [Vec::new(), Vec::new(), Vec::new(), Vec::new()],
+        }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -145,6 +167,10 @@ impl System {
             return Err(ExecutionError::CycleLimitExceeded(max_cycles));
         }
 
+        if !self.record_trace {
+            return Ok(());
+        }
+
         let clk = self.clk as usize;
 
         self.clk_trace[clk] = Felt::from(self.clk);
@@ -283,6 +309,10 @@ impl System {
     ///
     /// Trace length is doubled every time it needs to be increased.
     pub fn ensure_trace_capacity(&mut self) {
+        if !self.record_trace {
+            return;
+        }
+
         let current_capacity = self.clk_trace.len();
         if self.clk + 1 >= current_capacity as u32 {
             let new_length = current_capacity * 2;
@@ -0,0 +1,195 @@
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use vm_core::{
+    crypto::hash::{Rpo256, RpoDigest},
+    Felt, Word, ZERO,
+};
+
+// KEY-VALUE MAP
+// ================================================================================================
+
+/// A host-side mirror of a map managed by the `std::collections::map` module.
+///
+/// The map places keys into slots exactly the same way as the MASM procedures do, and thus can be
+/// used to build the advice inputs which are needed to load the map into the memory of the VM via
+/// `map::load` (see [KvMap::commitment()] and [KvMap::to_advice_map()]), as well as to compute the
+/// expected results of map operations.
+///
+/// Same as in the MASM module, [ZERO; 4] is treated as an empty value. Removing a key sets its
+/// value to [ZERO; 4], but the key keeps occupying its slot.
+///
+/// Note that an access to the map in the VM hashes only the slot holding the key, while the cost
+/// of comparing the keys stored in other probed slots grows with the length of the probing
+/// sequence of the key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KvMap {
+    slots: Vec<Option<(Word, Word)>>,
+    len: usize,
+}
+
+impl KvMap {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new empty map with the specified capacity.
+    ///
+    /// # Panics
+    /// Panics if the capacity is not a power of two or does not fit into 32 bits.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity.is_power_of_two(), "map capacity must be a power of two");
+        assert!(capacity <= u32::MAX as usize, "map capacity must fit into 32 bits");
+        Self {
+            slots: vec![None; capacity],
+            len: 0,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of slots of this map.
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns the number of keys with non-empty values in this map.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if this map does not contain any keys with non-empty values.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the value associated with the specified key, or [ZERO; 4] if the key is not in this
+    /// map.
+    pub fn get(&self, key: Word) -> Word {
+        match self.find(key) {
+            Some(idx) => self.slots[idx].map_or([ZERO; 4], |(_, value)| value),
+            None => [ZERO; 4],
+        }
+    }
+
+    /// Returns the data of this map as laid out in the memory of the VM: the capacity, the number
+    /// of entries, and the key and the commitment to the entry of every slot of the map.
+    pub fn memory_image(&self) -> Vec<Word> {
+        let mut image = Vec::with_capacity(2 * self.capacity() + 2);
+        image.push([Felt::from(self.capacity() as u32), ZERO, ZERO, ZERO]);
+        image.push([Felt::from(self.len as u32), ZERO, ZERO, ZERO]);
+        for slot in self.slots.iter() {
+            match slot {
+                Some((key, value)) => {
+                    image.push(*key);
+                    image.push(entry_commitment(*key, *value).into());
+                }
+                None => image.extend([[ZERO; 4]; 2]),
+            }
+        }
+        image
+    }
+
+    /// Returns the commitment to this map, i.e., the value returned by `map::commit` for this map.
+    pub fn commitment(&self) -> RpoDigest {
+        let elements: Vec<Felt> = self.memory_image().into_iter().flatten().collect();
+        Rpo256::hash_elements(&elements)
+    }
+
+    /// Returns the advice map entries which are required to load this map via `map::load` and to
+    /// access its entries: the memory image of the map keyed by its commitment, and the [KEY,
+    /// VALUE] pre-images of all occupied slots keyed by their commitments.
+    pub fn to_advice_map(&self) -> Vec<(RpoDigest, Vec<Felt>)> {
+        let image: Vec<Felt> = self.memory_image().into_iter().flatten().collect();
+        let mut advice_map = vec![(Rpo256::hash_elements(&image), image)];
+        for (key, value) in self.slots.iter().flatten() {
+            let preimage = key.iter().chain(value.iter()).copied().collect();
+            advice_map.push((entry_commitment(*key, *value), preimage));
+        }
+        advice_map
+    }
+
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Associates the specified value with the specified key, and returns the value which was
+    /// previously associated with the key ([ZERO; 4] if the key was not in this map).
+    ///
+    /// Inserting [ZERO; 4] removes the key from this map.
+    ///
+    /// # Errors
+    /// Returns an error if the key is not in this map, the value is not empty, and all slots of
+    /// this map are occupied.
+    pub fn insert(&mut self, key: Word, value: Word) -> Result<Word, KvMapError> {
+        let Some(idx) = self.find(key) else {
+            return if is_empty_word(value) {
+                Ok([ZERO; 4])
+            } else {
+                Err(KvMapError::MapFull(self.capacity()))
+            };
+        };
+
+        let old_value = self.slots[idx].map_or([ZERO; 4], |(_, value)| value);
+        if self.slots[idx].is_some() || !is_empty_word(value) {
+            self.slots[idx] = Some((key, value));
+        }
+        self.len = self.len + !is_empty_word(value) as usize - !is_empty_word(old_value) as usize;
+        Ok(old_value)
+    }
+
+    /// Removes the specified key from this map, and returns the value which was associated with
+    /// the key ([ZERO; 4] if the key was not in this map).
+    pub fn remove(&mut self, key: Word) -> Word {
+        self.insert(key, [ZERO; 4]).expect("removing a key cannot fail")
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the index of the slot holding the specified key, or the index of the first empty
+    /// slot probed for this key if the key is not in this map. Returns None if the key is not in
+    /// this map and all slots are occupied.
+    fn find(&self, key: Word) -> Option<usize> {
+        let mask = self.capacity() - 1;
+        let start = (key[3].as_int() as u32) as usize & mask;
+        (0..self.capacity()).map(|offset| (start + offset) & mask).find(|&idx| {
+            match self.slots[idx] {
+                Some((slot_key, _)) => slot_key == key,
+                None => true,
+            }
+        })
+    }
+}
+
+// KEY-VALUE MAP ERROR
+// ================================================================================================
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KvMapError {
+    MapFull(usize),
+}
+
+impl fmt::Display for KvMapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use KvMapError::*;
+        match self {
+            MapFull(capacity) => {
+                write!(f, "all {capacity} slots of the map are occupied")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for KvMapError {}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the commitment to a map entry, i.e., hash(KEY, VALUE).
+fn entry_commitment(key: Word, value: Word) -> RpoDigest {
+    Rpo256::merge(&[key.into(), value.into()])
+}
+
+fn is_empty_word(word: Word) -> bool {
+    word == [ZERO; 4]
+}
@@ -1,10 +1,17 @@
 #![no_std]
 
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
 use assembly::{
     ast::ModuleAst, utils::Deserializable, Library, LibraryNamespace, LibraryPath, MaslLibrary,
     Version,
 };
 
+pub mod collections;
+
 // STANDARD LIBRARY
 // ================================================================================================
 
@@ -58,8 +65,18 @@ fn test_compile() {
             .ast
             .procs()
             .iter()
-            .any(|proc| module.path.append(&proc.name).unwrap().as_str() == path)
+            .any(|proc| module.path.append_unchecked(&proc.name) == path)
     });
 
     assert!(exists);
 }
+
+#[test]
+fn test_memory_layout() {
+    let layout = StdLibrary::default().memory_layout();
+    assert!(layout.find_overlap().is_none(), "{layout}");
+
+    let (path, region) = layout.find_region(4294903200).unwrap();
+    assert_eq!(path.as_str(), "std::crypto::stark::constants");
+    assert_eq!(region.name(), "FRI_COM_PTR");
+}
@@ -0,0 +1,272 @@
+use miden_stdlib::collections::{KvMap, KvMapError};
+use test_utils::{crypto::MerkleStore, proptest::prelude::*, Felt, Word, ONE};
+
+// TEST DATA
+// ================================================================================================
+
+const MAP_PTR: u64 = 1000;
+
+/// Keys 0 and 1 are placed into the same slot of a map with capacity 4, and key 2 is placed into
+/// the next slot, so that accessing it requires probing past the slot of key 1.
+const KEYS: [[u64; 4]; 3] = [[1, 2, 3, 5], [4, 5, 6, 9], [7, 8, 9, 6]];
+
+const VALUES: [[u64; 4]; 3] = [[11, 12, 13, 14], [15, 16, 17, 18], [19, 20, 21, 22]];
+
+// MAP OPERATIONS
+// ================================================================================================
+
+#[test]
+fn test_map_insert_get() {
+    let mut map = KvMap::new(4);
+    let mut source = new_map_source(4);
+    for (key, value) in KEYS.iter().zip(VALUES.iter()) {
+        map.insert(to_word(key), to_word(value)).unwrap();
+        source.push_str(&insert_source(key, value));
+        source.push_str(" dropw");
+    }
+    // overwrite the value of a key which collides with another key
+    let old_value = map.insert(to_word(&KEYS[1]), to_word(&VALUES[0])).unwrap();
+    assert_eq!(old_value, to_word(&VALUES[1]));
+    source.push_str(&insert_source(&KEYS[1], &VALUES[0]));
+    source.push_str(&format!(" push.{} assert_eqw", word_imm(&VALUES[1])));
+
+    for key in KEYS.iter() {
+        source.push_str(&format!(" push.{MAP_PTR} push.{} exec.map::get", word_imm(key)));
+    }
+    source.push_str(&format!(" push.{MAP_PTR} exec.map::len end"));
+
+    let mut expected = vec![map.len() as u64];
+    for key in KEYS.iter().rev() {
+        expected.extend(word_to_stack(map.get(to_word(key))));
+    }
+
+    assert_eq!(map.len(), 3);
+    build_test!(&source, &[]).expect_stack(&expected);
+}
+
+#[test]
+fn test_map_remove() {
+    let mut map = KvMap::new(4);
+    let mut source = new_map_source(4);
+    for (key, value) in KEYS.iter().zip(VALUES.iter()) {
+        map.insert(to_word(key), to_word(value)).unwrap();
+        source.push_str(&insert_source(key, value));
+        source.push_str(" dropw");
+    }
+
+    // remove a key which precedes other keys in the probing sequence; the keys after it must
+    // remain accessible
+    let old_value = map.remove(to_word(&KEYS[0]));
+    assert_eq!(old_value, to_word(&VALUES[0]));
+    source.push_str(&format!(" push.{MAP_PTR} push.{} exec.map::remove", word_imm(&KEYS[0])));
+
+    for key in KEYS.iter() {
+        source.push_str(&format!(" push.{MAP_PTR} push.{} exec.map::contains", word_imm(key)));
+    }
+    source.push_str(&format!(" push.{MAP_PTR} push.{} exec.map::get", word_imm(&KEYS[2])));
+    source.push_str(&format!(" push.{MAP_PTR} exec.map::len end"));
+
+    let mut expected = vec![2];
+    expected.extend(word_to_stack(to_word(&VALUES[2])));
+    expected.extend([1, 1, 0]);
+    expected.extend(word_to_stack(old_value));
+
+    assert_eq!(map.len(), 2);
+    build_test!(&source, &[]).expect_stack(&expected);
+}
+
+#[test]
+fn test_map_full() {
+    let mut map = KvMap::new(2);
+    let mut source = new_map_source(2);
+    for (key, value) in KEYS.iter().zip(VALUES.iter()).take(2) {
+        map.insert(to_word(key), to_word(value)).unwrap();
+        source.push_str(&insert_source(key, value));
+        source.push_str(" dropw");
+    }
+
+    // removing an absent key from a full map succeeds
+    assert_eq!(map.remove(to_word(&KEYS[2])), [0_u64; 4].map(Felt::new));
+    let remove_source =
+        format!("{source} push.{MAP_PTR} push.{} exec.map::remove end", word_imm(&KEYS[2]));
+    build_test!(&remove_source, &[]).expect_stack(&[0, 0, 0, 0]);
+
+    // inserting a new key into a full map fails
+    assert_eq!(map.insert(to_word(&KEYS[2]), to_word(&VALUES[2])), Err(KvMapError::MapFull(2)));
+    let full_source = format!("{source}{} end", insert_source(&KEYS[2], &VALUES[2]));
+    assert!(build_test!(&full_source, &[]).execute().is_err());
+}
+
+#[test]
+fn test_map_access_cycles() {
+    let mut source = new_map_source(4);
+    for (key, value) in KEYS.iter().zip(VALUES.iter()) {
+        source.push_str(&insert_source(key, value));
+        source.push_str(" dropw");
+    }
+
+    // returns the number of cycles taken by getting the value of the specified key
+    let get_cycles = |key: &[u64; 4]| {
+        let source = format!(
+            "{source} clk push.{MAP_PTR} push.{} exec.map::get clk movup.5 sub end",
+            word_imm(key)
+        );
+        let trace = build_test!(&source, &[]).execute().unwrap();
+        trace.stack_outputs().stack()[0].as_int()
+    };
+
+    // key 0 is found in the first probed slot, which is hashed to authenticate the value
+    assert_eq!(get_cycles(&KEYS[0]), 225);
+
+    // key 1 collides with key 0; probing past the slot of key 0 compares the stored key with the
+    // requested one without hashing the slot, and only the slot of key 1 is hashed
+    assert_eq!(get_cycles(&KEYS[1]), 354);
+}
+
+#[test]
+fn test_map_new_invalid_capacity() {
+    let source = "
+        use.std::collections::map
+        begin
+            push.1000.3 exec.map::new
+        end";
+    assert!(build_test!(source, &[]).execute().is_err());
+}
+
+// LOADING AND COMMITTING
+// ================================================================================================
+
+#[test]
+fn test_map_load() {
+    let mut map = KvMap::new(4);
+    for (key, value) in KEYS.iter().zip(VALUES.iter()) {
+        map.insert(to_word(key), to_word(value)).unwrap();
+    }
+    map.remove(to_word(&KEYS[1]));
+
+    let source = format!(
+        "
+        use.std::collections::map
+        begin
+            exec.map::load
+            push.{MAP_PTR} push.{} exec.map::get
+            push.{MAP_PTR} push.{} exec.map::get
+            push.{MAP_PTR} exec.map::len
+            push.{MAP_PTR} exec.map::commit
+        end",
+        word_imm(&KEYS[1]),
+        word_imm(&KEYS[2])
+    );
+
+    let mut expected = word_to_stack(map.commitment().into()).to_vec();
+    expected.push(map.len() as u64);
+    expected.extend(word_to_stack(to_word(&VALUES[2])));
+    expected.extend([0, 0, 0, 0]);
+
+    let stack_inputs = load_inputs(&map);
+    build_test!(&source, &stack_inputs, &[], MerkleStore::new(), map.to_advice_map())
+        .expect_stack(&expected);
+}
+
+#[test]
+fn test_map_load_invalid_entry() {
+    let mut map = KvMap::new(4);
+    for (key, value) in KEYS.iter().zip(VALUES.iter()) {
+        map.insert(to_word(key), to_word(value)).unwrap();
+    }
+
+    let source = format!(
+        "
+        use.std::collections::map
+        begin
+            exec.map::load
+            push.{MAP_PTR} push.{} exec.map::get
+        end",
+        word_imm(&KEYS[2])
+    );
+
+    // replace the value of every entry in the advice map with a different one
+    let mut advice_map = map.to_advice_map();
+    for (_, preimage) in advice_map.iter_mut().skip(1) {
+        preimage[4] += ONE;
+    }
+
+    let stack_inputs = load_inputs(&map);
+    let test = build_test!(&source, &stack_inputs, &[], MerkleStore::new(), advice_map);
+    assert!(test.execute().is_err());
+}
+
+#[test]
+fn test_map_load_invalid_commitment() {
+    let map = KvMap::new(4);
+    let source = "
+        use.std::collections::map
+        begin
+            exec.map::load
+        end";
+
+    let mut stack_inputs = load_inputs(&map);
+    stack_inputs[1] += 1;
+    let test = build_test!(source, &stack_inputs, &[], MerkleStore::new(), map.to_advice_map());
+    assert!(test.execute().is_err());
+}
+
+proptest! {
+    #[test]
+    fn map_insert_proptest(entries in prop::collection::vec((0_u64..8, 0_u64..4), 1..12)) {
+        let mut map = KvMap::new(8);
+        let mut source = new_map_source(8);
+        for (key, value) in entries {
+            let key = [key, key + 1, key + 2, key % 3];
+            let value = [value, 0, 0, 0];
+            map.insert(to_word(&key), to_word(&value)).unwrap();
+            source.push_str(&insert_source(&key, &value));
+            source.push_str(" dropw");
+        }
+        source.push_str(&format!(" push.{MAP_PTR} exec.map::commit end"));
+
+        build_test!(&source, &[]).prop_expect_stack(&word_to_stack(map.commitment().into()))?;
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the beginning of a program which creates an empty map with the specified capacity.
+fn new_map_source(capacity: u64) -> String {
+    format!(
+        "
+        use.std::collections::map
+        begin
+            push.{MAP_PTR}.{capacity} exec.map::new"
+    )
+}
+
+/// Returns the code which inserts the specified key-value pair into the map.
+fn insert_source(key: &[u64; 4], value: &[u64; 4]) -> String {
+    format!(
+        " push.{MAP_PTR} push.{} push.{} exec.map::insert",
+        word_imm(key),
+        word_imm(value)
+    )
+}
+
+/// Returns the operand stack inputs of `map::load` for the specified map.
+fn load_inputs(map: &KvMap) -> Vec<u64> {
+    let commitment: Word = map.commitment().into();
+    let mut inputs = vec![MAP_PTR];
+    inputs.extend(commitment.iter().map(|felt| felt.as_int()));
+    inputs
+}
+
+fn word_imm(word: &[u64; 4]) -> String {
+    format!("{}.{}.{}.{}", word[0], word[1], word[2], word[3])
+}
+
+fn to_word(word: &[u64; 4]) -> Word {
+    word.map(Felt::new)
+}
+
+fn word_to_stack(word: Word) -> [u64; 4] {
+    [word[3].as_int(), word[2].as_int(), word[1].as_int(), word[0].as_int()]
+}
@@ -3,5 +3,7 @@ use test_utils::{
     Felt, Word, EMPTY_WORD,
 };
 
+mod map;
 mod mmr;
 mod smt;
+mod sorted_array;
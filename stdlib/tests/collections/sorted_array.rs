@@ -0,0 +1,137 @@
+use test_utils::{proptest::prelude::*, Felt, StarkField};
+
+// TEST DATA
+// ================================================================================================
+
+const START_PTR: u64 = 100;
+
+const ARRAY: [u64; 6] = [3, 5, 5, 9, 20, Felt::MODULUS - 1];
+
+// SORTEDNESS
+// ================================================================================================
+
+#[test]
+fn is_sorted() {
+    build_test!(&is_sorted_source(&ARRAY), &[]).expect_stack(&[1]);
+    build_test!(&is_sorted_source(&[]), &[]).expect_stack(&[1]);
+    build_test!(&is_sorted_source(&[7]), &[]).expect_stack(&[1]);
+    build_test!(&is_sorted_source(&[3, 5, 4, 9]), &[]).expect_stack(&[0]);
+    build_test!(&is_sorted_source(&[Felt::MODULUS - 1, 0]), &[]).expect_stack(&[0]);
+}
+
+#[test]
+fn is_sorted_invalid_range() {
+    let source = "
+        use.std::collections::sorted_array
+        begin
+            push.100.101 exec.sorted_array::is_sorted
+        end";
+    assert!(build_test!(source, &[]).execute().is_err());
+}
+
+// SEARCH
+// ================================================================================================
+
+#[test]
+fn lower_bound() {
+    let cases = [(0, 0), (3, 0), (4, 1), (5, 1), (6, 3), (20, 4), (21, 5), (Felt::MODULUS - 1, 5)];
+    for (value, idx) in cases {
+        let source = search_source(&ARRAY, "lower_bound", value);
+        build_test!(&source, &[]).expect_stack(&[START_PTR + idx]);
+    }
+
+    // the lower bound of any value in an empty array is the end of the array
+    let source = search_source(&[], "lower_bound", 5);
+    build_test!(&source, &[]).expect_stack(&[START_PTR]);
+}
+
+#[test]
+fn find() {
+    let cases = [(0, 0, 0), (3, 1, 0), (5, 1, 1), (6, 0, 3), (9, 1, 3), (Felt::MODULUS - 1, 1, 5)];
+    for (value, is_found, idx) in cases {
+        let source = search_source(&ARRAY, "find", value);
+        build_test!(&source, &[]).expect_stack(&[is_found, START_PTR + idx]);
+
+        let source = search_source(&ARRAY, "contains", value);
+        build_test!(&source, &[]).expect_stack(&[is_found]);
+    }
+
+    // a value greater than all elements of the array
+    let array = &ARRAY[..5];
+    let source = search_source(array, "find", 21);
+    build_test!(&source, &[]).expect_stack(&[0, START_PTR + 5]);
+}
+
+#[test]
+fn assert_contains() {
+    let source = search_source(&ARRAY, "assert_contains", 9);
+    build_test!(&source, &[]).expect_stack(&[START_PTR + 3]);
+
+    let source = search_source(&ARRAY, "assert_contains", 10);
+    assert!(build_test!(&source, &[]).execute().is_err());
+}
+
+#[test]
+fn assert_not_contains() {
+    let source = search_source(&ARRAY, "assert_not_contains", 10);
+    build_test!(&source, &[]).expect_stack(&[START_PTR + 4]);
+
+    let source = search_source(&ARRAY, "assert_not_contains", 9);
+    assert!(build_test!(&source, &[]).execute().is_err());
+}
+
+proptest! {
+    #[test]
+    fn find_proptest(mut array in prop::collection::vec(0_u64..64, 0..16), value in 0_u64..64) {
+        array.sort();
+        let idx = array.partition_point(|&element| element < value);
+        let is_found = array.get(idx) == Some(&value);
+
+        let source = search_source(&array, "find", value);
+        build_test!(&source, &[]).prop_expect_stack(&[is_found as u64, START_PTR + idx as u64])?;
+    }
+
+    #[test]
+    fn is_sorted_proptest(array in prop::collection::vec(0_u64..8, 0..16)) {
+        let is_sorted = array.windows(2).all(|pair| pair[0] <= pair[1]);
+        build_test!(&is_sorted_source(&array), &[]).prop_expect_stack(&[is_sorted as u64])?;
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the code which stores the specified array in memory starting at `START_PTR`.
+fn store_array_source(array: &[u64]) -> String {
+    array
+        .iter()
+        .enumerate()
+        .map(|(i, element)| format!("push.{element} mem_store.{} ", START_PTR + i as u64))
+        .collect()
+}
+
+fn is_sorted_source(array: &[u64]) -> String {
+    format!(
+        "
+        use.std::collections::sorted_array
+        begin
+            {}
+            push.{}.{START_PTR} exec.sorted_array::is_sorted
+        end",
+        store_array_source(array),
+        START_PTR + array.len() as u64
+    )
+}
+
+fn search_source(array: &[u64], procedure: &str, value: u64) -> String {
+    format!(
+        "
+        use.std::collections::sorted_array
+        begin
+            {}
+            push.{}.{START_PTR}.{value} exec.sorted_array::{procedure}
+        end",
+        store_array_source(array),
+        START_PTR + array.len() as u64
+    )
+}
@@ -1,4 +1,10 @@
-use test_utils::{group_slice_elements, rand::rand_array, Felt, IntoBytes};
+use super::{hash_memory_inputs, hash_memory_source};
+use test_utils::{
+    group_slice_elements,
+    proptest::prelude::*,
+    rand::{rand_array, rand_value},
+    Felt, IntoBytes,
+};
 
 #[test]
 fn blake3_hash_64_bytes() {
@@ -61,3 +67,44 @@ fn blake3_hash_32_bytes() {
     let test = build_test!(source, &ifelts);
     test.expect_stack(&ofelts);
 }
+
+#[test]
+fn blake3_hash_memory() {
+    // lengths around the boundaries of blocks ( = 64 -bytes ) and chunks ( = 1024 -bytes )
+    for length in [0, 1, 4, 63, 64, 65, 1023, 1024, 1025, 2048, 2049, 4097] {
+        let ibytes: Vec<u8> = (0..length).map(|_| rand_value()).collect();
+        let (ifelts, ofelts) = hash_memory_test_data(&ibytes);
+
+        let test = build_test!(&hash_memory_source("blake3"), &ifelts);
+        test.expect_stack(&ofelts);
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn blake3_hash_memory_proptest(ibytes in prop::collection::vec(any::<u8>(), 0..5000)) {
+        let (ifelts, ofelts) = hash_memory_test_data(&ibytes);
+
+        build_test!(&hash_memory_source("blake3"), &ifelts).prop_expect_stack(&ofelts)?;
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the stack inputs of the `hash_memory` test program for the provided message, and the
+/// expected BLAKE3 digest of the message.
+fn hash_memory_test_data(ibytes: &[u8]) -> (Vec<u64>, Vec<u64>) {
+    let ifelts = hash_memory_inputs(ibytes);
+
+    let hasher = blake3::hash(ibytes);
+    let obytes = hasher.as_bytes();
+    let ofelts = group_slice_elements::<u8, 4>(obytes)
+        .iter()
+        .map(|&bytes| u32::from_le_bytes(bytes) as u64)
+        .collect::<Vec<u64>>();
+
+    (ifelts, ofelts)
+}
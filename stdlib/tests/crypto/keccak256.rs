@@ -1,5 +1,7 @@
+use super::{hash_memory_inputs, hash_memory_source};
 use sha3::{Digest, Keccak256};
 use test_utils::{
+    proptest::prelude::*,
     rand::{rand_array, rand_value},
     Felt, IntoBytes, STACK_TOP_SIZE,
 };
@@ -69,6 +71,47 @@ fn keccak256_2_to_1_hash() {
     test.expect_stack(&expected_stack);
 }
 
+#[test]
+fn keccak256_hash_memory() {
+    // lengths around the boundaries of lanes ( = 8 -bytes ) and blocks ( = 136 -bytes )
+    for length in [0, 1, 7, 8, 9, 135, 136, 137, 271, 272, 273] {
+        let ibytes: Vec<u8> = (0..length).map(|_| rand_value()).collect();
+        let (ifelts, ofelts) = hash_memory_test_data(&ibytes);
+
+        let test = build_test!(&hash_memory_source("keccak256"), &ifelts);
+        test.expect_stack(&ofelts);
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn keccak256_hash_memory_proptest(ibytes in prop::collection::vec(any::<u8>(), 0..1000)) {
+        let (ifelts, ofelts) = hash_memory_test_data(&ibytes);
+
+        build_test!(&hash_memory_source("keccak256"), &ifelts).prop_expect_stack(&ofelts)?;
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the stack inputs of the `hash_memory` test program for the provided message, and the
+/// expected keccak256 digest of the message.
+fn hash_memory_test_data(ibytes: &[u8]) -> (Vec<u64>, Vec<u64>) {
+    let ifelts = hash_memory_inputs(ibytes);
+
+    let mut hasher = Keccak256::new();
+    hasher.update(ibytes);
+    let digest = hasher.finalize();
+
+    let mut ofelts = vec![0u64; STACK_TOP_SIZE >> 1];
+    to_stack(&digest, &mut ofelts);
+
+    (ifelts, ofelts)
+}
+
 /// Given N -many bytes ( such that N % 8 == 0 ), this function considers
 /// each block of contiguous 8 -bytes as little endian 64 -bit unsigned
 /// integer word and converts each u64 into two u32s such that first one holds
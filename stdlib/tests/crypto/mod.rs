@@ -9,3 +9,55 @@ mod keccak256;
 mod native;
 mod sha256;
 mod stark;
+
+use test_utils::group_slice_elements;
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a program which stores the message provided via the stack inputs at memory addresses
+/// 1000, 1001, ... and hashes it using the `hash_memory` procedure of the specified module of
+/// `std::crypto::hashes`.
+fn hash_memory_source(module: &str) -> String {
+    format!(
+        "
+    use.std::crypto::hashes::{module}
+
+    begin
+        # mem.0 - address of the next message word, mem.1 - length of the message in bytes
+        push.1000 mem_store.0
+        mem_store.1
+
+        # store the message at memory addresses 1000, 1001, ...
+        mem_load.1 add.15 u32assert u32div.16
+        dup neq.0
+        while.true
+            movdn.4 mem_load.0 mem_storew dropw
+            mem_load.0 add.1 mem_store.0
+            sub.1 dup neq.0
+        end
+        drop
+
+        mem_load.1
+        push.1000
+        exec.{module}::hash_memory
+    end"
+    )
+}
+
+/// Returns the stack inputs of the program built by [hash_memory_source] for the provided message.
+///
+/// The message is padded with zeros to a multiple of 16 bytes, and every 4 bytes of it are packed
+/// into a little endian 32 -bit word.
+fn hash_memory_inputs(ibytes: &[u8]) -> Vec<u64> {
+    let ipadding = vec![0u8; (16 - ibytes.len() % 16) % 16];
+    [
+        group_slice_elements::<u8, 4>(&[ibytes, ipadding.as_slice()].concat())
+            .iter()
+            .map(|&bytes| u32::from_le_bytes(bytes) as u64)
+            .rev()
+            .collect::<Vec<u64>>(),
+        vec![ibytes.len() as u64],
+    ]
+    .concat()
+}
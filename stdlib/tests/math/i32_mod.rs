@@ -0,0 +1,263 @@
+use test_utils::proptest::prelude::*;
+
+// ADDITION, SUBTRACTION AND MULTIPLICATION
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn overflowing_add() {
+    let source = "
+        use.std::math::i32
+        begin
+            exec.i32::overflowing_add
+        end";
+
+    build_test!(source, &[to_stack(-5), to_stack(3)]).expect_stack(&[0, to_stack(-2)]);
+    build_test!(source, &[to_stack(i32::MAX), 1]).expect_stack(&[1, to_stack(i32::MIN)]);
+    build_test!(source, &[to_stack(i32::MIN), to_stack(-1)]).expect_stack(&[1, to_stack(i32::MAX)]);
+}
+
+#[test]
+fn checked_add_fail() {
+    let source = "
+        use.std::math::i32
+        begin
+            exec.i32::checked_add
+        end";
+
+    assert!(build_test!(source, &[to_stack(i32::MAX), 1]).execute().is_err());
+    assert!(build_test!(source, &[to_stack(i32::MIN), to_stack(-1)]).execute().is_err());
+}
+
+#[test]
+fn overflowing_sub() {
+    let source = "
+        use.std::math::i32
+        begin
+            exec.i32::overflowing_sub
+        end";
+
+    build_test!(source, &[to_stack(-5), to_stack(-3)]).expect_stack(&[0, to_stack(-2)]);
+    build_test!(source, &[to_stack(i32::MIN), 1]).expect_stack(&[1, to_stack(i32::MAX)]);
+    build_test!(source, &[0, to_stack(i32::MIN)]).expect_stack(&[1, to_stack(i32::MIN)]);
+}
+
+#[test]
+fn overflowing_mul() {
+    let source = "
+        use.std::math::i32
+        begin
+            exec.i32::overflowing_mul
+        end";
+
+    build_test!(source, &[to_stack(-7), to_stack(6)]).expect_stack(&[0, to_stack(-42)]);
+    build_test!(source, &[to_stack(i32::MIN), 1]).expect_stack(&[0, to_stack(i32::MIN)]);
+    build_test!(source, &[to_stack(i32::MIN), to_stack(-1)]).expect_stack(&[1, to_stack(i32::MIN)]);
+    build_test!(source, &[65536, 32768]).expect_stack(&[1, to_stack(i32::MIN)]);
+    build_test!(source, &[65536, to_stack(-32768)]).expect_stack(&[0, to_stack(i32::MIN)]);
+}
+
+// NEGATION AND ABSOLUTE VALUE
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn neg_abs_min() {
+    let min = to_stack(i32::MIN);
+    for proc in ["wrapping_neg", "wrapping_abs"] {
+        let source = format!(
+            "
+            use.std::math::i32
+            begin
+                exec.i32::{proc}
+            end"
+        );
+        build_test!(&source, &[min]).expect_stack(&[min]);
+    }
+
+    for proc in ["checked_neg", "checked_abs"] {
+        let source = format!(
+            "
+            use.std::math::i32
+            begin
+                exec.i32::{proc}
+            end"
+        );
+        assert!(build_test!(&source, &[min]).execute().is_err());
+    }
+}
+
+// DIVISION
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn div() {
+    let source = "
+        use.std::math::i32
+        begin
+            exec.i32::div
+        end";
+
+    // the quotient is rounded towards zero
+    build_test!(source, &[to_stack(-7), 2]).expect_stack(&[to_stack(-3)]);
+    build_test!(source, &[7, to_stack(-2)]).expect_stack(&[to_stack(-3)]);
+    build_test!(source, &[to_stack(-7), to_stack(-2)]).expect_stack(&[3]);
+    build_test!(source, &[to_stack(i32::MIN), 1]).expect_stack(&[to_stack(i32::MIN)]);
+
+    // division by zero and overflowing division fail
+    assert!(build_test!(source, &[7, 0]).execute().is_err());
+    assert!(build_test!(source, &[to_stack(i32::MIN), to_stack(-1)]).execute().is_err());
+}
+
+#[test]
+fn rem() {
+    let source = "
+        use.std::math::i32
+        begin
+            exec.i32::rem
+        end";
+
+    // the remainder has the same sign as the dividend
+    build_test!(source, &[to_stack(-7), 2]).expect_stack(&[to_stack(-1)]);
+    build_test!(source, &[7, to_stack(-2)]).expect_stack(&[1]);
+    build_test!(source, &[to_stack(i32::MIN), to_stack(-1)]).expect_stack(&[0]);
+
+    assert!(build_test!(source, &[7, 0]).execute().is_err());
+}
+
+// BIT SHIFTS
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn shr() {
+    let source = "
+        use.std::math::i32
+        begin
+            exec.i32::shr
+        end";
+
+    build_test!(source, &[to_stack(-1), 31]).expect_stack(&[to_stack(-1)]);
+    build_test!(source, &[to_stack(i32::MIN), 4]).expect_stack(&[to_stack(i32::MIN >> 4)]);
+    assert!(build_test!(source, &[to_stack(-1), 32]).execute().is_err());
+}
+
+// RANDOMIZED TESTS
+// ================================================================================================
+
+proptest! {
+    #[test]
+    fn overflowing_arithmetic_proptest(a in any::<i32>(), b in any::<i32>()) {
+        let operands = [to_stack(a), to_stack(b)];
+        let results = [a.overflowing_add(b), a.overflowing_sub(b), a.overflowing_mul(b)];
+        for (op, (c, overflow)) in ["add", "sub", "mul"].iter().zip(results) {
+            let source = format!(
+                "
+                use.std::math::i32
+                begin
+                    exec.i32::overflowing_{op}
+                end"
+            );
+            build_test!(&source, &operands).prop_expect_stack(&[overflow as u64, to_stack(c)])?;
+
+            let source = format!(
+                "
+                use.std::math::i32
+                begin
+                    exec.i32::wrapping_{op}
+                end"
+            );
+            build_test!(&source, &operands).prop_expect_stack(&[to_stack(c)])?;
+        }
+    }
+
+    #[test]
+    fn div_rem_proptest(a in any::<i32>(), b in any::<i32>()) {
+        prop_assume!(b != 0 && !(a == i32::MIN && b == -1));
+
+        let source = "
+            use.std::math::i32
+            begin
+                dup.1
+                dup.1
+                exec.i32::rem
+                movdn.2
+                exec.i32::div
+            end";
+
+        build_test!(source, &[to_stack(a), to_stack(b)])
+            .prop_expect_stack(&[to_stack(a / b), to_stack(a % b)])?;
+    }
+
+    #[test]
+    fn neg_abs_proptest(a in any::<i32>()) {
+        prop_assume!(a != i32::MIN);
+
+        let source = "
+            use.std::math::i32
+            begin
+                dup
+                exec.i32::checked_abs
+                swap
+                exec.i32::checked_neg
+            end";
+
+        build_test!(source, &[to_stack(a)]).prop_expect_stack(&[to_stack(-a), to_stack(a.abs())])?;
+    }
+
+    #[test]
+    fn shr_proptest(a in any::<i32>(), b in 0_u32..32) {
+        let source = "
+            use.std::math::i32
+            begin
+                exec.i32::shr
+            end";
+
+        build_test!(source, &[5, to_stack(a), b as u64]).prop_expect_stack(&[to_stack(a >> b), 5])?;
+    }
+
+    #[test]
+    fn sign_extend_proptest(a in any::<u32>()) {
+        let source = "
+            use.std::math::i32
+            begin
+                dup
+                exec.i32::sign_extend_8
+                swap
+                exec.i32::sign_extend_16
+            end";
+
+        let c8 = a as i8 as i32;
+        let c16 = a as i16 as i32;
+        build_test!(source, &[a as u64]).prop_expect_stack(&[to_stack(c16), to_stack(c8)])?;
+    }
+
+    #[test]
+    fn comparison_proptest(a in any::<i32>(), b in any::<i32>()) {
+        let source = "
+            use.std::math::i32
+            begin
+                dup.1 dup.1 exec.i32::lt movdn.2
+                dup.1 dup.1 exec.i32::lte movdn.2
+                dup.1 dup.1 exec.i32::gt movdn.2
+                dup.1 dup.1 exec.i32::gte movdn.2
+                dup.1 dup.1 exec.i32::min movdn.2
+                exec.i32::max
+            end";
+
+        let expected = [
+            to_stack(a.max(b)),
+            to_stack(a.min(b)),
+            (a >= b) as u64,
+            (a > b) as u64,
+            (a <= b) as u64,
+            (a < b) as u64,
+        ];
+        build_test!(source, &[to_stack(a), to_stack(b)]).prop_expect_stack(&expected)?;
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the two's complement encoding of the provided value as a stack element.
+fn to_stack(value: i32) -> u64 {
+    value as u32 as u64
+}
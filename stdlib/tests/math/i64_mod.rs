@@ -0,0 +1,336 @@
+use test_utils::proptest::prelude::*;
+
+// ADDITION, SUBTRACTION AND MULTIPLICATION
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn overflowing_add() {
+    let source = "
+        use.std::math::i64
+        begin
+            exec.i64::overflowing_add
+        end";
+
+    let test = build_test!(source, &binary_operands(-5, 3));
+    test.expect_stack(&with_flag(false, -2));
+    let test = build_test!(source, &binary_operands(i64::MAX, 1));
+    test.expect_stack(&with_flag(true, i64::MIN));
+    let test = build_test!(source, &binary_operands(i64::MIN, -1));
+    test.expect_stack(&with_flag(true, i64::MAX));
+}
+
+#[test]
+fn checked_add_fail() {
+    let source = "
+        use.std::math::i64
+        begin
+            exec.i64::checked_add
+        end";
+
+    assert!(build_test!(source, &binary_operands(i64::MAX, 1)).execute().is_err());
+    assert!(build_test!(source, &binary_operands(i64::MIN, -1)).execute().is_err());
+}
+
+#[test]
+fn overflowing_sub() {
+    let source = "
+        use.std::math::i64
+        begin
+            exec.i64::overflowing_sub
+        end";
+
+    let test = build_test!(source, &binary_operands(-5, -3));
+    test.expect_stack(&with_flag(false, -2));
+    let test = build_test!(source, &binary_operands(i64::MIN, 1));
+    test.expect_stack(&with_flag(true, i64::MAX));
+    let test = build_test!(source, &binary_operands(0, i64::MIN));
+    test.expect_stack(&with_flag(true, i64::MIN));
+}
+
+#[test]
+fn overflowing_mul() {
+    let source = "
+        use.std::math::i64
+        begin
+            exec.i64::overflowing_mul
+        end";
+
+    let test = build_test!(source, &binary_operands(-7, 6));
+    test.expect_stack(&with_flag(false, -42));
+    let test = build_test!(source, &binary_operands(i64::MIN, -1));
+    test.expect_stack(&with_flag(true, i64::MIN));
+    let test = build_test!(source, &binary_operands(1 << 32, 1 << 31));
+    test.expect_stack(&with_flag(true, i64::MIN));
+    let test = build_test!(source, &binary_operands(1 << 32, -(1 << 31)));
+    test.expect_stack(&with_flag(false, i64::MIN));
+}
+
+// NEGATION AND ABSOLUTE VALUE
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn neg_abs_min() {
+    let (min1, min0) = split_i64(i64::MIN);
+    for proc in ["wrapping_neg", "wrapping_abs"] {
+        let source = format!(
+            "
+            use.std::math::i64
+            begin
+                exec.i64::{proc}
+            end"
+        );
+        build_test!(&source, &[min0, min1]).expect_stack(&[min1, min0]);
+    }
+
+    for proc in ["checked_neg", "checked_abs"] {
+        let source = format!(
+            "
+            use.std::math::i64
+            begin
+                exec.i64::{proc}
+            end"
+        );
+        assert!(build_test!(&source, &[min0, min1]).execute().is_err());
+    }
+}
+
+// DIVISION
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn div() {
+    let source = "
+        use.std::math::i64
+        begin
+            exec.i64::div
+        end";
+
+    // the quotient is rounded towards zero
+    build_test!(source, &binary_operands(-7, 2)).expect_stack(&to_stack(-3));
+    build_test!(source, &binary_operands(7, -2)).expect_stack(&to_stack(-3));
+    build_test!(source, &binary_operands(-7, -2)).expect_stack(&to_stack(3));
+    build_test!(source, &binary_operands(i64::MIN, 1)).expect_stack(&to_stack(i64::MIN));
+
+    // division by zero and overflowing division fail
+    assert!(build_test!(source, &binary_operands(7, 0)).execute().is_err());
+    assert!(build_test!(source, &binary_operands(i64::MIN, -1)).execute().is_err());
+}
+
+#[test]
+fn rem() {
+    let source = "
+        use.std::math::i64
+        begin
+            exec.i64::rem
+        end";
+
+    // the remainder has the same sign as the dividend
+    build_test!(source, &binary_operands(-7, 2)).expect_stack(&to_stack(-1));
+    build_test!(source, &binary_operands(7, -2)).expect_stack(&to_stack(1));
+    build_test!(source, &binary_operands(i64::MIN, -1)).expect_stack(&to_stack(0));
+
+    assert!(build_test!(source, &binary_operands(7, 0)).execute().is_err());
+}
+
+// BIT SHIFTS
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn shr() {
+    let source = "
+        use.std::math::i64
+        begin
+            exec.i64::shr
+        end";
+
+    let (a1, a0) = split_i64(i64::MIN);
+    build_test!(source, &[a0, a1, 63]).expect_stack(&to_stack(-1));
+    build_test!(source, &[a0, a1, 40]).expect_stack(&to_stack(i64::MIN >> 40));
+    assert!(build_test!(source, &[a0, a1, 64]).execute().is_err());
+}
+
+// CONVERSIONS
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn to_i32_fail() {
+    let source = "
+        use.std::math::i64
+        begin
+            exec.i64::to_i32
+        end";
+
+    let (a1, a0) = split_i64(i32::MAX as i64 + 1);
+    assert!(build_test!(source, &[a0, a1]).execute().is_err());
+    let (a1, a0) = split_i64(i32::MIN as i64 - 1);
+    assert!(build_test!(source, &[a0, a1]).execute().is_err());
+}
+
+// RANDOMIZED TESTS
+// ================================================================================================
+
+proptest! {
+    #[test]
+    fn overflowing_arithmetic_proptest(a in any::<i64>(), b in any::<i64>()) {
+        let operands = binary_operands(a, b);
+        let results = [a.overflowing_add(b), a.overflowing_sub(b), a.overflowing_mul(b)];
+        for (op, (c, overflow)) in ["add", "sub", "mul"].iter().zip(results) {
+            let source = format!(
+                "
+                use.std::math::i64
+                begin
+                    exec.i64::overflowing_{op}
+                end"
+            );
+            build_test!(&source, &operands).prop_expect_stack(&with_flag(overflow, c))?;
+
+            let source = format!(
+                "
+                use.std::math::i64
+                begin
+                    exec.i64::wrapping_{op}
+                end"
+            );
+            build_test!(&source, &operands).prop_expect_stack(&to_stack(c))?;
+        }
+    }
+
+    #[test]
+    fn small_mul_proptest(a in any::<i32>(), b in any::<i32>()) {
+        // products of values close to the boundaries of the range are checked separately, since
+        // they are unlikely to be produced by multiplying random 64-bit values
+        let (a, b) = ((a as i64) << 16, (b as i64) << 15);
+        let (c, overflow) = a.overflowing_mul(b);
+
+        let source = "
+            use.std::math::i64
+            begin
+                exec.i64::overflowing_mul
+            end";
+
+        build_test!(source, &binary_operands(a, b)).prop_expect_stack(&with_flag(overflow, c))?;
+    }
+
+    #[test]
+    fn div_rem_proptest(a in any::<i64>(), b in any::<i64>()) {
+        prop_assume!(b != 0 && !(a == i64::MIN && b == -1));
+
+        let source = "
+            use.std::math::i64
+            begin
+                dupw
+                exec.i64::rem
+                movdn.5
+                movdn.5
+                exec.i64::div
+            end";
+
+        let (q1, q0) = split_i64(a / b);
+        let (r1, r0) = split_i64(a % b);
+        build_test!(source, &binary_operands(a, b)).prop_expect_stack(&[q1, q0, r1, r0])?;
+    }
+
+    #[test]
+    fn neg_abs_proptest(a in any::<i64>()) {
+        prop_assume!(a != i64::MIN);
+
+        let source = "
+            use.std::math::i64
+            begin
+                dup.1
+                dup.1
+                exec.i64::checked_abs
+                movdn.3
+                movdn.3
+                exec.i64::checked_neg
+            end";
+
+        let (a1, a0) = split_i64(a);
+        let (c1, c0) = split_i64(-a);
+        let (d1, d0) = split_i64(a.abs());
+        build_test!(source, &[a0, a1]).prop_expect_stack(&[c1, c0, d1, d0])?;
+    }
+
+    #[test]
+    fn shr_proptest(a in any::<i64>(), b in 0_u32..64) {
+        let source = "
+            use.std::math::i64
+            begin
+                exec.i64::shr
+            end";
+
+        let (a1, a0) = split_i64(a);
+        let (c1, c0) = split_i64(a >> b);
+        build_test!(source, &[5, a0, a1, b as u64]).prop_expect_stack(&[c1, c0, 5])?;
+    }
+
+    #[test]
+    fn comparison_proptest(a in any::<i64>(), b in any::<i64>()) {
+        let (a1, a0) = split_i64(a);
+        let (b1, b0) = split_i64(b);
+        for (op, c) in [("lt", a < b), ("lte", a <= b), ("gt", a > b), ("gte", a >= b)] {
+            let source = format!(
+                "
+                use.std::math::i64
+                begin
+                    exec.i64::{op}
+                end"
+            );
+            build_test!(&source, &[a0, a1, b0, b1]).prop_expect_stack(&[c as u64])?;
+        }
+
+        for (op, c) in [("min", a.min(b)), ("max", a.max(b))] {
+            let source = format!(
+                "
+                use.std::math::i64
+                begin
+                    exec.i64::{op}
+                end"
+            );
+            build_test!(&source, &[a0, a1, b0, b1]).prop_expect_stack(&to_stack(c))?;
+        }
+    }
+
+    #[test]
+    fn i32_conversion_proptest(a in any::<i32>()) {
+        let source = "
+            use.std::math::i64
+            begin
+                exec.i64::from_i32
+                dup.1
+                dup.1
+                exec.i64::to_i32
+            end";
+
+        let (c1, c0) = split_i64(a as i64);
+        build_test!(source, &[a as u32 as u64]).prop_expect_stack(&[a as u32 as u64, c1, c0])?;
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Splits the two's complement encoding of the provided value into 32 high and low bits.
+fn split_i64(value: i64) -> (u64, u64) {
+    let value = value as u64;
+    (value >> 32, value as u32 as u64)
+}
+
+/// Returns the limbs of the provided value in the order in which they are placed on the stack.
+fn to_stack(value: i64) -> [u64; 2] {
+    let (hi, lo) = split_i64(value);
+    [hi, lo]
+}
+
+/// Returns the limbs of the provided value preceded by the provided flag.
+fn with_flag(flag: bool, value: i64) -> [u64; 3] {
+    let (hi, lo) = split_i64(value);
+    [flag as u64, hi, lo]
+}
+
+/// Returns the stack inputs for a binary operation such that `b` is at the top of the stack.
+fn binary_operands(a: i64, b: i64) -> [u64; 4] {
+    let (a1, a0) = split_i64(a);
+    let (b1, b0) = split_i64(b);
+    [a0, a1, b0, b1]
+}
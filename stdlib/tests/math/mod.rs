@@ -1,4 +1,6 @@
 pub mod ecgfp5;
+mod i32_mod;
+mod i64_mod;
 mod secp256k1;
 mod u256_mod;
 mod u64_mod;
@@ -1,5 +1,222 @@
 use num_bigint::BigUint;
-use test_utils::rand::rand_vector;
+use test_utils::{
+    proptest::prelude::*,
+    rand::{rand_value, rand_vector},
+};
+
+// ADDITION AND SUBTRACTION
+// ================================================================================================
+
+#[test]
+fn overflowing_add() {
+    let max = (BigUint::from(1_u8) << 256_u32) - 1_u8;
+    let source = "
+        use.std::math::u256
+        begin
+            exec.u256::overflowing_add
+        end";
+
+    for (a, b) in [
+        (rand_u256(), rand_u256()),
+        (max.clone(), BigUint::from(1_u8)),
+        (max.clone(), max),
+    ] {
+        let sum = &a + &b;
+        let overflow = (sum.bits() > 256) as u64;
+        let mut expected = vec![overflow];
+        expected.extend(u256_to_limbs(&sum).iter().rev());
+
+        build_test!(source, &binary_operands(&a, &b)).expect_stack(&expected);
+    }
+}
+
+#[test]
+fn checked_add() {
+    let a = rand_u256() >> 1_u32;
+    let b = rand_u256() >> 1_u32;
+
+    let source = "
+        use.std::math::u256
+        begin
+            exec.u256::checked_add
+        end";
+
+    let expected = u256_to_limbs(&(&a + &b)).into_iter().rev().collect::<Vec<_>>();
+    build_test!(source, &binary_operands(&a, &b)).expect_stack(&expected);
+
+    // the sum of the values does not fit into 256 bits
+    let a = (BigUint::from(1_u8) << 256_u32) - 1_u8;
+    let b = BigUint::from(1_u8);
+    assert!(build_test!(source, &binary_operands(&a, &b)).execute().is_err());
+}
+
+#[test]
+fn overflowing_sub() {
+    let source = "
+        use.std::math::u256
+        begin
+            exec.u256::overflowing_sub
+        end";
+
+    let a = rand_u256();
+    let b = rand_u256();
+    for (a, b) in [(a.clone(), b.clone()), (b, a.clone()), (a.clone(), a)] {
+        let (underflow, diff) = if a >= b {
+            (0, &a - &b)
+        } else {
+            (1, (BigUint::from(1_u8) << 256_u32) + &a - &b)
+        };
+        let mut expected = vec![underflow];
+        expected.extend(u256_to_limbs(&diff).iter().rev());
+
+        build_test!(source, &binary_operands(&a, &b)).expect_stack(&expected);
+    }
+}
+
+#[test]
+fn checked_sub() {
+    let a = rand_u256();
+    let b = rand_u256();
+    let (a, b) = if a >= b { (a, b) } else { (b, a) };
+
+    let source = "
+        use.std::math::u256
+        begin
+            exec.u256::checked_sub
+        end";
+
+    let expected = u256_to_limbs(&(&a - &b)).into_iter().rev().collect::<Vec<_>>();
+    build_test!(source, &binary_operands(&a, &b)).expect_stack(&expected);
+
+    // the result of the subtraction is negative
+    let (a, b) = (b, a + 1_u8);
+    assert!(build_test!(source, &binary_operands(&a, &b)).execute().is_err());
+}
+
+// COMPARISON
+// ================================================================================================
+
+#[test]
+fn comparison() {
+    let a = rand_u256();
+    let b = rand_u256();
+    let c = &a ^ BigUint::from(1_u8);
+
+    for (a, b) in [(&a, &b), (&b, &a), (&a, &a), (&a, &c), (&c, &a)] {
+        let operands = binary_operands(a, b);
+        for (proc, expected) in [
+            ("lt", a < b),
+            ("lte", a <= b),
+            ("gt", a > b),
+            ("gte", a >= b),
+            ("eq_unsafe", a == b),
+        ] {
+            let source = format!(
+                "
+                use.std::math::u256
+                begin
+                    exec.u256::{proc}
+                end"
+            );
+            build_test!(&source, &operands).expect_stack(&[expected as u64]);
+        }
+
+        let source = "
+            use.std::math::u256
+            begin
+                exec.u256::min
+            end";
+        let expected = u256_to_limbs(a.min(b)).into_iter().rev().collect::<Vec<_>>();
+        build_test!(source, &operands).expect_stack(&expected);
+
+        let source = "
+            use.std::math::u256
+            begin
+                exec.u256::max
+            end";
+        let expected = u256_to_limbs(a.max(b)).into_iter().rev().collect::<Vec<_>>();
+        build_test!(source, &operands).expect_stack(&expected);
+    }
+}
+
+// BIT SHIFTS
+// ================================================================================================
+
+#[test]
+fn shifts() {
+    let a = rand_u256();
+    for n in [0_u32, 1, 31, 32, 33, 100, 224, 255, rand_value::<u32>() % 256] {
+        let (shl, shr, rotl, rotr) = expected_shifts(&a, n);
+        let mut operands = u256_to_limbs(&a);
+        operands.push(n as u64);
+
+        for (proc, expected) in [("shl", shl), ("shr", shr), ("rotl", rotl), ("rotr", rotr)] {
+            let source = format!(
+                "
+                use.std::math::u256
+                begin
+                    exec.u256::{proc}
+                end"
+            );
+            let expected = u256_to_limbs(&expected).into_iter().rev().collect::<Vec<_>>();
+            build_test!(&source, &operands).expect_stack(&expected);
+        }
+    }
+}
+
+#[test]
+fn shifts_fail() {
+    let mut operands = u256_to_limbs(&rand_u256());
+    operands.push(256);
+
+    for proc in ["shl", "shr", "rotl", "rotr"] {
+        let source = format!(
+            "
+            use.std::math::u256
+            begin
+                exec.u256::{proc}
+            end"
+        );
+        assert!(build_test!(&source, &operands).execute().is_err());
+    }
+}
+
+// BIT COUNTS
+// ================================================================================================
+
+#[test]
+fn clz_ctz() {
+    let a = rand_u256();
+    let modulus = BigUint::from(1_u8) << 256_u32;
+    let values = [
+        BigUint::from(0_u8),
+        BigUint::from(1_u8),
+        BigUint::from(1_u8) << 255_u32,
+        &a >> 100_u32,
+        ((&a >> 40_u32) << 70_u32) % &modulus,
+        a,
+    ];
+
+    for value in values.iter() {
+        let operands = u256_to_limbs(value);
+        let leading_zeros = 256 - value.bits();
+        let trailing_zeros = value.trailing_zeros().unwrap_or(256);
+
+        let source = "
+            use.std::math::u256
+            begin
+                exec.u256::clz
+            end";
+        build_test!(source, &operands).expect_stack(&[leading_zeros]);
+
+        let source = "
+            use.std::math::u256
+            begin
+                exec.u256::ctz
+            end";
+        build_test!(source, &operands).expect_stack(&[trailing_zeros]);
+    }
+}
 
 // MULTIPLICATION
 // ================================================================================================
@@ -32,6 +249,148 @@ fn mul_unsafe() {
     build_test!(source, &operands).expect_stack(&result);
 }
 
+#[test]
+fn widening_mul() {
+    let a = rand_u256();
+    let b = rand_u256();
+
+    let source = "
+        use.std::math::u256
+        begin
+            exec.u256::widening_mul
+        end";
+
+    let mut expected = (&a * &b).to_u32_digits().iter().map(|&v| v as u64).collect::<Vec<_>>();
+    expected.resize(16, 0);
+    expected.reverse();
+
+    build_test!(source, &binary_operands(&a, &b)).expect_stack(&expected);
+}
+
+// DIVISION
+// ================================================================================================
+
+#[test]
+fn divmod() {
+    let a = rand_u256();
+    let b = rand_u256();
+    let c = rand_u256() >> 160_u32;
+
+    for (a, b) in [(&a, &b), (&b, &a), (&a, &c), (&a, &a), (&c, &a)] {
+        let operands = binary_operands(a, b);
+        let quotient = u256_to_limbs(&(a / b));
+        let remainder = u256_to_limbs(&(a % b));
+
+        let source = "
+            use.std::math::u256
+            begin
+                exec.u256::divmod
+            end";
+        let expected = quotient.iter().chain(remainder.iter()).rev().copied().collect::<Vec<_>>();
+        build_test!(source, &operands).expect_stack(&expected);
+
+        let source = "
+            use.std::math::u256
+            begin
+                exec.u256::div
+            end";
+        let expected = quotient.iter().rev().copied().collect::<Vec<_>>();
+        build_test!(source, &operands).expect_stack(&expected);
+
+        let source = "
+            use.std::math::u256
+            begin
+                exec.u256::mod
+            end";
+        let expected = remainder.iter().rev().copied().collect::<Vec<_>>();
+        build_test!(source, &operands).expect_stack(&expected);
+    }
+}
+
+#[test]
+fn div_by_zero() {
+    let source = "
+        use.std::math::u256
+        begin
+            exec.u256::div
+        end";
+
+    let operands = binary_operands(&rand_u256(), &BigUint::from(0_u8));
+    assert!(build_test!(source, &operands).execute().is_err());
+}
+
+// CONVERSIONS
+// ================================================================================================
+
+#[test]
+fn u64_conversions() {
+    let a = rand_value::<u64>();
+    let a_hi = a >> 32;
+    let a_lo = a as u32 as u64;
+
+    let source = "
+        use.std::math::u256
+        begin
+            exec.u256::from_u64
+        end";
+    build_test!(source, &[a_lo, a_hi]).expect_stack(&[0, 0, 0, 0, 0, 0, a_hi, a_lo]);
+
+    let source = "
+        use.std::math::u256
+        begin
+            exec.u256::to_u64
+        end";
+    build_test!(source, &u256_to_limbs(&BigUint::from(a))).expect_stack(&[a_hi, a_lo]);
+
+    // the value does not fit into 64 bits
+    let operands = u256_to_limbs(&(BigUint::from(a) << 64_u32));
+    assert!(build_test!(source, &operands).execute().is_err());
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn divmod_proptest(a in any::<[u32; 8]>(), b in any::<[u32; 8]>(), b_len in 1_usize..=8) {
+        // the number of non-zero limbs in the divisor is randomized to cover short divisors
+        let a = BigUint::from_slice(&a);
+        let b = BigUint::from_slice(&b[..b_len]) | BigUint::from(1_u8);
+
+        let source = "
+            use.std::math::u256
+            begin
+                exec.u256::divmod
+            end";
+
+        let expected = u256_to_limbs(&(&a / &b))
+            .into_iter()
+            .chain(u256_to_limbs(&(&a % &b)))
+            .rev()
+            .collect::<Vec<_>>();
+        build_test!(source, &binary_operands(&a, &b)).prop_expect_stack(&expected)?;
+    }
+
+    #[test]
+    fn shifts_proptest(a in any::<[u32; 8]>(), n in 0_u32..256) {
+        let a = BigUint::from_slice(&a);
+        let mut operands = u256_to_limbs(&a);
+        operands.push(n as u64);
+
+        let (shl, shr, rotl, rotr) = expected_shifts(&a, n);
+        for (proc, expected) in [("shl", shl), ("shr", shr), ("rotl", rotl), ("rotr", rotr)] {
+            let source = format!(
+                "
+                use.std::math::u256
+                begin
+                    exec.u256::{proc}
+                end"
+            );
+            let expected = u256_to_limbs(&expected).into_iter().rev().collect::<Vec<_>>();
+            build_test!(&source, &operands).prop_expect_stack(&expected)?;
+        }
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -39,3 +398,27 @@ fn rand_u256() -> BigUint {
     let limbs = rand_vector::<u64>(8).iter().map(|&v| v as u32).collect::<Vec<_>>();
     BigUint::new(limbs)
 }
+
+/// Returns the eight 32-bit limbs of the provided value starting with the least significant one.
+fn u256_to_limbs(value: &BigUint) -> Vec<u64> {
+    let mut limbs = value.to_u32_digits().iter().map(|&v| v as u64).collect::<Vec<_>>();
+    limbs.resize(8, 0);
+    limbs
+}
+
+/// Returns stack inputs for a binary operation such that b is at the top of the stack.
+fn binary_operands(a: &BigUint, b: &BigUint) -> Vec<u64> {
+    let mut operands = u256_to_limbs(a);
+    operands.extend(u256_to_limbs(b));
+    operands
+}
+
+/// Returns the results of shifting and rotating the provided value left and right by n bits.
+fn expected_shifts(a: &BigUint, n: u32) -> (BigUint, BigUint, BigUint, BigUint) {
+    let modulus = BigUint::from(1_u8) << 256_u32;
+    let shl = (a << n) % &modulus;
+    let shr = a >> n;
+    let rotl = (&shl | (a >> (256 - n))) % &modulus;
+    let rotr = (&shr | (a << (256 - n))) % &modulus;
+    (shl, shr, rotl, rotr)
+}